@@ -3,7 +3,7 @@
  * Licensed under the MIT License.
  */
 
-//! Creating vector indexes with the typed helpers and `VectorSimilarity`.
+//! Creating vector indexes with the typed helpers and `VectorSimilarity`, then searching them.
 //!
 //! Run with: `cargo run --example vector_index` (needs a FalkorDB server on 127.0.0.1:6379).
 
@@ -51,8 +51,16 @@ fn main() -> FalkorResult<()> {
     });
     println!("Product.embedding vector index operational: {has_vector_index}");
 
-    // You then run vector similarity searches in Cypher (e.g. `CALL db.idx.vector.queryNodes(...)`
-    // or by ordering on a distance function), using `vecf32([...])` to build query vectors.
+    // `vector_search` checks the query vector against the index dimension, then returns the `k`
+    // nearest nodes with their distance, nearest first. An optional filter runs on the neighbours.
+    let nearest = graph
+        .vector_search("Product", "embedding", [0.2, 0.2, 0.3, 0.4], 2)
+        .with_filter("node.name <> $skip")
+        .with_param("skip", "b")
+        .execute()?;
+    for (product, distance) in nearest {
+        println!("{:?} at distance {distance}", product.properties["name"]);
+    }
 
     graph.delete()?;
     Ok(())
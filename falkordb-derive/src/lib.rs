@@ -0,0 +1,368 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Derive macros for the [`falkordb`](https://docs.rs/falkordb) crate.
+//!
+//! Don't depend on this crate directly: enable the `falkordb` crate's `derive` feature, which
+//! re-exports [`FromRow`](macro@FromRow), [`FromNode`](macro@FromNode),
+//! [`IntoFalkorParam`](macro@IntoFalkorParam) and [`IntoFalkorParams`](macro@IntoFalkorParams)
+//! next to the traits they implement.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{
+    ext::IdentExt, parse_macro_input, Attribute, Data, DeriveInput, Fields, FieldsNamed, LitStr,
+    PathArguments, Type,
+};
+
+/// Implements `falkordb::FromRow`, building the struct from a whole result row.
+///
+/// Each named field is read from the column of the same name with `Row::try_get`, so its type
+/// only needs to implement `FromFalkorValue` (including a `#[derive(FromNode)]` struct for a node
+/// column). Tuple-struct fields are read by column index instead. Rename a column with
+/// `#[falkordb(rename = "column")]`.
+#[proc_macro_derive(FromRow, attributes(falkordb))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_row(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `falkordb::FromNode` and `falkordb::FromFalkorValue`, building the struct from a
+/// node's properties.
+///
+/// Each named field is read from the property of the same name (renamed with
+/// `#[falkordb(rename = "property")]`). FalkorDB does not store `null` properties, so an absent
+/// property is `None` for an `Option` field and a `MissingProperty` error otherwise.
+#[proc_macro_derive(FromNode, attributes(falkordb))]
+pub fn derive_from_node(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_node(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `falkordb::IntoFalkorParams` for the struct and for references to it, binding one
+/// query parameter per named field.
+///
+/// Pass the struct (or `&struct`) to `QueryBuilder::with_params`; each field becomes the `$field`
+/// parameter. Rename a parameter with `#[falkordb(rename = "param")]` and leave a field out with
+/// `#[falkordb(skip)]`. Every bound field's type must implement `IntoFalkorParam`.
+#[proc_macro_derive(IntoFalkorParams, attributes(falkordb))]
+pub fn derive_into_falkor_params(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_into_params(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `falkordb::IntoFalkorParam`, encoding the struct as a single Cypher map parameter.
+///
+/// Each named field becomes a map key, in declaration order, so the struct can be bound with
+/// `with_param` or nested inside another parameter (for example a `Vec` for `UNWIND`). Rename a
+/// key with `#[falkordb(rename = "key")]` and leave a field out with `#[falkordb(skip)]`.
+#[proc_macro_derive(IntoFalkorParam, attributes(falkordb))]
+pub fn derive_into_falkor_param(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_into_param(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_from_row(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    reject_container_attrs(&input.attrs)?;
+    let fields = struct_fields(input, "FromRow")?;
+
+    let body = match fields {
+        Fields::Named(named) => {
+            let inits = named
+                .named
+                .iter()
+                .map(|field| {
+                    let ident = field.ident.as_ref().expect("named field");
+                    let ty = &field.ty;
+                    let column = FieldAttrs::parse(&field.attrs)?
+                        .reject_skip("FromRow")?
+                        .name_or(ident);
+                    Ok(quote! {
+                        #ident: ::falkordb::Row::try_get::<#ty>(&row, #column)?
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! { Self { #(#inits),* } }
+        }
+        Fields::Unnamed(unnamed) => {
+            let inits = unnamed
+                .unnamed
+                .iter()
+                .enumerate()
+                .map(|(index, field)| {
+                    let attrs = FieldAttrs::parse(&field.attrs)?.reject_skip("FromRow")?;
+                    if let Some(rename) = attrs.rename {
+                        return Err(syn::Error::new_spanned(
+                            rename,
+                            "tuple struct fields are read by column index and cannot be renamed",
+                        ));
+                    }
+                    let ty = &field.ty;
+                    Ok(quote! { ::falkordb::Row::try_get_at::<#ty>(&row, #index)? })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! { Self(#(#inits),*) }
+        }
+        Fields::Unit => quote! { Self },
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::falkordb::FromRow for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn from_row(row: ::falkordb::Row) -> ::falkordb::FalkorResult<Self> {
+                ::core::result::Result::Ok(#body)
+            }
+        }
+    })
+}
+
+fn expand_from_node(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    reject_container_attrs(&input.attrs)?;
+    let named = named_fields(input, "FromNode")?;
+
+    let inits = named
+        .named
+        .iter()
+        .map(|field| {
+            let ident = field.ident.as_ref().expect("named field");
+            let ty = &field.ty;
+            let property = FieldAttrs::parse(&field.attrs)?
+                .reject_skip("FromNode")?
+                .name_or(ident);
+            let value = if is_option(ty) {
+                quote! {
+                    match properties.remove(#property) {
+                        ::core::option::Option::Some(value) => {
+                            <#ty as ::falkordb::FromFalkorValue>::from_falkor_value(value)?
+                        }
+                        ::core::option::Option::None => ::core::option::Option::None,
+                    }
+                }
+            } else {
+                quote! {
+                    <#ty as ::falkordb::FromFalkorValue>::from_falkor_value(
+                        properties.remove(#property).ok_or_else(|| {
+                            ::falkordb::FalkorDBError::MissingProperty {
+                                name: ::std::string::ToString::to_string(#property),
+                            }
+                        })?,
+                    )?
+                }
+            };
+            Ok(quote! { #ident: #value })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::falkordb::FromNode for #name #ty_generics #where_clause {
+            #[allow(unused_mut, unused_variables)]
+            fn from_node(node: ::falkordb::Node) -> ::falkordb::FalkorResult<Self> {
+                let mut properties = node.properties;
+                ::core::result::Result::Ok(Self { #(#inits),* })
+            }
+        }
+
+        impl #impl_generics ::falkordb::FromFalkorValue for #name #ty_generics #where_clause {
+            fn from_falkor_value(
+                value: ::falkordb::FalkorValue,
+            ) -> ::falkordb::FalkorResult<Self> {
+                <::falkordb::Node as ::falkordb::FromFalkorValue>::from_falkor_value(value)
+                    .and_then(<Self as ::falkordb::FromNode>::from_node)
+            }
+        }
+    })
+}
+
+fn expand_into_params(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    reject_container_attrs(&input.attrs)?;
+    let fields = bound_fields(named_fields(input, "IntoFalkorParams")?, true)?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::falkordb::__private::EncodeParams for #name #ty_generics #where_clause {
+            fn write_params(
+                &self,
+                out: &mut ::falkordb::__private::ParamsWriter<'_>,
+            ) {
+                out.fields(&[#(#fields),*]);
+            }
+        }
+    })
+}
+
+fn expand_into_param(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    reject_container_attrs(&input.attrs)?;
+    let fields = bound_fields(named_fields(input, "IntoFalkorParam")?, false)?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::falkordb::__private::EncodeParam for #name #ty_generics #where_clause {
+            fn write_param(
+                &self,
+                out: &mut ::falkordb::__private::ParamWriter<'_>,
+            ) -> ::falkordb::FalkorResult<()> {
+                out.map(&[#(#fields),*])
+            }
+        }
+    })
+}
+
+/// `("name", &self.field as &dyn IntoFalkorParam)` for every field not marked `skip`. Parameter
+/// names end up as `$name` in the query, so they are checked to be Cypher identifiers here;
+/// map keys are quoted by the encoder and only need to be non-empty.
+fn bound_fields(
+    fields: &FieldsNamed,
+    param_names: bool,
+) -> syn::Result<Vec<TokenStream2>> {
+    let mut bound = Vec::new();
+    for field in &fields.named {
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+        if attrs.skip.is_some() {
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("named field");
+        let key = attrs.name_or(ident);
+        let key_value = key.value();
+        if param_names && !is_cypher_identifier(&key_value) {
+            return Err(syn::Error::new_spanned(
+                key,
+                "parameter names must be Cypher identifiers ([A-Za-z_][A-Za-z0-9_]*)",
+            ));
+        }
+        if key_value.is_empty() {
+            return Err(syn::Error::new_spanned(key, "map keys must not be empty"));
+        }
+        bound.push(quote! {
+            (#key, &self.#ident as &dyn ::falkordb::IntoFalkorParam)
+        });
+    }
+    Ok(bound)
+}
+
+fn is_cypher_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The named fields of a struct, or an error naming the derive for any other shape.
+fn named_fields<'a>(
+    input: &'a DeriveInput,
+    derive: &str,
+) -> syn::Result<&'a FieldsNamed> {
+    match struct_fields(input, derive)? {
+        Fields::Named(named) => Ok(named),
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("{derive} can only be derived for structs with named fields"),
+        )),
+    }
+}
+
+/// The fields of a struct, or an error naming the derive for enums and unions.
+fn struct_fields<'a>(
+    input: &'a DeriveInput,
+    derive: &str,
+) -> syn::Result<&'a Fields> {
+    match &input.data {
+        Data::Struct(data) => Ok(&data.fields),
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("{derive} can only be derived for structs"),
+        )),
+    }
+}
+
+/// `#[falkordb(...)]` only configures fields; reject it on the struct rather than ignore it.
+fn reject_container_attrs(attrs: &[Attribute]) -> syn::Result<()> {
+    match attrs.iter().find(|attr| attr.path().is_ident("falkordb")) {
+        Some(attr) => Err(syn::Error::new_spanned(
+            attr,
+            "`#[falkordb(...)]` is only supported on fields",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Whether `ty` is spelled as an `Option<..>`, so an absent node property maps to `None`.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            path.path.segments.last().is_some_and(|segment| {
+                segment.ident == "Option"
+                    && matches!(segment.arguments, PathArguments::AngleBracketed(_))
+            })
+        }
+        _ => false,
+    }
+}
+
+/// The `#[falkordb(...)]` options of one field.
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<LitStr>,
+    skip: Option<syn::Path>,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("falkordb")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    parsed.rename = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    parsed.skip = Some(meta.path.clone());
+                    Ok(())
+                } else {
+                    Err(meta.error(format!(
+                        "unsupported falkordb attribute `{}`, expected `rename = \"...\"` or `skip`",
+                        meta.path.to_token_stream()
+                    )))
+                }
+            })?;
+        }
+        Ok(parsed)
+    }
+
+    /// `skip` only makes sense when writing parameters; a decoded struct needs every field.
+    fn reject_skip(
+        self,
+        derive: &str,
+    ) -> syn::Result<Self> {
+        match self.skip {
+            Some(skip) => Err(syn::Error::new_spanned(
+                skip,
+                format!("`skip` is not supported by {derive}, every field must be read"),
+            )),
+            None => Ok(self),
+        }
+    }
+
+    /// The column or property name: the rename if given, else the field name without any `r#`.
+    fn name_or(
+        self,
+        ident: &syn::Ident,
+    ) -> LitStr {
+        self.rename
+            .unwrap_or_else(|| LitStr::new(&ident.unraw().to_string(), ident.span()))
+    }
+}
@@ -0,0 +1,138 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Generates Rust types for the schema of a live FalkorDB graph; see `falkordb::codegen`.
+
+use falkordb::codegen::CodeGenerator;
+use falkordb::{FalkorClientBuilder, FalkorConnectionInfo};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: falkordb-codegen --graph <NAME> [OPTIONS]
+
+Options:
+  --graph <NAME>         The graph to read the schema of
+  --url <URL>            The server to connect to [default: falkor://127.0.0.1:6379]
+  --sample-size <N>      Infer property types from at most N entities per label or relationship type
+  --serde                Also derive serde traits
+  --output <FILE>        Write the generated module to FILE instead of standard output
+  -h, --help             Print this help";
+
+struct Args {
+    graph: String,
+    url: String,
+    sample_size: Option<u64>,
+    serde: bool,
+    output: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut graph = None;
+    let mut url = "falkor://127.0.0.1:6379".to_string();
+    let mut sample_size = None;
+    let mut serde = false;
+    let mut output = None;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{name} needs a value"));
+        match arg.as_str() {
+            "--graph" => graph = Some(value("--graph")?),
+            "--url" => url = value("--url")?,
+            "--sample-size" => {
+                let size = value("--sample-size")?;
+                sample_size = Some(
+                    size.parse()
+                        .map_err(|_| format!("--sample-size must be a number, not '{size}'"))?,
+                );
+            }
+            "--serde" => serde = true,
+            "--output" => output = Some(value("--output")?),
+            "-h" | "--help" => return Ok(None),
+            other => return Err(format!("unexpected argument '{other}'")),
+        }
+    }
+    Ok(Some(Args {
+        graph: graph.ok_or("--graph is required")?,
+        url,
+        sample_size,
+        serde,
+        output,
+    }))
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let info: FalkorConnectionInfo = args
+        .url
+        .as_str()
+        .try_into()
+        .map_err(|err| format!("{err}"))?;
+    let client = FalkorClientBuilder::new()
+        .with_connection_info(info)
+        .build()
+        .map_err(|err| format!("cannot connect: {err}"))?;
+    let mut generator = CodeGenerator::new();
+    if let Some(sample_size) = args.sample_size {
+        generator = generator.with_sample_size(sample_size);
+    }
+    if args.serde {
+        generator = generator.with_serde();
+    }
+    let source = generator
+        .generate(&mut client.select_graph(&args.graph))
+        .map_err(|err| format!("cannot read the schema of '{}': {err}", args.graph))?;
+    match args.output {
+        Some(path) => {
+            std::fs::write(&path, source).map_err(|err| format!("cannot write {path}: {err}"))
+        }
+        None => {
+            print!("{source}");
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Args>, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = parse(&["--graph", "social", "--sample-size", "50", "--serde"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(args.graph, "social");
+        assert_eq!(args.url, "falkor://127.0.0.1:6379");
+        assert_eq!(args.sample_size, Some(50));
+        assert!(args.serde && args.output.is_none());
+        assert!(parse(&["--help"]).unwrap().is_none());
+        assert_eq!(parse(&[]).err().unwrap(), "--graph is required");
+        assert!(parse(&["--graph"]).is_err());
+        assert!(parse(&["--graph", "g", "--sample-size", "many"]).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use super::{collect_properties, BulkChunk, BulkEncoder, BulkLoadReport, BulkNodeId, BulkValue};
+use crate::{client::asynchronous::FalkorAsyncClientInner, FalkorResult};
+use std::sync::Arc;
+
+/// Loads a new graph through `GRAPH.BULK`, created with [`FalkorAsyncClient::bulk_loader`](crate::FalkorAsyncClient::bulk_loader).
+///
+/// The async counterpart of [`BulkLoader`](crate::BulkLoader): nodes and relationships are
+/// encoded as they are added and sent in size-bounded chunks, the first of which creates the graph.
+/// Call [`finish`](Self::finish) to send the last, partly filled chunk; dropping the loader
+/// discards it. Each chunk is committed on its own, as described by [`report`](Self::report).
+#[must_use = "the last chunk is only sent by `finish`"]
+pub struct AsyncBulkLoader {
+    client: Arc<FalkorAsyncClientInner>,
+    graph_name: String,
+    encoder: BulkEncoder,
+    report: BulkLoadReport,
+}
+
+impl AsyncBulkLoader {
+    pub(crate) fn new(
+        client: Arc<FalkorAsyncClientInner>,
+        graph_name: String,
+    ) -> Self {
+        Self {
+            client,
+            graph_name,
+            encoder: BulkEncoder::new(),
+            report: BulkLoadReport::default(),
+        }
+    }
+
+    /// Caps the encoded size of each `GRAPH.BULK` command (32 MiB by default). A single entity
+    /// larger than the cap is still sent, in a command of its own.
+    ///
+    /// # Arguments
+    /// * `max_chunk_bytes`: The maximum payload size of one command, in bytes.
+    pub fn with_max_chunk_bytes(
+        mut self,
+        max_chunk_bytes: usize,
+    ) -> Self {
+        self.encoder.set_max_chunk_bytes(max_chunk_bytes);
+        self
+    }
+
+    /// Queues a node, sending a chunk first if the node does not fit in the current one.
+    ///
+    /// # Arguments
+    /// * `labels`: The node's labels; at least one, none of them containing `:`.
+    /// * `properties`: The node's properties, as `(key, value)` pairs.
+    ///
+    /// # Returns
+    /// A [`BulkNodeId`] to connect relationships to this node.
+    pub async fn add_node<K, V>(
+        &mut self,
+        labels: &[&str],
+        properties: impl IntoIterator<Item = (K, V)>,
+    ) -> FalkorResult<BulkNodeId>
+    where
+        K: Into<String>,
+        V: Into<BulkValue>,
+    {
+        let id = self
+            .encoder
+            .add_node(labels, collect_properties(properties))?;
+        self.send_ready().await?;
+        Ok(id)
+    }
+
+    /// Queues a relationship between two nodes added to this loader, sending a chunk first if the
+    /// relationship does not fit in the current one.
+    ///
+    /// # Arguments
+    /// * `relationship_type`: The relationship's type.
+    /// * `src`: The node the relationship starts from.
+    /// * `dst`: The node the relationship points to.
+    /// * `properties`: The relationship's properties, as `(key, value)` pairs.
+    pub async fn add_relationship<K, V>(
+        &mut self,
+        relationship_type: &str,
+        src: BulkNodeId,
+        dst: BulkNodeId,
+        properties: impl IntoIterator<Item = (K, V)>,
+    ) -> FalkorResult<()>
+    where
+        K: Into<String>,
+        V: Into<BulkValue>,
+    {
+        self.encoder.add_relationship(
+            relationship_type,
+            src,
+            dst,
+            collect_properties(properties),
+        )?;
+        self.send_ready().await
+    }
+
+    /// What has been written to the server so far.
+    pub fn report(&self) -> &BulkLoadReport {
+        &self.report
+    }
+
+    /// Sends everything still queued.
+    ///
+    /// # Returns
+    /// A [`BulkLoadReport`] with the number of nodes and relationships created, per label and per
+    /// relationship type.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Finish Bulk Load", skip_all, level = "info")
+    )]
+    pub async fn finish(mut self) -> FalkorResult<BulkLoadReport> {
+        self.encoder.flush();
+        self.send_ready().await?;
+        Ok(self.report)
+    }
+
+    async fn send_ready(&mut self) -> FalkorResult<()> {
+        while let Some(chunk) = self.encoder.take_ready() {
+            self.send(chunk).await?;
+        }
+        Ok(())
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Send Bulk Chunk", skip_all, level = "debug")
+    )]
+    async fn send(
+        &mut self,
+        chunk: BulkChunk,
+    ) -> FalkorResult<()> {
+        let cmd = chunk.to_cmd(&self.graph_name, self.report.commands == 0);
+        self.client
+            .borrow_connection(self.client.clone())
+            .await?
+            .execute_cmd(&cmd)
+            .await?;
+        self.report.record(&chunk);
+        Ok(())
+    }
+}
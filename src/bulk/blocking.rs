@@ -0,0 +1,147 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+use super::{collect_properties, BulkChunk, BulkEncoder, BulkLoadReport, BulkNodeId, BulkValue};
+use crate::{client::blocking::FalkorSyncClientInner, FalkorResult};
+use std::sync::Arc;
+
+/// Loads a new graph through `GRAPH.BULK`, created with [`FalkorSyncClient::bulk_loader`](crate::FalkorSyncClient::bulk_loader).
+///
+/// Nodes and relationships are encoded as they are added and sent in chunks of at most
+/// [`with_max_chunk_bytes`](Self::with_max_chunk_bytes) bytes, so memory use stays bounded however
+/// large the load. The first chunk creates the graph and fails if it already exists. Call
+/// [`finish`](Self::finish) to send the last, partly filled chunk; dropping the loader discards it.
+///
+/// Each chunk is committed on its own: if a chunk fails, the chunks before it are already in the
+/// graph, as described by [`report`](Self::report).
+#[must_use = "the last chunk is only sent by `finish`"]
+pub struct BulkLoader {
+    client: Arc<FalkorSyncClientInner>,
+    graph_name: String,
+    encoder: BulkEncoder,
+    report: BulkLoadReport,
+}
+
+impl BulkLoader {
+    pub(crate) fn new(
+        client: Arc<FalkorSyncClientInner>,
+        graph_name: String,
+    ) -> Self {
+        Self {
+            client,
+            graph_name,
+            encoder: BulkEncoder::new(),
+            report: BulkLoadReport::default(),
+        }
+    }
+
+    /// Caps the encoded size of each `GRAPH.BULK` command (32 MiB by default). A single entity
+    /// larger than the cap is still sent, in a command of its own.
+    ///
+    /// # Arguments
+    /// * `max_chunk_bytes`: The maximum payload size of one command, in bytes.
+    pub fn with_max_chunk_bytes(
+        mut self,
+        max_chunk_bytes: usize,
+    ) -> Self {
+        self.encoder.set_max_chunk_bytes(max_chunk_bytes);
+        self
+    }
+
+    /// Queues a node, sending a chunk first if the node does not fit in the current one.
+    ///
+    /// # Arguments
+    /// * `labels`: The node's labels; at least one, none of them containing `:`.
+    /// * `properties`: The node's properties, as `(key, value)` pairs.
+    ///
+    /// # Returns
+    /// A [`BulkNodeId`] to connect relationships to this node.
+    pub fn add_node<K, V>(
+        &mut self,
+        labels: &[&str],
+        properties: impl IntoIterator<Item = (K, V)>,
+    ) -> FalkorResult<BulkNodeId>
+    where
+        K: Into<String>,
+        V: Into<BulkValue>,
+    {
+        let id = self
+            .encoder
+            .add_node(labels, collect_properties(properties))?;
+        self.send_ready()?;
+        Ok(id)
+    }
+
+    /// Queues a relationship between two nodes added to this loader, sending a chunk first if the
+    /// relationship does not fit in the current one.
+    ///
+    /// # Arguments
+    /// * `relationship_type`: The relationship's type.
+    /// * `src`: The node the relationship starts from.
+    /// * `dst`: The node the relationship points to.
+    /// * `properties`: The relationship's properties, as `(key, value)` pairs.
+    pub fn add_relationship<K, V>(
+        &mut self,
+        relationship_type: &str,
+        src: BulkNodeId,
+        dst: BulkNodeId,
+        properties: impl IntoIterator<Item = (K, V)>,
+    ) -> FalkorResult<()>
+    where
+        K: Into<String>,
+        V: Into<BulkValue>,
+    {
+        self.encoder.add_relationship(
+            relationship_type,
+            src,
+            dst,
+            collect_properties(properties),
+        )?;
+        self.send_ready()
+    }
+
+    /// What has been written to the server so far.
+    pub fn report(&self) -> &BulkLoadReport {
+        &self.report
+    }
+
+    /// Sends everything still queued.
+    ///
+    /// # Returns
+    /// A [`BulkLoadReport`] with the number of nodes and relationships created, per label and per
+    /// relationship type.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Finish Bulk Load", skip_all, level = "info")
+    )]
+    pub fn finish(mut self) -> FalkorResult<BulkLoadReport> {
+        self.encoder.flush();
+        self.send_ready()?;
+        Ok(self.report)
+    }
+
+    fn send_ready(&mut self) -> FalkorResult<()> {
+        while let Some(chunk) = self.encoder.take_ready() {
+            self.send(chunk)?;
+        }
+        Ok(())
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Send Bulk Chunk", skip_all, level = "debug")
+    )]
+    fn send(
+        &mut self,
+        chunk: BulkChunk,
+    ) -> FalkorResult<()> {
+        let cmd = chunk.to_cmd(&self.graph_name, self.report.commands == 0);
+        self.client
+            .borrow_connection(self.client.clone())?
+            .execute_cmd(&cmd)?;
+        self.report.record(&chunk);
+        Ok(())
+    }
+}
@@ -0,0 +1,680 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Bulk loading through FalkorDB's `GRAPH.BULK` command.
+//!
+//! `GRAPH.BULK` creates a new graph from binary-encoded nodes and relationships, skipping the
+//! Cypher parser entirely, which makes it far faster than `UNWIND` batches for initial loads. A
+//! command carries a list of node blobs followed by a list of relationship blobs; each blob starts
+//! with a header naming its label (or relationship type) and property keys, followed by one record
+//! per entity. Nodes get consecutive IDs in the order they are sent, starting from 0 in the new
+//! graph, which is how relationship records refer to their endpoints.
+//!
+//! [`BulkEncoder`] does the encoding and chunking without any I/O; the sync
+//! [`BulkLoader`](blocking::BulkLoader) and async [`AsyncBulkLoader`](asynchronous::AsyncBulkLoader)
+//! send each chunk as soon as it is full.
+
+use crate::{FalkorDBError, FalkorResult};
+use std::collections::{BTreeMap, VecDeque};
+
+pub(crate) mod blocking;
+
+#[cfg(feature = "tokio")]
+pub(crate) mod asynchronous;
+
+/// The type tags FalkorDB expects before each property value in a bulk record.
+const BI_NULL: u8 = 0;
+const BI_BOOL: u8 = 1;
+const BI_DOUBLE: u8 = 2;
+const BI_STRING: u8 = 3;
+const BI_LONG: u8 = 4;
+const BI_ARRAY: u8 = 5;
+
+/// The default upper bound on the encoded size of one `GRAPH.BULK` command.
+pub(crate) const DEFAULT_MAX_CHUNK_BYTES: usize = 32 * 1024 * 1024;
+/// The most blobs one command carries, mirroring the reference bulk loader's token limit.
+const MAX_BLOBS_PER_CHUNK: usize = 1024;
+
+/// A property value that `GRAPH.BULK` can store.
+///
+/// The bulk format carries only scalars and (nested) arrays of them. Build one with `From`, e.g.
+/// `BulkValue::from("Alice")`, `BulkValue::from(42)` or `BulkValue::from(vec![1.5, 2.5])`; `None`
+/// becomes [`BulkValue::Null`], which leaves the property unset.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BulkValue {
+    /// No value: the property is not set on the entity.
+    Null,
+    /// A boolean.
+    Bool(bool),
+    /// A 64-bit integer.
+    I64(i64),
+    /// A 64-bit float.
+    F64(f64),
+    /// A string, which must not contain a NUL byte.
+    String(String),
+    /// An array of values.
+    Array(Vec<BulkValue>),
+}
+
+macro_rules! impl_bulk_value_from {
+    ($($t:ty => $variant:ident),* $(,)?) => {$(
+        impl From<$t> for BulkValue {
+            fn from(value: $t) -> Self {
+                BulkValue::$variant(value.into())
+            }
+        }
+    )*};
+}
+impl_bulk_value_from!(
+    bool => Bool,
+    i8 => I64,
+    i16 => I64,
+    i32 => I64,
+    i64 => I64,
+    u8 => I64,
+    u16 => I64,
+    u32 => I64,
+    f32 => F64,
+    f64 => F64,
+    String => String,
+    &str => String,
+);
+
+impl<T: Into<BulkValue>> From<Option<T>> for BulkValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(BulkValue::Null, Into::into)
+    }
+}
+
+impl<T: Into<BulkValue>> From<Vec<T>> for BulkValue {
+    fn from(value: Vec<T>) -> Self {
+        BulkValue::Array(value.into_iter().map(Into::into).collect())
+    }
+}
+
+/// A node queued in a bulk load, used to connect relationships to it.
+///
+/// Because `GRAPH.BULK` only creates new graphs, [`id`](Self::id) is also the ID the node has in
+/// the loaded graph, so it can be matched with `WHERE ID(n) = $id` afterwards.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct BulkNodeId(u64);
+
+impl BulkNodeId {
+    /// The node's ID in the loaded graph.
+    pub fn id(self) -> u64 {
+        self.0
+    }
+}
+
+/// What a bulk load has written to the server so far.
+///
+/// Only chunks the server accepted are counted, so after an error this describes the part of the
+/// load that was committed.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct BulkLoadReport {
+    /// The number of nodes created.
+    pub nodes_created: u64,
+    /// The number of relationships created.
+    pub relationships_created: u64,
+    /// Nodes created per label; a node with several labels counts towards each of them.
+    pub labels: BTreeMap<String, u64>,
+    /// Relationships created per relationship type.
+    pub relationship_types: BTreeMap<String, u64>,
+    /// The number of `GRAPH.BULK` commands sent.
+    pub commands: usize,
+}
+
+impl BulkLoadReport {
+    fn record(
+        &mut self,
+        chunk: &BulkChunk,
+    ) {
+        self.nodes_created += chunk.nodes;
+        self.relationships_created += chunk.relationships;
+        for (label, count) in &chunk.labels {
+            *self.labels.entry(label.clone()).or_default() += count;
+        }
+        for (relationship_type, count) in &chunk.relationship_types {
+            *self
+                .relationship_types
+                .entry(relationship_type.clone())
+                .or_default() += count;
+        }
+        self.commands += 1;
+    }
+}
+
+fn bulk_err(message: impl Into<String>) -> FalkorDBError {
+    FalkorDBError::BulkEncoding {
+        message: message.into(),
+    }
+}
+
+/// Appends `value` as a NUL-terminated string, as the bulk format stores every name and string.
+fn push_c_str(
+    value: &str,
+    out: &mut Vec<u8>,
+) -> FalkorResult<()> {
+    if value.contains('\0') {
+        return Err(bulk_err(format!("'{value}' contains a NUL byte")));
+    }
+    out.extend_from_slice(value.as_bytes());
+    out.push(0);
+    Ok(())
+}
+
+fn encode_value(
+    value: &BulkValue,
+    out: &mut Vec<u8>,
+) -> FalkorResult<()> {
+    match value {
+        BulkValue::Null => out.push(BI_NULL),
+        BulkValue::Bool(boolean) => {
+            out.push(BI_BOOL);
+            out.push(u8::from(*boolean));
+        }
+        BulkValue::I64(int) => {
+            out.push(BI_LONG);
+            out.extend_from_slice(&int.to_le_bytes());
+        }
+        BulkValue::F64(float) => {
+            out.push(BI_DOUBLE);
+            out.extend_from_slice(&float.to_le_bytes());
+        }
+        BulkValue::String(string) => {
+            out.push(BI_STRING);
+            push_c_str(string, out)?;
+        }
+        BulkValue::Array(items) => {
+            out.push(BI_ARRAY);
+            out.extend_from_slice(&(items.len() as i64).to_le_bytes());
+            for item in items {
+                encode_value(item, out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Encodes an entity's property values in key order, rejecting repeated keys.
+fn encode_properties(
+    properties: &[(String, BulkValue)],
+    out: &mut Vec<u8>,
+) -> FalkorResult<()> {
+    for (index, (key, value)) in properties.iter().enumerate() {
+        if properties[..index].iter().any(|(other, _)| other == key) {
+            return Err(bulk_err(format!(
+                "property '{key}' is given more than once"
+            )));
+        }
+        encode_value(value, out).map_err(|err| match err {
+            FalkorDBError::BulkEncoding { message } => {
+                bulk_err(format!("property '{key}': {message}"))
+            }
+            other => other,
+        })?;
+    }
+    Ok(())
+}
+
+/// One label's (or relationship type's) run of records, under a header naming its property keys.
+struct Blob {
+    name: String,
+    keys: Vec<String>,
+    bytes: Vec<u8>,
+}
+
+impl Blob {
+    fn new(
+        name: String,
+        keys: Vec<String>,
+    ) -> FalkorResult<Self> {
+        let mut bytes = Vec::new();
+        push_c_str(&name, &mut bytes)?;
+        bytes.extend_from_slice(&(keys.len() as u32).to_le_bytes());
+        for key in &keys {
+            push_c_str(key, &mut bytes)?;
+        }
+        Ok(Self { name, keys, bytes })
+    }
+
+    fn matches(
+        &self,
+        name: &str,
+        properties: &[(String, BulkValue)],
+    ) -> bool {
+        self.name == name
+            && self.keys.len() == properties.len()
+            && self
+                .keys
+                .iter()
+                .zip(properties)
+                .all(|(key, (other, _))| key == other)
+    }
+}
+
+/// The content of one `GRAPH.BULK` command.
+#[derive(Default)]
+pub(crate) struct BulkChunk {
+    node_blobs: Vec<Blob>,
+    relationship_blobs: Vec<Blob>,
+    nodes: u64,
+    relationships: u64,
+    labels: BTreeMap<String, u64>,
+    relationship_types: BTreeMap<String, u64>,
+    bytes: usize,
+}
+
+impl BulkChunk {
+    fn is_empty(&self) -> bool {
+        self.nodes == 0 && self.relationships == 0
+    }
+
+    fn blob_count(&self) -> usize {
+        self.node_blobs.len() + self.relationship_blobs.len()
+    }
+
+    /// Builds the command; only the first command of a load carries `BEGIN`, which makes the
+    /// server refuse to load into a graph that already exists.
+    pub(crate) fn to_cmd(
+        &self,
+        graph_name: &str,
+        begin: bool,
+    ) -> redis::Cmd {
+        let mut cmd = redis::cmd("GRAPH.BULK");
+        cmd.arg(graph_name);
+        if begin {
+            cmd.arg("BEGIN");
+        }
+        cmd.arg(self.nodes)
+            .arg(self.relationships)
+            .arg(self.node_blobs.len())
+            .arg(self.relationship_blobs.len());
+        for blob in self.node_blobs.iter().chain(&self.relationship_blobs) {
+            cmd.arg(blob.bytes.as_slice());
+        }
+        cmd
+    }
+}
+
+/// Encodes nodes and relationships into size-bounded `GRAPH.BULK` chunks, without any I/O.
+///
+/// A record is only ever appended to the *last* blob of its kind, so nodes are sent, and therefore
+/// numbered by the server, in the order they were added.
+pub(crate) struct BulkEncoder {
+    max_chunk_bytes: usize,
+    current: BulkChunk,
+    ready: VecDeque<BulkChunk>,
+    next_node_id: u64,
+}
+
+impl BulkEncoder {
+    pub(crate) fn new() -> Self {
+        Self {
+            max_chunk_bytes: DEFAULT_MAX_CHUNK_BYTES,
+            current: BulkChunk::default(),
+            ready: VecDeque::new(),
+            next_node_id: 0,
+        }
+    }
+
+    pub(crate) fn set_max_chunk_bytes(
+        &mut self,
+        max_chunk_bytes: usize,
+    ) {
+        self.max_chunk_bytes = max_chunk_bytes.max(1);
+    }
+
+    pub(crate) fn add_node(
+        &mut self,
+        labels: &[&str],
+        properties: Vec<(String, BulkValue)>,
+    ) -> FalkorResult<BulkNodeId> {
+        if labels.is_empty() {
+            return Err(bulk_err("a node needs at least one label"));
+        }
+        if let Some(label) = labels
+            .iter()
+            .find(|label| label.is_empty() || label.contains(':'))
+        {
+            return Err(bulk_err(format!(
+                "label '{label}' must be non-empty and must not contain ':'"
+            )));
+        }
+        let mut record = Vec::new();
+        encode_properties(&properties, &mut record)?;
+        self.push(labels.join(":"), properties, record, true)?;
+
+        for label in labels {
+            *self.current.labels.entry(label.to_string()).or_default() += 1;
+        }
+        self.current.nodes += 1;
+        let id = BulkNodeId(self.next_node_id);
+        self.next_node_id += 1;
+        Ok(id)
+    }
+
+    pub(crate) fn add_relationship(
+        &mut self,
+        relationship_type: &str,
+        src: BulkNodeId,
+        dst: BulkNodeId,
+        properties: Vec<(String, BulkValue)>,
+    ) -> FalkorResult<()> {
+        if relationship_type.is_empty() {
+            return Err(bulk_err("a relationship type must be non-empty"));
+        }
+        if let Some(unknown) = [src, dst].into_iter().find(|id| id.0 >= self.next_node_id) {
+            return Err(bulk_err(format!(
+                "node {} was not added to this bulk load",
+                unknown.0
+            )));
+        }
+        let mut record = Vec::new();
+        record.extend_from_slice(&src.0.to_le_bytes());
+        record.extend_from_slice(&dst.0.to_le_bytes());
+        encode_properties(&properties, &mut record)?;
+        self.push(relationship_type.to_string(), properties, record, false)?;
+
+        *self
+            .current
+            .relationship_types
+            .entry(relationship_type.to_string())
+            .or_default() += 1;
+        self.current.relationships += 1;
+        Ok(())
+    }
+
+    /// Appends an encoded record to the current chunk, first sealing the chunk when the record
+    /// would push it past the size or blob limits. A record larger than the limit on its own
+    /// still goes out, alone in its chunk.
+    fn push(
+        &mut self,
+        name: String,
+        properties: Vec<(String, BulkValue)>,
+        record: Vec<u8>,
+        is_node: bool,
+    ) -> FalkorResult<()> {
+        let blobs = if is_node {
+            &self.current.node_blobs
+        } else {
+            &self.current.relationship_blobs
+        };
+        let reuse = blobs
+            .last()
+            .is_some_and(|blob| blob.matches(&name, &properties));
+        let new_blob = if reuse {
+            None
+        } else {
+            let keys = properties.into_iter().map(|(key, _)| key).collect();
+            Some(Blob::new(name, keys)?)
+        };
+
+        let added = record.len() + new_blob.as_ref().map_or(0, |blob| blob.bytes.len());
+        let full = self.current.bytes + added > self.max_chunk_bytes
+            || (new_blob.is_some() && self.current.blob_count() >= MAX_BLOBS_PER_CHUNK);
+        if full && !self.current.is_empty() {
+            self.ready.push_back(std::mem::take(&mut self.current));
+        }
+
+        let new_blob = match (new_blob, full) {
+            (Some(blob), _) => Some(blob),
+            // The record continued the last blob, but that blob left with the sealed chunk.
+            (None, true) => {
+                let last = if is_node {
+                    self.ready.back().and_then(|chunk| chunk.node_blobs.last())
+                } else {
+                    self.ready
+                        .back()
+                        .and_then(|chunk| chunk.relationship_blobs.last())
+                };
+                last.map(|blob| Blob::new(blob.name.clone(), blob.keys.clone()))
+                    .transpose()?
+            }
+            (None, false) => None,
+        };
+        let blobs = if is_node {
+            &mut self.current.node_blobs
+        } else {
+            &mut self.current.relationship_blobs
+        };
+        if let Some(blob) = new_blob {
+            self.current.bytes += blob.bytes.len();
+            blobs.push(blob);
+        }
+        let blob = blobs.last_mut().expect("a blob was just ensured");
+        self.current.bytes += record.len();
+        blob.bytes.extend_from_slice(&record);
+        Ok(())
+    }
+
+    /// Takes the next chunk that is full and ready to send.
+    pub(crate) fn take_ready(&mut self) -> Option<BulkChunk> {
+        self.ready.pop_front()
+    }
+
+    /// Seals the partly filled chunk, so every remaining chunk is ready to send.
+    pub(crate) fn flush(&mut self) {
+        if !self.current.is_empty() {
+            self.ready.push_back(std::mem::take(&mut self.current));
+        }
+    }
+}
+
+/// Collects caller-supplied properties into the encoder's owned form.
+pub(crate) fn collect_properties<K, V>(
+    properties: impl IntoIterator<Item = (K, V)>
+) -> Vec<(String, BulkValue)>
+where
+    K: Into<String>,
+    V: Into<BulkValue>,
+{
+    properties
+        .into_iter()
+        .map(|(key, value)| (key.into(), value.into()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn props(pairs: &[(&str, BulkValue)]) -> Vec<(String, BulkValue)> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect()
+    }
+
+    fn args(cmd: &redis::Cmd) -> Vec<Vec<u8>> {
+        cmd.args_iter()
+            .map(|arg| match arg {
+                redis::Arg::Simple(bytes) => bytes.to_vec(),
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    fn drain(encoder: &mut BulkEncoder) -> Vec<BulkChunk> {
+        encoder.flush();
+        std::iter::from_fn(|| encoder.take_ready()).collect()
+    }
+
+    #[test]
+    fn test_encode_values() {
+        let mut out = Vec::new();
+        encode_value(&BulkValue::from(true), &mut out).unwrap();
+        encode_value(&BulkValue::from(-2), &mut out).unwrap();
+        encode_value(&BulkValue::from(0.5), &mut out).unwrap();
+        encode_value(&BulkValue::from("hi"), &mut out).unwrap();
+        encode_value(&BulkValue::from(None::<i64>), &mut out).unwrap();
+        encode_value(&BulkValue::from(vec![7]), &mut out).unwrap();
+
+        let mut expected = vec![BI_BOOL, 1, BI_LONG];
+        expected.extend_from_slice(&(-2i64).to_le_bytes());
+        expected.push(BI_DOUBLE);
+        expected.extend_from_slice(&0.5f64.to_le_bytes());
+        expected.extend_from_slice(&[BI_STRING, b'h', b'i', 0, BI_NULL, BI_ARRAY]);
+        expected.extend_from_slice(&1i64.to_le_bytes());
+        expected.push(BI_LONG);
+        expected.extend_from_slice(&7i64.to_le_bytes());
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_encode_rejects_nul_strings() {
+        let mut out = Vec::new();
+        assert!(matches!(
+            encode_value(&BulkValue::from("a\0b"), &mut out),
+            Err(FalkorDBError::BulkEncoding { .. })
+        ));
+    }
+
+    #[test]
+    fn test_chunk_command_layout() {
+        let mut encoder = BulkEncoder::new();
+        let alice = encoder
+            .add_node(&["Person"], props(&[("name", "Alice".into())]))
+            .unwrap();
+        let bob = encoder
+            .add_node(&["Person"], props(&[("name", "Bob".into())]))
+            .unwrap();
+        encoder
+            .add_relationship("KNOWS", alice, bob, Vec::new())
+            .unwrap();
+        let chunks = drain(&mut encoder);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!((alice.id(), bob.id()), (0, 1));
+
+        let begin = args(&chunks[0].to_cmd("social", true));
+        assert_eq!(
+            begin[..7],
+            [
+                b"GRAPH.BULK".to_vec(),
+                b"social".to_vec(),
+                b"BEGIN".to_vec(),
+                b"2".to_vec(),
+                b"1".to_vec(),
+                b"1".to_vec(),
+                b"1".to_vec(),
+            ]
+        );
+
+        let mut nodes = b"Person\0".to_vec();
+        nodes.extend_from_slice(&1u32.to_le_bytes());
+        nodes.extend_from_slice(b"name\0");
+        nodes.extend_from_slice(&[BI_STRING, b'A', b'l', b'i', b'c', b'e', 0]);
+        nodes.extend_from_slice(&[BI_STRING, b'B', b'o', b'b', 0]);
+        assert_eq!(begin[7], nodes);
+
+        let mut relationships = b"KNOWS\0".to_vec();
+        relationships.extend_from_slice(&0u32.to_le_bytes());
+        relationships.extend_from_slice(&0u64.to_le_bytes());
+        relationships.extend_from_slice(&1u64.to_le_bytes());
+        assert_eq!(begin[8], relationships);
+        assert_eq!(begin.len(), 9);
+
+        assert_eq!(args(&chunks[0].to_cmd("social", false))[2], b"2".to_vec());
+    }
+
+    #[test]
+    fn test_new_blob_when_label_or_keys_change() {
+        let mut encoder = BulkEncoder::new();
+        encoder
+            .add_node(&["Person"], props(&[("name", "Alice".into())]))
+            .unwrap();
+        encoder
+            .add_node(&["Person"], props(&[("age", 3.into())]))
+            .unwrap();
+        encoder
+            .add_node(&["Person", "Admin"], props(&[("age", 4.into())]))
+            .unwrap();
+        encoder
+            .add_node(&["Person", "Admin"], props(&[("age", 5.into())]))
+            .unwrap();
+        let chunks = drain(&mut encoder);
+        let names = chunks[0]
+            .node_blobs
+            .iter()
+            .map(|blob| blob.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Person", "Person", "Person:Admin"]);
+        assert_eq!(chunks[0].labels["Person"], 4);
+        assert_eq!(chunks[0].labels["Admin"], 2);
+    }
+
+    #[test]
+    fn test_chunks_are_size_bounded() {
+        let mut encoder = BulkEncoder::new();
+        encoder.set_max_chunk_bytes(64);
+        for index in 0..20 {
+            encoder
+                .add_node(&["Item"], props(&[("n", index.into())]))
+                .unwrap();
+        }
+        let chunks = drain(&mut encoder);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|chunk| chunk.bytes <= 64));
+        assert!(chunks.iter().all(|chunk| chunk.node_blobs.len() == 1));
+        assert_eq!(chunks.iter().map(|chunk| chunk.nodes).sum::<u64>(), 20);
+        assert!(chunks.iter().all(|chunk| chunk.bytes
+            == chunk
+                .node_blobs
+                .iter()
+                .map(|blob| blob.bytes.len())
+                .sum::<usize>()));
+    }
+
+    #[test]
+    fn test_oversized_record_gets_its_own_chunk() {
+        let mut encoder = BulkEncoder::new();
+        encoder.set_max_chunk_bytes(16);
+        encoder
+            .add_node(&["Doc"], props(&[("body", "x".repeat(100).into())]))
+            .unwrap();
+        encoder.add_node(&["Doc"], Vec::new()).unwrap();
+        let chunks = drain(&mut encoder);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].nodes, 1);
+    }
+
+    #[test]
+    fn test_rejects_invalid_entities() {
+        let mut encoder = BulkEncoder::new();
+        assert!(encoder.add_node(&[], Vec::new()).is_err());
+        assert!(encoder.add_node(&["A:B"], Vec::new()).is_err());
+        assert!(encoder
+            .add_node(&["A"], props(&[("k", 1.into()), ("k", 2.into())]))
+            .is_err());
+        let node = encoder.add_node(&["A"], Vec::new()).unwrap();
+        assert!(encoder
+            .add_relationship("R", node, BulkNodeId(5), Vec::new())
+            .is_err());
+        assert!(encoder
+            .add_relationship("", node, node, Vec::new())
+            .is_err());
+        // Rejected nodes do not take up an ID.
+        assert_eq!(node.id(), 0);
+    }
+
+    #[test]
+    fn test_report_records_chunks() {
+        let mut encoder = BulkEncoder::new();
+        let a = encoder.add_node(&["A", "B"], Vec::new()).unwrap();
+        encoder.add_relationship("R", a, a, Vec::new()).unwrap();
+        let chunks = drain(&mut encoder);
+        let mut report = BulkLoadReport::default();
+        report.record(&chunks[0]);
+        assert_eq!(report.nodes_created, 1);
+        assert_eq!(report.relationships_created, 1);
+        assert_eq!(
+            report.labels,
+            BTreeMap::from([("A".into(), 1), ("B".into(), 1)])
+        );
+        assert_eq!(report.relationship_types["R"], 1);
+        assert_eq!(report.commands, 1);
+    }
+}
@@ -4,7 +4,10 @@
  */
 
 use crate::{
-    client::{ConnectionStrategy, FalkorClientProvider, ProvidesSyncConnections, ReadPreference},
+    client::{
+        ConnectionStrategy, FalkorClientProvider, Instrumentation, ProvidesSyncConnections,
+        ReadPreference,
+    },
     connection::{
         asynchronous::{BorrowedAsyncConnection, FalkorAsyncConnection},
         blocking::FalkorSyncConnection,
@@ -12,12 +15,14 @@ use crate::{
     parser::{parse_config_hashmap, redis_value_as_untyped_string_vec},
     AsyncGraph, ConfigValue, FalkorConnectionInfo, FalkorDBError, FalkorResult, RetryPolicy,
 };
-use std::num::NonZeroUsize;
+use std::future::Future;
+use std::num::{NonZeroU8, NonZeroUsize};
+use std::pin::Pin;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::{collections::HashMap, sync::Arc};
 use tokio::{
     runtime::{Handle, RuntimeFlavor},
-    sync::{mpsc, Mutex},
+    sync::{mpsc, Mutex, MutexGuard},
     task,
 };
 
@@ -32,16 +37,87 @@ pub(crate) struct AsyncConnectionPool {
 /// Holds a fixed number of independent, shared multiplexed connections and hands out
 /// cheap clones round-robin. Used by the [`ConnectionStrategy::Multiplexed`] strategy.
 pub(crate) struct MultiplexedExecutor {
-    conns: Vec<FalkorAsyncConnection>,
+    conns: parking_lot::RwLock<MultiplexedConnections>,
     next: AtomicUsize,
+    /// Whether the connections were resolved through Sentinel, and so must be re-resolved
+    /// (rather than merely reconnected) when the node they point at fails over.
+    reresolve_on_failover: bool,
+    /// Per-socket in-flight cap, kept so re-resolved connections are built like the originals.
+    max_inflight: Option<NonZeroUsize>,
+}
+
+/// The current set of multiplexed connections, replaced as a whole on re-resolution.
+struct MultiplexedConnections {
+    conns: Vec<FalkorAsyncConnection>,
+    /// Bumped on every replacement, so concurrent callers that all observed the same failure
+    /// re-resolve only once.
+    generation: u64,
 }
 
 impl MultiplexedExecutor {
+    fn new(
+        conns: Vec<FalkorAsyncConnection>,
+        reresolve_on_failover: bool,
+        max_inflight: Option<NonZeroUsize>,
+    ) -> Self {
+        Self {
+            conns: parking_lot::RwLock::new(MultiplexedConnections {
+                conns,
+                generation: 0,
+            }),
+            next: AtomicUsize::new(0),
+            reresolve_on_failover,
+            max_inflight,
+        }
+    }
+
     /// Returns the next connection (a cheap clone of a shared multiplexed socket),
-    /// selected round-robin across the underlying connections.
-    fn pick(&self) -> FalkorAsyncConnection {
-        let idx = self.next.fetch_add(1, Ordering::Relaxed) % self.conns.len();
-        self.conns[idx].clone_handle()
+    /// selected round-robin across the underlying connections, together with the
+    /// generation of the connection set it was taken from.
+    fn pick(&self) -> (FalkorAsyncConnection, u64) {
+        let current = self.conns.read();
+        let idx = self.next.fetch_add(1, Ordering::Relaxed) % current.conns.len();
+        (current.conns[idx].clone_handle(), current.generation)
+    }
+
+    /// The generation of the current connection set.
+    fn generation(&self) -> u64 {
+        self.conns.read().generation
+    }
+
+    /// Swap in a freshly resolved connection set. Callers already holding a clone keep
+    /// their old socket until they finish; new commands go to the new connections.
+    fn replace(
+        &self,
+        conns: Vec<FalkorAsyncConnection>,
+    ) {
+        let mut current = self.conns.write();
+        current.conns = conns;
+        current.generation += 1;
+    }
+
+    /// Swap in the connection set built by `resolve`, unless the set the failed command ran on
+    /// (`generation`) has already been replaced. `resolve` is handed the guard of `lock` and must
+    /// hold it until the set is built: that serializes re-resolution, so of several callers that
+    /// observed the same failure only the first rebuilds the set and the others return once it
+    /// is in place.
+    async fn reresolve_with<'a, P, F, Fut>(
+        &self,
+        lock: &'a Mutex<P>,
+        generation: u64,
+        resolve: F,
+    ) -> FalkorResult<()>
+    where
+        F: FnOnce(MutexGuard<'a, P>) -> Fut,
+        Fut: Future<Output = FalkorResult<Vec<FalkorAsyncConnection>>>,
+    {
+        let guard = lock.lock().await;
+        if self.generation() != generation {
+            return Ok(());
+        }
+        let conns = resolve(guard).await?;
+        self.replace(conns);
+        Ok(())
     }
 }
 
@@ -57,10 +133,11 @@ pub(crate) enum AsyncExecutor {
 /// The idea is that each member here is either Copy, or locked in some form, and the public struct only has an Arc to this struct
 /// allowing thread safe operations and cloning
 pub struct FalkorAsyncClientInner {
-    _inner: Mutex<FalkorClientProvider>,
+    /// Shared with the [sessions](FalkorAsyncSession) opened from this client, which reconnect
+    /// through the same provider.
+    _inner: Arc<Mutex<FalkorClientProvider>>,
 
-    /// The effective strategy this client runs (may differ from the requested one, e.g.
-    /// a Sentinel deployment that falls back to pooling).
+    /// The strategy this client runs.
     strategy: ConnectionStrategy,
     /// Backend serving primary (read-write) commands.
     primary: AsyncExecutor,
@@ -71,11 +148,17 @@ pub struct FalkorAsyncClientInner {
     /// Opt-in retry policy applied to eligible operations; [`disabled`](RetryPolicy::disabled) by
     /// default, in which case every operation is attempted exactly once.
     retry_policy: RetryPolicy,
-    /// When set, the raw query text is recorded as a span field (opt-in via `with_query_logging`).
+    /// The opt-in instrumentation settings (`with_query_logging`, `with_trace_context`,
+    /// `with_query_stats`).
     #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
-    query_logging: bool,
+    instrumentation: Instrumentation,
     /// Client-wide default read preference for read-only queries (overridable per query).
     read_preference: ReadPreference,
+    /// Page size for transparently paged read-only queries; `None` fetches every reply whole.
+    stream_page_size: Option<NonZeroUsize>,
+    /// Set on a [session](FalkorAsyncSession)'s inner when its pinned connection was taken from
+    /// a pool, which gets it back once the session and every graph selected from it are dropped.
+    release_to: Option<mpsc::Sender<FalkorAsyncConnection>>,
 }
 
 impl FalkorAsyncClientInner {
@@ -90,6 +173,11 @@ impl FalkorAsyncClientInner {
         self.read_preference
     }
 
+    /// The page size for transparently paged read-only queries, if enabled.
+    pub(crate) fn stream_page_size(&self) -> Option<NonZeroUsize> {
+        self.stream_page_size
+    }
+
     /// The active connection strategy (used for observability span fields and metric labels).
     #[cfg(any(feature = "tracing", feature = "metrics"))]
     pub(crate) fn strategy(&self) -> ConnectionStrategy {
@@ -99,7 +187,20 @@ impl FalkorAsyncClientInner {
     /// Whether raw query text may be recorded on spans (opt-in; `false` by default).
     #[cfg(feature = "tracing")]
     pub(crate) fn query_logging(&self) -> bool {
-        self.query_logging
+        self.instrumentation.query_logging
+    }
+
+    /// Whether the current span's `traceparent` is appended to each query (opt-in; `false` by
+    /// default).
+    #[cfg(feature = "opentelemetry")]
+    pub(crate) fn trace_context(&self) -> bool {
+        self.instrumentation.trace_context
+    }
+
+    /// The query statistics registry, when enabled with `with_query_stats`.
+    #[cfg(feature = "query-stats")]
+    pub(crate) fn query_stats(&self) -> Option<&crate::QueryStats> {
+        self.instrumentation.query_stats.as_ref()
     }
 
     /// Borrow a connection from the given executor. For the pooled strategy this waits
@@ -112,17 +213,7 @@ impl FalkorAsyncClientInner {
     ) -> FalkorResult<BorrowedAsyncConnection> {
         match executor {
             AsyncExecutor::Pooled(pool) => {
-                #[cfg(feature = "metrics")]
-                let wait_start = std::time::Instant::now();
-                let conn = pool
-                    .rx
-                    .lock()
-                    .await
-                    .recv()
-                    .await
-                    .ok_or(FalkorDBError::EmptyConnection)?;
-                #[cfg(feature = "metrics")]
-                crate::observability::record_pool_wait(readonly, wait_start.elapsed());
+                let conn = Self::take_from(pool, readonly).await?;
                 Ok(BorrowedAsyncConnection::new(
                     conn,
                     pool.tx.clone(),
@@ -130,14 +221,68 @@ impl FalkorAsyncClientInner {
                     readonly,
                 ))
             }
-            AsyncExecutor::Multiplexed(executor) => Ok(BorrowedAsyncConnection::new_multiplexed(
-                executor.pick(),
-                pool_owner,
-                readonly,
-            )),
+            AsyncExecutor::Multiplexed(executor) => {
+                let (conn, generation) = executor.pick();
+                Ok(BorrowedAsyncConnection::new_multiplexed(
+                    conn,
+                    generation,
+                    executor.reresolve_on_failover,
+                    pool_owner,
+                    readonly,
+                ))
+            }
         }
     }
 
+    /// Take a connection out of a pool, waiting for one to become available. The caller is
+    /// responsible for sending it back through the pool's `tx`.
+    async fn take_from(
+        pool: &AsyncConnectionPool,
+        #[cfg_attr(not(feature = "metrics"), allow(unused_variables))] readonly: bool,
+    ) -> FalkorResult<FalkorAsyncConnection> {
+        #[cfg(feature = "metrics")]
+        let wait_start = std::time::Instant::now();
+        let conn = pool
+            .rx
+            .lock()
+            .await
+            .recv()
+            .await
+            .ok_or(FalkorDBError::EmptyConnection)?;
+        #[cfg(feature = "metrics")]
+        crate::observability::record_pool_wait(readonly, wait_start.elapsed());
+        Ok(conn)
+    }
+
+    /// Build the inner of a session pinned to one primary connection: a single-connection pool
+    /// sharing this client's provider and settings, with no replica routing. Pooled clients lend
+    /// one of their connections, released back to the pool when the session inner is dropped;
+    /// multiplexed clients share their sockets, so a dedicated connection is opened instead.
+    async fn pinned(&self) -> FalkorResult<Self> {
+        let (conn, release_to) = match &self.primary {
+            AsyncExecutor::Pooled(pool) => {
+                (Self::take_from(pool, false).await?, Some(pool.tx.clone()))
+            }
+            AsyncExecutor::Multiplexed(_) => (self.get_async_connection().await?, None),
+        };
+        let primary = FalkorAsyncClient::pool_from_connections(vec![conn])
+            .map(AsyncExecutor::Pooled)
+            .ok_or(FalkorDBError::EmptyConnection)?;
+        Ok(Self {
+            _inner: self._inner.clone(),
+            strategy: ConnectionStrategy::Pooled {
+                size: NonZeroU8::MIN,
+            },
+            primary,
+            readonly: None,
+            retry_policy: self.retry_policy,
+            instrumentation: self.instrumentation.clone(),
+            read_preference: self.read_preference,
+            stream_page_size: self.stream_page_size,
+            release_to,
+        })
+    }
+
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(
@@ -153,6 +298,22 @@ impl FalkorAsyncClientInner {
         Self::borrow_from(&self.primary, pool_owner, false).await
     }
 
+    /// Borrow a primary connection that no other caller uses until the borrow ends, as needed
+    /// for connection-scoped state such as `WATCH`. Pooled executors lend a pooled connection as
+    /// usual; multiplexed executors share their sockets, so a dedicated connection is opened.
+    pub(crate) async fn borrow_exclusive_connection(
+        &self,
+        pool_owner: Arc<Self>,
+    ) -> FalkorResult<BorrowedAsyncConnection> {
+        match &self.primary {
+            AsyncExecutor::Pooled(_) => Self::borrow_from(&self.primary, pool_owner, false).await,
+            AsyncExecutor::Multiplexed(_) => {
+                let conn = self.get_async_connection().await?;
+                Ok(BorrowedAsyncConnection::new_dedicated(conn, pool_owner))
+            }
+        }
+    }
+
     /// Borrow a connection for a read-only query. When a replica-routed read-only
     /// backend exists the connection is taken from it (serving the query from a
     /// replica), otherwise it falls back to the primary backend.
@@ -192,7 +353,7 @@ impl FalkorAsyncClientInner {
             _ => &self.primary,
         };
         match executor {
-            AsyncExecutor::Multiplexed(executor) => Ok(executor.pick()),
+            AsyncExecutor::Multiplexed(executor) => Ok(executor.pick().0),
             AsyncExecutor::Pooled(_) => {
                 if readonly && self.readonly.is_some() {
                     self.get_async_replica_connection().await
@@ -203,6 +364,57 @@ impl FalkorAsyncClientInner {
         }
     }
 
+    /// Re-resolve a Sentinel-backed multiplexed executor after one of its connections went
+    /// down, swapping in connections to the node Sentinel currently reports. `generation` is
+    /// the connection set the failed command ran on; if it has already been replaced (another
+    /// caller re-resolved first), nothing is done. Executors that were not resolved through
+    /// Sentinel reconnect on their own and are left untouched.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Re-resolve Sentinel Connections", skip_all, level = "info")
+    )]
+    pub(crate) async fn reresolve_multiplexed(
+        &self,
+        readonly: bool,
+        generation: u64,
+    ) -> FalkorResult<()> {
+        let executor = match (&self.readonly, readonly) {
+            (Some(AsyncExecutor::Multiplexed(executor)), true) => executor,
+            (_, false) => match &self.primary {
+                AsyncExecutor::Multiplexed(executor) => executor,
+                AsyncExecutor::Pooled(_) => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+        if !executor.reresolve_on_failover {
+            return Ok(());
+        }
+
+        // Holding the provider lock serializes re-resolution with every other use of the
+        // provider, so the new connections are built from one consistent Sentinel view.
+        let count = self.strategy.connection_count().get() as usize;
+        let max_inflight = executor.max_inflight;
+        // Boxed so callers proving their futures `Send` stop at this trait object instead of
+        // walking the whole provider and connection types again.
+        let rebuild: Pin<Box<dyn Future<Output = FalkorResult<()>> + Send + '_>> = Box::pin(
+            executor.reresolve_with(&self._inner, generation, |mut provider| async move {
+                let mut conns = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let conn = if readonly {
+                        provider
+                            .get_async_replica_connection_manager(max_inflight)
+                            .await?
+                    } else {
+                        provider.get_async_connection_manager(max_inflight).await?
+                    };
+                    conns.push(conn);
+                }
+                Ok(conns)
+            }),
+        );
+        rebuild.await
+    }
+
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(
@@ -227,6 +439,20 @@ impl FalkorAsyncClientInner {
     }
 }
 
+impl Drop for FalkorAsyncClientInner {
+    /// A session's pinned connection is back in its single-slot pool once nothing borrows it, so
+    /// it can be handed back to the pool it was taken from, which has a free slot for it.
+    fn drop(&mut self) {
+        if let (Some(release_to), AsyncExecutor::Pooled(pool)) =
+            (&self.release_to, &mut self.primary)
+        {
+            if let Ok(conn) = pool.rx.get_mut().try_recv() {
+                release_to.try_send(conn).ok();
+            }
+        }
+    }
+}
+
 impl ProvidesSyncConnections for FalkorAsyncClientInner {
     #[cfg_attr(
         feature = "tracing",
@@ -259,35 +485,21 @@ pub struct FalkorAsyncClient {
 }
 
 impl FalkorAsyncClient {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn create(
         mut client: FalkorClientProvider,
         connection_info: FalkorConnectionInfo,
-        requested_strategy: ConnectionStrategy,
+        strategy: ConnectionStrategy,
         max_inflight: Option<NonZeroUsize>,
         retry_policy: RetryPolicy,
-        query_logging: bool,
+        instrumentation: Instrumentation,
         read_preference: ReadPreference,
+        stream_page_size: Option<NonZeroUsize>,
     ) -> FalkorResult<Self> {
         // A multiplexed ConnectionManager built from a Sentinel-resolved client pins to a
-        // single node and reconnects to the same address rather than re-resolving the
-        // current master/replica via Sentinel on failover. The pooled strategy opens a
-        // fresh, Sentinel-resolved connection on every (re)connect, so for Sentinel
-        // deployments we downgrade to an equivalently-sized pool. `connection_strategy()`
-        // reports this effective value.
-        let strategy = match requested_strategy {
-            ConnectionStrategy::Multiplexed { connections } if client.has_sentinel() => {
-                #[cfg(feature = "tracing")]
-                tracing::warn!(
-                    "Sentinel deployment detected: downgrading ConnectionStrategy from \
-                     Multiplexed to Pooled. A multiplexed ConnectionManager pins to a single \
-                     resolved node and will not re-resolve on failover; the pooled strategy \
-                     opens a fresh Sentinel-resolved connection on every reconnect."
-                );
-                ConnectionStrategy::Pooled { size: connections }
-            }
-            other => other,
-        };
-
+        // single node and reconnects to the same address. For Sentinel deployments the
+        // multiplexed executor therefore re-resolves through Sentinel whenever a command
+        // reports the node down or demoted (see `reresolve_multiplexed`).
         let primary = Self::build_executor(&mut client, strategy, max_inflight, false).await?;
 
         // Best-effort replica-routed read-only backend. Absent (transparent fallback to
@@ -303,13 +515,15 @@ impl FalkorAsyncClient {
 
         Ok(Self {
             inner: Arc::new(FalkorAsyncClientInner {
-                _inner: client.into(),
+                _inner: Arc::new(client.into()),
                 strategy,
                 primary,
                 readonly,
                 retry_policy,
-                query_logging,
+                instrumentation,
                 read_preference,
+                stream_page_size,
+                release_to: None,
             }),
             _connection_info: connection_info,
         })
@@ -354,10 +568,11 @@ impl FalkorAsyncClient {
                     };
                     conns.push(conn);
                 }
-                Ok(AsyncExecutor::Multiplexed(MultiplexedExecutor {
+                Ok(AsyncExecutor::Multiplexed(MultiplexedExecutor::new(
                     conns,
-                    next: AtomicUsize::new(0),
-                }))
+                    client.has_sentinel(),
+                    max_inflight,
+                )))
             }
         }
     }
@@ -409,6 +624,16 @@ impl FalkorAsyncClient {
         self.inner.read_preference()
     }
 
+    /// The query statistics registry of this client, or `None` unless it was built with
+    /// [`FalkorClientBuilder::with_query_stats`](crate::FalkorClientBuilder::with_query_stats).
+    ///
+    /// The returned handle is a cheap clone of the registry the client keeps recording into, so it
+    /// can be handed to a debug endpoint and read at any time.
+    #[cfg(feature = "query-stats")]
+    pub fn query_stats(&self) -> Option<crate::QueryStats> {
+        self.inner.query_stats().cloned()
+    }
+
     /// Whether read-only queries (`ro_query` / `call_procedure_ro`) are routed to
     /// replica nodes. This is `true` only for Redis Sentinel deployments that expose
     /// readable replicas; otherwise read-only queries are served by the primary.
@@ -504,12 +729,56 @@ impl FalkorAsyncClient {
         AsyncGraph::new(self.inner.clone(), graph_name)
     }
 
+    /// Starts a [`AsyncBulkLoader`](crate::AsyncBulkLoader) that creates `graph_name` from nodes and
+    /// relationships sent through `GRAPH.BULK`, much faster than `UNWIND` batches for large
+    /// initial loads. The graph must not exist yet.
+    ///
+    /// # Arguments
+    /// * `graph_name`: The name of the graph to create.
+    ///
+    /// # Returns
+    /// A [`AsyncBulkLoader`](crate::AsyncBulkLoader); nothing is sent until its first chunk fills up.
+    pub fn bulk_loader<T: ToString>(
+        &self,
+        graph_name: T,
+    ) -> crate::AsyncBulkLoader {
+        crate::AsyncBulkLoader::new(self.inner.clone(), graph_name.to_string())
+    }
+
+    /// Opens a [`FalkorAsyncSession`]: a view of this client pinned to one connection for as long
+    /// as the session (or any graph selected from it) is alive.
+    ///
+    /// Every command issued through the session runs on that connection, so connection-scoped
+    /// state — a `CLIENT SETNAME`, a `WATCH`, or a read of the session's own writes — carries over
+    /// from one command to the next. With the pooled [`ConnectionStrategy`] the connection is
+    /// taken from the pool (waiting for a free one), which has one connection fewer until the
+    /// session is dropped; with the multiplexed strategy, whose sockets are shared, a dedicated
+    /// connection is opened for the session.
+    ///
+    /// # Returns
+    /// A [`FalkorAsyncSession`] holding the pinned connection.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Open Session", skip_all, level = "info")
+    )]
+    pub async fn session(&self) -> FalkorResult<FalkorAsyncSession> {
+        Ok(FalkorAsyncSession {
+            client: FalkorAsyncClient {
+                inner: Arc::new(self.inner.pinned().await?),
+                _connection_info: self._connection_info.clone(),
+            },
+        })
+    }
+
     /// Copies an entire graph and returns the [`AsyncGraph`] for the new copied graph.
     ///
     /// # Arguments
     /// * `graph_to_clone`: A string identifier of the graph to copy.
     /// * `new_graph_name`: The name to give the new graph.
     ///
+    /// On a Redis Cluster both names must hash to the same slot, or
+    /// [`FalkorDBError::CrossSlotGraphCopy`] is returned without contacting the server.
+    ///
     /// # Returns
     /// If successful, will return the new [`AsyncGraph`] object.
     #[cfg_attr(
@@ -653,6 +922,81 @@ impl FalkorAsyncClient {
     }
 }
 
+/// A [`FalkorAsyncClient`] pinned to a single connection, opened with
+/// [`FalkorAsyncClient::session`].
+///
+/// Graphs selected from a session run every query, batch and transaction on the pinned
+/// connection, one command at a time, and read-only queries are never routed to replicas. If
+/// the connection drops it is replaced by a fresh one, which does not carry over any
+/// connection-scoped state. The connection is released once the session, its clones, and every
+/// graph selected from it are dropped.
+#[derive(Clone)]
+pub struct FalkorAsyncSession {
+    client: FalkorAsyncClient,
+}
+
+impl FalkorAsyncSession {
+    /// Opens a graph context whose operations all run on the session's connection.
+    ///
+    /// # Arguments
+    /// * `graph_name`: A string identifier of the graph to open.
+    ///
+    /// # Returns
+    /// a [`AsyncGraph`] object bound to this session.
+    pub fn select_graph<T: ToString>(
+        &self,
+        graph_name: T,
+    ) -> AsyncGraph {
+        self.client.select_graph(graph_name)
+    }
+
+    /// Names the session's connection with `CLIENT SETNAME`, so it can be told apart in
+    /// `CLIENT LIST` and the server's logs.
+    ///
+    /// # Arguments
+    /// * `name`: The connection name; Redis rejects names containing spaces.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Set Session Client Name", skip_all, level = "info")
+    )]
+    pub async fn set_client_name(
+        &self,
+        name: &str,
+    ) -> FalkorResult<()> {
+        self.client
+            .borrow_connection()
+            .await?
+            .execute_command(None, "CLIENT", Some("SETNAME"), Some(&[name]))
+            .await
+            .map(|_| ())
+    }
+
+    /// Return a list of graphs currently residing in the database, using the session's
+    /// connection. See [`FalkorAsyncClient::list_graphs`].
+    pub async fn list_graphs(&self) -> FalkorResult<Vec<String>> {
+        self.client.list_graphs().await
+    }
+
+    /// Return the current value of a configuration option, using the session's connection. See
+    /// [`FalkorAsyncClient::config_get`].
+    pub async fn config_get(
+        &self,
+        config_key: &str,
+    ) -> FalkorResult<HashMap<String, ConfigValue>> {
+        self.client.config_get(config_key).await
+    }
+
+    /// Set a configuration option, using the session's connection. See
+    /// [`FalkorAsyncClient::config_set`].
+    pub async fn config_set<C: Into<ConfigValue>>(
+        &self,
+        config_key: &str,
+        value: C,
+    ) -> FalkorResult<redis::Value> {
+        self.client.config_set(config_key, value).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -669,6 +1013,7 @@ mod tests {
         mem,
         num::{NonZeroU8, NonZeroUsize},
         thread,
+        time::Duration,
     };
     use tokio::sync::mpsc::error::TryRecvError;
 
@@ -763,6 +1108,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_multiplexed_executor_replace_bumps_generation() {
+        let executor = MultiplexedExecutor::new(vec![], true, None);
+        assert_eq!(executor.generation(), 0);
+        executor.replace(vec![]);
+        executor.replace(vec![]);
+        assert_eq!(executor.generation(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_stale_generation_reresolves_once() {
+        // Every caller whose command failed on generation 0 asks to re-resolve at once; the
+        // first rebuilds the set while the rest wait on the lock, then complete without
+        // rebuilding again because the generation has already moved on.
+        let executor = Arc::new(MultiplexedExecutor::new(vec![], true, None));
+        let lock = Arc::new(Mutex::new(()));
+        let rebuilds = Arc::new(AtomicUsize::new(0));
+
+        let callers: Vec<_> = (0..8)
+            .map(|_| {
+                let (executor, lock, rebuilds) = (executor.clone(), lock.clone(), rebuilds.clone());
+                tokio::spawn(async move {
+                    executor
+                        .reresolve_with(&lock, 0, |guard| async move {
+                            let _held = guard;
+                            rebuilds.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(20)).await;
+                            Ok(vec![])
+                        })
+                        .await
+                })
+            })
+            .collect();
+        for caller in callers {
+            caller
+                .await
+                .expect("Caller task panicked")
+                .expect("Every in-flight caller completes");
+        }
+
+        assert_eq!(rebuilds.load(Ordering::SeqCst), 1);
+        assert_eq!(executor.generation(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reresolve_multiplexed_skips_non_sentinel() {
+        // A multiplexed client on a direct (non-Sentinel) deployment relies on its managers'
+        // own reconnects; re-resolution must leave its connection set untouched.
+        let client = FalkorClientBuilder::new_async()
+            .with_connection_strategy(ConnectionStrategy::Multiplexed {
+                connections: NonZeroU8::new(1).expect("Could not create a perfectly valid u8"),
+            })
+            .build()
+            .await
+            .expect("Could not build a single-socket multiplexed client");
+
+        client
+            .inner
+            .reresolve_multiplexed(false, 0)
+            .await
+            .expect("Re-resolving a non-Sentinel executor is a no-op");
+        let AsyncExecutor::Multiplexed(executor) = &client.inner.primary else {
+            panic!("The primary executor must be multiplexed");
+        };
+        assert_eq!(executor.generation(), 0);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_async_with_num_connections_preserves_multiplexed() {
         // `with_num_connections` updates the count of the builder's active strategy; on
@@ -1009,15 +1421,17 @@ mod tests {
         );
 
         let inner = Arc::new(FalkorAsyncClientInner {
-            _inner: Mutex::new(provider),
+            _inner: Arc::new(Mutex::new(provider)),
             strategy: ConnectionStrategy::Pooled {
                 size: NonZeroU8::new(1).unwrap(),
             },
             primary,
             readonly: Some(readonly),
             retry_policy: RetryPolicy::disabled(),
-            query_logging: false,
+            instrumentation: Instrumentation::default(),
             read_preference: ReadPreference::Primary,
+            stream_page_size: None,
+            release_to: None,
         });
 
         assert!(inner.has_readonly_pool());
@@ -4,7 +4,7 @@
  */
 
 use crate::{
-    client::{FalkorClientProvider, ProvidesSyncConnections, ReadPreference},
+    client::{FalkorClientProvider, Instrumentation, ProvidesSyncConnections, ReadPreference},
     connection::blocking::{BorrowedSyncConnection, FalkorSyncConnection},
     parser::{parse_config_hashmap, redis_value_as_untyped_string_vec},
     ConfigValue, FalkorConnectionInfo, FalkorDBError, FalkorResult, RetryPolicy, SyncGraph,
@@ -27,7 +27,9 @@ pub(crate) struct SyncConnectionPool {
 /// The idea is that each member here is either Copy, or locked in some form, and the public struct only has an Arc to this struct
 /// allowing thread safe operations and cloning
 pub(crate) struct FalkorSyncClientInner {
-    _inner: Mutex<FalkorClientProvider>,
+    /// Shared with the [sessions](FalkorSyncSession) opened from this client, which reconnect
+    /// through the same provider.
+    _inner: Arc<Mutex<FalkorClientProvider>>,
 
     connection_pool_size: u8,
     connection_pool_tx: mpsc::SyncSender<FalkorSyncConnection>,
@@ -39,11 +41,15 @@ pub(crate) struct FalkorSyncClientInner {
     /// Opt-in retry policy applied to eligible operations; [`disabled`](RetryPolicy::disabled) by
     /// default, in which case every operation is attempted exactly once.
     retry_policy: RetryPolicy,
-    /// When set, the raw query text is recorded as a span field (opt-in via `with_query_logging`).
+    /// The opt-in instrumentation settings (`with_query_logging`, `with_trace_context`,
+    /// `with_query_stats`).
     #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
-    query_logging: bool,
+    instrumentation: Instrumentation,
     /// Client-wide default read preference for read-only queries (overridable per query).
     read_preference: ReadPreference,
+    /// Set on a [session](FalkorSyncSession)'s inner: the pool its pinned connection was taken
+    /// from, which gets it back once the session and every graph selected from it are dropped.
+    release_to: Option<mpsc::SyncSender<FalkorSyncConnection>>,
 }
 
 impl FalkorSyncClientInner {
@@ -61,7 +67,20 @@ impl FalkorSyncClientInner {
     /// Whether raw query text may be recorded on spans (opt-in; `false` by default).
     #[cfg(feature = "tracing")]
     pub(crate) fn query_logging(&self) -> bool {
-        self.query_logging
+        self.instrumentation.query_logging
+    }
+
+    /// Whether the current span's `traceparent` is appended to each query (opt-in; `false` by
+    /// default).
+    #[cfg(feature = "opentelemetry")]
+    pub(crate) fn trace_context(&self) -> bool {
+        self.instrumentation.trace_context
+    }
+
+    /// The query statistics registry, when enabled with `with_query_stats`.
+    #[cfg(feature = "query-stats")]
+    pub(crate) fn query_stats(&self) -> Option<&crate::QueryStats> {
+        self.instrumentation.query_stats.as_ref()
     }
 
     #[cfg_attr(
@@ -76,6 +95,18 @@ impl FalkorSyncClientInner {
         &self,
         pool_owner: Arc<Self>,
     ) -> FalkorResult<BorrowedSyncConnection> {
+        let conn = self.take_connection()?;
+        Ok(BorrowedSyncConnection::new(
+            conn,
+            self.connection_pool_tx.clone(),
+            pool_owner,
+            false,
+        ))
+    }
+
+    /// Take a connection out of the primary pool, waiting for one to become available. The
+    /// caller is responsible for sending it back through `connection_pool_tx`.
+    fn take_connection(&self) -> FalkorResult<FalkorSyncConnection> {
         #[cfg(feature = "metrics")]
         let wait_start = std::time::Instant::now();
         let conn = self
@@ -85,12 +116,30 @@ impl FalkorSyncClientInner {
             .map_err(|_| FalkorDBError::EmptyConnection)?;
         #[cfg(feature = "metrics")]
         crate::observability::record_pool_wait(false, wait_start.elapsed());
-        Ok(BorrowedSyncConnection::new(
-            conn,
-            self.connection_pool_tx.clone(),
-            pool_owner,
-            false,
-        ))
+        Ok(conn)
+    }
+
+    /// Build the inner of a session pinned to `conn`: a single-connection pool sharing this
+    /// client's provider and settings, with no replica routing, which releases `conn` back to
+    /// this client's pool when dropped.
+    fn pinned(
+        &self,
+        conn: FalkorSyncConnection,
+    ) -> Self {
+        let (connection_pool_tx, connection_pool_rx) = mpsc::sync_channel(1);
+        // A fresh single-slot channel always has room for the one connection.
+        connection_pool_tx.send(conn).ok();
+        Self {
+            _inner: self._inner.clone(),
+            connection_pool_size: 1,
+            connection_pool_tx,
+            connection_pool_rx: Mutex::new(connection_pool_rx),
+            readonly_pool: None,
+            retry_policy: self.retry_policy,
+            instrumentation: self.instrumentation.clone(),
+            read_preference: self.read_preference,
+            release_to: Some(self.connection_pool_tx.clone()),
+        }
     }
 
     /// Borrow a connection for a read-only query. When a replica-routed read-only
@@ -143,6 +192,18 @@ impl FalkorSyncClientInner {
     }
 }
 
+impl Drop for FalkorSyncClientInner {
+    /// A session's pinned connection is back in its single-slot pool once nothing borrows it, so
+    /// it can be handed back to the pool it was taken from, which has a free slot for it.
+    fn drop(&mut self) {
+        if let Some(release_to) = &self.release_to {
+            if let Ok(conn) = self.connection_pool_rx.get_mut().try_recv() {
+                release_to.try_send(conn).ok();
+            }
+        }
+    }
+}
+
 impl ProvidesSyncConnections for FalkorSyncClientInner {
     #[cfg_attr(
         feature = "tracing",
@@ -180,7 +241,7 @@ impl FalkorSyncClient {
         connection_info: FalkorConnectionInfo,
         num_connections: u8,
         retry_policy: RetryPolicy,
-        query_logging: bool,
+        instrumentation: Instrumentation,
         read_preference: ReadPreference,
     ) -> FalkorResult<Self> {
         let (connection_pool_tx, connection_pool_rx) = mpsc::sync_channel(num_connections as usize);
@@ -200,14 +261,15 @@ impl FalkorSyncClient {
 
         Ok(Self {
             inner: Arc::new(FalkorSyncClientInner {
-                _inner: client.into(),
+                _inner: Arc::new(client.into()),
                 connection_pool_size: num_connections,
                 connection_pool_tx,
                 connection_pool_rx: Mutex::new(connection_pool_rx),
                 readonly_pool,
                 retry_policy,
-                query_logging,
+                instrumentation,
                 read_preference,
+                release_to: None,
             }),
             _connection_info: connection_info,
         })
@@ -271,6 +333,16 @@ impl FalkorSyncClient {
         self.inner.read_preference()
     }
 
+    /// The query statistics registry of this client, or `None` unless it was built with
+    /// [`FalkorClientBuilder::with_query_stats`](crate::FalkorClientBuilder::with_query_stats).
+    ///
+    /// The returned handle is a cheap clone of the registry the client keeps recording into, so it
+    /// can be handed to a debug endpoint and read at any time.
+    #[cfg(feature = "query-stats")]
+    pub fn query_stats(&self) -> Option<crate::QueryStats> {
+        self.inner.query_stats().cloned()
+    }
+
     /// Whether read-only queries (`ro_query` / `call_procedure_ro`) are routed to
     /// replica nodes. This is `true` only for Redis Sentinel deployments that expose
     /// readable replicas; otherwise read-only queries are served by the primary.
@@ -363,12 +435,55 @@ impl FalkorSyncClient {
         SyncGraph::new(self.inner.clone(), graph_name)
     }
 
+    /// Starts a [`BulkLoader`](crate::BulkLoader) that creates `graph_name` from nodes and
+    /// relationships sent through `GRAPH.BULK`, much faster than `UNWIND` batches for large
+    /// initial loads. The graph must not exist yet.
+    ///
+    /// # Arguments
+    /// * `graph_name`: The name of the graph to create.
+    ///
+    /// # Returns
+    /// A [`BulkLoader`](crate::BulkLoader); nothing is sent until its first chunk fills up.
+    pub fn bulk_loader<T: ToString>(
+        &self,
+        graph_name: T,
+    ) -> crate::BulkLoader {
+        crate::BulkLoader::new(self.inner.clone(), graph_name.to_string())
+    }
+
+    /// Opens a [`FalkorSyncSession`]: a view of this client pinned to one connection, taken from
+    /// the pool for as long as the session (or any graph selected from it) is alive.
+    ///
+    /// Every command issued through the session runs on that connection, so connection-scoped
+    /// state — a `CLIENT SETNAME`, a `WATCH`, or a read of the session's own writes — carries over
+    /// from one command to the next. This call waits for a free pooled connection, and the pool
+    /// has one connection fewer until the session is dropped.
+    ///
+    /// # Returns
+    /// A [`FalkorSyncSession`] holding the pinned connection.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Open Session", skip_all, level = "info")
+    )]
+    pub fn session(&self) -> FalkorResult<FalkorSyncSession> {
+        let conn = self.inner.take_connection()?;
+        Ok(FalkorSyncSession {
+            client: FalkorSyncClient {
+                inner: Arc::new(self.inner.pinned(conn)),
+                _connection_info: self._connection_info.clone(),
+            },
+        })
+    }
+
     /// Copies an entire graph and returns the [`SyncGraph`] for the new copied graph.
     ///
     /// # Arguments
     /// * `graph_to_clone`: A string identifier of the graph to copy.
     /// * `new_graph_name`: The name to give the new graph.
     ///
+    /// On a Redis Cluster both names must hash to the same slot, or
+    /// [`FalkorDBError::CrossSlotGraphCopy`] is returned without contacting the server.
+    ///
     /// # Returns
     /// If successful, will return the new [`SyncGraph`] object.
     #[cfg_attr(
@@ -499,19 +614,93 @@ impl FalkorSyncClient {
     }
 }
 
+/// A [`FalkorSyncClient`] pinned to a single connection, opened with
+/// [`FalkorSyncClient::session`].
+///
+/// Graphs selected from a session run every query, batch and transaction on the pinned
+/// connection, and read-only queries are never routed to replicas. If the connection drops it
+/// is replaced by a fresh one, which does not carry over any connection-scoped state. The
+/// connection returns to the client's pool once the session, its clones, and every graph
+/// selected from it are dropped.
+#[derive(Clone)]
+pub struct FalkorSyncSession {
+    client: FalkorSyncClient,
+}
+
+impl FalkorSyncSession {
+    /// Opens a graph context whose operations all run on the session's connection.
+    ///
+    /// # Arguments
+    /// * `graph_name`: A string identifier of the graph to open.
+    ///
+    /// # Returns
+    /// a [`SyncGraph`] object bound to this session.
+    pub fn select_graph<T: ToString>(
+        &self,
+        graph_name: T,
+    ) -> SyncGraph {
+        self.client.select_graph(graph_name)
+    }
+
+    /// Names the session's connection with `CLIENT SETNAME`, so it can be told apart in
+    /// `CLIENT LIST` and the server's logs.
+    ///
+    /// # Arguments
+    /// * `name`: The connection name; Redis rejects names containing spaces.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Set Session Client Name", skip_all, level = "info")
+    )]
+    pub fn set_client_name(
+        &self,
+        name: &str,
+    ) -> FalkorResult<()> {
+        self.client
+            .borrow_connection()?
+            .execute_command(None, "CLIENT", Some("SETNAME"), Some(&[name]))
+            .map(|_| ())
+    }
+
+    /// Return a list of graphs currently residing in the database, using the session's
+    /// connection. See [`FalkorSyncClient::list_graphs`].
+    pub fn list_graphs(&self) -> FalkorResult<Vec<String>> {
+        self.client.list_graphs()
+    }
+
+    /// Return the current value of a configuration option, using the session's connection. See
+    /// [`FalkorSyncClient::config_get`].
+    pub fn config_get(
+        &self,
+        config_key: &str,
+    ) -> FalkorResult<HashMap<String, ConfigValue>> {
+        self.client.config_get(config_key)
+    }
+
+    /// Set a configuration option, using the session's connection. See
+    /// [`FalkorSyncClient::config_set`].
+    pub fn config_set<C: Into<ConfigValue>>(
+        &self,
+        config_key: &str,
+        value: C,
+    ) -> FalkorResult<redis::Value> {
+        self.client.config_set(config_key, value)
+    }
+}
+
 #[cfg(test)]
 pub(crate) fn create_empty_inner_sync_client() -> Arc<FalkorSyncClientInner> {
     let (tx, rx) = mpsc::sync_channel(1);
     tx.send(FalkorSyncConnection::None).ok();
     Arc::new(FalkorSyncClientInner {
-        _inner: Mutex::new(FalkorClientProvider::None),
+        _inner: Arc::new(Mutex::new(FalkorClientProvider::None)),
         connection_pool_size: 0,
         connection_pool_tx: tx,
         connection_pool_rx: Mutex::new(rx),
         readonly_pool: None,
         retry_policy: RetryPolicy::disabled(),
-        query_logging: false,
+        instrumentation: Instrumentation::default(),
         read_preference: ReadPreference::Primary,
+        release_to: None,
     })
 }
 
@@ -695,14 +884,15 @@ mod tests {
         let (tx, rx) = mpsc::sync_channel(1);
         tx.send(FalkorSyncConnection::None).ok();
         let inner = Arc::new(FalkorSyncClientInner {
-            _inner: Mutex::new(FalkorClientProvider::None),
+            _inner: Arc::new(Mutex::new(FalkorClientProvider::None)),
             connection_pool_size: 0,
             connection_pool_tx: tx,
             connection_pool_rx: Mutex::new(rx),
             readonly_pool: Some(pool),
             retry_policy: RetryPolicy::disabled(),
-            query_logging: false,
+            instrumentation: Instrumentation::default(),
             read_preference: ReadPreference::Primary,
+            release_to: None,
         });
 
         assert!(inner.has_readonly_pool());
@@ -718,6 +908,51 @@ mod tests {
         drop(borrowed_again);
     }
 
+    #[test]
+    fn test_session_releases_pinned_connection_after_last_graph() {
+        let parent = create_empty_inner_sync_client();
+        let conn = parent
+            .take_connection()
+            .expect("parent pool holds one connection");
+        let session = Arc::new(parent.pinned(conn));
+        assert_eq!(session.connection_pool_size, 1);
+        assert!(!session.has_readonly_pool());
+
+        // A graph selected from the session keeps the pinned connection out of the parent pool.
+        let graph = SyncGraph::new(session.clone(), "test_session_release");
+        let borrowed = session
+            .borrow_connection(session.clone())
+            .expect("the pinned connection is free");
+        drop(borrowed);
+        drop(session);
+        assert!(matches!(
+            parent.connection_pool_rx.lock().try_recv(),
+            Err(TryRecvError::Empty)
+        ));
+
+        drop(graph);
+        assert!(parent.connection_pool_rx.lock().try_recv().is_ok());
+    }
+
+    #[test]
+    fn test_session_keeps_connection_state() {
+        let client = create_test_client();
+        let session = client.session().expect("Could not open a session");
+        session
+            .set_client_name("falkordb-rs-session")
+            .expect("Could not name the session connection");
+
+        let name = session
+            .client
+            .borrow_connection()
+            .and_then(|mut conn| conn.execute_command(None, "CLIENT", Some("GETNAME"), None))
+            .expect("Could not read the connection name");
+        assert_eq!(
+            name,
+            redis::Value::BulkString(b"falkordb-rs-session".to_vec())
+        );
+    }
+
     #[test]
     fn test_read_vec32() {
         let client = create_test_client();
@@ -4,7 +4,7 @@
  */
 
 use crate::{
-    client::{ConnectionStrategy, FalkorClientProvider, ReadPreference},
+    client::{ConnectionStrategy, FalkorClientProvider, Instrumentation, ReadPreference},
     FalkorConnectionInfo, FalkorDBError, FalkorResult, FalkorSyncClient, RetryPolicy,
 };
 use std::num::{NonZeroU8, NonZeroUsize};
@@ -19,9 +19,11 @@ pub struct FalkorClientBuilder<const R: char> {
     strategy: ConnectionStrategy,
     #[cfg_attr(not(feature = "tokio"), allow(dead_code))]
     max_inflight: Option<NonZeroUsize>,
+    #[cfg_attr(not(feature = "tokio"), allow(dead_code))]
+    stream_page_size: Option<NonZeroUsize>,
     tcp_settings: Option<redis::io::tcp::TcpSettings>,
     retry_policy: RetryPolicy,
-    query_logging: bool,
+    instrumentation: Instrumentation,
     read_preference: ReadPreference,
     response_timeout: Option<Duration>,
 }
@@ -109,7 +111,60 @@ impl<const R: char> FalkorClientBuilder<R> {
         enabled: bool,
     ) -> Self {
         Self {
-            query_logging: enabled,
+            instrumentation: Instrumentation {
+                query_logging: enabled,
+                ..self.instrumentation
+            },
+            ..self
+        }
+    }
+
+    /// Opt in to propagating the W3C trace context: each query sent through `query`, `ro_query`
+    /// or `call_procedure` ends with a `/*traceparent='00-<trace-id>-<span-id>-<flags>'*/` Cypher
+    /// comment naming the current OpenTelemetry span, so server-side logs (the slow log, `MONITOR`)
+    /// and downstream consumers can be joined to the client trace. **Off by default.**
+    ///
+    /// The comment makes every query text unique, which defeats FalkorDB's query plan cache; enable
+    /// it where following a request end to end matters more than the cache. Nothing is appended
+    /// while no OpenTelemetry span is active. Has no effect unless the `opentelemetry` feature is
+    /// enabled and a `tracing-opentelemetry` layer is installed.
+    pub fn with_trace_context(
+        self,
+        enabled: bool,
+    ) -> Self {
+        Self {
+            instrumentation: Instrumentation {
+                trace_context: enabled,
+                ..self.instrumentation
+            },
+            ..self
+        }
+    }
+
+    /// Opt in to the in-process query statistics registry: every query run through `query`,
+    /// `ro_query` and the helpers built on them is recorded under its redacted fingerprint, with
+    /// call and error counts, latency percentiles, rows returned and plan cache hits. Calls at or
+    /// above the configured slow threshold also go to a client-side slow query log, together with
+    /// the query's `GRAPH.EXPLAIN` plan. **Off by default.**
+    ///
+    /// Read the registry at runtime through the built client's `query_stats()`. Only redacted query
+    /// text is kept: literals become `?` and parameter values are never recorded.
+    ///
+    /// # Arguments
+    /// * `config`: the [`QueryStatsConfig`](crate::QueryStatsConfig) limits and slow threshold.
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    #[cfg(feature = "query-stats")]
+    pub fn with_query_stats(
+        self,
+        config: crate::QueryStatsConfig,
+    ) -> Self {
+        Self {
+            instrumentation: Instrumentation {
+                query_stats: Some(crate::QueryStats::new(config)),
+                ..self.instrumentation
+            },
             ..self
         }
     }
@@ -294,6 +349,24 @@ impl<const R: char> FalkorClientBuilder<R> {
                         response_timeout,
                     }
                 }
+                #[cfg(feature = "cluster")]
+                FalkorConnectionInfo::Cluster(ref nodes) => {
+                    // The seed nodes only bootstrap slot discovery; the cluster client
+                    // learns the rest of the topology (and its changes) from them.
+                    let builder = redis::cluster::ClusterClient::builder(nodes.clone());
+                    let builder = match tcp_settings {
+                        Some(settings) => builder.tcp_settings(settings.clone()),
+                        None => builder,
+                    };
+                    let client = builder
+                        .build()
+                        .map_err(|err| FalkorDBError::RedisError(err.to_string()))?;
+                    FalkorClientProvider::Cluster {
+                        client,
+                        #[cfg(feature = "tokio")]
+                        response_timeout,
+                    }
+                }
                 #[cfg(feature = "embedded-core")]
                 FalkorConnectionInfo::Embedded(_) => unreachable!("Handled above"),
             },
@@ -315,9 +388,10 @@ impl FalkorClientBuilder<'S'> {
                 size: NonZeroU8::new(8).expect("Error creating perfectly valid u8"),
             },
             max_inflight: None,
+            stream_page_size: None,
             tcp_settings: None,
             retry_policy: RetryPolicy::disabled(),
-            query_logging: false,
+            instrumentation: Instrumentation::default(),
             read_preference: ReadPreference::Primary,
             response_timeout: None,
         }
@@ -352,7 +426,7 @@ impl FalkorClientBuilder<'S'> {
             actual_connection_info,
             self.strategy.connection_count().get(),
             self.retry_policy,
-            self.query_logging,
+            self.instrumentation,
             self.read_preference,
         )
     }
@@ -371,9 +445,10 @@ impl FalkorClientBuilder<'A'> {
                 connections: NonZeroU8::new(8).expect("Error creating perfectly valid u8"),
             },
             max_inflight: None,
+            stream_page_size: None,
             tcp_settings: None,
             retry_policy: RetryPolicy::disabled(),
-            query_logging: false,
+            instrumentation: Instrumentation::default(),
             read_preference: ReadPreference::Primary,
             response_timeout: None,
         }
@@ -445,6 +520,36 @@ impl FalkorClientBuilder<'A'> {
         }
     }
 
+    /// Opt in to paging ordered async read-only queries transparently, holding at most
+    /// `page_size` rows in memory at once.
+    ///
+    /// When set, a [`ro_query`](crate::AsyncGraph::ro_query) whose final `RETURN` is ordered by
+    /// an `ORDER BY` (and which has no trailing `SKIP`/`LIMIT`, no `UNION` and no `CALL`) is run
+    /// like [`paginate`](crate::QueryBuilder::paginate): [`execute`](crate::QueryBuilder::execute)
+    /// fetches the first `page_size` rows, and the returned [`RowStream`](crate::RowStream)
+    /// fetches each following page only once the previous one has been consumed. Any other query
+    /// is fetched as a single reply.
+    ///
+    /// Paging trades server work and consistency for memory: every page re-runs the query and
+    /// skips the rows before it, pages are not one snapshot (a concurrent write, or rows tied on
+    /// the `ORDER BY` key, can repeat or drop rows between pages), and the result's statistics
+    /// are those of the first page. Defaults to `None`, fetching every query as a single reply.
+    ///
+    /// # Arguments
+    /// * `page_size`: the page size for transparently paged queries, or `None` to disable paging.
+    ///
+    /// # Returns
+    /// The consumed and modified self.
+    pub fn with_stream_page_size(
+        self,
+        page_size: Option<NonZeroUsize>,
+    ) -> Self {
+        Self {
+            stream_page_size: page_size,
+            ..self
+        }
+    }
+
     /// Consume the builder, returning the newly constructed async client
     ///
     /// # Returns
@@ -475,8 +580,9 @@ impl FalkorClientBuilder<'A'> {
             self.strategy,
             self.max_inflight,
             self.retry_policy,
-            self.query_logging,
+            self.instrumentation,
             self.read_preference,
+            self.stream_page_size,
         )
         .await
     }
@@ -533,6 +639,29 @@ mod tests {
         assert!(builder.response_timeout.is_none());
     }
 
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_builder_async_stream_page_size() {
+        let builder = FalkorClientBuilder::new_async();
+        assert!(builder.stream_page_size.is_none());
+
+        let page_size = NonZeroUsize::new(500).unwrap();
+        let builder = builder.with_stream_page_size(Some(page_size));
+        assert_eq!(builder.stream_page_size, Some(page_size));
+    }
+
+    #[test]
+    fn test_builder_instrumentation_flags_are_independent() {
+        let builder = FalkorClientBuilder::new();
+        assert!(!builder.instrumentation.query_logging && !builder.instrumentation.trace_context);
+
+        let builder = builder.with_trace_context(true).with_query_logging(true);
+        assert!(builder.instrumentation.query_logging && builder.instrumentation.trace_context);
+
+        let builder = builder.with_trace_context(false);
+        assert!(builder.instrumentation.query_logging && !builder.instrumentation.trace_context);
+    }
+
     #[test]
     fn test_builder_with_tcp_keepalive() {
         let builder = FalkorClientBuilder::new().with_tcp_keepalive(Duration::from_secs(30));
@@ -122,6 +122,21 @@ pub enum ReadPreference {
     PreferReplica,
 }
 
+/// The opt-in instrumentation settings of a client, set with `with_query_logging`,
+/// `with_trace_context` and `with_query_stats`.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+pub(crate) struct Instrumentation {
+    /// When set, the raw query text is recorded as a span field.
+    pub(crate) query_logging: bool,
+    /// When set, the current span's W3C `traceparent` is appended to each query as a comment.
+    #[cfg_attr(not(feature = "opentelemetry"), allow(dead_code))]
+    pub(crate) trace_context: bool,
+    /// When set, every query is recorded in this registry.
+    #[cfg(feature = "query-stats")]
+    pub(crate) query_stats: Option<crate::QueryStats>,
+}
+
 #[allow(clippy::large_enum_variant)]
 pub(crate) enum FalkorClientProvider {
     #[cfg(test)]
@@ -151,6 +166,18 @@ pub(crate) enum FalkorClientProvider {
         /// keeps executing the query.
         response_timeout: Option<std::time::Duration>,
     },
+
+    /// A Redis Cluster deployment. Every `GRAPH.*` command carries the graph name as its key,
+    /// so the cluster client routes it to the node owning that key's hash slot and follows
+    /// MOVED/ASK redirections transparently.
+    #[cfg(feature = "cluster")]
+    Cluster {
+        client: redis::cluster::ClusterClient,
+        /// Client-side response timeout applied to async cluster connections; see
+        /// [`Redis`](Self::Redis) for why `None` is the default. Sync connections never read it.
+        #[cfg(feature = "tokio")]
+        response_timeout: Option<std::time::Duration>,
+    },
 }
 
 impl FalkorClientProvider {
@@ -170,6 +197,12 @@ impl FalkorClientProvider {
                     .get_connection()
                     .map_err(|err| FalkorDBError::RedisError(err.to_string()))?,
             ),
+            #[cfg(feature = "cluster")]
+            FalkorClientProvider::Cluster { client, .. } => FalkorSyncConnection::Cluster(
+                client
+                    .get_connection()
+                    .map_err(|err| FalkorDBError::RedisError(err.to_string()))?,
+            ),
             #[cfg(test)]
             FalkorClientProvider::None => Err(FalkorDBError::UnavailableProvider)?,
         })
@@ -212,6 +245,11 @@ impl FalkorClientProvider {
                     .await
                     .map_err(|err| FalkorDBError::RedisError(err.to_string()))?,
             ),
+            #[cfg(feature = "cluster")]
+            FalkorClientProvider::Cluster {
+                client,
+                response_timeout,
+            } => Self::cluster_async_connection(client, *response_timeout).await?,
             #[cfg(test)]
             FalkorClientProvider::None => Err(FalkorDBError::UnavailableProvider)?,
         })
@@ -302,12 +340,46 @@ impl FalkorClientProvider {
                 .await
                 .map_err(|err| FalkorDBError::RedisError(err.to_string()))?,
             FalkorClientProvider::Redis { client, .. } => client.clone(),
+            // A cluster connection already multiplexes and reconnects per node, so it serves
+            // as its own manager. `max_inflight` has no cluster counterpart and is not applied.
+            #[cfg(feature = "cluster")]
+            FalkorClientProvider::Cluster { client, .. } => {
+                return Self::cluster_async_connection(client, response_timeout).await;
+            }
             #[cfg(test)]
             FalkorClientProvider::None => return Err(FalkorDBError::UnavailableProvider),
         };
         Self::manager_from_client(client, max_inflight, response_timeout).await
     }
 
+    /// Open a multiplexed async connection to a Redis Cluster. Needs the `tokio-cluster`
+    /// feature; with only `cluster` enabled, cluster deployments are reachable from the
+    /// synchronous client alone.
+    #[cfg(all(feature = "tokio", feature = "cluster"))]
+    async fn cluster_async_connection(
+        client: &redis::cluster::ClusterClient,
+        response_timeout: Option<std::time::Duration>,
+    ) -> FalkorResult<FalkorAsyncConnection> {
+        #[cfg(feature = "tokio-cluster")]
+        {
+            let config = redis::cluster::ClusterConfig::new();
+            let config = match response_timeout {
+                Some(timeout) => config.set_response_timeout(timeout),
+                None => config,
+            };
+            client
+                .get_async_connection_with_config(config)
+                .await
+                .map(FalkorAsyncConnection::Cluster)
+                .map_err(|err| FalkorDBError::RedisError(err.to_string()))
+        }
+        #[cfg(not(feature = "tokio-cluster"))]
+        {
+            let _ = (client, response_timeout);
+            Err(FalkorDBError::UnavailableProvider)
+        }
+    }
+
     /// Replica-routed counterpart of
     /// [`get_async_connection_manager`](Self::get_async_connection_manager). Returns a
     /// multiplexed manager pinned to a replica node, without falling back to the primary.
@@ -339,6 +411,10 @@ impl FalkorClientProvider {
             FalkorClientProvider::Redis {
                 response_timeout, ..
             } => *response_timeout,
+            #[cfg(feature = "cluster")]
+            FalkorClientProvider::Cluster {
+                response_timeout, ..
+            } => *response_timeout,
             #[cfg(test)]
             FalkorClientProvider::None => None,
         }
@@ -370,6 +446,8 @@ impl FalkorClientProvider {
     ) {
         match self {
             FalkorClientProvider::Redis { sentinel, .. } => *sentinel = Some(sentinel_client),
+            #[cfg(feature = "cluster")]
+            FalkorClientProvider::Cluster { .. } => {}
             #[cfg(test)]
             FalkorClientProvider::None => {}
         }
@@ -383,6 +461,8 @@ impl FalkorClientProvider {
             FalkorClientProvider::Redis {
                 sentinel_replica, ..
             } => *sentinel_replica = Some(sentinel_client),
+            #[cfg(feature = "cluster")]
+            FalkorClientProvider::Cluster { .. } => {}
             #[cfg(test)]
             FalkorClientProvider::None => {}
         }
@@ -488,7 +568,13 @@ impl FalkorClientProvider {
         }
 
         let sentinel_masters = conn
-            .execute_command(None, "SENTINEL", Some("MASTERS"), None)
+            .execute_command(
+                None,
+                "SENTINEL",
+                Some("MASTERS"),
+                None,
+                crate::connection::map_redis_err,
+            )
             .await
             .and_then(redis_value_as_vec)?;
 
@@ -718,6 +804,54 @@ mod tests {
         assert!(provider.has_sentinel_replica());
     }
 
+    #[test]
+    #[cfg(feature = "cluster")]
+    fn test_cluster_provider_ignores_sentinel() {
+        // Cluster deployments route by hash slot, never through Sentinel; setting a replica
+        // Sentinel must be a no-op, and an unreachable seed node surfaces as a RedisError.
+        let client = redis::cluster::ClusterClient::new(vec!["redis://127.0.0.1:1"]).unwrap();
+        let mut provider = FalkorClientProvider::Cluster {
+            client,
+            #[cfg(feature = "tokio")]
+            response_timeout: None,
+        };
+        let connection_info = redis::ConnectionInfo::from_str("redis://127.0.0.1:26379").unwrap();
+        let replica = redis::sentinel::SentinelClient::build(
+            vec![connection_info],
+            "mymaster".to_string(),
+            None,
+            redis::sentinel::SentinelServerType::Replica,
+        )
+        .unwrap();
+        provider.set_sentinel_replica(replica);
+        assert!(!provider.has_sentinel_replica());
+        assert!(matches!(
+            provider.get_replica_connection(),
+            Err(FalkorDBError::UnavailableProvider)
+        ));
+        assert!(matches!(
+            provider.get_connection(),
+            Err(FalkorDBError::RedisError(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "tokio-cluster")]
+    fn test_cluster_provider_async_connection_manager_errors_when_unreachable() {
+        use tokio::runtime::Runtime;
+        let rt = Runtime::new().unwrap();
+        rt.block_on(async {
+            let client = redis::cluster::ClusterClient::new(vec!["redis://127.0.0.1:1"]).unwrap();
+            let mut provider = FalkorClientProvider::Cluster {
+                client,
+                response_timeout: None,
+            };
+            assert!(!provider.has_sentinel());
+            let result = provider.get_async_connection_manager(None).await;
+            assert!(matches!(result, Err(FalkorDBError::RedisError(_))));
+        });
+    }
+
     #[test]
     #[cfg(feature = "embedded-core")]
     fn test_falkor_client_provider_with_embedded_server() {
@@ -0,0 +1,603 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Rust code generation from a live graph schema.
+//!
+//! [`CodeGenerator`] reads a graph with [`SyncGraph::describe`] and emits one struct per node label
+//! and relationship type, with a field per property key, `LABEL`/`RELATIONSHIP_TYPE` and property
+//! name constants, and a `FromFalkorValue` implementation. Checking the output in and regenerating
+//! it in CI turns a drift between the code and the graph into a compile error or a diff:
+//!
+//! ```no_run
+//! # fn main() -> Result<(), falkordb::FalkorDBError> {
+//! use falkordb::codegen::CodeGenerator;
+//! use falkordb::FalkorClientBuilder;
+//! # let info: falkordb::FalkorConnectionInfo = "falkor://127.0.0.1:6379".try_into()?;
+//! let client = FalkorClientBuilder::new().with_connection_info(info).build()?;
+//!
+//! let source = CodeGenerator::new()
+//!     .with_sample_size(10_000)
+//!     .generate(&mut client.select_graph("social"))?;
+//! std::fs::write("src/schema.rs", source).expect("write file");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The `falkordb-codegen` binary does the same from the command line. Field types follow the
+//! values seen in the graph: a property holding one type maps to the matching Rust type, integers
+//! mixed with floats to `f64`, and any other mix to [`FalkorValue`](crate::FalkorValue). A property
+//! missing from some of the entities looked at is an `Option`.
+//!
+//! Node structs derive `falkordb::FromNode`, so the generated code needs the `derive` feature;
+//! relationship structs get a written-out `FromFalkorValue` implementation.
+
+use crate::{
+    FalkorResult, GraphDescription, LabelDescription, PropertyDescription,
+    RelationshipTypeDescription, SyncGraph,
+};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+
+/// The first line of every generated file.
+pub const HEADER: &str =
+    "// @generated by falkordb codegen from a live graph schema. Do not edit by hand.";
+
+const KEYWORDS: [&str; 51] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while",
+];
+
+/// Generates Rust types for the labels and relationship types of a graph; see the
+/// [module documentation](self).
+#[derive(Clone, Debug, Default)]
+pub struct CodeGenerator {
+    sample_size: Option<u64>,
+    serde: bool,
+}
+
+impl CodeGenerator {
+    /// Creates a generator that looks at every entity and derives no serde traits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Infers property types from at most `sample_size` entities per label and relationship type.
+    ///
+    /// # Arguments
+    /// * `sample_size`: Passed to [`DescribeBuilder::with_sample_size`](crate::DescribeBuilder::with_sample_size).
+    pub fn with_sample_size(
+        mut self,
+        sample_size: u64,
+    ) -> Self {
+        self.sample_size = Some(sample_size);
+        self
+    }
+
+    /// Also derives `serde::Serialize`, and `serde::Deserialize` on the structs whose fields are
+    /// all integers, floats, booleans or strings (the FalkorDB value types have no `Deserialize`).
+    pub fn with_serde(mut self) -> Self {
+        self.serde = true;
+        self
+    }
+
+    /// Describes `graph` and renders the types for its schema.
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to read the schema of.
+    ///
+    /// # Returns
+    /// The source of a Rust module.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Generate Graph Types", skip_all, level = "info")
+    )]
+    pub fn generate(
+        &self,
+        graph: &mut SyncGraph,
+    ) -> FalkorResult<String> {
+        let describe = graph.describe();
+        let describe = match self.sample_size {
+            Some(sample_size) => describe.with_sample_size(sample_size),
+            None => describe,
+        };
+        Ok(self.render(&describe.execute()?))
+    }
+
+    /// Describes `graph` and renders the types for its schema; see [`CodeGenerator::generate`].
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to read the schema of.
+    ///
+    /// # Returns
+    /// The source of a Rust module.
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Generate Graph Types Async", skip_all, level = "info")
+    )]
+    pub async fn generate_async(
+        &self,
+        graph: &mut crate::AsyncGraph,
+    ) -> FalkorResult<String> {
+        let describe = graph.describe();
+        let describe = match self.sample_size {
+            Some(sample_size) => describe.with_sample_size(sample_size),
+            None => describe,
+        };
+        Ok(self.render(&describe.execute().await?))
+    }
+
+    /// Renders the types for an already read schema.
+    ///
+    /// # Arguments
+    /// * `description`: The schema, as returned by [`SyncGraph::describe`].
+    ///
+    /// # Returns
+    /// The source of a Rust module.
+    pub fn render(
+        &self,
+        description: &GraphDescription,
+    ) -> String {
+        let mut out = format!("{HEADER}\n");
+        let mut type_names = HashSet::new();
+        for label in &description.labels {
+            let entity = Entity::label(label, description.sample_size, &mut type_names);
+            self.render_entity(&entity, &mut out);
+        }
+        for relationship_type in &description.relationship_types {
+            let entity = Entity::relationship_type(
+                relationship_type,
+                description.sample_size,
+                &mut type_names,
+            );
+            self.render_entity(&entity, &mut out);
+        }
+        out
+    }
+
+    fn render_entity(
+        &self,
+        entity: &Entity,
+        out: &mut String,
+    ) {
+        let (kind, constant) = if entity.node {
+            ("node label", "LABEL")
+        } else {
+            ("relationship type", "RELATIONSHIP_TYPE")
+        };
+        let mut derives = vec!["Clone", "Debug", "PartialEq"];
+        if entity.node {
+            derives.push("falkordb::FromNode");
+        }
+        if self.serde {
+            derives.push("serde::Serialize");
+            if entity.fields.iter().all(|field| field.plain) {
+                derives.push("serde::Deserialize");
+            }
+        }
+        let _ = writeln!(out, "\n/// The `{}` {kind}.", entity.name.escape_debug());
+        let _ = writeln!(out, "#[derive({})]", derives.join(", "));
+        if entity.fields.is_empty() {
+            let _ = writeln!(out, "pub struct {} {{}}", entity.type_name);
+        } else {
+            let _ = writeln!(out, "pub struct {} {{", entity.type_name);
+        }
+        for field in &entity.fields {
+            let _ = writeln!(out, "    /// The `{}` property.", field.key.escape_debug());
+            if field.ident.trim_start_matches("r#") != field.key {
+                if entity.node {
+                    let _ = writeln!(out, "    #[falkordb(rename = {:?})]", field.key);
+                }
+                if self.serde {
+                    let _ = writeln!(out, "    #[serde(rename = {:?})]", field.key);
+                }
+            }
+            let _ = writeln!(out, "    pub {}: {},", field.ident, field.full_type());
+        }
+        if !entity.fields.is_empty() {
+            let _ = writeln!(out, "}}");
+        }
+
+        let _ = writeln!(out, "\nimpl {} {{", entity.type_name);
+        let _ = writeln!(out, "    /// The {kind}.");
+        let _ = writeln!(
+            out,
+            "    pub const {constant}: &'static str = {:?};",
+            entity.name
+        );
+        for field in &entity.fields {
+            let _ = writeln!(
+                out,
+                "    /// The `{}` property key.",
+                field.key.escape_debug()
+            );
+            let _ = writeln!(
+                out,
+                "    pub const {}: &'static str = {:?};",
+                field.constant, field.key
+            );
+        }
+        let _ = writeln!(out, "}}");
+
+        if !entity.node {
+            render_edge_impl(entity, out);
+        }
+    }
+}
+
+/// A relationship type struct reads the properties of an `Edge`, like `FromNode` does for a node.
+fn render_edge_impl(
+    entity: &Entity,
+    out: &mut String,
+) {
+    let _ = writeln!(
+        out,
+        "\nimpl falkordb::FromFalkorValue for {} {{",
+        entity.type_name
+    );
+    let _ = writeln!(
+        out,
+        "    fn from_falkor_value(value: falkordb::FalkorValue) -> falkordb::FalkorResult<Self> {{"
+    );
+    let binding = if entity.fields.is_empty() {
+        "_properties"
+    } else {
+        "mut properties"
+    };
+    let _ = writeln!(
+        out,
+        "        let {binding} = <falkordb::Edge as falkordb::FromFalkorValue>::from_falkor_value(value)?.properties;"
+    );
+    let _ = writeln!(out, "        Ok(Self {{");
+    for field in &entity.fields {
+        if field.optional {
+            let _ = writeln!(
+                out,
+                "            {}: properties.remove({:?}).map(falkordb::FromFalkorValue::from_falkor_value).transpose()?,",
+                field.ident, field.key
+            );
+        } else {
+            let _ = writeln!(
+                out,
+                "            {}: falkordb::FromFalkorValue::from_falkor_value(properties.remove({:?}).ok_or_else(|| falkordb::FalkorDBError::MissingProperty {{ name: {:?}.to_string() }})?)?,",
+                field.ident, field.key, field.key
+            );
+        }
+    }
+    let _ = writeln!(out, "        }})");
+    let _ = writeln!(out, "    }}");
+    let _ = writeln!(out, "}}");
+}
+
+/// A struct to render.
+struct Entity {
+    node: bool,
+    name: String,
+    type_name: String,
+    fields: Vec<Field>,
+}
+
+impl Entity {
+    fn label(
+        label: &LabelDescription,
+        sample_size: Option<u64>,
+        type_names: &mut HashSet<String>,
+    ) -> Self {
+        Self {
+            node: true,
+            name: label.name.clone(),
+            type_name: unique(type_name(&label.name), type_names),
+            fields: fields(
+                &label.properties,
+                observed(label.count, sample_size),
+                "LABEL",
+            ),
+        }
+    }
+
+    fn relationship_type(
+        relationship_type: &RelationshipTypeDescription,
+        sample_size: Option<u64>,
+        type_names: &mut HashSet<String>,
+    ) -> Self {
+        Self {
+            node: false,
+            name: relationship_type.name.clone(),
+            type_name: unique(type_name(&relationship_type.name), type_names),
+            fields: fields(
+                &relationship_type.properties,
+                observed(relationship_type.count, sample_size),
+                "RELATIONSHIP_TYPE",
+            ),
+        }
+    }
+}
+
+/// The entities whose properties were looked at.
+fn observed(
+    count: i64,
+    sample_size: Option<u64>,
+) -> i64 {
+    match sample_size {
+        Some(sample_size) => count.min(i64::try_from(sample_size).unwrap_or(i64::MAX)),
+        None => count,
+    }
+}
+
+/// A struct field for a property key.
+struct Field {
+    key: String,
+    ident: String,
+    constant: String,
+    rust_type: &'static str,
+    optional: bool,
+    /// Whether serde can deserialize the type.
+    plain: bool,
+}
+
+impl Field {
+    fn full_type(&self) -> String {
+        if self.optional {
+            format!("Option<{}>", self.rust_type)
+        } else {
+            self.rust_type.to_string()
+        }
+    }
+}
+
+fn fields(
+    properties: &[PropertyDescription],
+    observed: i64,
+    reserved_constant: &str,
+) -> Vec<Field> {
+    let mut idents = HashSet::new();
+    let mut constants = HashSet::from([reserved_constant.to_string()]);
+    properties
+        .iter()
+        .map(|property| {
+            let ident = unique(field_name(&property.name), &mut idents);
+            let constant = unique(
+                ident.trim_start_matches("r#").to_ascii_uppercase(),
+                &mut constants,
+            );
+            let rust_type = rust_type(property);
+            Field {
+                key: property.name.clone(),
+                ident,
+                constant,
+                rust_type,
+                optional: property.count < observed,
+                plain: matches!(rust_type, "i64" | "f64" | "bool" | "String"),
+            }
+        })
+        .collect()
+}
+
+/// The Rust type for the values seen of `property`.
+fn rust_type(property: &PropertyDescription) -> &'static str {
+    let mut types = property
+        .value_types
+        .iter()
+        .map(|value_type| single_type(&value_type.type_name))
+        .collect::<Vec<_>>();
+    types.dedup();
+    match types.as_slice() {
+        [Some(rust_type)] => rust_type,
+        [Some("i64"), Some("f64")] | [Some("f64"), Some("i64")] => "f64",
+        _ => "falkordb::FalkorValue",
+    }
+}
+
+/// The Rust type for a Cypher `typeOf` name, or `None` if only `FalkorValue` holds it.
+fn single_type(type_name: &str) -> Option<&'static str> {
+    let normalized = type_name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    Some(match normalized.as_str() {
+        "integer" => "i64",
+        "float" => "f64",
+        "boolean" => "bool",
+        "string" => "String",
+        "point" => "falkordb::Point",
+        "date" => "falkordb::Date",
+        "datetime" => "falkordb::DateTime",
+        "time" | "localtime" => "falkordb::Time",
+        "duration" => "falkordb::Duration",
+        "list" | "array" => "Vec<falkordb::FalkorValue>",
+        "map" => "std::collections::HashMap<String, falkordb::FalkorValue>",
+        _ => return None,
+    })
+}
+
+/// `name` with a numeric suffix if `taken` already holds it; the result is added to `taken`.
+fn unique(
+    name: String,
+    taken: &mut HashSet<String>,
+) -> String {
+    let mut candidate = name.clone();
+    let mut suffix = 2;
+    while !taken.insert(candidate.clone()) {
+        candidate = format!("{name}{suffix}");
+        suffix += 1;
+    }
+    candidate
+}
+
+/// The words of `name`, split at non-alphanumeric characters and lower-to-upper case changes.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// An `UpperCamelCase` type name for a label or relationship type.
+fn type_name(name: &str) -> String {
+    let mut out = String::new();
+    for word in words(name) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.extend(first.to_uppercase());
+            out.extend(chars.flat_map(char::to_lowercase));
+        }
+    }
+    if !out.starts_with(|c: char| c.is_alphabetic()) {
+        out.insert_str(0, "Entity");
+    }
+    out
+}
+
+/// A `snake_case` field name for a property key.
+fn field_name(key: &str) -> String {
+    let mut out = words(key)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    if !out.starts_with(|c: char| c.is_alphabetic()) {
+        out.insert_str(0, "property_");
+    }
+    match out.as_str() {
+        "self" | "super" | "crate" => out.push('_'),
+        _ if KEYWORDS.contains(&out.as_str()) => out.insert_str(0, "r#"),
+        _ => {}
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ValueTypeCount;
+
+    fn property(
+        name: &str,
+        count: i64,
+        types: &[&str],
+    ) -> PropertyDescription {
+        PropertyDescription {
+            name: name.to_string(),
+            count,
+            value_types: types
+                .iter()
+                .map(|type_name| ValueTypeCount {
+                    type_name: type_name.to_string(),
+                    count: 1,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_names_become_rust_identifiers() {
+        assert_eq!(type_name("ACTED_IN"), "ActedIn");
+        assert_eq!(type_name("movieGenre"), "MovieGenre");
+        assert_eq!(type_name("2d shape"), "Entity2dShape");
+        assert_eq!(field_name("firstName"), "first_name");
+        assert_eq!(field_name("type"), "r#type");
+        assert_eq!(field_name("self"), "self_");
+        assert_eq!(field_name("9lives"), "property_9lives");
+        let mut taken = HashSet::new();
+        assert_eq!(unique("Person".to_string(), &mut taken), "Person");
+        assert_eq!(unique("Person".to_string(), &mut taken), "Person2");
+    }
+
+    #[test]
+    fn test_property_types_follow_observed_values() {
+        assert_eq!(rust_type(&property("a", 1, &["Integer"])), "i64");
+        assert_eq!(rust_type(&property("a", 1, &["Float", "Integer"])), "f64");
+        assert_eq!(
+            rust_type(&property("a", 1, &["Integer", "String"])),
+            "falkordb::FalkorValue"
+        );
+        assert_eq!(
+            rust_type(&property("a", 1, &["Local Time"])),
+            "falkordb::Time"
+        );
+        assert_eq!(
+            rust_type(&property("a", 1, &["Vectorf32"])),
+            "falkordb::FalkorValue"
+        );
+    }
+
+    #[test]
+    fn test_render_nodes_and_relationships() {
+        let description = GraphDescription {
+            labels: vec![LabelDescription {
+                name: "Person".to_string(),
+                count: 10,
+                properties: vec![
+                    property("age", 4, &["Integer"]),
+                    property("firstName", 10, &["String"]),
+                    property("label", 10, &["String"]),
+                ],
+                ..Default::default()
+            }],
+            relationship_types: vec![RelationshipTypeDescription {
+                name: "KNOWS".to_string(),
+                count: 5,
+                properties: vec![property("since", 5, &["Date"])],
+                ..Default::default()
+            }],
+            sample_size: None,
+        };
+
+        let source = CodeGenerator::new().with_serde().render(&description);
+        assert!(source.starts_with(HEADER));
+        assert!(source.contains(
+            "#[derive(Clone, Debug, PartialEq, falkordb::FromNode, serde::Serialize, serde::Deserialize)]\npub struct Person {"
+        ));
+        assert!(source.contains("    pub age: Option<i64>,"));
+        assert!(source.contains(
+            "    #[falkordb(rename = \"firstName\")]\n    #[serde(rename = \"firstName\")]\n    pub first_name: String,"
+        ));
+        assert!(source.contains("    pub const LABEL: &'static str = \"Person\";"));
+        assert!(source.contains("    pub const LABEL2: &'static str = \"label\";"));
+        assert!(source
+            .contains("#[derive(Clone, Debug, PartialEq, serde::Serialize)]\npub struct Knows {"));
+        assert!(source.contains("    pub since: falkordb::Date,"));
+        assert!(source.contains("impl falkordb::FromFalkorValue for Knows {"));
+        assert!(source.contains("properties.remove(\"since\").ok_or_else"));
+    }
+
+    #[test]
+    fn test_sampled_properties_are_optional_only_when_missing_from_the_sample() {
+        let description = GraphDescription {
+            labels: vec![LabelDescription {
+                name: "Tag".to_string(),
+                count: 1_000,
+                properties: vec![property("name", 100, &["String"])],
+                ..Default::default()
+            }],
+            relationship_types: Vec::new(),
+            sample_size: Some(100),
+        };
+        let source = CodeGenerator::new().render(&description);
+        assert!(source.contains("    pub name: String,"));
+        assert!(!source.contains("serde"));
+    }
+}
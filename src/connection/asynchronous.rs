@@ -4,8 +4,10 @@
  */
 
 use crate::{
-    client::asynchronous::FalkorAsyncClientInner, connection::map_redis_err,
-    parser::parse_redis_info, FalkorDBError, FalkorResult,
+    client::asynchronous::FalkorAsyncClientInner,
+    connection::{map_failover_err, map_redis_err},
+    parser::parse_redis_info,
+    FalkorDBError, FalkorResult,
 };
 use std::{collections::HashMap, sync::Arc};
 use tokio::sync::mpsc;
@@ -16,15 +18,22 @@ pub(crate) enum FalkorAsyncConnection {
     /// clone shares the same underlying socket and routes responses back to the right
     /// caller, so many clones can have commands in flight concurrently.
     Managed(redis::aio::ConnectionManager),
+    /// A multiplexed Redis Cluster connection. Cheaply cloneable like [`Managed`](Self::Managed);
+    /// each command is routed to the node owning its key's hash slot, reconnecting and following
+    /// MOVED/ASK redirections on its own.
+    #[cfg(feature = "tokio-cluster")]
+    Cluster(redis::cluster_async::ClusterConnection),
 }
 
 impl FalkorAsyncConnection {
-    /// Clone this connection handle. Both variants are cheap, reference-counted handles
+    /// Clone this connection handle. Every variant is a cheap, reference-counted handles
     /// over a shared multiplexed socket, so the clone can carry commands concurrently.
     pub(crate) fn clone_handle(&self) -> Self {
         match self {
             FalkorAsyncConnection::Redis(conn) => FalkorAsyncConnection::Redis(conn.clone()),
             FalkorAsyncConnection::Managed(conn) => FalkorAsyncConnection::Managed(conn.clone()),
+            #[cfg(feature = "tokio-cluster")]
+            FalkorAsyncConnection::Cluster(conn) => FalkorAsyncConnection::Cluster(conn.clone()),
         }
     }
 
@@ -38,6 +47,7 @@ impl FalkorAsyncConnection {
         command: &str,
         subcommand: Option<&str>,
         params: Option<&[&str]>,
+        map_err: fn(redis::RedisError) -> FalkorDBError,
     ) -> FalkorResult<redis::Value> {
         let mut cmd = redis::cmd(command);
         cmd.arg(subcommand);
@@ -47,17 +57,41 @@ impl FalkorAsyncConnection {
                 cmd.arg(*param);
             }
         }
+        #[cfg(feature = "tokio-cluster")]
+        if let FalkorAsyncConnection::Cluster(cluster_conn) = self {
+            if let Some(routing) =
+                super::cluster::route_for(command, subcommand, graph_name, params)?
+            {
+                return cluster_conn
+                    .route_command(cmd, routing)
+                    .await
+                    .map_err(map_err);
+            }
+        }
+        self.execute_cmd(&cmd, map_err).await
+    }
+
+    /// Send an already-built [`redis::Cmd`], for commands whose arguments are not all strings
+    /// (such as the binary payloads of `GRAPH.BULK`).
+    pub(crate) async fn execute_cmd(
+        &mut self,
+        cmd: &redis::Cmd,
+        map_err: fn(redis::RedisError) -> FalkorDBError,
+    ) -> FalkorResult<redis::Value> {
         match self {
-            FalkorAsyncConnection::Redis(redis_conn) => redis_conn
-                .send_packed_command(&cmd)
-                .await
-                .map_err(map_redis_err),
+            FalkorAsyncConnection::Redis(redis_conn) => {
+                redis_conn.send_packed_command(cmd).await.map_err(map_err)
+            }
             FalkorAsyncConnection::Managed(redis_conn) => {
                 use redis::aio::ConnectionLike as _;
-                redis_conn
-                    .req_packed_command(&cmd)
-                    .await
-                    .map_err(map_redis_err)
+                redis_conn.req_packed_command(cmd).await.map_err(map_err)
+            }
+            // Graph commands take the graph name as their first argument, so the cluster routes
+            // them by the graph key's hash slot.
+            #[cfg(feature = "tokio-cluster")]
+            FalkorAsyncConnection::Cluster(cluster_conn) => {
+                use redis::aio::ConnectionLike as _;
+                cluster_conn.req_packed_command(cmd).await.map_err(map_err)
             }
         }
     }
@@ -71,6 +105,7 @@ impl FalkorAsyncConnection {
     pub(crate) async fn execute_pipeline(
         &mut self,
         pipeline: &redis::Pipeline,
+        map_err: fn(redis::RedisError) -> FalkorDBError,
     ) -> FalkorResult<Vec<redis::Value>> {
         use redis::aio::ConnectionLike as _;
         let count = pipeline.len();
@@ -78,11 +113,19 @@ impl FalkorAsyncConnection {
             FalkorAsyncConnection::Redis(redis_conn) => redis_conn
                 .req_packed_commands(pipeline, 0, count)
                 .await
-                .map_err(map_redis_err),
+                .map_err(map_err),
             FalkorAsyncConnection::Managed(redis_conn) => redis_conn
                 .req_packed_commands(pipeline, 0, count)
                 .await
-                .map_err(map_redis_err),
+                .map_err(map_err),
+            #[cfg(feature = "tokio-cluster")]
+            FalkorAsyncConnection::Cluster(cluster_conn) => {
+                super::cluster::check_pipeline(pipeline)?;
+                cluster_conn
+                    .req_packed_commands(pipeline, 0, count)
+                    .await
+                    .map_err(map_err)
+            }
         }
     }
 
@@ -94,7 +137,7 @@ impl FalkorAsyncConnection {
         &mut self,
         section: Option<&str>,
     ) -> FalkorResult<HashMap<String, String>> {
-        self.execute_command(None, "INFO", section, None)
+        self.execute_command(None, "INFO", section, None, map_redis_err)
             .await
             .and_then(parse_redis_info)
     }
@@ -113,8 +156,16 @@ enum ConnReturn {
     /// Send the connection back to a bounded pool (the `Pooled` strategy).
     Pool(mpsc::Sender<FalkorAsyncConnection>),
     /// Drop the connection. Multiplexed connections are cheap `Arc` clones over a
-    /// shared socket, so there is nothing to return.
-    Discard,
+    /// shared socket, so there is nothing to return. `generation` identifies the connection
+    /// set the clone was taken from, so a failure can trigger a single re-resolution, and
+    /// `reresolve_on_failover` whether that set was resolved through Sentinel.
+    Discard {
+        generation: u64,
+        reresolve_on_failover: bool,
+    },
+    /// Drop the connection. A dedicated connection opened for one exclusive borrow, closed
+    /// once the borrow ends.
+    Close,
 }
 
 /// A container for a connection that is borrowed from the pool.
@@ -150,6 +201,8 @@ impl BorrowedAsyncConnection {
     /// is discarded on drop rather than returned to a pool.
     pub(crate) fn new_multiplexed(
         conn: FalkorAsyncConnection,
+        generation: u64,
+        reresolve_on_failover: bool,
         client: Arc<FalkorAsyncClientInner>,
         readonly: bool,
     ) -> Self {
@@ -157,16 +210,48 @@ impl BorrowedAsyncConnection {
         crate::observability::connection_borrow_started(readonly);
         Self {
             conn: Some(conn),
-            return_to: ConnReturn::Discard,
+            return_to: ConnReturn::Discard {
+                generation,
+                reresolve_on_failover,
+            },
             client,
             readonly,
         }
     }
 
+    /// Create a borrowed connection over a dedicated connection that no other caller shares,
+    /// closed on drop.
+    pub(crate) fn new_dedicated(
+        conn: FalkorAsyncConnection,
+        client: Arc<FalkorAsyncClientInner>,
+    ) -> Self {
+        #[cfg(feature = "metrics")]
+        crate::observability::connection_borrow_started(false);
+        Self {
+            conn: Some(conn),
+            return_to: ConnReturn::Close,
+            client,
+            readonly: false,
+        }
+    }
+
     pub(crate) fn as_inner(&mut self) -> FalkorResult<&mut FalkorAsyncConnection> {
         self.conn.as_mut().ok_or(FalkorDBError::EmptyConnection)
     }
 
+    /// How errors of this connection map to [`FalkorDBError`]. A `READONLY` reply only signals a
+    /// failover on a Sentinel-resolved multiplexed connection, which re-resolves on
+    /// `ConnectionDown`; everywhere else it is surfaced as the server's error.
+    fn error_mapper(&self) -> fn(redis::RedisError) -> FalkorDBError {
+        match self.return_to {
+            ConnReturn::Discard {
+                reresolve_on_failover: true,
+                ..
+            } => map_failover_err,
+            _ => map_redis_err,
+        }
+    }
+
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(
@@ -176,18 +261,33 @@ impl BorrowedAsyncConnection {
         )
     )]
     pub(crate) async fn execute_command(
-        mut self,
+        &mut self,
         graph_name: Option<&str>,
         command: &str,
         subcommand: Option<&str>,
         params: Option<&[&str]>,
     ) -> FalkorResult<redis::Value> {
+        let map_err = self.error_mapper();
         let result = self
             .as_inner()?
-            .execute_command(graph_name, command, subcommand, params)
+            .execute_command(graph_name, command, subcommand, params, map_err)
             .await;
         self.recover_on_connection_down(result).await
-        // `self` is dropped here, returning the connection (see the `Drop` impl below).
+    }
+
+    /// Send an already-built [`redis::Cmd`], with the same dead-connection recovery as
+    /// [`execute_command`](Self::execute_command).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Borrowed Connection Execute Cmd", skip_all, level = "trace")
+    )]
+    pub(crate) async fn execute_cmd(
+        &mut self,
+        cmd: &redis::Cmd,
+    ) -> FalkorResult<redis::Value> {
+        let map_err = self.error_mapper();
+        let result = self.as_inner()?.execute_cmd(cmd, map_err).await;
+        self.recover_on_connection_down(result).await
     }
 
     /// Dispatch a whole pipeline in one round-trip, with the same dead-connection recovery as
@@ -201,22 +301,32 @@ impl BorrowedAsyncConnection {
         )
     )]
     pub(crate) async fn execute_pipeline(
-        mut self,
+        &mut self,
         pipeline: &redis::Pipeline,
     ) -> FalkorResult<Vec<redis::Value>> {
-        let result = self.as_inner()?.execute_pipeline(pipeline).await;
+        let map_err = self.error_mapper();
+        let result = self.as_inner()?.execute_pipeline(pipeline, map_err).await;
         self.recover_on_connection_down(result).await
     }
 
     /// On a dead-connection error, swap in a fresh connection so a live one is returned to the pool
     /// on drop, then re-surface `ConnectionDown` (or `NoConnection` if none is available); other
-    /// results pass through unchanged.
+    /// results pass through unchanged. A Sentinel-backed multiplexed connection is first
+    /// re-resolved, so the next command reaches the node Sentinel now reports.
     async fn recover_on_connection_down<T>(
         &mut self,
         result: FalkorResult<T>,
     ) -> FalkorResult<T> {
         match result {
             Err(FalkorDBError::ConnectionDown) => {
+                if let ConnReturn::Discard { generation, .. } = self.return_to {
+                    // Best effort: if Sentinel cannot be reached, the current connections stay
+                    // in place and the next failure tries again.
+                    let _ = self
+                        .client
+                        .reresolve_multiplexed(self.readonly, generation)
+                        .await;
+                }
                 if let Ok(new_conn) = self.client.fresh_connection(self.readonly).await {
                     self.conn = Some(new_conn);
                     return Err(FalkorDBError::ConnectionDown);
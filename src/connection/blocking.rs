@@ -14,11 +14,16 @@ use std::{
     sync::{mpsc, Arc},
 };
 
+#[allow(clippy::large_enum_variant)]
 pub(crate) enum FalkorSyncConnection {
     #[cfg(test)]
     None,
 
     Redis(redis::Connection),
+    /// A Redis Cluster connection, routing each command to the node owning its key's hash slot
+    /// and following MOVED/ASK redirections.
+    #[cfg(feature = "cluster")]
+    Cluster(redis::cluster::ClusterConnection),
 }
 
 impl FalkorSyncConnection {
@@ -33,18 +38,43 @@ impl FalkorSyncConnection {
         subcommand: Option<&str>,
         params: Option<&[&str]>,
     ) -> FalkorResult<redis::Value> {
+        let mut cmd = redis::cmd(command);
+        cmd.arg(subcommand);
+        cmd.arg(graph_name);
+        if let Some(params) = params {
+            for param in params {
+                cmd.arg(param.to_string());
+            }
+        }
+        #[cfg(feature = "cluster")]
+        if let FalkorSyncConnection::Cluster(cluster_conn) = self {
+            if let Some(routing) =
+                super::cluster::route_for(command, subcommand, graph_name, params)?
+            {
+                return cluster_conn
+                    .route_command(&cmd, routing)
+                    .map_err(map_redis_err);
+            }
+        }
+        self.execute_cmd(&cmd)
+    }
+
+    /// Send an already-built [`redis::Cmd`], for commands whose arguments are not all strings
+    /// (such as the binary payloads of `GRAPH.BULK`).
+    pub(crate) fn execute_cmd(
+        &mut self,
+        cmd: &redis::Cmd,
+    ) -> FalkorResult<redis::Value> {
+        use redis::ConnectionLike as _;
         match self {
             FalkorSyncConnection::Redis(redis_conn) => {
-                use redis::ConnectionLike as _;
-                let mut cmd = redis::cmd(command);
-                cmd.arg(subcommand);
-                cmd.arg(graph_name);
-                if let Some(params) = params {
-                    for param in params {
-                        cmd.arg(param.to_string());
-                    }
-                }
-                redis_conn.req_command(&cmd).map_err(map_redis_err)
+                redis_conn.req_command(cmd).map_err(map_redis_err)
+            }
+            // Graph commands take the graph name as their first argument, so the cluster routes
+            // them by the graph key's hash slot.
+            #[cfg(feature = "cluster")]
+            FalkorSyncConnection::Cluster(cluster_conn) => {
+                cluster_conn.req_command(cmd).map_err(map_redis_err)
             }
             #[cfg(test)]
             FalkorSyncConnection::None => Ok(redis::Value::Nil),
@@ -61,10 +91,15 @@ impl FalkorSyncConnection {
         &mut self,
         pipeline: &redis::Pipeline,
     ) -> FalkorResult<Vec<redis::Value>> {
+        use redis::ConnectionLike as _;
         match self {
-            FalkorSyncConnection::Redis(redis_conn) => {
-                use redis::ConnectionLike as _;
-                redis_conn
+            FalkorSyncConnection::Redis(redis_conn) => redis_conn
+                .req_packed_commands(&pipeline.get_packed_pipeline(), 0, pipeline.len())
+                .map_err(map_redis_err),
+            #[cfg(feature = "cluster")]
+            FalkorSyncConnection::Cluster(cluster_conn) => {
+                super::cluster::check_pipeline(pipeline)?;
+                cluster_conn
                     .req_packed_commands(&pipeline.get_packed_pipeline(), 0, pipeline.len())
                     .map_err(map_redis_err)
             }
@@ -147,6 +182,20 @@ impl BorrowedSyncConnection {
         self.recover_on_connection_down(result)
     }
 
+    /// Send an already-built [`redis::Cmd`], with the same dead-connection recovery as
+    /// [`execute_command`](Self::execute_command).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Borrowed Connection Execute Cmd", skip_all, level = "trace")
+    )]
+    pub(crate) fn execute_cmd(
+        &mut self,
+        cmd: &redis::Cmd,
+    ) -> FalkorResult<redis::Value> {
+        let result = self.as_inner()?.execute_cmd(cmd);
+        self.recover_on_connection_down(result)
+    }
+
     /// Dispatch a whole pipeline in one round-trip, with the same dead-connection recovery as
     /// [`execute_command`](Self::execute_command).
     #[cfg_attr(
@@ -0,0 +1,197 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Cluster routing for the graph commands that are not keyed by their first argument.
+//!
+//! The cluster client routes an unknown command by the hash slot of its first argument, which is
+//! the graph name for `GRAPH.QUERY` and friends. The client-level commands either take no key
+//! (`GRAPH.LIST`), take a subcommand first (`GRAPH.CONFIG`, `GRAPH.UDF`), or take two keys
+//! (`GRAPH.COPY`), so they are routed explicitly here. Transactions are rejected: the cluster
+//! client sends each command of a pipeline to the node of its own key, so neither `MULTI`/`EXEC`
+//! nor a `WATCH` would hold across the commands they guard.
+
+use crate::{FalkorDBError, FalkorResult};
+use redis::cluster_routing::{
+    MultipleNodeRoutingInfo, ResponsePolicy, RoutingInfo, SingleNodeRoutingInfo, Slot,
+};
+
+/// Returns how a cluster connection must route `command`, or `None` to let the cluster client
+/// route it by the slot of its first argument.
+///
+/// * `GRAPH.LIST` runs on every primary and the graph names of all shards are merged.
+/// * `GRAPH.CONFIG SET` and the `GRAPH.UDF` subcommands that change the loaded libraries run on
+///   every primary, so all shards share one configuration and one set of functions.
+/// * `GRAPH.CONFIG GET` and `GRAPH.UDF LIST` read from any one primary.
+/// * `GRAPH.COPY` must copy within one hash slot; copying between slots is rejected with
+///   [`FalkorDBError::CrossSlotGraphCopy`] before anything is sent.
+/// * `WATCH`, which starts a graph transaction, is rejected with
+///   [`FalkorDBError::UnsupportedOnCluster`].
+pub(crate) fn route_for(
+    command: &str,
+    subcommand: Option<&str>,
+    graph_name: Option<&str>,
+    params: Option<&[&str]>,
+) -> FalkorResult<Option<RoutingInfo>> {
+    let is = |expected: &str| subcommand.is_some_and(|sub| sub.eq_ignore_ascii_case(expected));
+    let route = match command {
+        "GRAPH.LIST" => Some(all_primaries(ResponsePolicy::CombineArrays)),
+        "GRAPH.CONFIG" if is("SET") => Some(all_primaries(ResponsePolicy::AllSucceeded)),
+        "GRAPH.UDF" if is("LOAD") || is("DELETE") || is("FLUSH") => {
+            Some(all_primaries(ResponsePolicy::AllSucceeded))
+        }
+        "GRAPH.CONFIG" | "GRAPH.UDF" => Some(RoutingInfo::SingleNode(
+            SingleNodeRoutingInfo::RandomPrimary,
+        )),
+        "WATCH" => {
+            return Err(FalkorDBError::UnsupportedOnCluster {
+                operation: "a graph transaction",
+            })
+        }
+        "GRAPH.COPY" => {
+            if let (Some(graph), Some(&[new_graph, ..])) = (graph_name, params) {
+                check_same_slot(graph, new_graph)?;
+            }
+            None
+        }
+        _ => None,
+    };
+    Ok(route)
+}
+
+/// Rejects a `MULTI`/`EXEC` pipeline, such as an atomic batch, with
+/// [`FalkorDBError::UnsupportedOnCluster`] before anything is sent.
+pub(crate) fn check_pipeline(pipeline: &redis::Pipeline) -> FalkorResult<()> {
+    let is_transaction = pipeline
+        .cmd_iter()
+        .next()
+        .and_then(|cmd| cmd.args_iter().next())
+        .is_some_and(
+            |name| matches!(name, redis::Arg::Simple(name) if name.eq_ignore_ascii_case(b"MULTI")),
+        );
+    if is_transaction {
+        return Err(FalkorDBError::UnsupportedOnCluster {
+            operation: "an atomic batch",
+        });
+    }
+    Ok(())
+}
+
+fn all_primaries(policy: ResponsePolicy) -> RoutingInfo {
+    RoutingInfo::MultiNode((MultipleNodeRoutingInfo::AllMasters, Some(policy)))
+}
+
+/// A cluster can only copy a graph into a key of the same hash slot, since both live on one shard.
+fn check_same_slot(
+    graph: &str,
+    new_graph: &str,
+) -> FalkorResult<()> {
+    if Slot::for_key(graph) == Slot::for_key(new_graph) {
+        return Ok(());
+    }
+    Err(FalkorDBError::CrossSlotGraphCopy {
+        graph: graph.to_string(),
+        new_graph: new_graph.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(
+        command: &str,
+        subcommand: Option<&str>,
+    ) -> Option<RoutingInfo> {
+        route_for(command, subcommand, None, None).expect("routable")
+    }
+
+    fn is_all_primaries(
+        route: Option<RoutingInfo>,
+        expected: ResponsePolicy,
+    ) -> bool {
+        matches!(
+            route,
+            Some(RoutingInfo::MultiNode((MultipleNodeRoutingInfo::AllMasters, Some(policy))))
+                if policy == expected
+        )
+    }
+
+    #[test]
+    fn keyless_and_cluster_wide_commands_fan_out_to_every_primary() {
+        assert!(is_all_primaries(
+            route("GRAPH.LIST", None),
+            ResponsePolicy::CombineArrays
+        ));
+        for (command, subcommand) in [
+            ("GRAPH.CONFIG", "SET"),
+            ("GRAPH.UDF", "LOAD"),
+            ("GRAPH.UDF", "delete"),
+            ("GRAPH.UDF", "FLUSH"),
+        ] {
+            assert!(
+                is_all_primaries(
+                    route(command, Some(subcommand)),
+                    ResponsePolicy::AllSucceeded
+                ),
+                "{command} {subcommand}"
+            );
+        }
+    }
+
+    #[test]
+    fn subcommand_reads_go_to_one_primary_and_graph_commands_keep_key_routing() {
+        for (command, subcommand) in [("GRAPH.CONFIG", "GET"), ("GRAPH.UDF", "LIST")] {
+            assert_eq!(
+                route(command, Some(subcommand)),
+                Some(RoutingInfo::SingleNode(
+                    SingleNodeRoutingInfo::RandomPrimary
+                )),
+                "{command} {subcommand}"
+            );
+        }
+        for command in [
+            "GRAPH.QUERY",
+            "GRAPH.RO_QUERY",
+            "GRAPH.DELETE",
+            "GRAPH.BULK",
+        ] {
+            assert_eq!(route(command, None), None, "{command}");
+        }
+    }
+
+    #[test]
+    fn graph_copy_must_stay_within_one_slot() {
+        let copy =
+            |graph, new_graph| route_for("GRAPH.COPY", None, Some(graph), Some(&[new_graph]));
+        assert_eq!(copy("{users}", "{users}-backup"), Ok(None));
+        assert!(matches!(
+            copy("users", "users-backup"),
+            Err(FalkorDBError::CrossSlotGraphCopy { .. })
+        ));
+    }
+
+    #[test]
+    fn transactions_are_rejected_before_anything_is_sent() {
+        assert!(matches!(
+            route_for("WATCH", None, Some("users"), None),
+            Err(FalkorDBError::UnsupportedOnCluster { .. })
+        ));
+
+        let queries = [crate::BatchQuery::write("CREATE (:N)")];
+        let atomic = crate::graph::batch::prepare_atomic("users", &queries).expect("encodes");
+        assert!(matches!(
+            check_pipeline(&atomic),
+            Err(FalkorDBError::UnsupportedOnCluster { .. })
+        ));
+
+        let mut batch = redis::pipe();
+        batch.cmd("GRAPH.QUERY").arg("users").arg("CREATE (:N)");
+        batch
+            .cmd("GRAPH.RO_QUERY")
+            .arg("users")
+            .arg("MATCH (n) RETURN n");
+        assert_eq!(check_pipeline(&batch), Ok(()));
+    }
+}
@@ -10,7 +10,10 @@ pub(crate) mod blocking;
 #[cfg(feature = "tokio")]
 pub(crate) mod asynchronous;
 
-fn map_redis_err(error: redis::RedisError) -> FalkorDBError {
+#[cfg(feature = "cluster")]
+mod cluster;
+
+pub(crate) fn map_redis_err(error: redis::RedisError) -> FalkorDBError {
     match error.kind() {
         redis::ErrorKind::Io
         | redis::ErrorKind::ClusterConnectionNotFound
@@ -22,6 +25,17 @@ fn map_redis_err(error: redis::RedisError) -> FalkorDBError {
     }
 }
 
+/// Like [`map_redis_err`], but also treats a `READONLY` reply as a dead connection. Only used by
+/// multiplexed connections resolved through Sentinel: there, `READONLY` means a failover demoted
+/// the node the connection is pinned to, and the `ConnectionDown` makes the client re-resolve it.
+#[cfg(feature = "tokio")]
+fn map_failover_err(error: redis::RedisError) -> FalkorDBError {
+    match error.kind() {
+        redis::ErrorKind::Server(redis::ServerErrorKind::ReadOnly) => FalkorDBError::ConnectionDown,
+        _ => map_redis_err(error),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,6 +74,23 @@ mod tests {
         assert!(matches!(result, FalkorDBError::ConnectionDown));
     }
 
+    #[test]
+    fn test_map_redis_err_read_only() {
+        let error = redis::RedisError::from((
+            redis::ErrorKind::Server(redis::ServerErrorKind::ReadOnly),
+            "test",
+        ));
+        assert!(matches!(
+            map_redis_err(error.clone()),
+            FalkorDBError::RedisError(_)
+        ));
+        #[cfg(feature = "tokio")]
+        assert!(matches!(
+            map_failover_err(error),
+            FalkorDBError::ConnectionDown
+        ));
+    }
+
     #[test]
     fn test_map_redis_err_other() {
         let error = redis::RedisError::from((redis::ErrorKind::UnexpectedReturnType, "test error"));
@@ -18,6 +18,10 @@ pub enum FalkorConnectionInfo {
     /// by both "embedded" and "embedded-bundle")
     #[cfg(feature = "embedded-core")]
     Embedded(EmbeddedConfig),
+    /// A Redis Cluster, reached through one or more of its nodes (requires the "cluster"
+    /// feature). Graph commands are routed to the node owning the graph key's hash slot.
+    #[cfg(feature = "cluster")]
+    Cluster(Vec<redis::ConnectionInfo>),
 }
 
 impl FalkorConnectionInfo {
@@ -33,15 +37,64 @@ impl FalkorConnectionInfo {
         }))
     }
 
+    /// Creates a Redis Cluster connection info from the addresses of one or more of its nodes.
+    /// The remaining nodes and the slot layout are discovered from the cluster itself, so the seed
+    /// nodes only need to include one reachable node.
+    ///
+    /// Graph commands are routed by the hash slot of the graph name. The client-wide commands are
+    /// spread over the cluster instead: [`list_graphs`](crate::FalkorSyncClient::list_graphs)
+    /// merges the graphs of every primary, a configuration change or UDF load/delete/flush is
+    /// applied on every primary, and configuration reads go to any one primary. A graph can only
+    /// be copied to a name in the same hash slot, so give both names a shared hash tag such as
+    /// `{movies}` and `{movies}-copy`. Atomic batches and transactions are not supported and fail
+    /// with [`FalkorDBError::UnsupportedOnCluster`] before anything is sent.
+    ///
+    /// # Arguments
+    /// * `nodes`: the seed nodes, each in any form accepted for a single connection (for example
+    ///   `"falkor://127.0.0.1:7000"` or `("127.0.0.1", 7000)`)
+    ///
+    /// # Returns
+    /// A [`FalkorConnectionInfo::Cluster`], or an error if a node address is invalid or no node
+    /// was given.
+    #[cfg(feature = "cluster")]
+    pub fn cluster<I, T>(nodes: I) -> FalkorResult<FalkorConnectionInfo>
+    where
+        I: IntoIterator<Item = T>,
+        T: TryInto<FalkorConnectionInfo, Error = FalkorDBError>,
+    {
+        let nodes = nodes
+            .into_iter()
+            .map(|node| match node.try_into()? {
+                FalkorConnectionInfo::Redis(info) => Ok(info),
+                _ => Err(FalkorDBError::InvalidConnectionInfo(
+                    "cluster seed nodes must be Redis addresses".to_string(),
+                )),
+            })
+            .collect::<FalkorResult<Vec<_>>>()?;
+        if nodes.is_empty() {
+            return Err(FalkorDBError::InvalidConnectionInfo(
+                "a cluster needs at least one seed node".to_string(),
+            ));
+        }
+        Ok(FalkorConnectionInfo::Cluster(nodes))
+    }
+
     /// Retrieves the internally stored address for this connection info
     ///
     /// # Returns
-    /// A [`String`] representation of the address and port, or a UNIX socket path
+    /// A [`String`] representation of the address and port, or a UNIX socket path. For a cluster,
+    /// the comma-separated addresses of its seed nodes.
     pub fn address(&self) -> String {
         match self {
             FalkorConnectionInfo::Redis(redis_info) => redis_info.addr().to_string(),
             #[cfg(feature = "embedded-core")]
             FalkorConnectionInfo::Embedded(_) => "embedded".to_string(),
+            #[cfg(feature = "cluster")]
+            FalkorConnectionInfo::Cluster(nodes) => nodes
+                .iter()
+                .map(|node| node.addr().to_string())
+                .collect::<Vec<_>>()
+                .join(","),
         }
     }
 }
@@ -98,7 +151,7 @@ mod tests {
             FalkorConnectionInfo::Redis(redis) => {
                 assert_eq!(redis.addr().to_string(), "127.0.0.1:6379".to_string());
             }
-            #[cfg(feature = "embedded-core")]
+            #[cfg(any(feature = "embedded-core", feature = "cluster"))]
             _ => panic!("Expected Redis connection info"),
         }
     }
@@ -120,7 +173,7 @@ mod tests {
             FalkorConnectionInfo::Redis(conn) => {
                 assert_eq!(conn.addr(), raw_redis_conn.addr());
             }
-            #[cfg(feature = "embedded-core")]
+            #[cfg(any(feature = "embedded-core", feature = "cluster"))]
             _ => panic!("Expected Redis connection info"),
         }
     }
@@ -237,4 +290,23 @@ mod tests {
         assert!(result2.is_ok());
         assert_ne!(result1.unwrap().address(), result2.unwrap().address());
     }
+
+    #[test]
+    #[cfg(feature = "cluster")]
+    fn test_cluster_from_seed_nodes() {
+        let info =
+            FalkorConnectionInfo::cluster(["127.0.0.1:7000", "falkor://127.0.0.1:7001"]).unwrap();
+        assert!(matches!(&info, FalkorConnectionInfo::Cluster(nodes) if nodes.len() == 2));
+        assert_eq!(info.address(), "127.0.0.1:7000,127.0.0.1:7001");
+    }
+
+    #[test]
+    #[cfg(feature = "cluster")]
+    fn test_cluster_requires_a_seed_node() {
+        let result = FalkorConnectionInfo::cluster(Vec::<&str>::new());
+        assert!(matches!(
+            result,
+            Err(FalkorDBError::InvalidConnectionInfo(_))
+        ));
+    }
 }
@@ -164,6 +164,90 @@ pub enum FalkorDBError {
         /// A human-readable description of why encoding failed.
         message: String,
     },
+    /// A node or relationship could not be encoded for `GRAPH.BULK` (for example a node without a
+    /// label, a repeated property key, a string containing a NUL byte, or a relationship to a node
+    /// that was not added to the same bulk load).
+    #[error("cannot bulk-load entity: {message}")]
+    BulkEncoding {
+        /// A human-readable description of why encoding failed.
+        message: String,
+    },
+    /// A chunk written by an [`UnwindWriter`](crate::UnwindWriter) failed, so no further chunk was
+    /// sent. The chunks that did succeed are summed in [`UnwindWriter::report`](crate::UnwindWriter::report).
+    #[error("UNWIND chunk {chunk} (rows {first_row}..{}) failed: {source}", .first_row + .rows)]
+    UnwindChunkFailed {
+        /// The zero-based index of the failed chunk.
+        chunk: usize,
+        /// The zero-based index of the chunk's first record.
+        first_row: usize,
+        /// The number of records in the chunk.
+        rows: usize,
+        /// Why the chunk failed.
+        source: Box<FalkorDBError>,
+    },
+    /// A CSV import was refused: a mapping does not fit its file or the target, or a line could not
+    /// be parsed or resolved.
+    #[cfg(feature = "csv")]
+    #[error("{input}{}: {message}", .line.map(|line| format!(", line {line}")).unwrap_or_default())]
+    CsvImport {
+        /// The file, or the mapping of data that was not read from a file.
+        input: String,
+        /// The 1-based line at fault (the header is line 1), if the error is about a line.
+        line: Option<u64>,
+        /// A human-readable description of the problem.
+        message: String,
+    },
+    /// A Cypher dump could not be written or replayed: the output failed, a value has no Cypher
+    /// literal, or a line of the dump is malformed.
+    #[error("Cypher dump: {message}")]
+    CypherDump {
+        /// A human-readable description of the problem.
+        message: String,
+    },
+    /// A JSON Lines or GraphML export could not be written: the output failed, or a value or name
+    /// has no representation in the format.
+    #[cfg(any(feature = "jsonl", feature = "graphml"))]
+    #[error("{format} export: {message}")]
+    GraphExport {
+        /// The format being written.
+        format: &'static str,
+        /// A human-readable description of the problem.
+        message: String,
+    },
+    /// A JSON Lines or GraphML import was refused: the input is malformed, or a relationship refers
+    /// to a node that does not come before it.
+    #[cfg(any(feature = "jsonl", feature = "graphml"))]
+    #[error("{format} import, {position}: {message}")]
+    GraphImport {
+        /// The format being read.
+        format: &'static str,
+        /// Where in the input the problem is, such as `line 12`.
+        position: String,
+        /// A human-readable description of the problem.
+        message: String,
+    },
+    /// A migration could not be planned or applied; the migrations before it stay applied.
+    #[cfg(feature = "migrations")]
+    #[error("Migration {version}: {message}")]
+    MigrationFailed {
+        /// The version of the migration.
+        version: u64,
+        /// A human-readable description of the problem.
+        message: String,
+    },
+    /// A migration was edited after it was applied: its checksum no longer matches the history.
+    #[cfg(feature = "migrations")]
+    #[error(
+        "Migration {version} changed after it was applied: checksum {current}, {recorded} recorded"
+    )]
+    MigrationChecksumMismatch {
+        /// The version of the migration.
+        version: u64,
+        /// The checksum recorded when it was applied.
+        recorded: String,
+        /// The checksum of its current steps.
+        current: String,
+    },
     /// An index `OPTIONS` key was not a valid Cypher identifier, so it could not be safely
     /// interpolated into the `OPTIONS` map.
     #[error("invalid index option key '{key}': {message}")]
@@ -173,12 +257,27 @@ pub enum FalkorDBError {
         /// A human-readable description of why the key is invalid.
         message: String,
     },
+    /// A label, relationship type or property name passed to a query helper could not be safely
+    /// quoted as a Cypher identifier (it is empty, or contains a backtick or a NUL byte).
+    #[error("invalid identifier '{identifier}': {message}")]
+    InvalidIdentifier {
+        /// The offending identifier.
+        identifier: String,
+        /// A human-readable description of why it is invalid.
+        message: String,
+    },
     /// A result row did not contain a column with the requested name.
     #[error("result row has no column named '{name}'")]
     MissingColumn {
         /// The requested column name.
         name: String,
     },
+    /// A node did not have the property a [`FromNode`](crate::FromNode) field is read from.
+    #[error("node has no property named '{name}'")]
+    MissingProperty {
+        /// The requested property name.
+        name: String,
+    },
     /// A result row was indexed past its number of columns.
     #[error("column index {index} is out of bounds for a row with {len} column(s)")]
     ColumnIndexOutOfBounds {
@@ -195,6 +294,16 @@ pub enum FalkorDBError {
         /// The number of values in the row.
         value_len: usize,
     },
+    /// A FalkorDB temporal value does not fit the `chrono`, `time` or `jiff` type it was
+    /// converted into, or the ISO 8601 literal it was written as (for example a year beyond the
+    /// library's range, or a `time` outside one day).
+    #[error("temporal value of {seconds} seconds is out of range for {target}")]
+    TemporalOutOfRange {
+        /// The FalkorDB temporal scalar, in seconds.
+        seconds: i64,
+        /// The type it was converted into.
+        target: &'static str,
+    },
     /// A [`crate::FalkorValue`] could not be converted into the requested Rust type.
     #[error("expected a value of type {expected}, but got {got}")]
     TypeError {
@@ -212,6 +321,59 @@ pub enum FalkorDBError {
         /// What the preference was set on — `"query"` or `"batch"`.
         context: &'static str,
     },
+    /// A query could not be paginated with `SKIP`/`LIMIT`, for example because it has no
+    /// `ORDER BY` (pages would not be stable) or already ends in its own `SKIP`/`LIMIT`.
+    #[error("cannot paginate query: {reason}")]
+    InvalidPagination {
+        /// Why the query cannot be paginated.
+        reason: &'static str,
+    },
+    /// A graph [`transaction`](crate::SyncGraph::transaction) was aborted because another client
+    /// modified the watched graph, on every attempt the client's
+    /// [`RetryPolicy`](crate::RetryPolicy) allowed. None of its writes were applied.
+    #[error("transaction aborted: the graph was modified concurrently")]
+    TransactionConflict,
+    /// [`copy_graph`](crate::FalkorSyncClient::copy_graph) on a Redis Cluster named a new graph
+    /// whose key hashes to a different slot than the source graph. A copy stays on the shard of
+    /// the source, so both names must share a slot; nothing was sent.
+    #[error("cannot copy graph '{graph}' to '{new_graph}': on a Redis Cluster both names must hash to the same slot")]
+    CrossSlotGraphCopy {
+        /// The graph to copy.
+        graph: String,
+        /// The name of the copy.
+        new_graph: String,
+    },
+    /// An operation that needs every command on one connection of one node, such as an
+    /// [atomic batch](crate::BatchBuilder::atomic) or a
+    /// [transaction](crate::SyncGraph::transaction), was attempted on a Redis Cluster connection,
+    /// which routes each command on its own. Nothing was sent.
+    #[error("{operation} is not supported on a Redis Cluster connection")]
+    UnsupportedOnCluster {
+        /// What was attempted, for example `"an atomic batch"`.
+        operation: &'static str,
+    },
+    /// A [`vector_search`](crate::SyncGraph::vector_search) targeted a label or relationship type
+    /// and property that `DB.INDEXES` lists no vector index for.
+    #[error("no vector index on {label}.{property}")]
+    VectorIndexNotFound {
+        /// The label or relationship type searched.
+        label: String,
+        /// The vector property searched.
+        property: String,
+    },
+    /// The query vector of a [`vector_search`](crate::SyncGraph::vector_search) does not have the
+    /// dimension of the vector index it searches.
+    #[error("the vector index on {label}.{property} has dimension {expected}, but the query vector has {actual}")]
+    VectorDimensionMismatch {
+        /// The label or relationship type searched.
+        label: String,
+        /// The vector property searched.
+        property: String,
+        /// The dimension of the index.
+        expected: u32,
+        /// The length of the query vector.
+        actual: usize,
+    },
 }
 
 impl FalkorDBError {
@@ -261,6 +423,22 @@ impl FalkorDBError {
                  cargo feature (for example `tokio` for async, or `rustls` / `native-tls` for TLS), \
                  and for read-only queries make sure a read replica is configured",
             ),
+            Self::TransactionConflict => Some(
+                "another client changed the graph while the transaction ran — configure a \
+                 `RetryPolicy` with more attempts to re-run the transaction automatically",
+            ),
+            Self::UnsupportedOnCluster { .. } => Some(
+                "run atomic batches and transactions against a standalone or Sentinel deployment, \
+                 or send the queries as a non-atomic batch",
+            ),
+            Self::CrossSlotGraphCopy { .. } => Some(
+                "give both graph names the same hash tag, e.g. `{movies}` and `{movies}-copy`, so \
+                 the copy is created on the same shard as the source",
+            ),
+            Self::VectorIndexNotFound { .. } => Some(
+                "create the index with `create_node_vector_index` or `create_edge_vector_index` \
+                 before searching, and check the label and property names",
+            ),
             Self::RedisError(message) | Self::EmbeddedServerError(message) => {
                 server_message_hint(message)
             }
@@ -316,6 +494,18 @@ impl serde::de::Error for FalkorDBError {
     }
 }
 
+/// Raised while encoding a [`SerdeParam`](crate::SerdeParam), so a custom error from a
+/// `Serialize` impl surfaces like any other parameter encoding failure.
+#[cfg(feature = "serde")]
+impl serde::ser::Error for FalkorDBError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        FalkorDBError::ParamEncoding {
+            parameter: None,
+            message: msg.to_string(),
+        }
+    }
+}
+
 impl From<strum::ParseError> for FalkorDBError {
     fn from(value: strum::ParseError) -> Self {
         FalkorDBError::InvalidEnumType(value.to_string())
@@ -0,0 +1,1074 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Cypher dump: write a whole graph out as a deterministic script of Cypher statements, and
+//! [`replay`] that script into another graph or instance.
+//!
+//! The dump lists the graph's indices, then its constraints, then every node and every
+//! relationship in id order, so exporting the same graph twice gives the same file:
+//!
+//! ```no_run
+//! # fn main() -> Result<(), falkordb::FalkorDBError> {
+//! use falkordb::export::cypher::{self, CypherExporter};
+//! use falkordb::FalkorClientBuilder;
+//! use std::io::{BufReader, BufWriter};
+//! # let info: falkordb::FalkorConnectionInfo = "falkor://127.0.0.1:6379".try_into()?;
+//! let client = FalkorClientBuilder::new().with_connection_info(info).build()?;
+//!
+//! let file = std::fs::File::create("social.cypher").expect("create dump");
+//! let exported = CypherExporter::new()
+//!     .with_page_size(5_000)
+//!     .export(&mut client.select_graph("social"), BufWriter::new(file))?;
+//! println!("{} nodes, {} relationships", exported.nodes, exported.relationships);
+//!
+//! let file = std::fs::File::open("social.cypher").expect("open dump");
+//! cypher::replay(&mut client.select_graph("social_copy"), BufReader::new(file))?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Every statement sits on a single line and ends with `;`, so the dump can also be replayed line
+//! by line with any other client. Nodes are created with a temporary `__FalkorDumpNode` label and
+//! `__falkordb_dump_id` property, which the relationship statements match on and the last
+//! statements of the dump remove again; replay into an empty graph. Constraints have no Cypher
+//! syntax, so they are written as `// CONSTRAINT …` comment lines holding the arguments of
+//! `GRAPH.CONSTRAINT CREATE`: [`replay`] runs them, other clients skip them as comments.
+//!
+//! Full-text indices are recreated with the server's default language and stop words.
+
+use crate::graph::{generate_create_index_query, generate_drop_index_query, quote_identifier};
+use crate::{
+    Constraint, ConstraintStatus, Edge, EntityType, FalkorDBError, FalkorIndex, FalkorResult,
+    FalkorValue, IndexType, IntoFalkorParam, Node, SyncGraph,
+};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, Write};
+
+/// The label every node carries while the dump is replayed.
+const DUMP_LABEL: &str = "__FalkorDumpNode";
+/// The property holding a node's id within the dump while it is replayed.
+const DUMP_ID: &str = "__falkordb_dump_id";
+/// The prefix of a comment line holding the arguments of a `GRAPH.CONSTRAINT CREATE`.
+const CONSTRAINT_DIRECTIVE: &str = "// CONSTRAINT ";
+/// The vector index options `DB.INDEXES` reports that `CREATE VECTOR INDEX` accepts.
+const VECTOR_OPTIONS: [&str; 5] = [
+    "dimension",
+    "similarityFunction",
+    "M",
+    "efConstruction",
+    "efRuntime",
+];
+
+fn dump_error(message: impl Into<String>) -> FalkorDBError {
+    FalkorDBError::CypherDump {
+        message: message.into(),
+    }
+}
+
+fn write_error(err: std::io::Error) -> FalkorDBError {
+    dump_error(format!("cannot write the dump: {err}"))
+}
+
+/// What a [`CypherExporter`] wrote.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CypherExportReport {
+    /// The `CREATE INDEX` statements, one per indexed field and index type.
+    pub indices: usize,
+    /// The constraints, skipping any whose construction failed.
+    pub constraints: usize,
+    /// The nodes.
+    pub nodes: usize,
+    /// The relationships.
+    pub relationships: usize,
+    /// The statements, counting constraints but not comments.
+    pub statements: usize,
+}
+
+/// What [`replay`] ran.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CypherReplayReport {
+    /// The Cypher statements.
+    pub statements: usize,
+    /// The constraints created.
+    pub constraints: usize,
+    /// The nodes created.
+    pub nodes_created: i64,
+    /// The relationships created.
+    pub relationships_created: i64,
+}
+
+/// Exports a graph as a replayable Cypher script; see the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct CypherExporter {
+    page_size: usize,
+    batch_size: usize,
+}
+
+impl Default for CypherExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CypherExporter {
+    /// Creates an exporter reading 1000 entities per page and writing 100 entities per statement.
+    pub fn new() -> Self {
+        Self {
+            page_size: 1_000,
+            batch_size: 100,
+        }
+    }
+
+    /// Sets the number of nodes or relationships read per round-trip (at least 1).
+    ///
+    /// # Arguments
+    /// * `page_size`: The page size of the `MATCH` queries walking the graph.
+    pub fn with_page_size(
+        mut self,
+        page_size: usize,
+    ) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Sets the number of nodes or relationships created per statement of the dump (at least 1).
+    ///
+    /// # Arguments
+    /// * `batch_size`: The most entities one `CREATE` or `UNWIND` statement creates.
+    pub fn with_batch_size(
+        mut self,
+        batch_size: usize,
+    ) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Writes the whole graph to `out`.
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to export.
+    /// * `out`: Where to write the script; wrap a file in a [`BufWriter`](std::io::BufWriter).
+    ///
+    /// # Returns
+    /// A [`CypherExportReport`], or the first error. [`FalkorDBError::CypherDump`] reports a failed
+    /// write, a property value with no Cypher literal (such as a non-finite float), or a name with
+    /// a line break; `out` may then hold a partial dump.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Export Cypher Dump", skip_all, level = "info")
+    )]
+    pub fn export<W: Write>(
+        &self,
+        graph: &mut SyncGraph,
+        out: W,
+    ) -> FalkorResult<CypherExportReport> {
+        let indices = graph.list_indices()?.data;
+        let constraints = graph.list_constraints()?.data;
+        let mut out = out;
+        let mut dump = DumpWriter::new(self.batch_size);
+        dump.schema(graph.graph_name(), &indices, &constraints)?;
+        super::for_each_node(graph, self.page_size, |node| {
+            dump.node(node)?;
+            dump.write_to(&mut out)
+        })?;
+        super::for_each_edge(graph, self.page_size, |edge| {
+            dump.edge(edge)?;
+            dump.write_to(&mut out)
+        })?;
+        let report = dump.finish();
+        dump.write_to(&mut out)?;
+        out.flush().map_err(write_error)?;
+        Ok(report)
+    }
+
+    /// Writes the whole graph to `out`; see [`CypherExporter::export`].
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to export.
+    /// * `out`: Where to write the script.
+    ///
+    /// # Returns
+    /// A [`CypherExportReport`], or the first error.
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Export Cypher Dump Async", skip_all, level = "info")
+    )]
+    pub async fn export_async<W: Write>(
+        &self,
+        graph: &mut crate::AsyncGraph,
+        out: W,
+    ) -> FalkorResult<CypherExportReport> {
+        let indices = graph.list_indices().await?.data;
+        let constraints = graph.list_constraints().await?.data;
+        let mut out = out;
+        let mut dump = DumpWriter::new(self.batch_size);
+        dump.schema(graph.graph_name(), &indices, &constraints)?;
+        super::for_each_node_async(graph, self.page_size, |node| {
+            dump.node(node)?;
+            dump.write_to(&mut out)
+        })
+        .await?;
+        super::for_each_edge_async(graph, self.page_size, |edge| {
+            dump.edge(edge)?;
+            dump.write_to(&mut out)
+        })
+        .await?;
+        let report = dump.finish();
+        dump.write_to(&mut out)?;
+        out.flush().map_err(write_error)?;
+        Ok(report)
+    }
+}
+
+/// Replays a dump written by [`CypherExporter`] into `graph`, one statement at a time.
+///
+/// # Arguments
+/// * `graph`: The graph to load into; it should be empty.
+/// * `dump`: The script, read line by line.
+///
+/// # Returns
+/// A [`CypherReplayReport`], or the first error: a failed statement, a failed read, or a line that
+/// is neither a comment nor a statement ending with `;`. The statements before it stay applied.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "Replay Cypher Dump", skip_all, level = "info")
+)]
+pub fn replay<R: BufRead>(
+    graph: &mut SyncGraph,
+    dump: R,
+) -> FalkorResult<CypherReplayReport> {
+    let mut replayer = Replayer::default();
+    for line in dump.lines() {
+        let line = line.map_err(|err| dump_error(format!("cannot read the dump: {err}")))?;
+        replayer.run(graph, &line)?;
+    }
+    Ok(replayer.report)
+}
+
+/// Replays a dump into `graph`; see [`replay`].
+///
+/// # Arguments
+/// * `graph`: The graph to load into; it should be empty.
+/// * `dump`: The script, read line by line.
+///
+/// # Returns
+/// A [`CypherReplayReport`], or the first error.
+#[cfg(feature = "tokio")]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "Replay Cypher Dump Async", skip_all, level = "info")
+)]
+pub async fn replay_async<R: BufRead>(
+    graph: &mut crate::AsyncGraph,
+    dump: R,
+) -> FalkorResult<CypherReplayReport> {
+    let mut replayer = Replayer::default();
+    for line in dump.lines() {
+        let line = line.map_err(|err| dump_error(format!("cannot read the dump: {err}")))?;
+        replayer.run_async(graph, &line).await?;
+    }
+    Ok(replayer.report)
+}
+
+/// Runs the lines of a dump one by one, summing what they did.
+#[derive(Default)]
+pub(crate) struct Replayer {
+    pub(crate) report: CypherReplayReport,
+    /// The number of lines run so far.
+    lines: u64,
+}
+
+impl Replayer {
+    pub(crate) fn run(
+        &mut self,
+        graph: &mut SyncGraph,
+        line: &str,
+    ) -> FalkorResult<()> {
+        self.lines += 1;
+        match parse_line(line, self.lines)? {
+            Line::Skip => {}
+            Line::Constraint(args) => {
+                let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+                graph.execute_command("GRAPH.CONSTRAINT", Some("CREATE"), Some(&args))?;
+                self.report.constraints += 1;
+            }
+            Line::Statement(statement) => {
+                let result = graph.query(statement).execute()?;
+                self.count(
+                    result.get_nodes_created(),
+                    result.get_relationship_created(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "tokio")]
+    pub(crate) async fn run_async(
+        &mut self,
+        graph: &mut crate::AsyncGraph,
+        line: &str,
+    ) -> FalkorResult<()> {
+        self.lines += 1;
+        match parse_line(line, self.lines)? {
+            Line::Skip => {}
+            Line::Constraint(args) => {
+                let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+                graph
+                    .execute_command("GRAPH.CONSTRAINT", Some("CREATE"), Some(&args))
+                    .await?;
+                self.report.constraints += 1;
+            }
+            Line::Statement(statement) => {
+                let result = graph.query(statement).execute().await?;
+                self.count(
+                    result.get_nodes_created(),
+                    result.get_relationship_created(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn count(
+        &mut self,
+        nodes_created: Option<i64>,
+        relationships_created: Option<i64>,
+    ) {
+        self.report.statements += 1;
+        self.report.nodes_created += nodes_created.unwrap_or(0);
+        self.report.relationships_created += relationships_created.unwrap_or(0);
+    }
+}
+
+/// One line of a dump.
+#[derive(Debug, PartialEq)]
+enum Line<'a> {
+    /// A blank line or a plain comment.
+    Skip,
+    /// The arguments of a `GRAPH.CONSTRAINT CREATE`.
+    Constraint(Vec<String>),
+    /// A Cypher statement, without its `;`.
+    Statement(&'a str),
+}
+
+fn parse_line(
+    line: &str,
+    number: u64,
+) -> FalkorResult<Line<'_>> {
+    let line = line.trim();
+    if let Some(args) = line.strip_prefix(CONSTRAINT_DIRECTIVE) {
+        return parse_args(args)
+            .map(Line::Constraint)
+            .map_err(|message| dump_error(format!("line {number}: {message}")));
+    }
+    if line.is_empty() || line.starts_with("//") {
+        return Ok(Line::Skip);
+    }
+    line.strip_suffix(';').map(Line::Statement).ok_or_else(|| {
+        dump_error(format!(
+            "line {number}: expected a comment or a statement ending with ';'"
+        ))
+    })
+}
+
+/// Writes a constraint argument, in double quotes when it is empty or holds a space, a quote, a
+/// backslash or a control character.
+fn write_arg(
+    arg: &str,
+    out: &mut String,
+) {
+    let plain = |c: char| !(c.is_whitespace() || c.is_control() || c == '"' || c == '\\');
+    if !arg.is_empty() && arg.chars().all(plain) {
+        out.push_str(arg);
+        return;
+    }
+    out.push('"');
+    for c in arg.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Splits the arguments written by [`write_arg`].
+fn parse_args(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let Some(first) = chars.next() else {
+            return Ok(args);
+        };
+        let mut arg = String::new();
+        if first != '"' {
+            arg.push(first);
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                arg.push(c);
+            }
+            args.push(arg);
+            continue;
+        }
+        loop {
+            match chars.next() {
+                Some('"') => break,
+                Some('\\') => match chars.next() {
+                    Some('n') => arg.push('\n'),
+                    Some('r') => arg.push('\r'),
+                    Some('t') => arg.push('\t'),
+                    Some(c @ ('"' | '\\')) => arg.push(c),
+                    _ => return Err("invalid escape in a constraint argument".to_string()),
+                },
+                Some(c) => arg.push(c),
+                None => return Err("unterminated constraint argument".to_string()),
+            }
+        }
+        args.push(arg);
+    }
+}
+
+/// A label, relationship type or property name, quoted for the dump. A line break would split the
+/// statement across lines, so it is refused.
+fn identifier(name: &str) -> FalkorResult<String> {
+    if name.contains(['\n', '\r']) {
+        return Err(dump_error(format!(
+            "the name {name:?} contains a line break"
+        )));
+    }
+    quote_identifier(name)
+}
+
+/// Writes `value` as a Cypher expression, or describes why it has none.
+fn write_literal(
+    value: &FalkorValue,
+    out: &mut String,
+) -> Result<(), String> {
+    let describe = |err: FalkorDBError| match err {
+        FalkorDBError::ParamEncoding { message, .. } => message,
+        err => err.to_string(),
+    };
+    let call =
+        |function: &str, out: &mut String, encode: &dyn Fn(&mut String) -> FalkorResult<()>| {
+            out.push_str(function);
+            out.push('(');
+            encode(out).map_err(describe)?;
+            out.push(')');
+            Ok(())
+        };
+    match value {
+        FalkorValue::Array(values) => {
+            out.push('[');
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                write_literal(value, out)?;
+            }
+            out.push(']');
+            Ok(())
+        }
+        FalkorValue::Map(map) => {
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+            out.push('{');
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                out.push_str(&identifier(key).map_err(describe)?);
+                out.push_str(": ");
+                write_literal(value, out)?;
+            }
+            out.push('}');
+            Ok(())
+        }
+        FalkorValue::Point(point) => call("point", out, &|out| point.encode_param(out)),
+        FalkorValue::Vec32(vec) => call("vecf32", out, &|out| vec.encode_param(out)),
+        FalkorValue::DateTime(value) => call("datetime", out, &|out| value.encode_iso_param(out)),
+        FalkorValue::Date(value) => call("date", out, &|out| value.encode_iso_param(out)),
+        FalkorValue::Time(value) => call("localtime", out, &|out| value.encode_iso_param(out)),
+        FalkorValue::Duration(value) => call("duration", out, &|out| value.encode_iso_param(out)),
+        FalkorValue::Node(_) | FalkorValue::Edge(_) | FalkorValue::Path(_) => {
+            Err("a graph entity cannot be stored as a property".to_string())
+        }
+        FalkorValue::Unparseable(raw) => Err(format!("the value could not be parsed: {raw}")),
+        scalar => scalar.encode_param(out).map_err(describe),
+    }
+}
+
+/// The properties of an entity, sorted by name.
+fn sorted_properties(properties: &HashMap<String, FalkorValue>) -> Vec<(&String, &FalkorValue)> {
+    let mut properties = properties.iter().collect::<Vec<_>>();
+    properties.sort_unstable_by_key(|(key, _)| *key);
+    properties
+}
+
+/// The statement being batched.
+enum Pending {
+    None,
+    /// The comma-separated node patterns of a `CREATE`.
+    Nodes(String),
+    /// The rows of an `UNWIND`, and the `MATCH … CREATE` that consumes them. Relationships share
+    /// a statement while they have the same type and property names.
+    Edges {
+        rows: String,
+        tail: String,
+    },
+}
+
+/// Turns a walk over the graph into the lines of a dump, which it holds until they are taken.
+pub(crate) struct DumpWriter {
+    lines: Vec<String>,
+    batch_size: usize,
+    /// The dump id of every exported node, by its id in the graph.
+    ids: HashMap<i64, u64>,
+    pending: Pending,
+    batched: usize,
+    report: CypherExportReport,
+}
+
+impl DumpWriter {
+    pub(crate) fn new(batch_size: usize) -> Self {
+        Self {
+            lines: Vec::new(),
+            batch_size,
+            ids: HashMap::new(),
+            pending: Pending::None,
+            batched: 0,
+            report: CypherExportReport::default(),
+        }
+    }
+
+    /// Takes the lines written since the last call.
+    #[cfg(any(test, feature = "jsonl", feature = "graphml"))]
+    pub(crate) fn take_lines(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.lines)
+    }
+
+    fn write_to<W: Write>(
+        &mut self,
+        out: &mut W,
+    ) -> FalkorResult<()> {
+        for line in self.lines.drain(..) {
+            writeln!(out, "{line}").map_err(write_error)?;
+        }
+        Ok(())
+    }
+
+    fn line(
+        &mut self,
+        line: impl Into<String>,
+    ) {
+        self.lines.push(line.into());
+    }
+
+    fn statement(
+        &mut self,
+        statement: &str,
+    ) {
+        self.report.statements += 1;
+        self.line(format!("{statement};"));
+    }
+
+    /// Writes the header, the indices and the constraints, then opens the node section.
+    pub(crate) fn schema(
+        &mut self,
+        graph_name: &str,
+        indices: &[FalkorIndex],
+        constraints: &[Constraint],
+    ) -> FalkorResult<()> {
+        self.line(format!("// FalkorDB Cypher dump of graph {graph_name:?}"));
+
+        let mut statements = Vec::new();
+        for index in indices {
+            // The options `DB.INDEXES` reports for a label are shared by its vector fields.
+            let options = index
+                .options
+                .iter()
+                .filter(|(key, _)| VECTOR_OPTIONS.contains(&key.as_str()))
+                .filter_map(|(key, value)| match value {
+                    FalkorValue::I64(value) => Some((key.clone(), value.to_string())),
+                    FalkorValue::String(value) => Some((key.clone(), value.clone())),
+                    _ => None,
+                })
+                .collect::<HashMap<_, _>>();
+            let label = identifier(&index.index_label)?;
+            for field in &index.fields {
+                let quoted = [identifier(field)?];
+                for index_type in index.field_types.get(field).into_iter().flatten() {
+                    let options = (*index_type == IndexType::Vector && !options.is_empty())
+                        .then_some(&options);
+                    statements.push(generate_create_index_query(
+                        *index_type,
+                        index.entity_type,
+                        &label,
+                        &quoted,
+                        options,
+                    )?);
+                }
+            }
+        }
+        statements.sort_unstable();
+        statements.dedup();
+        self.line("// Indices");
+        for statement in &statements {
+            self.statement(statement);
+        }
+        self.report.indices = statements.len();
+
+        let mut directives = Vec::new();
+        for constraint in constraints {
+            if constraint.status == ConstraintStatus::Failed {
+                continue;
+            }
+            let mut line = CONSTRAINT_DIRECTIVE.to_string();
+            let properties = constraint.properties.len().to_string();
+            let args = [
+                constraint.constraint_type.to_string(),
+                constraint.entity_type.to_string(),
+                constraint.label.clone(),
+                "PROPERTIES".to_string(),
+                properties,
+            ];
+            for (index, arg) in args.iter().chain(&constraint.properties).enumerate() {
+                if index > 0 {
+                    line.push(' ');
+                }
+                write_arg(arg, &mut line);
+            }
+            directives.push(line);
+        }
+        directives.sort_unstable();
+        directives.dedup();
+        self.line("// Constraints");
+        for directive in &directives {
+            self.report.statements += 1;
+            self.line(directive.as_str());
+        }
+        self.report.constraints = directives.len();
+        self.begin_nodes()
+    }
+
+    /// Opens the node section, creating the index the relationships match dump ids through.
+    pub(crate) fn begin_nodes(&mut self) -> FalkorResult<()> {
+        self.line("// Nodes");
+        self.statement(&generate_create_index_query(
+            IndexType::Range,
+            EntityType::Node,
+            DUMP_LABEL,
+            &[DUMP_ID],
+            None,
+        )?);
+        Ok(())
+    }
+
+    pub(crate) fn node(
+        &mut self,
+        node: Node,
+    ) -> FalkorResult<()> {
+        let dump_id = self.ids.len() as u64;
+        if self.ids.insert(node.entity_id, dump_id).is_some() {
+            return Err(dump_error(format!("node {} appears twice", node.entity_id)));
+        }
+
+        let mut pattern = String::from("(");
+        let mut labels = node
+            .labels
+            .iter()
+            .map(|label| identifier(label))
+            .collect::<FalkorResult<Vec<_>>>()?;
+        labels.sort_unstable();
+        for label in labels.iter().map(String::as_str).chain([DUMP_LABEL]) {
+            pattern.push(':');
+            pattern.push_str(label);
+        }
+        let _ = write!(pattern, " {{{DUMP_ID}: {dump_id}");
+        for (key, value) in sorted_properties(&node.properties) {
+            let _ = write!(pattern, ", {}: ", identifier(key)?);
+            write_literal(value, &mut pattern).map_err(|message| {
+                dump_error(format!(
+                    "property '{key}' of node {}: {message}",
+                    node.entity_id
+                ))
+            })?;
+        }
+        pattern.push_str("})");
+
+        match &mut self.pending {
+            Pending::Nodes(patterns) => {
+                patterns.push_str(", ");
+                patterns.push_str(&pattern);
+            }
+            _ => {
+                self.flush();
+                self.pending = Pending::Nodes(pattern);
+            }
+        }
+        self.report.nodes += 1;
+        self.batched += 1;
+        if self.batched == self.batch_size {
+            self.flush();
+        }
+        Ok(())
+    }
+
+    pub(crate) fn edge(
+        &mut self,
+        edge: Edge,
+    ) -> FalkorResult<()> {
+        let endpoint = |id: i64| {
+            self.ids.get(&id).copied().ok_or_else(|| {
+                dump_error(format!(
+                    "relationship {} references node {id}, which does not come before it",
+                    edge.entity_id
+                ))
+            })
+        };
+        let mut row = format!(
+            "[{}, {}",
+            endpoint(edge.src_node_id)?,
+            endpoint(edge.dst_node_id)?
+        );
+        let mut tail = format!(
+            "MATCH (a:{DUMP_LABEL} {{{DUMP_ID}: e[0]}}) MATCH (b:{DUMP_LABEL} {{{DUMP_ID}: e[1]}}) CREATE (a)-[:{}",
+            identifier(&edge.relationship_type)?
+        );
+        let properties = sorted_properties(&edge.properties);
+        if !properties.is_empty() {
+            tail.push_str(" {");
+        }
+        for (index, (key, value)) in properties.into_iter().enumerate() {
+            let separator = if index > 0 { ", " } else { "" };
+            let _ = write!(tail, "{separator}{}: e[{}]", identifier(key)?, index + 2);
+            row.push_str(", ");
+            write_literal(value, &mut row).map_err(|message| {
+                dump_error(format!(
+                    "property '{key}' of relationship {}: {message}",
+                    edge.entity_id
+                ))
+            })?;
+        }
+        tail.push_str(if edge.properties.is_empty() {
+            "]->(b)"
+        } else {
+            "}]->(b)"
+        });
+        row.push(']');
+
+        if self.report.relationships == 0 {
+            self.flush();
+            self.line("// Relationships");
+        }
+
+        match &mut self.pending {
+            Pending::Edges {
+                rows,
+                tail: pending_tail,
+            } if *pending_tail == tail => {
+                rows.push_str(", ");
+                rows.push_str(&row);
+            }
+            _ => {
+                self.flush();
+                self.pending = Pending::Edges { rows: row, tail };
+            }
+        }
+        self.report.relationships += 1;
+        self.batched += 1;
+        if self.batched == self.batch_size {
+            self.flush();
+        }
+        Ok(())
+    }
+
+    /// Writes the statement being batched, if any.
+    fn flush(&mut self) {
+        let statement = match std::mem::replace(&mut self.pending, Pending::None) {
+            Pending::None => return,
+            Pending::Nodes(patterns) => format!("CREATE {patterns}"),
+            Pending::Edges { rows, tail } => format!("UNWIND [{rows}] AS e {tail}"),
+        };
+        self.batched = 0;
+        self.statement(&statement);
+    }
+
+    /// Writes the last batch and the statements removing the dump label and ids.
+    pub(crate) fn finish(&mut self) -> CypherExportReport {
+        self.flush();
+        self.line("// Cleanup");
+        self.statement(&generate_drop_index_query(
+            IndexType::Range,
+            EntityType::Node,
+            DUMP_LABEL,
+            &[DUMP_ID],
+        ));
+        self.statement(&format!(
+            "MATCH (n:{DUMP_LABEL}) REMOVE n:{DUMP_LABEL} SET n.{DUMP_ID} = NULL"
+        ));
+        self.report.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::temporal::{Date, Duration};
+    use crate::value::vec32::Vec32;
+    use crate::{ConstraintType, IndexStatus, Point};
+
+    fn literal(value: FalkorValue) -> Result<String, String> {
+        let mut out = String::new();
+        write_literal(&value, &mut out).map(|()| out)
+    }
+
+    fn dump(
+        batch_size: usize,
+        write: impl FnOnce(&mut DumpWriter) -> FalkorResult<()>,
+    ) -> (Vec<String>, CypherExportReport) {
+        let mut writer = DumpWriter::new(batch_size);
+        writer.schema("social", &[], &[]).unwrap();
+        write(&mut writer).unwrap();
+        let report = writer.finish();
+        (writer.take_lines(), report)
+    }
+
+    fn node(
+        entity_id: i64,
+        labels: &[&str],
+        properties: &[(&str, FalkorValue)],
+    ) -> Node {
+        Node {
+            entity_id,
+            labels: labels.iter().map(|label| label.to_string()).collect(),
+            properties: properties
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+        }
+    }
+
+    fn edge(
+        entity_id: i64,
+        relationship_type: &str,
+        (src_node_id, dst_node_id): (i64, i64),
+        properties: &[(&str, FalkorValue)],
+    ) -> Edge {
+        Edge {
+            entity_id,
+            relationship_type: relationship_type.to_string(),
+            src_node_id,
+            dst_node_id,
+            properties: properties
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(literal(FalkorValue::F64(1.0)).unwrap(), "1.0");
+        assert_eq!(
+            literal(FalkorValue::String("it's\nhere".to_string())).unwrap(),
+            r"'it\'s\nhere'"
+        );
+        assert_eq!(
+            literal(FalkorValue::Array(vec![
+                FalkorValue::I64(1),
+                FalkorValue::Bool(true),
+                FalkorValue::None
+            ]))
+            .unwrap(),
+            "[1, true, null]"
+        );
+        assert_eq!(
+            literal(FalkorValue::Map(HashMap::from([
+                ("b".to_string(), FalkorValue::I64(2)),
+                ("a b".to_string(), FalkorValue::I64(1)),
+            ])))
+            .unwrap(),
+            "{`a b`: 1, b: 2}"
+        );
+        assert_eq!(
+            literal(FalkorValue::Point(Point {
+                latitude: 32.0,
+                longitude: 34.5
+            }))
+            .unwrap(),
+            "point({latitude: 32.0, longitude: 34.5})"
+        );
+        assert_eq!(
+            literal(FalkorValue::Vec32(Vec32 {
+                values: vec![1.0, -0.5]
+            }))
+            .unwrap(),
+            "vecf32([1.0, -0.5])"
+        );
+        assert_eq!(
+            literal(FalkorValue::Date(Date::new(-697_161_600))).unwrap(),
+            "date('1947-11-29')"
+        );
+        assert_eq!(
+            literal(FalkorValue::Duration(Duration::new(90))).unwrap(),
+            "duration({seconds: 90})"
+        );
+        assert!(literal(FalkorValue::F64(f64::NAN)).is_err());
+        assert!(literal(FalkorValue::Node(Node::default())).is_err());
+    }
+
+    #[test]
+    fn test_nodes_and_relationships_are_batched_with_dump_ids() {
+        let (lines, report) = dump(2, |writer| {
+            writer.node(node(10, &["Person"], &[("name", "Alice".into())]))?;
+            writer.node(node(
+                20,
+                &["Person", "Admin"],
+                &[("name", "Bob".into()), ("age", FalkorValue::I64(30))],
+            ))?;
+            writer.node(node(30, &[], &[]))?;
+            writer.edge(edge(
+                1,
+                "KNOWS",
+                (10, 20),
+                &[("since", FalkorValue::I64(2020))],
+            ))?;
+            writer.edge(edge(
+                2,
+                "KNOWS",
+                (20, 30),
+                &[("since", FalkorValue::I64(2021))],
+            ))?;
+            writer.edge(edge(3, "OWNS", (30, 30), &[]))
+        });
+        assert_eq!(
+            lines,
+            [
+                "// FalkorDB Cypher dump of graph \"social\"",
+                "// Indices",
+                "// Constraints",
+                "// Nodes",
+                "CREATE INDEX FOR (l:__FalkorDumpNode) ON (l.__falkordb_dump_id);",
+                "CREATE (:Person:__FalkorDumpNode {__falkordb_dump_id: 0, name: 'Alice'}), \
+                 (:Admin:Person:__FalkorDumpNode {__falkordb_dump_id: 1, age: 30, name: 'Bob'});",
+                "CREATE (:__FalkorDumpNode {__falkordb_dump_id: 2});",
+                "// Relationships",
+                "UNWIND [[0, 1, 2020], [1, 2, 2021]] AS e \
+                 MATCH (a:__FalkorDumpNode {__falkordb_dump_id: e[0]}) \
+                 MATCH (b:__FalkorDumpNode {__falkordb_dump_id: e[1]}) \
+                 CREATE (a)-[:KNOWS {since: e[2]}]->(b);",
+                "UNWIND [[2, 2]] AS e \
+                 MATCH (a:__FalkorDumpNode {__falkordb_dump_id: e[0]}) \
+                 MATCH (b:__FalkorDumpNode {__falkordb_dump_id: e[1]}) \
+                 CREATE (a)-[:OWNS]->(b);",
+                "// Cleanup",
+                "DROP  INDEX for (e:__FalkorDumpNode) ON (e.__falkordb_dump_id);",
+                "MATCH (n:__FalkorDumpNode) REMOVE n:__FalkorDumpNode SET n.__falkordb_dump_id = NULL;",
+            ]
+        );
+        assert_eq!(report.nodes, 3);
+        assert_eq!(report.relationships, 3);
+        assert_eq!(report.statements, 7);
+    }
+
+    #[test]
+    fn test_relationship_to_an_unexported_node_is_refused() {
+        let mut writer = DumpWriter::new(10);
+        writer.node(node(1, &["A"], &[])).unwrap();
+        let err = writer.edge(edge(7, "R", (1, 2), &[])).unwrap_err();
+        assert!(matches!(err, FalkorDBError::CypherDump { .. }));
+        assert!(err.to_string().contains("relationship 7 references node 2"));
+    }
+
+    #[test]
+    fn test_schema_is_sorted_and_constraints_are_directives() {
+        let index = FalkorIndex {
+            entity_type: EntityType::Node,
+            status: IndexStatus::Active,
+            index_label: "Person".to_string(),
+            fields: vec!["name".to_string(), "embedding".to_string()],
+            field_types: HashMap::from([
+                (
+                    "name".to_string(),
+                    vec![IndexType::Range, IndexType::Fulltext],
+                ),
+                ("embedding".to_string(), vec![IndexType::Vector]),
+            ]),
+            language: "english".to_string(),
+            stopwords: vec![],
+            info: HashMap::new(),
+            options: HashMap::from([
+                ("dimension".to_string(), FalkorValue::I64(3)),
+                (
+                    "similarityFunction".to_string(),
+                    FalkorValue::String("cosine".to_string()),
+                ),
+            ]),
+        };
+        let constraint = |constraint_type, status| Constraint {
+            constraint_type,
+            label: "Person".to_string(),
+            properties: vec!["full name".to_string()],
+            entity_type: EntityType::Node,
+            status,
+        };
+        let constraints = [
+            constraint(ConstraintType::Unique, ConstraintStatus::Active),
+            constraint(ConstraintType::Mandatory, ConstraintStatus::Failed),
+        ];
+
+        let mut writer = DumpWriter::new(10);
+        writer.schema("g", &[index], &constraints).unwrap();
+        let report = writer.finish();
+        assert_eq!(
+            writer.take_lines()[1..7],
+            [
+                "// Indices",
+                "CREATE FULLTEXT INDEX FOR (l:Person) ON (l.name);",
+                "CREATE INDEX FOR (l:Person) ON (l.name);",
+                "CREATE VECTOR INDEX FOR (l:Person) ON (l.embedding) OPTIONS { dimension: 3, similarityFunction: 'cosine' };",
+                "// Constraints",
+                "// CONSTRAINT UNIQUE NODE Person PROPERTIES 1 \"full name\"",
+            ]
+        );
+        assert_eq!(report.indices, 3);
+        assert_eq!(report.constraints, 1);
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(parse_line("", 1).unwrap(), Line::Skip);
+        assert_eq!(parse_line("// Nodes", 1).unwrap(), Line::Skip);
+        assert_eq!(
+            parse_line("CREATE (:A);", 1).unwrap(),
+            Line::Statement("CREATE (:A)")
+        );
+        assert!(parse_line("CREATE (:A)", 4)
+            .unwrap_err()
+            .to_string()
+            .contains("line 4"));
+
+        let args = ["UNIQUE", "a \"b\"\\c", "", "line\nbreak"];
+        let mut line = CONSTRAINT_DIRECTIVE.to_string();
+        for arg in args {
+            write_arg(arg, &mut line);
+            line.push(' ');
+        }
+        assert_eq!(
+            parse_line(&line, 1).unwrap(),
+            Line::Constraint(args.map(String::from).to_vec())
+        );
+        assert!(parse_line("// CONSTRAINT \"open", 1).is_err());
+    }
+}
@@ -0,0 +1,661 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! GraphML export: write a graph as a GraphML document that other graph tools can open, and
+//! `falkordb::import::graphml` can load back with its property types.
+//!
+//! Every node is a `<node id="n…">` and every relationship an `<edge id="e…">` between two nodes.
+//! Following the convention of other graph databases, node labels are one `labels` value of the
+//! form `:Person:Admin`, and the relationship type is the `label` value of an edge. Each property
+//! name gets a `<key>` per type it holds: booleans, integers (`long`), floats (`double`) and
+//! strings use the GraphML type, while the other types are GraphML strings with a `falkordb.type`
+//! attribute on their key naming the FalkorDB type:
+//!
+//! | `falkordb.type` | Text |
+//! |---|---|
+//! | `point` | `{"latitude":32.0,"longitude":34.5}` |
+//! | `vecf32` | `[1.0,2.0]` |
+//! | `date`, `datetime`, `localtime` | ISO 8601, such as `1990-04-01T12:00:00` |
+//! | `duration` | A number of seconds |
+//! | `list`, `map` | JSON, as in the JSON Lines format |
+//!
+//! GraphML declares every key before the graph, so the graph is read twice: once to collect the
+//! keys, then to write the nodes and relationships, page by page. Memory stays bounded by the page
+//! size and the number of keys.
+//!
+//! ```no_run
+//! # fn main() -> Result<(), falkordb::FalkorDBError> {
+//! use falkordb::export::graphml::GraphmlExporter;
+//! use falkordb::FalkorClientBuilder;
+//! use std::io::BufWriter;
+//! # let info: falkordb::FalkorConnectionInfo = "falkor://127.0.0.1:6379".try_into()?;
+//! let client = FalkorClientBuilder::new().with_connection_info(info).build()?;
+//!
+//! let file = std::fs::File::create("social.graphml").expect("create file");
+//! GraphmlExporter::new().export(&mut client.select_graph("social"), BufWriter::new(file))?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::export::{json_value, GraphExportReport};
+use crate::value::temporal::{Date, DateTime, Duration, Time};
+use crate::value::vec32::Vec32;
+use crate::{Edge, FalkorDBError, FalkorResult, FalkorValue, Node, Point, SyncGraph};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+
+/// The id of the key holding node labels.
+pub(crate) const LABELS_KEY: &str = "labels";
+/// The id of the key holding relationship types.
+pub(crate) const TYPE_KEY: &str = "label";
+
+fn export_error(message: impl Into<String>) -> FalkorDBError {
+    FalkorDBError::GraphExport {
+        format: "GraphML",
+        message: message.into(),
+    }
+}
+
+/// How a property value is written as GraphML text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Kind {
+    Boolean,
+    Long,
+    Double,
+    String,
+    Point,
+    VecF32,
+    Date,
+    DateTime,
+    LocalTime,
+    Duration,
+    List,
+    Map,
+}
+
+impl Kind {
+    /// The kind `value` is written as, or why it cannot be.
+    fn of(value: &FalkorValue) -> Result<Self, String> {
+        Ok(match value {
+            FalkorValue::Bool(_) => Kind::Boolean,
+            FalkorValue::I64(_) => Kind::Long,
+            FalkorValue::F64(_) => Kind::Double,
+            FalkorValue::String(_) => Kind::String,
+            FalkorValue::Point(_) => Kind::Point,
+            FalkorValue::Vec32(_) => Kind::VecF32,
+            FalkorValue::Date(_) => Kind::Date,
+            FalkorValue::DateTime(_) => Kind::DateTime,
+            FalkorValue::Time(_) => Kind::LocalTime,
+            FalkorValue::Duration(_) => Kind::Duration,
+            FalkorValue::Array(_) => Kind::List,
+            FalkorValue::Map(_) => Kind::Map,
+            FalkorValue::None => return Err("a property cannot be null".to_string()),
+            FalkorValue::Node(_) | FalkorValue::Edge(_) | FalkorValue::Path(_) => {
+                return Err("a graph entity cannot be stored as a property".to_string())
+            }
+            FalkorValue::Unparseable(raw) => {
+                return Err(format!("the value could not be parsed: {raw}"))
+            }
+        })
+    }
+
+    /// The GraphML `attr.type` of the key.
+    fn attr_type(self) -> &'static str {
+        match self {
+            Kind::Boolean => "boolean",
+            Kind::Long | Kind::Duration => "long",
+            Kind::Double => "double",
+            _ => "string",
+        }
+    }
+
+    /// The `falkordb.type` of the key, for the types GraphML does not have.
+    fn falkordb_type(self) -> Option<&'static str> {
+        Some(match self {
+            Kind::Boolean | Kind::Long | Kind::Double | Kind::String => return None,
+            Kind::Point => "point",
+            Kind::VecF32 => "vecf32",
+            Kind::Date => "date",
+            Kind::DateTime => "datetime",
+            Kind::LocalTime => "localtime",
+            Kind::Duration => "duration",
+            Kind::List => "list",
+            Kind::Map => "map",
+        })
+    }
+
+    /// The kind of a key declared with `attr_type` and `falkordb_type`, each defaulting to a
+    /// string.
+    pub(crate) fn of_key(
+        attr_type: Option<&str>,
+        falkordb_type: Option<&str>,
+    ) -> Result<Self, String> {
+        if let Some(falkordb_type) = falkordb_type {
+            return [
+                Kind::Point,
+                Kind::VecF32,
+                Kind::Date,
+                Kind::DateTime,
+                Kind::LocalTime,
+                Kind::Duration,
+                Kind::List,
+                Kind::Map,
+            ]
+            .into_iter()
+            .find(|kind| kind.falkordb_type() == Some(falkordb_type))
+            .ok_or_else(|| format!("unknown falkordb.type '{falkordb_type}'"));
+        }
+        match attr_type.unwrap_or("string") {
+            "boolean" => Ok(Kind::Boolean),
+            "int" | "long" => Ok(Kind::Long),
+            "float" | "double" => Ok(Kind::Double),
+            "string" => Ok(Kind::String),
+            other => Err(format!("unknown attr.type '{other}'")),
+        }
+    }
+
+    /// The text of `value`, of this kind.
+    fn write(
+        self,
+        value: &FalkorValue,
+    ) -> Result<String, String> {
+        Ok(match value {
+            FalkorValue::Bool(value) => value.to_string(),
+            FalkorValue::I64(value) => value.to_string(),
+            FalkorValue::F64(value) if value.is_nan() => "NaN".to_string(),
+            FalkorValue::F64(value) if value.is_infinite() => {
+                if *value > 0.0 { "INF" } else { "-INF" }.to_string()
+            }
+            FalkorValue::F64(value) => format!("{value:?}"),
+            FalkorValue::String(value) => value.clone(),
+            FalkorValue::Point(point) => format!(
+                "{{\"latitude\":{:?},\"longitude\":{:?}}}",
+                point.latitude, point.longitude
+            ),
+            FalkorValue::Vec32(vec) => serde_json::to_string(&vec.values)
+                .map_err(|err| format!("cannot write the vector: {err}"))?,
+            FalkorValue::Date(value) => value.to_iso(),
+            FalkorValue::DateTime(value) => value.to_iso(),
+            FalkorValue::Time(value) => value.to_iso().map_err(|err| err.to_string())?,
+            FalkorValue::Duration(value) => value.seconds().get().to_string(),
+            value => json_value::to_json(value)?.to_string(),
+        })
+    }
+
+    /// The value of `text`, of this kind.
+    pub(crate) fn read(
+        self,
+        text: &str,
+    ) -> Result<FalkorValue, String> {
+        let invalid = || format!("'{text}' is not a valid {}", self.name());
+        let json = || serde_json::from_str::<serde_json::Value>(text).map_err(|_| invalid());
+        Ok(match self {
+            Kind::Boolean => FalkorValue::Bool(match text.trim() {
+                "true" | "1" => true,
+                "false" | "0" => false,
+                _ => return Err(invalid()),
+            }),
+            Kind::Long => FalkorValue::I64(text.trim().parse().map_err(|_| invalid())?),
+            Kind::Double => FalkorValue::F64(text.trim().parse().map_err(|_| invalid())?),
+            Kind::String => FalkorValue::String(text.to_string()),
+            Kind::Point => {
+                let json = json()?;
+                let coordinate = |name| json.get(name).and_then(serde_json::Value::as_f64);
+                FalkorValue::Point(Point {
+                    latitude: coordinate("latitude").ok_or_else(invalid)?,
+                    longitude: coordinate("longitude").ok_or_else(invalid)?,
+                })
+            }
+            Kind::VecF32 => FalkorValue::Vec32(Vec32 {
+                values: serde_json::from_str(text).map_err(|_| invalid())?,
+            }),
+            Kind::Date => FalkorValue::Date(Date::parse_iso(text.trim()).ok_or_else(invalid)?),
+            Kind::DateTime => {
+                FalkorValue::DateTime(DateTime::parse_iso(text.trim()).ok_or_else(invalid)?)
+            }
+            Kind::LocalTime => FalkorValue::Time(Time::parse_iso(text.trim()).ok_or_else(invalid)?),
+            Kind::Duration => {
+                FalkorValue::Duration(Duration::new(text.trim().parse().map_err(|_| invalid())?))
+            }
+            Kind::List | Kind::Map => {
+                let value = json_value::from_json(json()?)?;
+                match (self, &value) {
+                    (Kind::List, FalkorValue::Array(_)) | (Kind::Map, FalkorValue::Map(_)) => value,
+                    _ => return Err(invalid()),
+                }
+            }
+        })
+    }
+
+    fn name(self) -> &'static str {
+        self.falkordb_type().unwrap_or_else(|| self.attr_type())
+    }
+}
+
+/// The keys of a document, by element (`node` or `edge`), property name and kind.
+#[derive(Default)]
+struct Keys(BTreeMap<(&'static str, String, Kind), String>);
+
+impl Keys {
+    fn collect(
+        &mut self,
+        element: &'static str,
+        entity_id: i64,
+        properties: &HashMap<String, FalkorValue>,
+    ) -> FalkorResult<()> {
+        for (name, value) in properties {
+            let kind = Kind::of(value).map_err(|message| {
+                export_error(format!(
+                    "property '{name}' of {element} {entity_id}: {message}"
+                ))
+            })?;
+            self.0.entry((element, name.clone(), kind)).or_default();
+        }
+        Ok(())
+    }
+
+    /// Numbers the keys in order, `d0`, `d1`, ….
+    fn number(&mut self) {
+        for (index, id) in self.0.values_mut().enumerate() {
+            *id = format!("d{index}");
+        }
+    }
+}
+
+/// Writes the document, element by element.
+struct DocumentWriter<W> {
+    out: W,
+    keys: Keys,
+    report: GraphExportReport,
+}
+
+impl<W: Write> DocumentWriter<W> {
+    fn write(
+        &mut self,
+        text: &str,
+    ) -> FalkorResult<()> {
+        self.out
+            .write_all(text.as_bytes())
+            .map_err(|err| export_error(format!("cannot write: {err}")))
+    }
+
+    fn header(
+        &mut self,
+        graph_name: &str,
+    ) -> FalkorResult<()> {
+        let mut text = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        );
+        text.push_str(&format!(
+            "  <key id=\"{LABELS_KEY}\" for=\"node\" attr.name=\"labels\" attr.type=\"string\"/>\n\
+             \x20 <key id=\"{TYPE_KEY}\" for=\"edge\" attr.name=\"label\" attr.type=\"string\"/>\n"
+        ));
+        for ((element, name, kind), id) in &self.keys.0 {
+            text.push_str(&format!(
+                "  <key id=\"{id}\" for=\"{element}\" attr.name=\"{}\" attr.type=\"{}\"",
+                escape(name)?,
+                kind.attr_type()
+            ));
+            if let Some(falkordb_type) = kind.falkordb_type() {
+                text.push_str(&format!(" falkordb.type=\"{falkordb_type}\""));
+            }
+            text.push_str("/>\n");
+        }
+        text.push_str(&format!(
+            "  <graph id=\"{}\" edgedefault=\"directed\">\n",
+            escape(graph_name)?
+        ));
+        self.write(&text)
+    }
+
+    /// The `<data>` elements of `properties`, sorted by name.
+    fn data(
+        &self,
+        element: &'static str,
+        entity_id: i64,
+        properties: &HashMap<String, FalkorValue>,
+        text: &mut String,
+    ) -> FalkorResult<()> {
+        let mut properties = properties.iter().collect::<Vec<_>>();
+        properties.sort_unstable_by_key(|(name, _)| *name);
+        for (name, value) in properties {
+            let in_context =
+                |message: String| format!("property '{name}' of {element} {entity_id}: {message}");
+            let kind = Kind::of(value).map_err(|message| export_error(in_context(message)))?;
+            let id = self
+                .keys
+                .0
+                .get(&(element, name.clone(), kind))
+                .ok_or_else(|| {
+                    export_error(in_context(
+                        "appeared after the keys were written; was the graph modified during the export?"
+                            .to_string(),
+                    ))
+                })?;
+            let value = kind
+                .write(value)
+                .map_err(|message| export_error(in_context(message)))?;
+            text.push_str(&format!(
+                "      <data key=\"{id}\">{}</data>\n",
+                escape(&value)?
+            ));
+        }
+        Ok(())
+    }
+
+    fn node(
+        &mut self,
+        node: &Node,
+    ) -> FalkorResult<()> {
+        let mut text = format!("    <node id=\"n{}\">\n", node.entity_id);
+        if !node.labels.is_empty() {
+            let mut labels = String::new();
+            for label in &node.labels {
+                if label.contains(':') {
+                    return Err(export_error(format!(
+                        "label '{label}' of node {} contains ':', which separates labels",
+                        node.entity_id
+                    )));
+                }
+                labels.push(':');
+                labels.push_str(label);
+            }
+            text.push_str(&format!(
+                "      <data key=\"{LABELS_KEY}\">{}</data>\n",
+                escape(&labels)?
+            ));
+        }
+        self.data("node", node.entity_id, &node.properties, &mut text)?;
+        text.push_str("    </node>\n");
+        self.report.nodes += 1;
+        self.write(&text)
+    }
+
+    fn edge(
+        &mut self,
+        edge: &Edge,
+    ) -> FalkorResult<()> {
+        let mut text = format!(
+            "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\">\n      <data key=\"{TYPE_KEY}\">{}</data>\n",
+            edge.entity_id,
+            edge.src_node_id,
+            edge.dst_node_id,
+            escape(&edge.relationship_type)?
+        );
+        self.data("edge", edge.entity_id, &edge.properties, &mut text)?;
+        text.push_str("    </edge>\n");
+        self.report.relationships += 1;
+        self.write(&text)
+    }
+
+    fn finish(mut self) -> FalkorResult<GraphExportReport> {
+        self.write("  </graph>\n</graphml>\n")?;
+        self.out
+            .flush()
+            .map_err(|err| export_error(format!("cannot write: {err}")))?;
+        Ok(self.report)
+    }
+}
+
+/// Escapes `text` for an XML attribute or element, keeping line breaks and tabs as character
+/// references so that they survive attribute normalization.
+fn escape(text: &str) -> FalkorResult<String> {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' => escaped.push_str("&#9;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => {
+                return Err(export_error(format!(
+                    "{text:?} holds the character {c:?}, which XML 1.0 cannot represent"
+                )))
+            }
+            c => escaped.push(c),
+        }
+    }
+    Ok(escaped)
+}
+
+/// Exports a graph as GraphML; see the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct GraphmlExporter {
+    page_size: usize,
+}
+
+impl Default for GraphmlExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphmlExporter {
+    /// Creates an exporter reading 1000 entities per page.
+    pub fn new() -> Self {
+        Self { page_size: 1_000 }
+    }
+
+    /// Sets the number of nodes or relationships read per round-trip (at least 1).
+    ///
+    /// # Arguments
+    /// * `page_size`: The page size of the `MATCH` queries walking the graph.
+    pub fn with_page_size(
+        mut self,
+        page_size: usize,
+    ) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Writes the graph to `out` as a GraphML document.
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to export.
+    /// * `out`: Where to write the document; wrap a file in a [`BufWriter`](std::io::BufWriter).
+    ///
+    /// # Returns
+    /// A [`GraphExportReport`], or the first error. [`FalkorDBError::GraphExport`] reports a failed
+    /// write, a label holding `:`, or a string holding a character XML cannot represent.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Export GraphML", skip_all, level = "info")
+    )]
+    pub fn export<W: Write>(
+        &self,
+        graph: &mut SyncGraph,
+        out: W,
+    ) -> FalkorResult<GraphExportReport> {
+        let mut keys = Keys::default();
+        super::for_each_node(graph, self.page_size, |node| {
+            keys.collect("node", node.entity_id, &node.properties)
+        })?;
+        super::for_each_edge(graph, self.page_size, |edge| {
+            keys.collect("edge", edge.entity_id, &edge.properties)
+        })?;
+        keys.number();
+
+        let mut document = DocumentWriter {
+            out,
+            keys,
+            report: GraphExportReport::default(),
+        };
+        document.header(graph.graph_name())?;
+        super::for_each_node(graph, self.page_size, |node| document.node(&node))?;
+        super::for_each_edge(graph, self.page_size, |edge| document.edge(&edge))?;
+        document.finish()
+    }
+
+    /// Writes the graph to `out` as a GraphML document; see [`GraphmlExporter::export`].
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to export.
+    /// * `out`: Where to write the document.
+    ///
+    /// # Returns
+    /// A [`GraphExportReport`], or the first error.
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Export GraphML Async", skip_all, level = "info")
+    )]
+    pub async fn export_async<W: Write>(
+        &self,
+        graph: &mut crate::AsyncGraph,
+        out: W,
+    ) -> FalkorResult<GraphExportReport> {
+        let mut keys = Keys::default();
+        super::for_each_node_async(graph, self.page_size, |node| {
+            keys.collect("node", node.entity_id, &node.properties)
+        })
+        .await?;
+        super::for_each_edge_async(graph, self.page_size, |edge| {
+            keys.collect("edge", edge.entity_id, &edge.properties)
+        })
+        .await?;
+        keys.number();
+
+        let mut document = DocumentWriter {
+            out,
+            keys,
+            report: GraphExportReport::default(),
+        };
+        document.header(graph.graph_name())?;
+        super::for_each_node_async(graph, self.page_size, |node| document.node(&node)).await?;
+        super::for_each_edge_async(graph, self.page_size, |edge| document.edge(&edge)).await?;
+        document.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(
+        nodes: &[Node],
+        edges: &[Edge],
+    ) -> String {
+        let mut keys = Keys::default();
+        for node in nodes {
+            keys.collect("node", node.entity_id, &node.properties)
+                .unwrap();
+        }
+        for edge in edges {
+            keys.collect("edge", edge.entity_id, &edge.properties)
+                .unwrap();
+        }
+        keys.number();
+        let mut out = Vec::new();
+        let mut document = DocumentWriter {
+            out: &mut out,
+            keys,
+            report: GraphExportReport::default(),
+        };
+        document.header("g").unwrap();
+        for node in nodes {
+            document.node(node).unwrap();
+        }
+        for edge in edges {
+            document.edge(edge).unwrap();
+        }
+        document.finish().unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_document_declares_a_key_per_property_type() {
+        let alice = Node {
+            entity_id: 0,
+            labels: vec!["Person".to_string(), "Admin".to_string()],
+            properties: HashMap::from([
+                (
+                    "name".to_string(),
+                    FalkorValue::String("A & <B>".to_string()),
+                ),
+                ("born".to_string(), FalkorValue::Date(Date::new(0))),
+            ]),
+        };
+        let bob = Node {
+            entity_id: 1,
+            labels: vec![],
+            properties: HashMap::from([("name".to_string(), FalkorValue::I64(7))]),
+        };
+        let knows = Edge {
+            entity_id: 0,
+            relationship_type: "KNOWS".to_string(),
+            src_node_id: 0,
+            dst_node_id: 1,
+            properties: HashMap::from([("w".to_string(), FalkorValue::F64(f64::NEG_INFINITY))]),
+        };
+        assert_eq!(
+            document(&[alice, bob], &[knows]),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n\
+             \x20 <key id=\"labels\" for=\"node\" attr.name=\"labels\" attr.type=\"string\"/>\n\
+             \x20 <key id=\"label\" for=\"edge\" attr.name=\"label\" attr.type=\"string\"/>\n\
+             \x20 <key id=\"d0\" for=\"edge\" attr.name=\"w\" attr.type=\"double\"/>\n\
+             \x20 <key id=\"d1\" for=\"node\" attr.name=\"born\" attr.type=\"string\" falkordb.type=\"date\"/>\n\
+             \x20 <key id=\"d2\" for=\"node\" attr.name=\"name\" attr.type=\"long\"/>\n\
+             \x20 <key id=\"d3\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n\
+             \x20 <graph id=\"g\" edgedefault=\"directed\">\n\
+             \x20   <node id=\"n0\">\n\
+             \x20     <data key=\"labels\">:Person:Admin</data>\n\
+             \x20     <data key=\"d1\">1970-01-01</data>\n\
+             \x20     <data key=\"d3\">A &amp; &lt;B&gt;</data>\n\
+             \x20   </node>\n\
+             \x20   <node id=\"n1\">\n\
+             \x20     <data key=\"d2\">7</data>\n\
+             \x20   </node>\n\
+             \x20   <edge id=\"e0\" source=\"n0\" target=\"n1\">\n\
+             \x20     <data key=\"label\">KNOWS</data>\n\
+             \x20     <data key=\"d0\">-INF</data>\n\
+             \x20   </edge>\n\
+             \x20 </graph>\n\
+             </graphml>\n"
+        );
+    }
+
+    #[test]
+    fn test_kinds_round_trip_through_text() {
+        let values = [
+            FalkorValue::Bool(true),
+            FalkorValue::I64(-4),
+            FalkorValue::F64(0.5),
+            FalkorValue::F64(f64::INFINITY),
+            FalkorValue::String(" padded ".to_string()),
+            FalkorValue::Point(Point {
+                latitude: 1.5,
+                longitude: -2.25,
+            }),
+            FalkorValue::Vec32(Vec32 {
+                values: vec![0.25, 3.0],
+            }),
+            FalkorValue::Date(Date::new(86_400)),
+            FalkorValue::DateTime(DateTime::new(1_700_000_000)),
+            FalkorValue::Time(Time::new(3_661)),
+            FalkorValue::Duration(Duration::new(-5)),
+            FalkorValue::Array(vec![FalkorValue::Date(Date::new(0))]),
+            FalkorValue::Map(HashMap::from([("k".to_string(), FalkorValue::I64(1))])),
+        ];
+        for value in values {
+            let kind = Kind::of(&value).unwrap();
+            let key = Kind::of_key(Some(kind.attr_type()), kind.falkordb_type()).unwrap();
+            assert_eq!(key, kind);
+            let text = kind.write(&value).unwrap();
+            assert_eq!(kind.read(&text).unwrap(), value, "{text}");
+        }
+    }
+
+    #[test]
+    fn test_unrepresentable_text_is_refused() {
+        assert!(escape("bell\u{7}").is_err());
+        assert_eq!(escape("a\nb\t'").unwrap(), "a&#10;b&#9;&apos;");
+    }
+}
@@ -0,0 +1,238 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! The JSON form of property values shared by the JSON Lines and GraphML formats.
+//!
+//! Strings, booleans, integers, floats, lists and maps are plain JSON (a float always keeps its
+//! decimal point, so it reads back as a float). The other types are one-key objects tagged with a
+//! `$` name: `{"$point": {"latitude": 32.0, "longitude": 34.5}}`, `{"$vecf32": [1.0, 2.0]}`,
+//! `{"$date": "1990-04-01"}`, `{"$datetime": "1990-04-01T12:00:00"}`, `{"$localtime": "12:00:00"}`
+//! and `{"$duration": 90}` (seconds). A map that would itself look like a tagged value is wrapped
+//! as `{"$map": {...}}`.
+
+use crate::value::temporal::{Date, DateTime, Duration, Time};
+use crate::value::vec32::Vec32;
+use crate::{FalkorValue, Point};
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+
+/// The JSON form of `value`, or why it has none.
+pub(crate) fn to_json(value: &FalkorValue) -> Result<Value, String> {
+    Ok(match value {
+        FalkorValue::None => Value::Null,
+        FalkorValue::Bool(value) => Value::Bool(*value),
+        FalkorValue::I64(value) => Value::from(*value),
+        FalkorValue::F64(value) => float(*value)?,
+        FalkorValue::String(value) => Value::String(value.clone()),
+        FalkorValue::Array(values) => {
+            Value::Array(values.iter().map(to_json).collect::<Result<_, _>>()?)
+        }
+        FalkorValue::Map(map) => {
+            let map = properties_to_json(map)?;
+            if is_tagged(&map) {
+                tagged("$map", Value::Object(map))
+            } else {
+                Value::Object(map)
+            }
+        }
+        FalkorValue::Point(point) => tagged(
+            "$point",
+            Value::Object(Map::from_iter([
+                ("latitude".to_string(), float(point.latitude)?),
+                ("longitude".to_string(), float(point.longitude)?),
+            ])),
+        ),
+        FalkorValue::Vec32(vec) => tagged(
+            "$vecf32",
+            Value::Array(
+                vec.values
+                    .iter()
+                    .map(|value| float(f64::from(*value)))
+                    .collect::<Result<_, _>>()?,
+            ),
+        ),
+        FalkorValue::DateTime(value) => tagged("$datetime", Value::String(value.to_iso())),
+        FalkorValue::Date(value) => tagged("$date", Value::String(value.to_iso())),
+        FalkorValue::Time(value) => tagged(
+            "$localtime",
+            Value::String(value.to_iso().map_err(|err| err.to_string())?),
+        ),
+        FalkorValue::Duration(value) => tagged("$duration", Value::from(value.seconds().get())),
+        FalkorValue::Node(_) | FalkorValue::Edge(_) | FalkorValue::Path(_) => {
+            return Err("a graph entity cannot be stored as a property".to_string())
+        }
+        FalkorValue::Unparseable(raw) => {
+            return Err(format!("the value could not be parsed: {raw}"))
+        }
+    })
+}
+
+/// The JSON object of a property map; its keys are sorted.
+pub(crate) fn properties_to_json(
+    properties: &HashMap<String, FalkorValue>
+) -> Result<Map<String, Value>, String> {
+    properties
+        .iter()
+        .map(|(key, value)| {
+            to_json(value)
+                .map(|value| (key.clone(), value))
+                .map_err(|message| format!("property '{key}': {message}"))
+        })
+        .collect()
+}
+
+/// The value of a JSON form written by [`to_json`], or why it is not one.
+pub(crate) fn from_json(value: Value) -> Result<FalkorValue, String> {
+    Ok(match value {
+        Value::Null => FalkorValue::None,
+        Value::Bool(value) => FalkorValue::Bool(value),
+        Value::Number(number) => match number.as_i64() {
+            Some(value) if !number.is_f64() => FalkorValue::I64(value),
+            _ => FalkorValue::F64(
+                number
+                    .as_f64()
+                    .ok_or_else(|| format!("the number {number} is out of range"))?,
+            ),
+        },
+        Value::String(value) => FalkorValue::String(value),
+        Value::Array(values) => FalkorValue::Array(
+            values
+                .into_iter()
+                .map(from_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Object(map) if is_tagged(&map) => {
+            let (tag, value) = map.into_iter().next().unwrap_or_default();
+            from_tagged(&tag, value)?
+        }
+        Value::Object(map) => FalkorValue::Map(properties_from_json(map)?),
+    })
+}
+
+/// The property map of a JSON object.
+pub(crate) fn properties_from_json(
+    map: Map<String, Value>
+) -> Result<HashMap<String, FalkorValue>, String> {
+    map.into_iter()
+        .map(|(key, value)| {
+            from_json(value)
+                .map(|value| (key.clone(), value))
+                .map_err(|message| format!("property '{key}': {message}"))
+        })
+        .collect()
+}
+
+fn from_tagged(
+    tag: &str,
+    value: Value,
+) -> Result<FalkorValue, String> {
+    let invalid = || format!("invalid {tag} value");
+    let text = |value: &Value| value.as_str().map(str::to_string).ok_or_else(invalid);
+    Ok(match tag {
+        "$map" => match value {
+            Value::Object(map) => FalkorValue::Map(properties_from_json(map)?),
+            _ => return Err(invalid()),
+        },
+        "$point" => {
+            let coordinate =
+                |name: &str| value.get(name).and_then(Value::as_f64).ok_or_else(invalid);
+            FalkorValue::Point(Point {
+                latitude: coordinate("latitude")?,
+                longitude: coordinate("longitude")?,
+            })
+        }
+        "$vecf32" => FalkorValue::Vec32(Vec32 {
+            values: value
+                .as_array()
+                .ok_or_else(invalid)?
+                .iter()
+                .map(|value| value.as_f64().map(|value| value as f32).ok_or_else(invalid))
+                .collect::<Result<_, _>>()?,
+        }),
+        "$datetime" => {
+            FalkorValue::DateTime(DateTime::parse_iso(&text(&value)?).ok_or_else(invalid)?)
+        }
+        "$date" => FalkorValue::Date(Date::parse_iso(&text(&value)?).ok_or_else(invalid)?),
+        "$localtime" => FalkorValue::Time(Time::parse_iso(&text(&value)?).ok_or_else(invalid)?),
+        "$duration" => FalkorValue::Duration(Duration::new(value.as_i64().ok_or_else(invalid)?)),
+        _ => return Err(format!("unknown tag {tag}")),
+    })
+}
+
+/// Whether `map` has the shape of a tagged value: a single key starting with `$`.
+fn is_tagged(map: &Map<String, Value>) -> bool {
+    map.len() == 1 && map.keys().all(|key| key.starts_with('$'))
+}
+
+fn tagged(
+    tag: &str,
+    value: Value,
+) -> Value {
+    Value::Object(Map::from_iter([(tag.to_string(), value)]))
+}
+
+fn float(value: f64) -> Result<Value, String> {
+    Number::from_f64(value)
+        .map(Value::Number)
+        .ok_or_else(|| format!("float {value} is not finite; JSON has no literal for NaN/Infinity"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(value: FalkorValue) {
+        let json = to_json(&value).unwrap();
+        let text = serde_json::to_string(&json).unwrap();
+        let parsed = from_json(serde_json::from_str(&text).unwrap()).unwrap();
+        assert_eq!(parsed, value, "{text}");
+    }
+
+    #[test]
+    fn test_values_round_trip_with_their_types() {
+        round_trip(FalkorValue::I64(-3));
+        round_trip(FalkorValue::F64(2.0));
+        round_trip(FalkorValue::String("$date".to_string()));
+        round_trip(FalkorValue::Point(Point {
+            latitude: 32.08,
+            longitude: 34.78,
+        }));
+        round_trip(FalkorValue::Vec32(Vec32 {
+            values: vec![0.5, -1.25],
+        }));
+        round_trip(FalkorValue::Date(Date::new(-697_161_600)));
+        round_trip(FalkorValue::DateTime(DateTime::new(1_700_000_000)));
+        round_trip(FalkorValue::Time(Time::new(45_296)));
+        round_trip(FalkorValue::Duration(Duration::new(-90)));
+        round_trip(FalkorValue::Array(vec![
+            FalkorValue::None,
+            FalkorValue::Bool(false),
+            FalkorValue::Date(Date::new(0)),
+        ]));
+        round_trip(FalkorValue::Map(HashMap::from([(
+            "$point".to_string(),
+            FalkorValue::I64(1),
+        )])));
+    }
+
+    #[test]
+    fn test_tagged_json_shapes() {
+        assert_eq!(
+            to_json(&FalkorValue::Date(Date::new(0))).unwrap(),
+            serde_json::json!({"$date": "1970-01-01"})
+        );
+        assert_eq!(
+            to_json(&FalkorValue::Map(HashMap::from([(
+                "$x".to_string(),
+                FalkorValue::I64(1)
+            )])))
+            .unwrap(),
+            serde_json::json!({"$map": {"$x": 1}})
+        );
+        assert!(to_json(&FalkorValue::F64(f64::INFINITY)).is_err());
+        assert!(from_json(serde_json::json!({"$date": "1970-13-01"})).is_err());
+        assert!(from_json(serde_json::json!({"$nope": 1})).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! JSON Lines export: one JSON object per node, then one per relationship, each on its own line.
+//!
+//! Objects carry the fields of [`Node`] and [`Edge`], plus a `type` of `node` or `relationship`:
+//!
+//! ```text
+//! {"type":"node","entity_id":0,"labels":["Person"],"properties":{"home":{"$point":{"latitude":32.0,"longitude":34.5}},"name":"Alice"}}
+//! {"type":"relationship","entity_id":0,"relationship_type":"KNOWS","src_node_id":0,"dst_node_id":1,"properties":{"since":2015}}
+//! ```
+//!
+//! Property values are plain JSON where JSON has the type; points, vectors and temporal values are
+//! `$`-tagged objects such as `{"$date": "1990-04-01"}`, so `falkordb::import::jsonl` restores
+//! them with their types. The graph is read page by page and written line by line, so memory
+//! stays bounded by the page size.
+
+use crate::export::{json_value, GraphExportReport};
+use crate::{Edge, FalkorDBError, FalkorResult, Node, SyncGraph};
+use serde_json::Value;
+use std::io::Write;
+
+fn export_error(message: impl Into<String>) -> FalkorDBError {
+    FalkorDBError::GraphExport {
+        format: "JSON Lines",
+        message: message.into(),
+    }
+}
+
+/// Exports a graph as JSON Lines; see the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct JsonLinesExporter {
+    page_size: usize,
+}
+
+impl Default for JsonLinesExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonLinesExporter {
+    /// Creates an exporter reading 1000 entities per page.
+    pub fn new() -> Self {
+        Self { page_size: 1_000 }
+    }
+
+    /// Sets the number of nodes or relationships read per round-trip (at least 1).
+    ///
+    /// # Arguments
+    /// * `page_size`: The page size of the `MATCH` queries walking the graph.
+    pub fn with_page_size(
+        mut self,
+        page_size: usize,
+    ) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Writes every node, then every relationship, to `out`.
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to export.
+    /// * `out`: Where to write the lines; wrap a file in a [`BufWriter`](std::io::BufWriter).
+    ///
+    /// # Returns
+    /// A [`GraphExportReport`], or the first error. [`FalkorDBError::GraphExport`] reports a failed
+    /// write or a property value with no JSON form (a non-finite float).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Export JSON Lines", skip_all, level = "info")
+    )]
+    pub fn export<W: Write>(
+        &self,
+        graph: &mut SyncGraph,
+        mut out: W,
+    ) -> FalkorResult<GraphExportReport> {
+        let mut report = GraphExportReport::default();
+        super::for_each_node(graph, self.page_size, |node| {
+            report.nodes += 1;
+            write_line(&mut out, node_line(&node)?)
+        })?;
+        super::for_each_edge(graph, self.page_size, |edge| {
+            report.relationships += 1;
+            write_line(&mut out, edge_line(&edge)?)
+        })?;
+        out.flush()
+            .map_err(|err| export_error(format!("cannot write: {err}")))?;
+        Ok(report)
+    }
+
+    /// Writes every node, then every relationship, to `out`; see [`JsonLinesExporter::export`].
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to export.
+    /// * `out`: Where to write the lines.
+    ///
+    /// # Returns
+    /// A [`GraphExportReport`], or the first error.
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Export JSON Lines Async", skip_all, level = "info")
+    )]
+    pub async fn export_async<W: Write>(
+        &self,
+        graph: &mut crate::AsyncGraph,
+        mut out: W,
+    ) -> FalkorResult<GraphExportReport> {
+        let mut report = GraphExportReport::default();
+        super::for_each_node_async(graph, self.page_size, |node| {
+            report.nodes += 1;
+            write_line(&mut out, node_line(&node)?)
+        })
+        .await?;
+        super::for_each_edge_async(graph, self.page_size, |edge| {
+            report.relationships += 1;
+            write_line(&mut out, edge_line(&edge)?)
+        })
+        .await?;
+        out.flush()
+            .map_err(|err| export_error(format!("cannot write: {err}")))?;
+        Ok(report)
+    }
+}
+
+/// The fields of a line, in the order they are written.
+type Line = Vec<(&'static str, Value)>;
+
+fn node_line(node: &Node) -> FalkorResult<Line> {
+    let properties = json_value::properties_to_json(&node.properties)
+        .map_err(|message| export_error(format!("node {}: {message}", node.entity_id)))?;
+    Ok(vec![
+        ("type", Value::from("node")),
+        ("entity_id", Value::from(node.entity_id)),
+        ("labels", Value::from(node.labels.clone())),
+        ("properties", Value::Object(properties)),
+    ])
+}
+
+fn edge_line(edge: &Edge) -> FalkorResult<Line> {
+    let properties = json_value::properties_to_json(&edge.properties)
+        .map_err(|message| export_error(format!("relationship {}: {message}", edge.entity_id)))?;
+    Ok(vec![
+        ("type", Value::from("relationship")),
+        ("entity_id", Value::from(edge.entity_id)),
+        (
+            "relationship_type",
+            Value::from(edge.relationship_type.as_str()),
+        ),
+        ("src_node_id", Value::from(edge.src_node_id)),
+        ("dst_node_id", Value::from(edge.dst_node_id)),
+        ("properties", Value::Object(properties)),
+    ])
+}
+
+/// Writes `line` as one JSON object, keeping its field order.
+fn write_line<W: Write>(
+    out: &mut W,
+    line: Line,
+) -> FalkorResult<()> {
+    let mut text = String::from("{");
+    for (index, (key, value)) in line.into_iter().enumerate() {
+        if index > 0 {
+            text.push(',');
+        }
+        text.push_str(&format!("\"{key}\":{value}"));
+    }
+    text.push('}');
+    writeln!(out, "{text}").map_err(|err| export_error(format!("cannot write: {err}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::temporal::Date;
+    use crate::{FalkorValue, Point};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_lines_keep_the_entity_field_order() {
+        let node = Node {
+            entity_id: 4,
+            labels: vec!["Person".to_string()],
+            properties: HashMap::from([
+                ("name".to_string(), FalkorValue::String("Alice".to_string())),
+                (
+                    "home".to_string(),
+                    FalkorValue::Point(Point {
+                        latitude: 32.0,
+                        longitude: 34.5,
+                    }),
+                ),
+            ]),
+        };
+        let edge = Edge {
+            entity_id: 9,
+            relationship_type: "KNOWS".to_string(),
+            src_node_id: 4,
+            dst_node_id: 5,
+            properties: HashMap::from([("since".to_string(), FalkorValue::Date(Date::new(0)))]),
+        };
+        let mut out = Vec::new();
+        write_line(&mut out, node_line(&node).unwrap()).unwrap();
+        write_line(&mut out, edge_line(&edge).unwrap()).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"type\":\"node\",\"entity_id\":4,\"labels\":[\"Person\"],\"properties\":{\"home\":{\"$point\":{\"latitude\":32.0,\"longitude\":34.5}},\"name\":\"Alice\"}}\n\
+             {\"type\":\"relationship\",\"entity_id\":9,\"relationship_type\":\"KNOWS\",\"src_node_id\":4,\"dst_node_id\":5,\"properties\":{\"since\":{\"$date\":\"1970-01-01\"}}}\n"
+        );
+    }
+
+    #[test]
+    fn test_non_finite_float_is_refused() {
+        let node = Node {
+            entity_id: 1,
+            properties: HashMap::from([("x".to_string(), FalkorValue::F64(f64::NAN))]),
+            ..Default::default()
+        };
+        let err = node_line(&node).unwrap_err();
+        assert!(matches!(err, FalkorDBError::GraphExport { .. }));
+        assert!(err.to_string().contains("node 1: property 'x'"));
+    }
+}
@@ -0,0 +1,156 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Exporters that walk a whole graph through id-ordered, keyset-paged reads and write it out: a
+//! replayable Cypher dump, and with the `jsonl` and `graphml` features the JSON Lines and GraphML
+//! formats that `falkordb::import` reads back.
+
+pub mod cypher;
+#[cfg(feature = "graphml")]
+pub mod graphml;
+#[cfg(any(feature = "jsonl", feature = "graphml"))]
+pub(crate) mod json_value;
+#[cfg(feature = "jsonl")]
+pub mod jsonl;
+
+use crate::{Edge, FalkorResult, FromFalkorValue, Node, SyncGraph};
+
+/// What a JSON Lines or GraphML exporter wrote.
+#[cfg(any(feature = "jsonl", feature = "graphml"))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GraphExportReport {
+    /// The nodes.
+    pub nodes: usize,
+    /// The relationships.
+    pub relationships: usize,
+}
+
+/// The next page of nodes after the id `$last`, in id order so that the export is deterministic.
+/// Pages are keyset-paged: each one seeks past the last id already seen instead of skipping over
+/// every earlier node, so walking a whole graph stays linear in its size.
+const NODES_QUERY: &str = "MATCH (n) WHERE ID(n) > $last RETURN n ORDER BY ID(n) LIMIT $page";
+/// The next page of relationships after the id `$last`, in id order.
+const EDGES_QUERY: &str =
+    "MATCH ()-[e]->() WHERE ID(e) > $last RETURN e ORDER BY ID(e) LIMIT $page";
+
+/// Feeds every node of `graph` to `visit`, `page_size` nodes per round-trip.
+pub(crate) fn for_each_node(
+    graph: &mut SyncGraph,
+    page_size: usize,
+    visit: impl FnMut(Node) -> FalkorResult<()>,
+) -> FalkorResult<()> {
+    for_each_page(
+        graph,
+        NODES_QUERY,
+        "n",
+        page_size,
+        |node: &Node| node.entity_id,
+        visit,
+    )
+}
+
+/// Feeds every relationship of `graph` to `visit`, `page_size` relationships per round-trip.
+pub(crate) fn for_each_edge(
+    graph: &mut SyncGraph,
+    page_size: usize,
+    visit: impl FnMut(Edge) -> FalkorResult<()>,
+) -> FalkorResult<()> {
+    for_each_page(
+        graph,
+        EDGES_QUERY,
+        "e",
+        page_size,
+        |edge: &Edge| edge.entity_id,
+        visit,
+    )
+}
+
+/// Runs the keyset-paged `query` until a page comes back short, feeding each entity of `column`
+/// to `visit` and resuming the next page after the `id` of the last one.
+fn for_each_page<T: FromFalkorValue>(
+    graph: &mut SyncGraph,
+    query: &str,
+    column: &str,
+    page_size: usize,
+    id: impl Fn(&T) -> i64,
+    mut visit: impl FnMut(T) -> FalkorResult<()>,
+) -> FalkorResult<()> {
+    let page_size = page_size.max(1);
+    let mut last = -1;
+    loop {
+        let rows = graph
+            .ro_query(query)
+            .with_param("last", last)
+            .with_param("page", page_size)
+            .execute()?
+            .data;
+        let mut fetched = 0;
+        for row in rows {
+            let entity = row?.try_get(column)?;
+            last = id(&entity);
+            fetched += 1;
+            visit(entity)?;
+        }
+        if fetched < page_size {
+            return Ok(());
+        }
+    }
+}
+
+/// Feeds every node of `graph` to `visit`; see [`for_each_node`].
+#[cfg(feature = "tokio")]
+pub(crate) async fn for_each_node_async(
+    graph: &mut crate::AsyncGraph,
+    page_size: usize,
+    visit: impl FnMut(Node) -> FalkorResult<()>,
+) -> FalkorResult<()> {
+    let id = |node: &Node| node.entity_id;
+    for_each_page_async(graph, NODES_QUERY, "n", page_size, id, visit).await
+}
+
+/// Feeds every relationship of `graph` to `visit`; see [`for_each_edge`].
+#[cfg(feature = "tokio")]
+pub(crate) async fn for_each_edge_async(
+    graph: &mut crate::AsyncGraph,
+    page_size: usize,
+    visit: impl FnMut(Edge) -> FalkorResult<()>,
+) -> FalkorResult<()> {
+    let id = |edge: &Edge| edge.entity_id;
+    for_each_page_async(graph, EDGES_QUERY, "e", page_size, id, visit).await
+}
+
+/// Runs the keyset-paged `query`; see [`for_each_page`].
+#[cfg(feature = "tokio")]
+async fn for_each_page_async<T: FromFalkorValue>(
+    graph: &mut crate::AsyncGraph,
+    query: &str,
+    column: &str,
+    page_size: usize,
+    id: impl Fn(&T) -> i64,
+    mut visit: impl FnMut(T) -> FalkorResult<()>,
+) -> FalkorResult<()> {
+    let page_size = page_size.max(1);
+    let mut last = -1;
+    loop {
+        let mut rows = graph
+            .ro_query(query)
+            .with_param("last", last)
+            .with_param("page", page_size)
+            .execute()
+            .await?
+            .data;
+        let mut fetched = 0;
+        while let Some(row) = rows.next_row().await {
+            let entity = row?.try_get(column)?;
+            last = id(&entity);
+            fetched += 1;
+            visit(entity)?;
+        }
+        if fetched < page_size {
+            return Ok(());
+        }
+    }
+}
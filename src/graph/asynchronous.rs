@@ -6,11 +6,16 @@
 use crate::{
     client::asynchronous::FalkorAsyncClientInner,
     graph::{
-        generate_create_index_query, generate_drop_index_query, vector_index_options,
-        VectorSimilarity,
+        fulltext::{
+            self, escape_fulltext_query, generate_create_fulltext_index_query, FulltextField,
+            FulltextIndexOptions,
+        },
+        generate_create_index_query, generate_drop_index_query,
+        spatial::{self, generate_nodes_within_radius_query},
+        vector_index_options, VectorSimilarity,
     },
     Constraint, ConstraintType, EntityType, ExecutionPlan, FalkorIndex, FalkorResult, GraphSchema,
-    IndexType, ProcedureQueryBuilder, QueryBuilder, QueryResult, RowStream, SlowlogEntry,
+    IndexType, Point, ProcedureQueryBuilder, QueryBuilder, QueryResult, RowStream, SlowlogEntry,
 };
 use parking_lot::RwLock;
 use std::{collections::HashMap, fmt::Display, sync::Arc};
@@ -61,7 +66,7 @@ impl AsyncGraph {
         feature = "tracing",
         tracing::instrument(name = "Graph Execute Command", skip_all, level = "info")
     )]
-    async fn execute_command(
+    pub(crate) async fn execute_command(
         &self,
         command: &str,
         subcommand: Option<&str>,
@@ -181,6 +186,25 @@ impl AsyncGraph {
         crate::BatchBuilder::new(self)
     }
 
+    /// Creates an [`UnwindWriter`](crate::UnwindWriter) that writes records in chunks of
+    /// `chunk_size`, running `UNWIND $rows AS row {query_template}` once per chunk over pipelined
+    /// batches and summing the statistics of every chunk.
+    ///
+    /// # Arguments
+    /// * `query_template`: The query run for each record, which it sees as `row`; for example
+    ///   `MERGE (:Person {id: row.id})`.
+    /// * `chunk_size`: The number of records per chunk.
+    ///
+    /// # Returns
+    /// An [`UnwindWriter`](crate::UnwindWriter) borrowing this graph.
+    pub fn unwind_writer(
+        &mut self,
+        query_template: &str,
+        chunk_size: std::num::NonZeroUsize,
+    ) -> crate::UnwindWriter<'_, Self> {
+        crate::UnwindWriter::new(self, query_template, chunk_size)
+    }
+
     /// Creates a [`ProcedureQueryBuilder`] for this graph
     /// This [`ProcedureQueryBuilder`] has to be dropped or ran using [`ProcedureQueryBuilder::execute`], before reusing the graph, as it takes a mutable reference to the graph for as long as it exists
     /// Read-only queries are more limited with the operations they are allowed to perform.
@@ -213,6 +237,64 @@ impl AsyncGraph {
         ProcedureQueryBuilder::new_readonly(self, procedure_name)
     }
 
+    /// Finds the nodes with `label` whose point `property` lies within `radius_meters` of
+    /// `center`, nearest first.
+    ///
+    /// Generates `MATCH (n:label) WHERE distance(n.property, point($center)) <= $radius`, quoting
+    /// the label and property and binding the center and radius as parameters, so no value is ever
+    /// formatted into the query. Each row has a `node` column and a `distance` column (in meters).
+    /// The returned [`QueryBuilder`] runs read-only and can be refined before executing, e.g. with
+    /// a timeout or a read preference; the `center` and `radius` parameter names are taken.
+    ///
+    /// # Arguments
+    /// * `label`: Only nodes with this label are considered.
+    /// * `property`: The node property holding the point.
+    /// * `center`: The center of the search, a [`Point`] or, with the `geo-types` feature, a
+    ///   `geo_types::Point<f64>` (`x` is the longitude).
+    /// * `radius_meters`: The search radius, in meters.
+    ///
+    /// # Returns
+    /// A [`QueryBuilder`] which, when executed, returns the matching rows, or [`FalkorDBError::InvalidIdentifier`](crate::FalkorDBError::InvalidIdentifier)
+    /// if the label or property cannot be quoted.
+    pub fn nodes_within_radius(
+        &mut self,
+        label: &str,
+        property: &str,
+        center: impl Into<Point>,
+        radius_meters: f64,
+    ) -> FalkorResult<QueryBuilder<'_, QueryResult<RowStream>, String, Self>> {
+        let query_str = generate_nodes_within_radius_query(label, property)?;
+        Ok(QueryBuilder::new(self, "GRAPH.RO_QUERY", query_str)
+            .with_param(spatial::CENTER_PARAM, center.into())
+            .with_param(spatial::RADIUS_PARAM, radius_meters))
+    }
+
+    /// Searches the fulltext index of `label` for nodes matching `query`, best match first.
+    ///
+    /// Calls `db.idx.fulltext.queryNodes` with `query` passed through [`escape_fulltext_query`],
+    /// so punctuation is matched literally instead of being read as fulltext operators, and all
+    /// of its words must match. Each row has a `node` column and a `score` column (an `f64`,
+    /// higher is better). The label and query are bound as parameters, and the returned
+    /// [`QueryBuilder`] runs read-only and can be refined before executing; the `label` and
+    /// `query` parameter names are taken. For raw fulltext syntax such as prefix matches, call
+    /// the procedure with [`call_procedure_ro`](Self::call_procedure_ro) instead.
+    ///
+    /// # Arguments
+    /// * `label`: The label whose fulltext index is searched.
+    /// * `query`: The text to search for.
+    ///
+    /// # Returns
+    /// A [`QueryBuilder`] which, when executed, returns the matching rows.
+    pub fn fulltext_search(
+        &mut self,
+        label: &str,
+        query: &str,
+    ) -> QueryBuilder<'_, QueryResult<RowStream>, &'static str, Self> {
+        QueryBuilder::new(self, "GRAPH.RO_QUERY", fulltext::FULLTEXT_SEARCH_QUERY)
+            .with_param(fulltext::LABEL_PARAM, label)
+            .with_param(fulltext::QUERY_PARAM, escape_fulltext_query(query))
+    }
+
     /// Calls the DB.INDICES procedure on the graph, returning all the indexing methods currently used
     ///
     /// # Returns
@@ -324,6 +406,36 @@ impl AsyncGraph {
         .await
     }
 
+    /// Creates a fulltext index on a node label, with typed per-index and per-field settings.
+    ///
+    /// Calls `db.idx.fulltext.createNodeIndex`, emitting the label, field names and settings as
+    /// escaped string literals. Calling it again for the same label adds the new fields to the
+    /// existing index. Use [`drop_index`](Self::drop_index) with [`IndexType::Fulltext`] to remove
+    /// fields again.
+    ///
+    /// # Arguments
+    /// * `label`: Nodes with this label will be indexed.
+    /// * `fields`: The properties to index, as [`FulltextField`]s or plain names.
+    /// * `options`: The index language and stopwords; see [`FulltextIndexOptions`].
+    ///
+    /// # Returns
+    /// A [`RowStream`] with the call's statistics, or [`FalkorDBError::InvalidIndexOption`](crate::FalkorDBError::InvalidIndexOption)
+    /// if a field weight is not finite and positive.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Create Fulltext Index", skip_all, level = "info")
+    )]
+    pub async fn create_fulltext_index<F: Into<FulltextField> + Clone>(
+        &mut self,
+        label: &str,
+        fields: &[F],
+        options: &FulltextIndexOptions,
+    ) -> FalkorResult<QueryResult<RowStream>> {
+        let fields: Vec<FulltextField> = fields.iter().cloned().map(Into::into).collect();
+        let query_str = generate_create_fulltext_index_query(label, &fields, options)?;
+        self.query(query_str).execute().await
+    }
+
     /// Drop an existing index, by specifying its type, entity, label and specific properties
     ///
     /// # Arguments
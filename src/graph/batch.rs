@@ -29,6 +29,47 @@ pub type BatchItemResult = FalkorResult<QueryResult<Vec<Row>>>;
 /// query failed to encode) returns `Ok` without a round-trip.
 pub type BatchResult = FalkorResult<Vec<BatchItemResult>>;
 
+/// The outcome of an [`AtomicBatchBuilder::execute`] that reached the server.
+#[derive(Debug)]
+pub enum AtomicBatchOutcome {
+    /// `EXEC` ran the transaction: one [`BatchItemResult`] per query, in submission order.
+    ///
+    /// Redis does not roll back a transaction whose commands fail while *running*: a query that
+    /// errors here (e.g. a constraint violation) is this slot's `Err`, and the others still
+    /// applied. The transaction guarantees the queries ran back to back, with nothing from another
+    /// client in between.
+    Committed(Vec<BatchItemResult>),
+    /// The transaction was discarded before running; **none** of the queries applied.
+    Aborted(AbortReason),
+}
+
+impl AtomicBatchOutcome {
+    /// Whether the transaction was discarded without applying any query.
+    pub fn is_aborted(&self) -> bool {
+        matches!(self, AtomicBatchOutcome::Aborted(_))
+    }
+}
+
+/// Why an atomic batch was discarded without running.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AbortReason {
+    /// The graph was `WATCH`ed on the connection, and another client modified it before `EXEC`.
+    /// An atomic batch never `WATCH`es, so only a [transaction](crate::SyncGraph::transaction)
+    /// runs into this, and it retries the closure instead of reporting it.
+    WatchedGraphChanged,
+    /// The server refused to queue one of the commands, discarding the whole transaction
+    /// (`EXECABORT`). Holds the server's message for the rejected command.
+    Rejected(String),
+}
+
+/// The result of [`AtomicBatchBuilder::execute`].
+///
+/// The **outer** `FalkorResult` fails if a query could not be encoded (nothing is sent) or the
+/// transaction could not be completed. A transport error after the pipeline was sent leaves the
+/// outcome unknown: the transaction either ran in full or not at all.
+pub type AtomicBatchResult = FalkorResult<AtomicBatchOutcome>;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum BatchCommand {
     Query,
@@ -124,6 +165,20 @@ impl BatchQuery {
         self.timeout = Some(timeout);
         self
     }
+
+    /// Encode the query and return the command arguments that follow the graph name: the query
+    /// text with its parameters, `--compact`, and the timeout if one is set.
+    pub(crate) fn encoded_args(&self) -> FalkorResult<Vec<String>> {
+        let mut args = vec![
+            construct_query(&self.query_string, &self.params)?,
+            "--compact".to_string(),
+        ];
+        if let Some(timeout) = self.timeout {
+            args.push("timeout".to_string());
+            args.push(timeout.to_string());
+        }
+        Ok(args)
+    }
 }
 
 /// Accumulates queries to run as a single pipelined batch. Create one with `graph.batch()`.
@@ -234,6 +289,62 @@ impl<'a, G> BatchBuilder<'a, G> {
     }
 }
 
+impl<'a, G> BatchBuilder<'a, G> {
+    /// Run the queued queries as a single `MULTI`/`EXEC` transaction instead of a plain pipeline.
+    ///
+    /// The transaction is still one round-trip. Unlike a plain batch, a query that fails to
+    /// encode fails the whole batch before anything is sent, and the queries run back to back
+    /// on the server. See [`AtomicBatchOutcome`] for what is (and is not) rolled back.
+    ///
+    /// # Returns
+    /// An [`AtomicBatchBuilder`] over the queued queries.
+    pub fn atomic(self) -> AtomicBatchBuilder<'a, G> {
+        AtomicBatchBuilder { batch: self }
+    }
+}
+
+/// A batch that runs as one `MULTI`/`EXEC` transaction. Created by [`BatchBuilder::atomic`].
+///
+/// The queries are sent in the same round-trip as the `MULTI`, so they cannot depend on anything
+/// read beforehand. Writes like the ones below, which compute new values on the server, are safe;
+/// when a write depends on a value read first, use
+/// [`SyncGraph::transaction`](crate::SyncGraph::transaction) instead, which `WATCH`es the graph
+/// across the read and retries on a conflicting change.
+///
+/// ```no_run
+/// # fn main() -> Result<(), falkordb::FalkorDBError> {
+/// use falkordb::{AtomicBatchOutcome, FalkorClientBuilder};
+/// # let info: falkordb::FalkorConnectionInfo = "falkor://127.0.0.1:6379".try_into()?;
+/// let client = FalkorClientBuilder::new().with_connection_info(info).build()?;
+/// let mut graph = client.select_graph("bank");
+///
+/// let mut batch = graph.batch();
+/// batch.query("MATCH (a:Account {id: 1}) SET a.balance = a.balance - 10");
+/// batch.query("MATCH (a:Account {id: 2}) SET a.balance = a.balance + 10");
+///
+/// match batch.atomic().execute()? {
+///     AtomicBatchOutcome::Committed(results) => assert_eq!(results.len(), 2),
+///     AtomicBatchOutcome::Aborted(reason) => println!("nothing applied: {reason:?}"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct AtomicBatchBuilder<'a, G> {
+    batch: BatchBuilder<'a, G>,
+}
+
+impl<G> AtomicBatchBuilder<'_, G> {
+    /// The number of queries in the transaction.
+    pub fn len(&self) -> usize {
+        self.batch.len()
+    }
+
+    /// Whether the transaction holds no queries.
+    pub fn is_empty(&self) -> bool {
+        self.batch.is_empty()
+    }
+}
+
 /// Builds the pipeline, pre-filling a result slot for every query that fails to encode (those are
 /// never sent), and recording the original index of each *submitted* command so replies can be
 /// woven back into submission order.
@@ -246,15 +357,9 @@ fn prepare(
     let mut slots: Vec<Option<BatchItemResult>> = (0..queries.len()).map(|_| None).collect();
 
     for (index, query) in queries.iter().enumerate() {
-        match construct_query(&query.query_string, &query.params) {
-            Ok(query_string) => {
-                pipe.cmd(query.command.as_str())
-                    .arg(graph_name)
-                    .arg(query_string)
-                    .arg("--compact");
-                if let Some(timeout) = query.timeout {
-                    pipe.arg("timeout").arg(timeout);
-                }
+        match query.encoded_args() {
+            Ok(args) => {
+                pipe.cmd(query.command.as_str()).arg(graph_name).arg(args);
                 submitted.push(index);
             }
             Err(err) => slots[index] = Some(Err(err)),
@@ -264,6 +369,67 @@ fn prepare(
     (pipe, submitted, slots)
 }
 
+/// Builds the `MULTI <queries> EXEC` pipeline for an atomic batch. Any encode error fails the
+/// whole transaction up front, so a partial transaction is never sent.
+pub(crate) fn prepare_atomic(
+    graph_name: &str,
+    queries: &[BatchQuery],
+) -> FalkorResult<redis::Pipeline> {
+    let mut pipe = redis::pipe();
+    pipe.cmd("MULTI");
+    for query in queries {
+        pipe.cmd(query.command.as_str())
+            .arg(graph_name)
+            .arg(query.encoded_args()?);
+    }
+    pipe.cmd("EXEC");
+    Ok(pipe)
+}
+
+/// Interprets the replies to a `MULTI <queries> EXEC` pipeline: a nil `EXEC` reply means the graph
+/// `WATCH`ed beforehand changed, an error means the transaction was discarded (reported with the
+/// rejected command's own message when there is one), and an array holds the per-query replies.
+pub(crate) fn weave_exec_reply(
+    mut replies: Vec<redis::Value>,
+    query_count: usize,
+    graph_schema: &mut GraphSchema,
+) -> AtomicBatchResult {
+    let expected = query_count + 2;
+    if replies.len() != expected {
+        return Err(FalkorDBError::RedisParsingError(format!(
+            "atomic batch expected {expected} replies but the server returned {}",
+            replies.len()
+        )));
+    }
+    let exec_reply = replies.pop().expect("reply count was checked above");
+    match exec_reply {
+        redis::Value::Nil => Ok(AtomicBatchOutcome::Aborted(
+            AbortReason::WatchedGraphChanged,
+        )),
+        redis::Value::ServerError(exec_err) => {
+            let rejected = replies
+                .iter()
+                .find_map(|reply| match reply {
+                    redis::Value::ServerError(err) => Some(err),
+                    _ => None,
+                })
+                .unwrap_or(&exec_err);
+            Ok(AtomicBatchOutcome::Aborted(AbortReason::Rejected(
+                rejected.details().unwrap_or("Unknown error").to_string(),
+            )))
+        }
+        redis::Value::Array(results) => {
+            let slots = (0..query_count).map(|_| None).collect();
+            let submitted: Vec<usize> = (0..query_count).collect();
+            weave_replies(slots, &submitted, results, graph_schema)
+                .map(AtomicBatchOutcome::Committed)
+        }
+        other => Err(FalkorDBError::RedisParsingError(format!(
+            "unexpected EXEC reply: {other:?}"
+        ))),
+    }
+}
+
 /// Returns whether any queued query is a write (so the batch needs a read-write connection).
 fn has_write(queries: &[BatchQuery]) -> bool {
     queries.iter().any(|query| !query.command.is_readonly())
@@ -334,6 +500,74 @@ impl BatchBuilder<'_, SyncGraph> {
     }
 }
 
+impl AtomicBatchBuilder<'_, SyncGraph> {
+    /// Send the transaction in one round-trip and report whether it committed. An empty
+    /// transaction commits without a round-trip.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Execute Atomic Batch", skip_all, level = "info")
+    )]
+    pub fn execute(self) -> AtomicBatchResult {
+        let batch = self.batch;
+        if batch.queries.is_empty() {
+            return Ok(AtomicBatchOutcome::Committed(Vec::new()));
+        }
+        let client = batch.graph.get_client().clone();
+        let use_replica = resolve_use_replica(
+            !has_write(&batch.queries),
+            batch.read_preference,
+            client.read_preference(),
+            "batch",
+        )?;
+
+        let pipe = prepare_atomic(batch.graph.graph_name(), &batch.queries)?;
+        let conn = if use_replica {
+            client.borrow_readonly_connection(client.clone())
+        } else {
+            client.borrow_connection(client.clone())
+        };
+        let replies = conn?.execute_pipeline(&pipe)?;
+
+        let graph_schema = batch.graph.get_graph_schema_mut();
+        weave_exec_reply(replies, batch.queries.len(), graph_schema)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl AtomicBatchBuilder<'_, crate::AsyncGraph> {
+    /// Send the transaction in one round-trip and report whether it committed. An empty
+    /// transaction commits without a round-trip.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Execute Atomic Batch", skip_all, level = "info")
+    )]
+    pub async fn execute(self) -> AtomicBatchResult {
+        let batch = self.batch;
+        if batch.queries.is_empty() {
+            return Ok(AtomicBatchOutcome::Committed(Vec::new()));
+        }
+        let client = batch.graph.get_client().clone();
+        let use_replica = resolve_use_replica(
+            !has_write(&batch.queries),
+            batch.read_preference,
+            client.read_preference(),
+            "batch",
+        )?;
+
+        let pipe = prepare_atomic(batch.graph.graph_name(), &batch.queries)?;
+        let conn = if use_replica {
+            client.borrow_readonly_connection(client.clone()).await
+        } else {
+            client.borrow_connection(client.clone()).await
+        };
+        let replies = conn?.execute_pipeline(&pipe).await?;
+
+        let schema = batch.graph.schema_handle();
+        let mut guard = schema.write();
+        weave_exec_reply(replies, batch.queries.len(), &mut guard)
+    }
+}
+
 #[cfg(feature = "tokio")]
 impl BatchBuilder<'_, crate::AsyncGraph> {
     /// Dispatch all queued queries over one pipelined round-trip and return their results in
@@ -488,6 +722,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn prepare_atomic_wraps_queries_in_multi_exec() {
+        let queries = vec![BatchQuery::write("RETURN 1"), BatchQuery::read("RETURN 2")];
+        let pipe = prepare_atomic("g", &queries).expect("encodes");
+        assert_eq!(pipe.len(), 4, "MULTI + two queries + EXEC");
+    }
+
+    #[test]
+    fn prepare_atomic_fails_whole_transaction_on_encode_error() {
+        let mut bad = BatchQuery::write("RETURN 2");
+        bad.with_param("invalid name!", 1);
+        let queries = vec![BatchQuery::write("RETURN 1"), bad];
+        assert!(prepare_atomic("g", &queries).is_err());
+    }
+
+    fn queued() -> redis::Value {
+        redis::Value::SimpleString("QUEUED".to_string())
+    }
+
+    #[test]
+    fn weave_exec_reply_commits_in_order() {
+        let mut schema = GraphSchema::new("test", create_empty_inner_sync_client());
+        let replies = vec![
+            redis::Value::Okay,
+            queued(),
+            queued(),
+            redis::Value::Array(vec![stats_only_reply(), stats_only_reply()]),
+        ];
+        let outcome = weave_exec_reply(replies, 2, &mut schema).expect("parsed");
+        let AtomicBatchOutcome::Committed(results) = outcome else {
+            panic!("expected a committed transaction");
+        };
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(Result::is_ok));
+    }
+
+    #[test]
+    fn weave_exec_reply_reports_watch_abort() {
+        let mut schema = GraphSchema::new("test", create_empty_inner_sync_client());
+        // The transaction's `WATCH` is sent on its own, so the pipeline starts at `MULTI`.
+        let replies = vec![redis::Value::Okay, queued(), redis::Value::Nil];
+        let outcome = weave_exec_reply(replies, 1, &mut schema).expect("parsed");
+        assert!(outcome.is_aborted());
+        assert!(matches!(
+            outcome,
+            AtomicBatchOutcome::Aborted(AbortReason::WatchedGraphChanged)
+        ));
+    }
+
+    #[test]
+    fn weave_exec_reply_reports_rejected_command() {
+        let mut schema = GraphSchema::new("test", create_empty_inner_sync_client());
+        // A server error reply, decoded from its wire form.
+        let server_error = |line: &str| {
+            redis::parse_redis_value(format!("-{line}\r\n").as_bytes()).expect("an error reply")
+        };
+        let replies = vec![
+            redis::Value::Okay,
+            queued(),
+            server_error("ERR wrong number of arguments"),
+            server_error("EXECABORT Transaction discarded because of previous errors."),
+        ];
+        let outcome = weave_exec_reply(replies, 2, &mut schema).expect("parsed");
+        assert!(
+            matches!(&outcome, AtomicBatchOutcome::Aborted(AbortReason::Rejected(m)) if m == "wrong number of arguments"),
+            "the rejected command's own message is reported, got {outcome:?}"
+        );
+    }
+
+    #[test]
+    fn weave_exec_reply_errors_on_reply_count_mismatch() {
+        let mut schema = GraphSchema::new("test", create_empty_inner_sync_client());
+        let result = weave_exec_reply(vec![redis::Value::Okay], 1, &mut schema);
+        assert!(matches!(result, Err(FalkorDBError::RedisParsingError(_))));
+    }
+
     #[test]
     fn weave_replies_errors_on_reply_count_mismatch() {
         let mut schema = GraphSchema::new("test", create_empty_inner_sync_client());
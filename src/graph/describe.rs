@@ -0,0 +1,580 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Schema introspection: the labels, relationship types, property types, relationship endpoints
+//! and attached indices and constraints of a graph, for [`SyncGraph::describe`].
+
+use super::quote_identifier;
+use crate::{Constraint, EntityType, FalkorIndex, FalkorResult, Row, SyncGraph};
+
+const LABELS_QUERY: &str = "CALL db.labels() YIELD label RETURN label ORDER BY label";
+const RELATIONSHIP_TYPES_QUERY: &str =
+    "CALL db.relationshipTypes() YIELD relationshipType RETURN relationshipType AS label ORDER BY label";
+
+/// The schema of a graph, as read by [`SyncGraph::describe`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct GraphDescription {
+    /// Every node label, by name.
+    pub labels: Vec<LabelDescription>,
+    /// Every relationship type, by name.
+    pub relationship_types: Vec<RelationshipTypeDescription>,
+    /// The most entities of each label and relationship type whose properties and endpoints were
+    /// looked at, or `None` if all were.
+    pub sample_size: Option<u64>,
+}
+
+/// A node label of a [`GraphDescription`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct LabelDescription {
+    /// The label.
+    pub name: String,
+    /// The nodes carrying the label.
+    pub count: i64,
+    /// The property keys seen on those nodes, by name.
+    pub properties: Vec<PropertyDescription>,
+    /// The indices on the label.
+    pub indices: Vec<FalkorIndex>,
+    /// The constraints on the label.
+    pub constraints: Vec<Constraint>,
+}
+
+/// A relationship type of a [`GraphDescription`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct RelationshipTypeDescription {
+    /// The relationship type.
+    pub name: String,
+    /// The relationships of this type.
+    pub count: i64,
+    /// The property keys seen on those relationships, by name.
+    pub properties: Vec<PropertyDescription>,
+    /// The label sets the relationships connect, most frequent first.
+    pub endpoints: Vec<EndpointDescription>,
+    /// The indices on the relationship type.
+    pub indices: Vec<FalkorIndex>,
+    /// The constraints on the relationship type.
+    pub constraints: Vec<Constraint>,
+}
+
+/// A property key seen on the entities of a label or relationship type.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PropertyDescription {
+    /// The property key.
+    pub name: String,
+    /// The entities holding the property.
+    pub count: i64,
+    /// The types of its values, as named by Cypher's `typeOf`, most frequent first.
+    pub value_types: Vec<ValueTypeCount>,
+}
+
+/// How many values of a property have one type.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ValueTypeCount {
+    /// The type, as named by Cypher's `typeOf` (`Integer`, `String`, `Point`, …).
+    pub type_name: String,
+    /// The values of this type.
+    pub count: i64,
+}
+
+/// The labels at both ends of some relationships of one type.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EndpointDescription {
+    /// The labels of the source nodes.
+    pub source_labels: Vec<String>,
+    /// The labels of the destination nodes.
+    pub target_labels: Vec<String>,
+    /// The relationships connecting them.
+    pub count: i64,
+}
+
+/// The statements describing one label or relationship type.
+struct Queries {
+    pattern: String,
+    sample: String,
+}
+
+impl Queries {
+    fn new(
+        entity_type: EntityType,
+        name: &str,
+        sample_size: Option<u64>,
+    ) -> FalkorResult<Self> {
+        let name = quote_identifier(name)?;
+        Ok(Self {
+            pattern: match entity_type {
+                EntityType::Node => format!("(e:{name})"),
+                EntityType::Edge => format!("()-[e:{name}]->()"),
+            },
+            sample: sample_size
+                .map(|limit| format!(" WITH e LIMIT {limit}"))
+                .unwrap_or_default(),
+        })
+    }
+
+    fn count(&self) -> String {
+        format!("MATCH {} RETURN count(e) AS count", self.pattern)
+    }
+
+    fn properties(&self) -> String {
+        format!(
+            "MATCH {}{} UNWIND keys(e) AS key RETURN key, typeOf(e[key]) AS type, count(*) AS count",
+            self.pattern, self.sample
+        )
+    }
+
+    /// Only for relationship types.
+    fn endpoints(&self) -> String {
+        format!(
+            "MATCH {}{} RETURN labels(startNode(e)) AS source, labels(endNode(e)) AS target, \
+             count(*) AS count",
+            self.pattern, self.sample
+        )
+    }
+}
+
+fn parse_name(row: Row) -> FalkorResult<String> {
+    row.try_get("label")
+}
+
+fn parse_count(rows: Vec<Row>) -> FalkorResult<i64> {
+    rows.into_iter()
+        .next()
+        .map_or(Ok(0), |row| row.try_get("count"))
+}
+
+/// Groups `(key, type, count)` rows by key, most frequent type first.
+fn parse_properties(rows: Vec<Row>) -> FalkorResult<Vec<PropertyDescription>> {
+    let mut properties: Vec<PropertyDescription> = Vec::new();
+    for row in rows {
+        let name: String = row.try_get("key")?;
+        let value_type = ValueTypeCount {
+            type_name: row.try_get("type")?,
+            count: row.try_get("count")?,
+        };
+        match properties.iter_mut().find(|property| property.name == name) {
+            Some(property) => {
+                property.count += value_type.count;
+                property.value_types.push(value_type);
+            }
+            None => properties.push(PropertyDescription {
+                name,
+                count: value_type.count,
+                value_types: vec![value_type],
+            }),
+        }
+    }
+    properties.sort_by(|left, right| left.name.cmp(&right.name));
+    for property in &mut properties {
+        property.value_types.sort_by(|left, right| {
+            right
+                .count
+                .cmp(&left.count)
+                .then_with(|| left.type_name.cmp(&right.type_name))
+        });
+    }
+    Ok(properties)
+}
+
+fn parse_endpoints(rows: Vec<Row>) -> FalkorResult<Vec<EndpointDescription>> {
+    let mut endpoints = rows
+        .into_iter()
+        .map(|row| {
+            let mut source_labels: Vec<String> = row.try_get("source")?;
+            let mut target_labels: Vec<String> = row.try_get("target")?;
+            source_labels.sort();
+            target_labels.sort();
+            Ok(EndpointDescription {
+                source_labels,
+                target_labels,
+                count: row.try_get("count")?,
+            })
+        })
+        .collect::<FalkorResult<Vec<_>>>()?;
+    endpoints.sort_by(|left, right| {
+        right
+            .count
+            .cmp(&left.count)
+            .then_with(|| left.source_labels.cmp(&right.source_labels))
+            .then_with(|| left.target_labels.cmp(&right.target_labels))
+    });
+    Ok(endpoints)
+}
+
+/// The indices and constraints on the `entity_type` named `name`.
+fn attached(
+    indices: &[FalkorIndex],
+    constraints: &[Constraint],
+    entity_type: EntityType,
+    name: &str,
+) -> (Vec<FalkorIndex>, Vec<Constraint>) {
+    (
+        indices
+            .iter()
+            .filter(|index| index.entity_type == entity_type && index.index_label == name)
+            .cloned()
+            .collect(),
+        constraints
+            .iter()
+            .filter(|constraint| constraint.entity_type == entity_type && constraint.label == name)
+            .cloned()
+            .collect(),
+    )
+}
+
+fn label_description(
+    name: String,
+    count: Vec<Row>,
+    properties: Vec<Row>,
+    indices: &[FalkorIndex],
+    constraints: &[Constraint],
+) -> FalkorResult<LabelDescription> {
+    let (indices, constraints) = attached(indices, constraints, EntityType::Node, &name);
+    Ok(LabelDescription {
+        count: parse_count(count)?,
+        properties: parse_properties(properties)?,
+        indices,
+        constraints,
+        name,
+    })
+}
+
+fn relationship_type_description(
+    name: String,
+    count: Vec<Row>,
+    properties: Vec<Row>,
+    endpoints: Vec<Row>,
+    indices: &[FalkorIndex],
+    constraints: &[Constraint],
+) -> FalkorResult<RelationshipTypeDescription> {
+    let (indices, constraints) = attached(indices, constraints, EntityType::Edge, &name);
+    Ok(RelationshipTypeDescription {
+        count: parse_count(count)?,
+        properties: parse_properties(properties)?,
+        endpoints: parse_endpoints(endpoints)?,
+        indices,
+        constraints,
+        name,
+    })
+}
+
+/// Builder reading the schema of a [`SyncGraph`].
+///
+/// Created via [`SyncGraph::describe`]. Entity counts always cover the whole graph; with
+/// [`with_sample_size`](Self::with_sample_size), property types and relationship endpoints are
+/// only observed on the first entities of each label and relationship type.
+#[must_use = "a describe builder does nothing unless `.execute()` is called"]
+pub struct DescribeBuilder<'a> {
+    graph: &'a mut SyncGraph,
+    sample_size: Option<u64>,
+}
+
+impl DescribeBuilder<'_> {
+    /// Observes property types and endpoints on at most `sample_size` entities per label and
+    /// relationship type, instead of scanning all of them.
+    ///
+    /// # Arguments
+    /// * `sample_size`: The most entities of each label or relationship type to look at.
+    pub fn with_sample_size(
+        mut self,
+        sample_size: u64,
+    ) -> Self {
+        self.sample_size = Some(sample_size);
+        self
+    }
+
+    /// Reads the schema with read-only queries.
+    ///
+    /// # Returns
+    /// The [`GraphDescription`], or the first error.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Describe Graph", skip_all, level = "info")
+    )]
+    pub fn execute(self) -> FalkorResult<GraphDescription> {
+        let graph = self.graph;
+        let indices = graph.list_indices()?.data;
+        let constraints = graph.list_constraints()?.data;
+        let mut description = GraphDescription {
+            sample_size: self.sample_size,
+            ..Default::default()
+        };
+        for row in rows(graph, LABELS_QUERY)? {
+            let name = parse_name(row)?;
+            let queries = Queries::new(EntityType::Node, &name, self.sample_size)?;
+            description.labels.push(label_description(
+                name,
+                rows(graph, &queries.count())?,
+                rows(graph, &queries.properties())?,
+                &indices,
+                &constraints,
+            )?);
+        }
+        for row in rows(graph, RELATIONSHIP_TYPES_QUERY)? {
+            let name = parse_name(row)?;
+            let queries = Queries::new(EntityType::Edge, &name, self.sample_size)?;
+            description
+                .relationship_types
+                .push(relationship_type_description(
+                    name,
+                    rows(graph, &queries.count())?,
+                    rows(graph, &queries.properties())?,
+                    rows(graph, &queries.endpoints())?,
+                    &indices,
+                    &constraints,
+                )?);
+        }
+        Ok(description)
+    }
+}
+
+fn rows(
+    graph: &mut SyncGraph,
+    query: &str,
+) -> FalkorResult<Vec<Row>> {
+    graph.ro_query(query).execute()?.data.collect()
+}
+
+/// Builder reading the schema of an [`AsyncGraph`](crate::AsyncGraph).
+///
+/// Created via [`AsyncGraph::describe`](crate::AsyncGraph::describe); the async counterpart of
+/// [`DescribeBuilder`].
+#[cfg(feature = "tokio")]
+#[must_use = "a describe builder does nothing unless `.execute()` is awaited"]
+pub struct AsyncDescribeBuilder<'a> {
+    graph: &'a mut crate::AsyncGraph,
+    sample_size: Option<u64>,
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncDescribeBuilder<'_> {
+    /// Observes property types and endpoints on at most `sample_size` entities per label and
+    /// relationship type, instead of scanning all of them.
+    ///
+    /// # Arguments
+    /// * `sample_size`: The most entities of each label or relationship type to look at.
+    pub fn with_sample_size(
+        mut self,
+        sample_size: u64,
+    ) -> Self {
+        self.sample_size = Some(sample_size);
+        self
+    }
+
+    /// Reads the schema with read-only queries.
+    ///
+    /// # Returns
+    /// The [`GraphDescription`], or the first error.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Describe Graph Async", skip_all, level = "info")
+    )]
+    pub async fn execute(self) -> FalkorResult<GraphDescription> {
+        let graph = self.graph;
+        let indices = graph.list_indices().await?.data;
+        let constraints = graph.list_constraints().await?.data;
+        let mut description = GraphDescription {
+            sample_size: self.sample_size,
+            ..Default::default()
+        };
+        for row in rows_async(graph, LABELS_QUERY).await? {
+            let name = parse_name(row)?;
+            let queries = Queries::new(EntityType::Node, &name, self.sample_size)?;
+            description.labels.push(label_description(
+                name,
+                rows_async(graph, &queries.count()).await?,
+                rows_async(graph, &queries.properties()).await?,
+                &indices,
+                &constraints,
+            )?);
+        }
+        for row in rows_async(graph, RELATIONSHIP_TYPES_QUERY).await? {
+            let name = parse_name(row)?;
+            let queries = Queries::new(EntityType::Edge, &name, self.sample_size)?;
+            description
+                .relationship_types
+                .push(relationship_type_description(
+                    name,
+                    rows_async(graph, &queries.count()).await?,
+                    rows_async(graph, &queries.properties()).await?,
+                    rows_async(graph, &queries.endpoints()).await?,
+                    &indices,
+                    &constraints,
+                )?);
+        }
+        Ok(description)
+    }
+}
+
+#[cfg(feature = "tokio")]
+async fn rows_async(
+    graph: &mut crate::AsyncGraph,
+    query: &str,
+) -> FalkorResult<Vec<Row>> {
+    // One reply, never paged, so the description is a single consistent read.
+    let mut rows = graph.ro_query(query).execute_single().await?.data;
+    let mut collected = Vec::new();
+    while let Some(row) = rows.next_row().await {
+        collected.push(row?);
+    }
+    Ok(collected)
+}
+
+impl SyncGraph {
+    /// Returns a builder that reads this graph's schema: its labels and relationship types with
+    /// their entity counts, property keys and value types, relationship endpoints, and the indices
+    /// and constraints on each.
+    pub fn describe(&mut self) -> DescribeBuilder<'_> {
+        DescribeBuilder {
+            graph: self,
+            sample_size: None,
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl crate::AsyncGraph {
+    /// Returns a builder that reads this graph's schema; see [`SyncGraph::describe`].
+    pub fn describe(&mut self) -> AsyncDescribeBuilder<'_> {
+        AsyncDescribeBuilder {
+            graph: self,
+            sample_size: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConstraintStatus, ConstraintType, FalkorValue, IndexStatus};
+    use std::collections::HashMap;
+
+    fn row(values: &[(&str, FalkorValue)]) -> Row {
+        Row::new(
+            values.iter().map(|(name, _)| name.to_string()).collect(),
+            values.iter().map(|(_, value)| value.clone()).collect(),
+        )
+    }
+
+    fn string(value: &str) -> FalkorValue {
+        FalkorValue::String(value.to_string())
+    }
+
+    fn strings(values: &[&str]) -> FalkorValue {
+        FalkorValue::Array(values.iter().map(|value| string(value)).collect())
+    }
+
+    #[test]
+    fn test_queries_quote_names_and_sample() {
+        let node = Queries::new(EntityType::Node, "Person", None).unwrap();
+        assert_eq!(node.count(), "MATCH (e:Person) RETURN count(e) AS count");
+        assert_eq!(
+            node.properties(),
+            "MATCH (e:Person) UNWIND keys(e) AS key RETURN key, typeOf(e[key]) AS type, count(*) AS count"
+        );
+
+        let edge = Queries::new(EntityType::Edge, "ACTED IN", Some(100)).unwrap();
+        assert_eq!(
+            edge.endpoints(),
+            "MATCH ()-[e:`ACTED IN`]->() WITH e LIMIT 100 RETURN labels(startNode(e)) AS source, labels(endNode(e)) AS target, count(*) AS count"
+        );
+        assert!(Queries::new(EntityType::Node, "a`b", None).is_err());
+    }
+
+    #[test]
+    fn test_properties_are_grouped_by_key() {
+        let rows = vec![
+            row(&[
+                ("key", string("name")),
+                ("type", string("String")),
+                ("count", FalkorValue::I64(4)),
+            ]),
+            row(&[
+                ("key", string("age")),
+                ("type", string("String")),
+                ("count", FalkorValue::I64(1)),
+            ]),
+            row(&[
+                ("key", string("age")),
+                ("type", string("Integer")),
+                ("count", FalkorValue::I64(3)),
+            ]),
+        ];
+        let properties = parse_properties(rows).unwrap();
+        assert_eq!(
+            properties
+                .iter()
+                .map(|property| (property.name.as_str(), property.count))
+                .collect::<Vec<_>>(),
+            [("age", 4), ("name", 4)]
+        );
+        assert_eq!(
+            properties[0]
+                .value_types
+                .iter()
+                .map(|value_type| value_type.type_name.as_str())
+                .collect::<Vec<_>>(),
+            ["Integer", "String"]
+        );
+    }
+
+    #[test]
+    fn test_relationship_type_collects_endpoints_and_attached_schema() {
+        let index = FalkorIndex {
+            entity_type: EntityType::Edge,
+            status: IndexStatus::Active,
+            index_label: "KNOWS".to_string(),
+            fields: vec!["since".to_string()],
+            field_types: HashMap::new(),
+            language: "english".to_string(),
+            stopwords: Vec::new(),
+            info: HashMap::new(),
+            options: HashMap::new(),
+        };
+        let constraint = Constraint {
+            constraint_type: ConstraintType::Mandatory,
+            label: "KNOWS".to_string(),
+            properties: vec!["since".to_string()],
+            entity_type: EntityType::Node,
+            status: ConstraintStatus::Active,
+        };
+        let description = relationship_type_description(
+            "KNOWS".to_string(),
+            vec![row(&[("count", FalkorValue::I64(5))])],
+            Vec::new(),
+            vec![
+                row(&[
+                    ("source", strings(&["Person"])),
+                    ("target", strings(&["Robot"])),
+                    ("count", FalkorValue::I64(1)),
+                ]),
+                row(&[
+                    ("source", strings(&["Person", "Admin"])),
+                    ("target", strings(&["Person"])),
+                    ("count", FalkorValue::I64(4)),
+                ]),
+            ],
+            std::slice::from_ref(&index),
+            std::slice::from_ref(&constraint),
+        )
+        .unwrap();
+        assert_eq!(description.count, 5);
+        assert_eq!(
+            description.endpoints[0],
+            EndpointDescription {
+                source_labels: vec!["Admin".to_string(), "Person".to_string()],
+                target_labels: vec!["Person".to_string()],
+                count: 4,
+            }
+        );
+        assert_eq!(description.indices, [index]);
+        // The constraint is on the `KNOWS` node label, not the relationship type.
+        assert!(description.constraints.is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Typed fulltext index options and the query generation behind
+//! [`SyncGraph::fulltext_search`](crate::SyncGraph::fulltext_search).
+
+use crate::{to_cypher_param, FalkorDBError, FalkorResult};
+
+/// The parameter the searched label is bound to.
+pub(crate) const LABEL_PARAM: &str = "label";
+/// The parameter the escaped fulltext query is bound to.
+pub(crate) const QUERY_PARAM: &str = "query";
+
+/// The read-only query behind `fulltext_search`; both arguments are bound as parameters.
+pub(crate) const FULLTEXT_SEARCH_QUERY: &str = "CALL db.idx.fulltext.queryNodes($label, $query) \
+     YIELD node, score RETURN node, score ORDER BY score DESC";
+
+/// Index-wide settings for a fulltext index, passed to
+/// [`SyncGraph::create_fulltext_index`](crate::SyncGraph::create_fulltext_index).
+///
+/// Both settings only take effect when the index is first created on a label; FalkorDB keeps the
+/// original language and stopwords when more fields are added later.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[must_use = "fulltext index options do nothing unless passed to create_fulltext_index"]
+pub struct FulltextIndexOptions {
+    language: Option<String>,
+    stopwords: Option<Vec<String>>,
+}
+
+impl FulltextIndexOptions {
+    /// Creates options using the FalkorDB defaults: English stemming and the default stopword list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the language used for stemming, e.g. `"German"`.
+    ///
+    /// # Arguments
+    /// * `language`: A language name supported by FalkorDB's fulltext engine.
+    pub fn with_language(
+        mut self,
+        language: impl Into<String>,
+    ) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Replaces the default stopword list; pass an empty list to index every word.
+    ///
+    /// # Arguments
+    /// * `stopwords`: The words to leave out of the index.
+    pub fn with_stopwords<S: Into<String>>(
+        mut self,
+        stopwords: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.stopwords = Some(stopwords.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+/// A property to include in a fulltext index, with its optional per-field settings.
+///
+/// A plain `&str` or [`String`] converts into a field with the default settings.
+#[derive(Clone, Debug, PartialEq)]
+#[must_use = "a fulltext field does nothing unless passed to create_fulltext_index"]
+pub struct FulltextField {
+    name: String,
+    weight: Option<f64>,
+    phonetic: Option<String>,
+    nostem: bool,
+}
+
+impl FulltextField {
+    /// Creates a field indexing the property `name` with the default settings.
+    ///
+    /// # Arguments
+    /// * `name`: The node property to index.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            weight: None,
+            phonetic: None,
+            nostem: false,
+        }
+    }
+
+    /// Scales the score of matches in this field; the default weight is `1.0`.
+    ///
+    /// # Arguments
+    /// * `weight`: A finite, positive weight.
+    pub fn with_weight(
+        mut self,
+        weight: f64,
+    ) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Enables phonetic matching for this field.
+    ///
+    /// # Arguments
+    /// * `phonetic`: The phonetic matcher, e.g. `"dm:en"` for Double Metaphone in English.
+    pub fn with_phonetic(
+        mut self,
+        phonetic: impl Into<String>,
+    ) -> Self {
+        self.phonetic = Some(phonetic.into());
+        self
+    }
+
+    /// Disables stemming for this field, so only exact word forms match.
+    pub fn nostem(mut self) -> Self {
+        self.nostem = true;
+        self
+    }
+
+    /// Whether the field needs the `{field: ...}` map form rather than a bare name.
+    fn has_settings(&self) -> bool {
+        self.weight.is_some() || self.phonetic.is_some() || self.nostem
+    }
+}
+
+impl From<&str> for FulltextField {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for FulltextField {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+/// Builds the `db.idx.fulltext.createNodeIndex` call behind `create_fulltext_index`.
+///
+/// The label, field names and settings are all emitted as escaped Cypher literals, so none of them
+/// needs to be an identifier. Fields without settings are passed by name, the others as
+/// `{field: ..., weight: ..., nostem: true, phonetic: ...}` maps. A weight that is not finite and
+/// positive is rejected with [`FalkorDBError::InvalidIndexOption`].
+pub(crate) fn generate_create_fulltext_index_query(
+    label: &str,
+    fields: &[FulltextField],
+    options: &FulltextIndexOptions,
+) -> FalkorResult<String> {
+    let mut config = vec![format!("label: {}", to_cypher_param(label)?)];
+    if let Some(language) = &options.language {
+        config.push(format!("language: {}", to_cypher_param(language)?));
+    }
+    if let Some(stopwords) = &options.stopwords {
+        config.push(format!("stopwords: {}", to_cypher_param(stopwords)?));
+    }
+
+    let mut arguments = vec![format!("{{{}}}", config.join(", "))];
+    for field in fields {
+        let name = to_cypher_param(&field.name)?;
+        if !field.has_settings() {
+            arguments.push(name);
+            continue;
+        }
+        let mut settings = vec![format!("field: {name}")];
+        if let Some(weight) = field.weight {
+            if !weight.is_finite() || weight <= 0.0 {
+                return Err(FalkorDBError::InvalidIndexOption {
+                    key: "weight".to_string(),
+                    message: format!(
+                        "the weight of field '{}' must be finite and positive, not {weight}",
+                        field.name
+                    ),
+                });
+            }
+            settings.push(format!("weight: {}", to_cypher_param(&weight)?));
+        }
+        if field.nostem {
+            settings.push("nostem: true".to_string());
+        }
+        if let Some(phonetic) = &field.phonetic {
+            settings.push(format!("phonetic: {}", to_cypher_param(phonetic)?));
+        }
+        arguments.push(format!("{{{}}}", settings.join(", ")));
+    }
+
+    Ok(format!(
+        "CALL db.idx.fulltext.createNodeIndex({})",
+        arguments.join(", ")
+    ))
+}
+
+/// Escapes a string so a fulltext index matches it as plain words.
+///
+/// Every ASCII punctuation character except `_` is an operator or a token separator in FalkorDB's
+/// fulltext query syntax (`-` negates, `|` is a union, `*` a prefix match, `@` a field filter,
+/// ...), so each one is prefixed with a backslash. Whitespace is kept, so the words of the input
+/// must all match. [`SyncGraph::fulltext_search`](crate::SyncGraph::fulltext_search) applies this
+/// to its query; use it directly when combining user input with operators of your own.
+///
+/// # Arguments
+/// * `text`: The text to search for.
+///
+/// # Returns
+/// The escaped query string.
+pub fn escape_fulltext_query(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if ch.is_ascii_punctuation() && ch != '_' {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_create_fulltext_index_query() {
+        assert_eq!(
+            generate_create_fulltext_index_query(
+                "Movie",
+                &["title".into()],
+                &FulltextIndexOptions::new()
+            )
+            .unwrap(),
+            "CALL db.idx.fulltext.createNodeIndex({label: 'Movie'}, 'title')"
+        );
+
+        let fields = [
+            FulltextField::new("title").with_weight(2.0),
+            FulltextField::new("plot").nostem().with_phonetic("dm:en"),
+        ];
+        let options = FulltextIndexOptions::new()
+            .with_language("German")
+            .with_stopwords(["der", "die"]);
+        assert_eq!(
+            generate_create_fulltext_index_query("Film's", &fields, &options).unwrap(),
+            "CALL db.idx.fulltext.createNodeIndex(\
+             {label: 'Film\\'s', language: 'German', stopwords: ['der', 'die']}, \
+             {field: 'title', weight: 2.0}, {field: 'plot', nostem: true, phonetic: 'dm:en'})"
+        );
+    }
+
+    #[test]
+    fn test_generate_create_fulltext_index_query_rejects_bad_weights() {
+        for weight in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let field = FulltextField::new("title").with_weight(weight);
+            assert!(matches!(
+                generate_create_fulltext_index_query("Movie", &[field], &FulltextIndexOptions::new()),
+                Err(FalkorDBError::InvalidIndexOption { ref key, .. }) if key == "weight"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_escape_fulltext_query() {
+        assert_eq!(escape_fulltext_query("matrix reloaded"), "matrix reloaded");
+        assert_eq!(
+            escape_fulltext_query("-x|y* @title:(a) don't"),
+            "\\-x\\|y\\* \\@title\\:\\(a\\) don\\'t"
+        );
+        assert_eq!(
+            escape_fulltext_query("snake_case 42 naïve"),
+            "snake_case 42 naïve"
+        );
+        assert_eq!(escape_fulltext_query("C:\\path"), "C\\:\\\\path");
+    }
+}
@@ -3,7 +3,9 @@
  * Licensed under the MIT License.
  */
 
-use crate::{EntityType, FalkorDBError, FalkorResult, GraphSchema, IndexType};
+use crate::{
+    EntityType, FalkorDBError, FalkorIndex, FalkorResult, FalkorValue, GraphSchema, IndexType,
+};
 use std::{collections::HashMap, fmt::Display};
 
 pub(crate) mod blocking;
@@ -11,8 +13,24 @@ pub(crate) mod query_builder;
 
 pub(crate) mod batch;
 
+pub(crate) mod describe;
+
+pub(crate) mod fulltext;
+
 pub(crate) mod ops;
 
+pub(crate) mod paginate;
+
+pub(crate) mod reconcile;
+
+pub(crate) mod spatial;
+
+pub(crate) mod transaction;
+
+pub(crate) mod unwind;
+
+pub(crate) mod vector;
+
 #[cfg(feature = "tokio")]
 pub(crate) mod asynchronous;
 
@@ -121,6 +139,120 @@ pub(crate) fn vector_index_options(
     ])
 }
 
+/// Reads back the `dimension` and similarity function `DB.INDEXES` reports for a vector index, or
+/// `None` if either is missing or not one this client knows.
+pub(crate) fn vector_index_settings(index: &FalkorIndex) -> Option<(u32, VectorSimilarity)> {
+    let dimension = match index.options.get("dimension")? {
+        FalkorValue::I64(dimension) => u32::try_from(*dimension).ok()?,
+        _ => return None,
+    };
+    let similarity = match index.options.get("similarityFunction")? {
+        FalkorValue::String(name) if name.eq_ignore_ascii_case("euclidean") => {
+            VectorSimilarity::Euclidean
+        }
+        FalkorValue::String(name) if name.eq_ignore_ascii_case("cosine") => {
+            VectorSimilarity::Cosine
+        }
+        _ => return None,
+    };
+    Some((dimension, similarity))
+}
+
+/// Cypher's reserved words, plus the FalkorDB clause keywords, which cannot stand bare as a label,
+/// relationship type or property name.
+const RESERVED_WORDS: &[&str] = &[
+    "ADD",
+    "ALL",
+    "AND",
+    "AS",
+    "ASC",
+    "ASCENDING",
+    "BY",
+    "CALL",
+    "CASE",
+    "CONSTRAINT",
+    "CONTAINS",
+    "CREATE",
+    "CSV",
+    "DELETE",
+    "DESC",
+    "DESCENDING",
+    "DETACH",
+    "DISTINCT",
+    "DO",
+    "DROP",
+    "ELSE",
+    "END",
+    "ENDS",
+    "EXISTS",
+    "FALSE",
+    "FOR",
+    "FOREACH",
+    "FROM",
+    "HEADERS",
+    "IN",
+    "INDEX",
+    "IS",
+    "LIMIT",
+    "LOAD",
+    "MANDATORY",
+    "MATCH",
+    "MERGE",
+    "NOT",
+    "NULL",
+    "OF",
+    "ON",
+    "OPTIONAL",
+    "OR",
+    "ORDER",
+    "REMOVE",
+    "REQUIRE",
+    "RETURN",
+    "SCALAR",
+    "SET",
+    "SKIP",
+    "STARTS",
+    "THEN",
+    "TRUE",
+    "UNION",
+    "UNIQUE",
+    "UNWIND",
+    "WHEN",
+    "WHERE",
+    "WITH",
+    "XOR",
+    "YIELD",
+];
+
+/// Quotes a label, relationship type or property name for interpolation into a query.
+///
+/// Identifiers are emitted bare when they already are Cypher identifiers and are not reserved
+/// words, and backtick-quoted otherwise. Empty names and names containing a backtick or a NUL byte
+/// cannot be quoted safely and are rejected with [`FalkorDBError::InvalidIdentifier`].
+pub(crate) fn quote_identifier(identifier: &str) -> FalkorResult<String> {
+    let invalid = |message: &str| FalkorDBError::InvalidIdentifier {
+        identifier: identifier.to_string(),
+        message: message.to_string(),
+    };
+    if identifier.is_empty() {
+        return Err(invalid("must not be empty"));
+    }
+    if identifier.contains('`') {
+        return Err(invalid("must not contain a backtick"));
+    }
+    if identifier.contains('\0') {
+        return Err(invalid("must not contain a NUL byte"));
+    }
+    let reserved = RESERVED_WORDS
+        .iter()
+        .any(|word| word.eq_ignore_ascii_case(identifier));
+    if crate::value::param::is_bare_identifier(identifier) && !reserved {
+        Ok(identifier.to_string())
+    } else {
+        Ok(format!("`{identifier}`"))
+    }
+}
+
 pub(crate) fn generate_drop_index_query<P: Display>(
     index_field_type: IndexType,
     entity_type: EntityType,
@@ -384,4 +516,21 @@ mod tests {
         assert!(query.contains("e.firstName"));
         assert!(query.contains("e.lastName"));
     }
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("Person").unwrap(), "Person");
+        assert_eq!(quote_identifier("_id2").unwrap(), "_id2");
+        assert_eq!(quote_identifier("home base").unwrap(), "`home base`");
+        assert_eq!(quote_identifier("2d").unwrap(), "`2d`");
+        for keyword in ["RETURN", "match", "Order"] {
+            assert_eq!(quote_identifier(keyword).unwrap(), format!("`{keyword}`"));
+        }
+        for bad in ["", "a`b", "a\0b"] {
+            assert!(matches!(
+                quote_identifier(bad),
+                Err(FalkorDBError::InvalidIdentifier { ref identifier, .. }) if identifier == bad
+            ));
+        }
+    }
 }
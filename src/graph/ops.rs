@@ -476,6 +476,36 @@ impl ConstraintWait {
     }
 }
 
+/// The readiness of a constraint command issued outside the op builders, checked the way their
+/// `wait` checks it: a create is done once the constraint is operational, a drop once it is gone.
+pub(crate) fn constraint_step(
+    constraints: &[Constraint],
+    drop: bool,
+    constraint_type: ConstraintType,
+    entity_type: EntityType,
+    label: &str,
+    properties: &[String],
+) -> Step<()> {
+    ConstraintWait {
+        drop,
+        constraint_type,
+        entity_type,
+        label: label.to_string(),
+        properties: properties.to_vec(),
+    }
+    .step(constraints)
+}
+
+/// [`Step::Done`] once every index in `indices` is operational, whichever operation created it.
+#[cfg(feature = "migrations")]
+pub(crate) fn all_indices_ready(indices: &[FalkorIndex]) -> Step<()> {
+    bool_step(
+        indices
+            .iter()
+            .all(|index| index.status == IndexStatus::Active),
+    )
+}
+
 fn bool_step(done: bool) -> Step<()> {
     if done {
         Step::Done(())
@@ -1133,6 +1163,31 @@ mod tests {
         assert_eq!(bool_step(false), Step::Retry);
     }
 
+    #[cfg(feature = "migrations")]
+    #[test]
+    fn all_indices_ready_waits_for_every_index() {
+        let active = index(
+            EntityType::Node,
+            "A",
+            IndexStatus::Active,
+            "x",
+            IndexType::Range,
+        );
+        let pending = index(
+            EntityType::Node,
+            "B",
+            IndexStatus::Pending,
+            "y",
+            IndexType::Fulltext,
+        );
+        assert_eq!(all_indices_ready(&[]), Step::Done(()));
+        assert_eq!(
+            all_indices_ready(std::slice::from_ref(&active)),
+            Step::Done(())
+        );
+        assert_eq!(all_indices_ready(&[active, pending]), Step::Retry);
+    }
+
     #[test]
     fn classify_copy_result_maps_each_outcome() {
         assert_eq!(classify_copy_result::<u8>(Ok(7)), Step::Done(7));
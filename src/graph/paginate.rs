@@ -0,0 +1,494 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! `SKIP`/`LIMIT` pagination: run an ordered, read-only query one page at a time, fetching the
+//! next page only once the current one has been consumed.
+
+use crate::{
+    FalkorDBError, FalkorResult, LazyResultSet, QueryBuilder, QueryResult, ReadPreference, Row,
+    SyncGraph,
+};
+use std::{collections::VecDeque, sync::Arc, sync::OnceLock};
+
+#[cfg(feature = "tokio")]
+use crate::{AsyncGraph, RowStream};
+#[cfg(feature = "tokio")]
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The parameter bound to the number of rows skipped before the current page.
+const SKIP_PARAM: &str = "falkordb_page_skip";
+/// The parameter bound to the page size.
+const LIMIT_PARAM: &str = "falkordb_page_limit";
+
+/// Blanks out string literals, quoted identifiers and comments (keeping every byte offset), so that
+/// keywords are only matched where they are Cypher clauses.
+fn clause_text(query: &str) -> String {
+    static QUOTED: OnceLock<regex::Regex> = OnceLock::new();
+    let quoted = QUOTED.get_or_init(|| {
+        regex::Regex::new(r#"'(?:[^'\\]|\\.)*'|"(?:[^"\\]|\\.)*"|`[^`]*`|//[^\n]*|(?s:/\*.*?\*/)"#)
+            .expect("the quoted text regex is a valid pattern")
+    });
+    quoted
+        .replace_all(query, |caps: &regex::Captures| " ".repeat(caps[0].len()))
+        .into_owned()
+}
+
+/// Validates that `query` can be paged and appends the `SKIP`/`LIMIT` clause to it.
+///
+/// The query's final `RETURN` must order its results (otherwise the server is free to return rows
+/// in a different order for every page, so rows could repeat or go missing), and must not already
+/// end in its own `SKIP`/`LIMIT`. The check is best-effort (regexes over the query with its string
+/// literals and comments blanked out, not a full Cypher parser): it looks for an `ORDER BY` after
+/// the last `RETURN`, and for a `SKIP`/`LIMIT` after that `ORDER BY`.
+fn paged_query_string(query: &str) -> FalkorResult<String> {
+    static RETURN: OnceLock<regex::Regex> = OnceLock::new();
+    static ORDER_BY: OnceLock<regex::Regex> = OnceLock::new();
+    static SKIP_OR_LIMIT: OnceLock<regex::Regex> = OnceLock::new();
+    let return_clause = RETURN.get_or_init(|| {
+        regex::Regex::new(r"(?i)\bRETURN\b").expect("the RETURN regex is a valid pattern")
+    });
+    let order_by = ORDER_BY.get_or_init(|| {
+        regex::Regex::new(r"(?i)\bORDER\s+BY\b").expect("the ORDER BY regex is a valid pattern")
+    });
+    let skip_or_limit = SKIP_OR_LIMIT.get_or_init(|| {
+        regex::Regex::new(r"(?i)\b(SKIP|LIMIT)\b").expect("the SKIP/LIMIT regex is a valid pattern")
+    });
+
+    let query = query.trim_end().trim_end_matches(';').trim_end();
+    let clauses = clause_text(query);
+    let last_return = return_clause
+        .find_iter(&clauses)
+        .last()
+        .map_or(0, |found| found.end());
+    let last_order_by = order_by.find_iter(&clauses[last_return..]).last().ok_or(
+        FalkorDBError::InvalidPagination {
+            reason: "the query's final RETURN has no ORDER BY, so its pages would not be stable",
+        },
+    )?;
+    if skip_or_limit.is_match(&clauses[last_return + last_order_by.end()..]) {
+        return Err(FalkorDBError::InvalidPagination {
+            reason: "the query already ends in its own SKIP or LIMIT",
+        });
+    }
+    Ok(format!("{query} SKIP ${SKIP_PARAM} LIMIT ${LIMIT_PARAM}"))
+}
+
+/// Whether an async `GRAPH.RO_QUERY` can be paged transparently by
+/// [`QueryBuilder::execute`](crate::QueryBuilder::execute) without changing its result: it must be
+/// pageable by [`paged_query_string`], must not bind the reserved paging parameters, and must not
+/// contain a `UNION` (whose appended `SKIP`/`LIMIT` would only apply to its last branch) or a
+/// `CALL` (procedures such as the index searches, and subqueries, are always fetched whole).
+#[cfg(feature = "tokio")]
+pub(crate) fn is_transparently_pageable(
+    query: &str,
+    params: &crate::FalkorParams,
+) -> bool {
+    static UNION_OR_CALL: OnceLock<regex::Regex> = OnceLock::new();
+    let union_or_call = UNION_OR_CALL.get_or_init(|| {
+        regex::Regex::new(r"(?i)\b(UNION|CALL)\b").expect("the UNION/CALL regex is a valid pattern")
+    });
+    !union_or_call.is_match(&clause_text(query))
+        && !params.contains(SKIP_PARAM)
+        && !params.contains(LIMIT_PARAM)
+        && paged_query_string(query).is_ok()
+}
+
+/// Checks the page size and the user's parameters, returning the paged query string and the
+/// encoded parameters to bind on every page.
+fn validate_page_query(
+    query_string: &str,
+    params: crate::FalkorParams,
+    page_size: usize,
+) -> FalkorResult<(String, Vec<(String, String)>)> {
+    if page_size == 0 {
+        return Err(FalkorDBError::InvalidPagination {
+            reason: "the page size must be at least 1",
+        });
+    }
+    let params = params.into_encoded()?;
+    if params
+        .iter()
+        .any(|(name, _)| name == SKIP_PARAM || name == LIMIT_PARAM)
+    {
+        return Err(FalkorDBError::InvalidPagination {
+            reason: "the falkordb_page_skip and falkordb_page_limit parameters are reserved",
+        });
+    }
+    Ok((paged_query_string(query_string)?, params))
+}
+
+/// Everything needed to re-issue the query for any page; cheap to clone.
+#[derive(Clone)]
+struct PageQuery {
+    query: Arc<str>,
+    params: Arc<[(String, String)]>,
+    timeout: Option<i64>,
+    read_preference: Option<ReadPreference>,
+    page_size: usize,
+}
+
+impl PageQuery {
+    /// Builds a `GRAPH.RO_QUERY` for the page starting at `offset`.
+    fn page<'g, Output, G>(
+        &self,
+        graph: &'g mut G,
+        offset: usize,
+    ) -> QueryBuilder<'g, Output, Arc<str>, G> {
+        let mut builder = QueryBuilder::new(graph, "GRAPH.RO_QUERY", Arc::clone(&self.query));
+        for (name, value) in self.params.iter() {
+            builder = builder.with_raw_param(name, value.as_str());
+        }
+        builder = builder
+            .with_param(SKIP_PARAM, offset)
+            .with_param(LIMIT_PARAM, self.page_size);
+        if let Some(timeout) = self.timeout {
+            builder = builder.with_timeout(timeout);
+        }
+        if let Some(read_preference) = self.read_preference {
+            builder = builder.with_read_preference(read_preference);
+        }
+        builder
+    }
+}
+
+/// A builder for a paginated, read-only query, created by
+/// [`QueryBuilder::paginate`](crate::QueryBuilder::paginate).
+///
+/// The query is run with `GRAPH.RO_QUERY` and an appended `SKIP $falkordb_page_skip LIMIT
+/// $falkordb_page_limit`, so a query that writes is rejected by the server, and those two
+/// parameter names are reserved. The query's final `RETURN` must have an `ORDER BY` so that
+/// consecutive pages are consistent with each other.
+pub struct PaginatedQueryBuilder<'a, G> {
+    graph: &'a mut G,
+    query: FalkorResult<PageQuery>,
+}
+
+impl<'a, G> PaginatedQueryBuilder<'a, G> {
+    pub(crate) fn new(
+        graph: &'a mut G,
+        query_string: &str,
+        params: crate::FalkorParams,
+        timeout: Option<i64>,
+        read_preference: Option<ReadPreference>,
+        page_size: usize,
+    ) -> Self {
+        let query =
+            validate_page_query(query_string, params, page_size).map(|(query, params)| PageQuery {
+                query: query.into(),
+                params: params.into(),
+                timeout,
+                read_preference,
+                page_size,
+            });
+        Self { graph, query }
+    }
+}
+
+impl<'a> PaginatedQueryBuilder<'a, SyncGraph> {
+    /// Fetches the first page and returns a [`PagedResultSet`] that fetches the following pages on
+    /// demand as it is iterated.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Execute Paginated Query", skip_all, level = "info")
+    )]
+    pub fn execute(self) -> FalkorResult<PagedResultSet<'a>> {
+        let mut result_set = PagedResultSet {
+            graph: self.graph,
+            query: self.query?,
+            offset: 0,
+            rows: VecDeque::new(),
+            exhausted: false,
+        };
+        result_set.fetch_page()?;
+        Ok(result_set)
+    }
+}
+
+/// The rows of a paginated query on a [`SyncGraph`], fetched one page at a time.
+///
+/// This implements [`Iterator`] with `Item = FalkorResult<Row>`. Only the current page is held in
+/// memory; the next page is requested from the server once the current one is drained, and
+/// iteration ends after a page comes back shorter than the page size. A failed page fetch is
+/// yielded as an `Err`, after which the iterator ends.
+pub struct PagedResultSet<'a> {
+    graph: &'a mut SyncGraph,
+    query: PageQuery,
+    offset: usize,
+    rows: VecDeque<FalkorResult<Row>>,
+    exhausted: bool,
+}
+
+impl PagedResultSet<'_> {
+    fn fetch_page(&mut self) -> FalkorResult<()> {
+        let page: QueryResult<LazyResultSet> = self
+            .query
+            .page::<QueryResult<LazyResultSet>, _>(self.graph, self.offset)
+            .execute()?;
+        self.rows = page.data.collect();
+        self.offset += self.rows.len();
+        self.exhausted = self.rows.len() < self.query.page_size;
+        Ok(())
+    }
+
+    /// Returns the page size this result set was created with.
+    pub fn page_size(&self) -> usize {
+        self.query.page_size
+    }
+}
+
+impl Iterator for PagedResultSet<'_> {
+    type Item = FalkorResult<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rows.is_empty() && !self.exhausted {
+            if let Err(err) = self.fetch_page() {
+                self.exhausted = true;
+                return Some(Err(err));
+            }
+        }
+        self.rows.pop_front()
+    }
+}
+
+#[cfg(feature = "tokio")]
+type PageFuture = Pin<Box<dyn Future<Output = FalkorResult<RowStream>> + Send>>;
+
+#[cfg(feature = "tokio")]
+impl PaginatedQueryBuilder<'_, AsyncGraph> {
+    /// Fetches the first page and returns a [`RowStream`] that transparently fetches the following
+    /// pages as it is polled, returning `Poll::Pending` while a page is in flight.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Execute Paginated Query", skip_all, level = "info")
+    )]
+    pub async fn execute(self) -> FalkorResult<RowStream> {
+        self.execute_result().await.map(|result| result.data)
+    }
+
+    /// Fetches the first page, returning it with its header and statistics and with a pager for
+    /// the following pages attached to its [`RowStream`].
+    pub(crate) async fn execute_result(self) -> FalkorResult<QueryResult<RowStream>> {
+        let query = self.query?;
+        let mut first = query
+            .page::<QueryResult<RowStream>, _>(self.graph, 0)
+            .execute_single()
+            .await?;
+        if first.data.len() < query.page_size {
+            return Ok(first);
+        }
+        let offset = first.data.len();
+        first.data = first.data.with_pager(AsyncPager {
+            graph: self.graph.clone(),
+            query,
+            offset,
+            in_flight: None,
+        });
+        Ok(first)
+    }
+}
+
+/// Fetches the pages after the first for a paginated [`RowStream`].
+#[cfg(feature = "tokio")]
+pub(crate) struct AsyncPager {
+    graph: AsyncGraph,
+    query: PageQuery,
+    offset: usize,
+    in_flight: Option<PageFuture>,
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncPager {
+    /// Polls for the next page, starting its fetch on the first call. Resolves to the page and
+    /// whether more pages may follow it.
+    pub(crate) fn poll_next_page(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<FalkorResult<(RowStream, bool)>> {
+        let in_flight = self.in_flight.get_or_insert_with(|| {
+            let mut graph = self.graph.clone();
+            let query = self.query.clone();
+            let offset = self.offset;
+            Box::pin(async move {
+                query
+                    .page::<QueryResult<RowStream>, _>(&mut graph, offset)
+                    .execute_single()
+                    .await
+                    .map(|result| result.data)
+            })
+        });
+        let page = match in_flight.as_mut().poll(cx) {
+            Poll::Ready(page) => page,
+            Poll::Pending => return Poll::Pending,
+        };
+        self.in_flight = None;
+        Poll::Ready(page.map(|page| {
+            self.offset += page.len();
+            let more = page.len() >= self.query.page_size;
+            (page, more)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paged_query_appends_skip_and_limit() {
+        assert_eq!(
+            paged_query_string("MATCH (n) RETURN n ORDER BY n.v").unwrap(),
+            "MATCH (n) RETURN n ORDER BY n.v SKIP $falkordb_page_skip LIMIT $falkordb_page_limit"
+        );
+    }
+
+    #[test]
+    fn paged_query_strips_trailing_semicolon_and_whitespace() {
+        assert_eq!(
+            paged_query_string("MATCH (n) RETURN n order by n.v ;\n").unwrap(),
+            "MATCH (n) RETURN n order by n.v SKIP $falkordb_page_skip LIMIT $falkordb_page_limit"
+        );
+    }
+
+    #[test]
+    fn paged_query_requires_order_by() {
+        for query in [
+            "MATCH (n) RETURN n",
+            // The ORDER BY must order the final RETURN, not an inner clause or a string.
+            "MATCH (n) WITH n ORDER BY n.v RETURN n",
+            "CALL { MATCH (n) RETURN n ORDER BY n.v } RETURN n",
+            "MATCH (n) WHERE n.note = 'ORDER BY' RETURN n",
+            "MATCH (n) RETURN n // ORDER BY n.v",
+        ] {
+            assert!(
+                matches!(
+                    paged_query_string(query),
+                    Err(FalkorDBError::InvalidPagination { .. })
+                ),
+                "{query}"
+            );
+        }
+    }
+
+    #[test]
+    fn paged_query_rejects_existing_skip_or_limit() {
+        for query in [
+            "MATCH (n) RETURN n ORDER BY n.v LIMIT 10",
+            "MATCH (n) RETURN n ORDER BY n.v SKIP 5",
+        ] {
+            assert!(matches!(
+                paged_query_string(query),
+                Err(FalkorDBError::InvalidPagination { .. })
+            ));
+        }
+        // A LIMIT before the final ORDER BY belongs to an inner clause and is allowed.
+        assert!(paged_query_string(
+            "MATCH (n) WITH n ORDER BY n.v LIMIT 100 RETURN n ORDER BY n.v"
+        )
+        .is_ok());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn transparent_paging_only_applies_to_safely_pageable_queries() {
+        let no_params = crate::FalkorParams::new();
+        assert!(is_transparently_pageable(
+            "MATCH (n) RETURN n ORDER BY n.v",
+            &no_params
+        ));
+        // Keywords inside a string literal are not clauses.
+        assert!(is_transparently_pageable(
+            "MATCH (n {note: 'a CALL or a UNION'}) RETURN n ORDER BY n.v",
+            &no_params
+        ));
+        for query in [
+            "MATCH (n) RETURN n",
+            "MATCH (n) RETURN n ORDER BY n.v LIMIT 10",
+            "MATCH (a:A) RETURN a.v AS v UNION MATCH (b:B) RETURN b.v AS v ORDER BY v",
+            "CALL db.idx.fulltext.queryNodes('L', 'q') YIELD node, score \
+             RETURN node ORDER BY score DESC",
+            "CALL { MATCH (n) RETURN n } RETURN n ORDER BY n.v",
+        ] {
+            assert!(!is_transparently_pageable(query, &no_params), "{query}");
+        }
+
+        let mut params = crate::FalkorParams::new();
+        params.add_param(LIMIT_PARAM, 3);
+        assert!(!is_transparently_pageable(
+            "MATCH (n) RETURN n ORDER BY n.v",
+            &params
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_zero_page_size_and_reserved_params() {
+        let query = "MATCH (n) RETURN n ORDER BY n.v";
+        assert!(matches!(
+            validate_page_query(query, crate::FalkorParams::new(), 0),
+            Err(FalkorDBError::InvalidPagination { .. })
+        ));
+
+        let mut params = crate::FalkorParams::new();
+        params.add_param(SKIP_PARAM, 3);
+        assert!(matches!(
+            validate_page_query(query, params, 10),
+            Err(FalkorDBError::InvalidPagination { .. })
+        ));
+    }
+
+    #[test]
+    fn test_paginate_sync() {
+        let mut graph = crate::test_utils::open_empty_test_graph("test_paginate_sync");
+        graph
+            .inner
+            .query("UNWIND range(1, 25) AS i CREATE (:N {v: i})")
+            .execute()
+            .expect("create");
+
+        let pages = graph
+            .inner
+            .ro_query("MATCH (n:N) WHERE n.v > $min RETURN n.v AS v ORDER BY v")
+            .with_param("min", 0)
+            .paginate(10)
+            .execute()
+            .expect("first page");
+        let values = pages
+            .map(|row| row?.try_get::<i64>("v"))
+            .collect::<FalkorResult<Vec<_>>>()
+            .expect("all pages");
+        assert_eq!(values, (1..=25).collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_paginate_async() {
+        use futures::TryStreamExt;
+
+        let mut graph = crate::test_utils::open_empty_async_test_graph("test_paginate_async").await;
+        graph
+            .inner
+            .query("UNWIND range(1, 25) AS i CREATE (:N {v: i})")
+            .execute()
+            .await
+            .expect("create");
+
+        let stream = graph
+            .inner
+            .ro_query("MATCH (n:N) RETURN n.v AS v ORDER BY v")
+            .paginate(10)
+            .execute()
+            .await
+            .expect("first page");
+        let values: Vec<i64> = stream
+            .and_then(|row| async move { row.try_get::<i64>("v") })
+            .try_collect()
+            .await
+            .expect("all pages");
+        assert_eq!(values, (1..=25).collect::<Vec<_>>());
+    }
+}
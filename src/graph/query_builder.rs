@@ -36,16 +36,16 @@ fn build_lazy_query_result<'g>(
     QueryResult::from_response(header, data, stats)
 }
 
-/// Builds a [`QueryResult`] over an owned [`RowStream`] by eagerly parsing every row against
-/// `graph_schema`, so the result is decoupled from later changes to the graph's schema.
+/// Builds a [`QueryResult`] over an owned [`RowStream`], decoding the received rows against the
+/// shared `graph_schema` handle before it is returned.
 #[cfg(feature = "tokio")]
 fn build_row_stream_result(
     header: Arc<[String]>,
     rows: Vec<redis::Value>,
     stats: redis::Value,
-    graph_schema: &mut GraphSchema,
+    graph_schema: Arc<parking_lot::RwLock<GraphSchema>>,
 ) -> FalkorResult<QueryResult<crate::RowStream>> {
-    let data = crate::RowStream::parse(Arc::clone(&header), rows, graph_schema);
+    let data = crate::RowStream::parse(Arc::clone(&header), rows, &mut graph_schema.write());
     QueryResult::from_response(header, data, stats)
 }
 
@@ -58,8 +58,9 @@ pub(crate) fn build_vec_rows(
     stats: redis::Value,
     graph_schema: &mut GraphSchema,
 ) -> FalkorResult<QueryResult<Vec<crate::Row>>> {
-    let data = crate::response::row::parse_rows(Arc::clone(&header), rows, graph_schema)
+    let data = rows
         .into_iter()
+        .map(|raw| crate::response::row::parse_row(&header, raw, graph_schema))
         .collect::<FalkorResult<Vec<_>>>()?;
     QueryResult::from_response(header, data, stats)
 }
@@ -328,6 +329,9 @@ impl<Out, T: Display> QueryBuilder<'_, Out, T, SyncGraph> {
                 db.query.fingerprint = tracing::field::Empty,
                 db.query.text = tracing::field::Empty,
                 error.type = tracing::field::Empty,
+                otel.kind = "client",
+                otel.name = tracing::field::Empty,
+                otel.status_code = tracing::field::Empty,
             ),
             level = "trace"
         )
@@ -335,6 +339,12 @@ impl<Out, T: Display> QueryBuilder<'_, Out, T, SyncGraph> {
     fn common_execute_steps(&mut self) -> FalkorResult<redis::Value> {
         let query = construct_query(&self.query_string, &self.params)?;
 
+        #[cfg(feature = "opentelemetry")]
+        let query = crate::observability::with_trace_context(
+            query,
+            self.graph.get_client().trace_context(),
+        );
+
         let timeout = self.timeout.map(|timeout| timeout.to_string());
         let mut params = vec![query.as_str(), "--compact"];
         if let Some(timeout) = timeout.as_deref() {
@@ -350,6 +360,7 @@ impl<Out, T: Display> QueryBuilder<'_, Out, T, SyncGraph> {
         let strategy = crate::observability::SYNC_STRATEGY;
         #[cfg(feature = "tracing")]
         crate::observability::record_request(
+            command,
             strategy,
             matches!(op_kind, OpKind::ReadOnly),
             &self.query_string.to_string(),
@@ -366,8 +377,8 @@ impl<Out, T: Display> QueryBuilder<'_, Out, T, SyncGraph> {
         let params_ref = params.as_slice();
         let policy = client.retry_policy();
 
-        #[cfg(feature = "metrics")]
-        let metrics_start = std::time::Instant::now();
+        #[cfg(any(feature = "metrics", feature = "query-stats"))]
+        let started = std::time::Instant::now();
         let result = run_with_retry_blocking(&policy, op_kind, || {
             let conn = if use_replica {
                 client.borrow_readonly_connection(client.clone())
@@ -388,11 +399,64 @@ impl<Out, T: Display> QueryBuilder<'_, Out, T, SyncGraph> {
             command,
             matches!(op_kind, OpKind::ReadOnly),
             strategy,
-            metrics_start.elapsed(),
+            started.elapsed(),
             result.as_ref().err(),
         );
+        #[cfg(feature = "query-stats")]
+        Self::record_query_stats(
+            self.graph,
+            command,
+            &self.query_string,
+            &query,
+            started.elapsed(),
+            &result,
+        );
         result
     }
+
+    /// Records a finished `GRAPH.QUERY`/`GRAPH.RO_QUERY` call in the client's query statistics
+    /// registry, when one is enabled, explaining `query` on the primary for the first slow call of
+    /// each fingerprint. A failed `GRAPH.EXPLAIN` only leaves the plan out.
+    #[cfg(feature = "query-stats")]
+    fn record_query_stats(
+        graph: &SyncGraph,
+        command: &str,
+        query_template: &T,
+        query: &str,
+        elapsed: std::time::Duration,
+        result: &FalkorResult<redis::Value>,
+    ) {
+        let client = graph.get_client();
+        let Some(stats) = client.query_stats() else {
+            return;
+        };
+        if !matches!(command, "GRAPH.QUERY" | "GRAPH.RO_QUERY") {
+            return;
+        }
+        let Some(capture) =
+            stats.record(&query_template.to_string(), elapsed, result.as_ref().ok())
+        else {
+            return;
+        };
+        let plan = if capture.needs_plan() {
+            client
+                .borrow_connection(client.clone())
+                .and_then(|mut conn| {
+                    conn.execute_command(
+                        Some(graph.graph_name()),
+                        "GRAPH.EXPLAIN",
+                        None,
+                        Some(&[query]),
+                    )
+                })
+                .and_then(ExecutionPlan::parse)
+                .ok()
+                .map(|plan| plan.plan().to_vec())
+        } else {
+            None
+        };
+        stats.finish_slow(capture, plan);
+    }
 }
 
 #[cfg(feature = "tokio")]
@@ -411,6 +475,9 @@ impl<'a, Out, T: Display> QueryBuilder<'a, Out, T, AsyncGraph> {
                 db.query.fingerprint = tracing::field::Empty,
                 db.query.text = tracing::field::Empty,
                 error.type = tracing::field::Empty,
+                otel.kind = "client",
+                otel.name = tracing::field::Empty,
+                otel.status_code = tracing::field::Empty,
             ),
             level = "trace"
         )
@@ -418,6 +485,12 @@ impl<'a, Out, T: Display> QueryBuilder<'a, Out, T, AsyncGraph> {
     async fn common_execute_steps(&mut self) -> FalkorResult<redis::Value> {
         let query = construct_query(&self.query_string, &self.params)?;
 
+        #[cfg(feature = "opentelemetry")]
+        let query = crate::observability::with_trace_context(
+            query,
+            self.graph.get_client().trace_context(),
+        );
+
         let timeout = self.timeout.map(|timeout| timeout.to_string());
         let mut params = vec![query.as_str(), "--compact"];
         if let Some(timeout) = timeout.as_deref() {
@@ -433,6 +506,7 @@ impl<'a, Out, T: Display> QueryBuilder<'a, Out, T, AsyncGraph> {
         let strategy = crate::observability::strategy_label(&client.strategy());
         #[cfg(feature = "tracing")]
         crate::observability::record_request(
+            command,
             strategy,
             matches!(op_kind, OpKind::ReadOnly),
             &self.query_string.to_string(),
@@ -449,8 +523,8 @@ impl<'a, Out, T: Display> QueryBuilder<'a, Out, T, AsyncGraph> {
         let params_ref = params.as_slice();
         let policy = client.retry_policy();
 
-        #[cfg(feature = "metrics")]
-        let metrics_start = std::time::Instant::now();
+        #[cfg(any(feature = "metrics", feature = "query-stats"))]
+        let started = std::time::Instant::now();
         let result = run_with_retry_async(&policy, op_kind, || async move {
             let conn = if use_replica {
                 client.borrow_readonly_connection(client.clone()).await
@@ -472,24 +546,77 @@ impl<'a, Out, T: Display> QueryBuilder<'a, Out, T, AsyncGraph> {
             command,
             matches!(op_kind, OpKind::ReadOnly),
             strategy,
-            metrics_start.elapsed(),
+            started.elapsed(),
             result.as_ref().err(),
         );
+        #[cfg(feature = "query-stats")]
+        Self::record_query_stats(
+            self.graph,
+            command,
+            &self.query_string,
+            &query,
+            started.elapsed(),
+            &result,
+        )
+        .await;
         result
     }
 
-    /// Eagerly parses the reply into an owned [`RowStream`] under the schema write lock, so the
-    /// result outlives the borrow of the graph, is `Send + 'static`, and is decoupled from any
-    /// later change to the graph's schema.
+    /// Records a finished `GRAPH.QUERY`/`GRAPH.RO_QUERY` call in the client's query statistics
+    /// registry, when one is enabled, explaining `query` on the primary for the first slow call of
+    /// each fingerprint. A failed `GRAPH.EXPLAIN` only leaves the plan out. Takes the graph rather
+    /// than `self`, so the returned future stays `Send` for builders whose output is not `Sync`.
+    #[cfg(feature = "query-stats")]
+    async fn record_query_stats(
+        graph: &AsyncGraph,
+        command: &str,
+        query_template: &T,
+        query: &str,
+        elapsed: std::time::Duration,
+        result: &FalkorResult<redis::Value>,
+    ) {
+        let client = graph.get_client();
+        let Some(stats) = client.query_stats() else {
+            return;
+        };
+        if !matches!(command, "GRAPH.QUERY" | "GRAPH.RO_QUERY") {
+            return;
+        }
+        let Some(capture) =
+            stats.record(&query_template.to_string(), elapsed, result.as_ref().ok())
+        else {
+            return;
+        };
+        let plan = if capture.needs_plan() {
+            match client.borrow_connection(client.clone()).await {
+                Ok(mut conn) => conn
+                    .execute_command(
+                        Some(graph.graph_name()),
+                        "GRAPH.EXPLAIN",
+                        None,
+                        Some(&[query]),
+                    )
+                    .await
+                    .and_then(ExecutionPlan::parse)
+                    .ok()
+                    .map(|plan| plan.plan().to_vec()),
+                Err(_) => None,
+            }
+        } else {
+            None
+        };
+        stats.finish_slow(capture, plan);
+    }
+
+    /// Decodes the reply into an owned [`RowStream`], so the result outlives the borrow of the
+    /// graph and is `Send + 'static`.
     fn generate_async_result_set(
         self,
         value: redis::Value,
     ) -> FalkorResult<QueryResult<RowStream>> {
-        let schema = self.graph.schema_handle();
-        let mut guard = schema.write();
         dispatch_query_response(
             unwrap_query_response(value)?,
-            &mut *guard,
+            self.graph.schema_handle(),
             build_row_stream_result,
         )
     }
@@ -522,10 +649,109 @@ impl<'a, T: Display> QueryBuilder<'a, QueryResult<LazyResultSet<'a>>, T, SyncGra
     }
 }
 
+impl<'a, T: Display> QueryBuilder<'a, QueryResult<LazyResultSet<'a>>, T, SyncGraph> {
+    /// Run this query one page at a time, appending an ordered `SKIP $offset LIMIT $page_size` and
+    /// fetching each page lazily once the previous one has been consumed.
+    ///
+    /// Pages are always fetched with `GRAPH.RO_QUERY`, so a query that writes is rejected by the
+    /// server. The query's final `RETURN` must have an `ORDER BY` (otherwise rows could repeat or
+    /// go missing between pages) and must not already end in its own `SKIP`/`LIMIT`; both are
+    /// reported as [`FalkorDBError::InvalidPagination`] when the paginated query is executed,
+    /// along with any parameter encoding error.
+    ///
+    /// # Arguments
+    /// * `page_size`: the maximum number of rows fetched per page, at least 1
+    ///
+    /// # Returns
+    /// A [`PaginatedQueryBuilder`](crate::PaginatedQueryBuilder) whose `execute` yields a
+    /// [`PagedResultSet`](crate::PagedResultSet) iterating the rows of every page.
+    pub fn paginate(
+        self,
+        page_size: usize,
+    ) -> crate::PaginatedQueryBuilder<'a, SyncGraph> {
+        crate::PaginatedQueryBuilder::new(
+            self.graph,
+            &self.query_string.to_string(),
+            self.params,
+            self.timeout,
+            self.read_preference,
+            page_size,
+        )
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<'a, T: Display> QueryBuilder<'a, QueryResult<RowStream>, T, AsyncGraph> {
+    /// Run this query one page at a time, appending an ordered `SKIP $offset LIMIT $page_size` and
+    /// fetching each page lazily once the previous one has been consumed.
+    ///
+    /// Pages are always fetched with `GRAPH.RO_QUERY`, so a query that writes is rejected by the
+    /// server. The query's final `RETURN` must have an `ORDER BY` (otherwise rows could repeat or
+    /// go missing between pages) and must not already end in its own `SKIP`/`LIMIT`; both are
+    /// reported as [`FalkorDBError::InvalidPagination`] when the paginated query is executed,
+    /// along with any parameter encoding error.
+    ///
+    /// # Arguments
+    /// * `page_size`: the maximum number of rows fetched per page, at least 1
+    ///
+    /// # Returns
+    /// A [`PaginatedQueryBuilder`](crate::PaginatedQueryBuilder) whose `execute` yields a
+    /// [`RowStream`] that fetches the following pages transparently as it is polled.
+    pub fn paginate(
+        self,
+        page_size: usize,
+    ) -> crate::PaginatedQueryBuilder<'a, AsyncGraph> {
+        crate::PaginatedQueryBuilder::new(
+            self.graph,
+            &self.query_string.to_string(),
+            self.params,
+            self.timeout,
+            self.read_preference,
+            page_size,
+        )
+    }
+}
+
 #[cfg(feature = "tokio")]
 impl<'a, T: Display> QueryBuilder<'a, QueryResult<RowStream>, T, AsyncGraph> {
     /// Executes the query, returning a [`QueryResult`] whose `data` is an owned [`RowStream`] — a
     /// `Send + 'static` [`futures_core::Stream`] of `FalkorResult<Row>`.
+    ///
+    /// If the client opted in with a
+    /// [stream page size](crate::FalkorClientBuilder::with_stream_page_size), a read-only query
+    /// whose final `RETURN` is ordered is paged, so the stream holds at most one page at a time;
+    /// its statistics are then those of the first page. Otherwise the query is one reply.
+    pub async fn execute(self) -> FalkorResult<QueryResult<RowStream>> {
+        let page_size = self.graph.get_client().stream_page_size();
+        let query_string = self.query_string.to_string();
+        match page_size {
+            Some(page_size)
+                if self.command == "GRAPH.RO_QUERY"
+                    && crate::graph::paginate::is_transparently_pageable(
+                        &query_string,
+                        &self.params,
+                    ) =>
+            {
+                // Boxed so the paged path does not deepen the `Send` check of every caller's future.
+                let paged: std::pin::Pin<Box<dyn std::future::Future<Output = _> + Send + '_>> =
+                    Box::pin(
+                        crate::PaginatedQueryBuilder::new(
+                            self.graph,
+                            &query_string,
+                            self.params,
+                            self.timeout,
+                            self.read_preference,
+                            page_size.get(),
+                        )
+                        .execute_result(),
+                    );
+                paged.await
+            }
+            _ => self.execute_single().await,
+        }
+    }
+
+    /// Executes the query as a single reply, without transparent paging.
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(
@@ -538,7 +764,7 @@ impl<'a, T: Display> QueryBuilder<'a, QueryResult<RowStream>, T, AsyncGraph> {
             level = "info"
         )
     )]
-    pub async fn execute(mut self) -> FalkorResult<QueryResult<RowStream>> {
+    pub(crate) async fn execute_single(mut self) -> FalkorResult<QueryResult<RowStream>> {
         let result = self
             .common_execute_steps()
             .await
@@ -648,7 +874,8 @@ where
     U: serde::de::DeserializeOwned,
 {
     /// Executes the query, returning a [`QueryResult`] whose `data` is a [`TypedRowStream`] that
-    /// deserializes each row into `U`.
+    /// deserializes each row into `U`. Ordered read-only queries are paged as for the untyped
+    /// [`execute`](QueryBuilder::execute).
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(
@@ -661,12 +888,19 @@ where
             level = "info"
         )
     )]
-    pub async fn execute(mut self) -> FalkorResult<QueryResult<TypedRowStream<U>>> {
-        let result = self
-            .common_execute_steps()
-            .await
-            .and_then(|res| self.generate_async_result_set(res))
-            .map(|result| result.into_typed::<U>())?;
+    pub async fn execute(self) -> FalkorResult<QueryResult<TypedRowStream<U>>> {
+        let result = QueryBuilder::<QueryResult<RowStream>, T, AsyncGraph> {
+            _unused: PhantomData,
+            graph: self.graph,
+            command: self.command,
+            query_string: self.query_string,
+            params: self.params,
+            timeout: self.timeout,
+            read_preference: self.read_preference,
+        }
+        .execute()
+        .await
+        .map(|result| result.into_typed::<U>())?;
         #[cfg(feature = "tracing")]
         crate::observability::record_result(
             result.data.len(),
@@ -885,6 +1119,9 @@ impl<Out> ProcedureQueryBuilder<'_, Out, SyncGraph> {
                 db.query.fingerprint = tracing::field::Empty,
                 db.query.text = tracing::field::Empty,
                 error.type = tracing::field::Empty,
+                otel.kind = "client",
+                otel.name = tracing::field::Empty,
+                otel.status_code = tracing::field::Empty,
             ),
             level = "trace"
         )
@@ -902,6 +1139,7 @@ impl<Out> ProcedureQueryBuilder<'_, Out, SyncGraph> {
         let strategy = crate::observability::SYNC_STRATEGY;
         #[cfg(feature = "tracing")]
         crate::observability::record_request(
+            command,
             strategy,
             matches!(self.op_kind, OpKind::ReadOnly),
             &query_string,
@@ -910,6 +1148,12 @@ impl<Out> ProcedureQueryBuilder<'_, Out, SyncGraph> {
 
         let query = construct_query(query_string, &params)?;
 
+        #[cfg(feature = "opentelemetry")]
+        let query = crate::observability::with_trace_context(
+            query,
+            self.graph.get_client().trace_context(),
+        );
+
         let client = self.graph.get_client();
         let graph_name = self.graph.graph_name();
         let use_replica = resolve_use_replica(
@@ -967,6 +1211,9 @@ impl<'a, Out> ProcedureQueryBuilder<'a, Out, AsyncGraph> {
                 db.query.fingerprint = tracing::field::Empty,
                 db.query.text = tracing::field::Empty,
                 error.type = tracing::field::Empty,
+                otel.kind = "client",
+                otel.name = tracing::field::Empty,
+                otel.status_code = tracing::field::Empty,
             ),
             level = "trace"
         )
@@ -986,6 +1233,7 @@ impl<'a, Out> ProcedureQueryBuilder<'a, Out, AsyncGraph> {
         let strategy = crate::observability::strategy_label(&client.strategy());
         #[cfg(feature = "tracing")]
         crate::observability::record_request(
+            command,
             strategy,
             matches!(self.op_kind, OpKind::ReadOnly),
             &query_string,
@@ -994,6 +1242,12 @@ impl<'a, Out> ProcedureQueryBuilder<'a, Out, AsyncGraph> {
 
         let query = construct_query(query_string, &params)?;
 
+        #[cfg(feature = "opentelemetry")]
+        let query = crate::observability::with_trace_context(
+            query,
+            self.graph.get_client().trace_context(),
+        );
+
         let graph_name = self.graph.graph_name();
         let use_replica = resolve_use_replica(
             self.readonly,
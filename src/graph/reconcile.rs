@@ -0,0 +1,1018 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Declarative schema reconciliation: diff a [`DesiredSchema`] against `DB.INDEXES` and
+//! `DB.CONSTRAINTS`, then print or apply the resulting [`SchemaPlan`].
+
+use super::ops::{constraint_step, poll_sync, WaitOperation, WaitOptions};
+use super::{
+    generate_create_index_query, generate_drop_index_query, vector_index_options,
+    vector_index_settings, VectorSimilarity,
+};
+use crate::{
+    Constraint, ConstraintStatus, ConstraintType, EntityType, FalkorIndex, FalkorResult, IndexType,
+    SyncGraph,
+};
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+/// The indices and constraints a graph should have, for [`SyncGraph::reconcile_schema`].
+///
+/// Indices are compared property by property, so a composite index definition is satisfied by
+/// any indices covering the same properties. A unique constraint also declares the range index
+/// FalkorDB backs it with. By default, indices and constraints the definition does not list are
+/// dropped; see [`retain_unlisted`](Self::retain_unlisted).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DesiredSchema {
+    indices: Vec<IndexField>,
+    constraints: Vec<ConstraintSpec>,
+    retain_unlisted: bool,
+}
+
+/// One indexed property.
+#[derive(Clone, Debug, PartialEq)]
+struct IndexField {
+    index_type: IndexType,
+    entity_type: EntityType,
+    label: String,
+    property: String,
+    /// The `dimension` and similarity function of a vector index.
+    vector: Option<(u32, VectorSimilarity)>,
+}
+
+impl IndexField {
+    fn same_field(
+        &self,
+        other: &IndexField,
+    ) -> bool {
+        self.index_type == other.index_type
+            && self.entity_type == other.entity_type
+            && self.label == other.label
+            && self.property == other.property
+    }
+
+    fn create(&self) -> SchemaAction {
+        SchemaAction::CreateIndex {
+            index_type: self.index_type,
+            entity_type: self.entity_type,
+            label: self.label.clone(),
+            property: self.property.clone(),
+            options: self
+                .vector
+                .map(|(dimension, similarity)| vector_index_options(dimension, similarity)),
+        }
+    }
+
+    fn drop(&self) -> SchemaAction {
+        SchemaAction::DropIndex {
+            index_type: self.index_type,
+            entity_type: self.entity_type,
+            label: self.label.clone(),
+            property: self.property.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct ConstraintSpec {
+    constraint_type: ConstraintType,
+    entity_type: EntityType,
+    label: String,
+    properties: Vec<String>,
+}
+
+impl ConstraintSpec {
+    fn matches(
+        &self,
+        constraint: &Constraint,
+    ) -> bool {
+        let mut properties = constraint.properties.clone();
+        properties.sort();
+        self.constraint_type == constraint.constraint_type
+            && self.entity_type == constraint.entity_type
+            && self.label == constraint.label
+            && self.properties == properties
+    }
+
+    fn create(&self) -> SchemaAction {
+        SchemaAction::CreateConstraint {
+            constraint_type: self.constraint_type,
+            entity_type: self.entity_type,
+            label: self.label.clone(),
+            properties: self.properties.clone(),
+        }
+    }
+}
+
+impl DesiredSchema {
+    /// Creates an empty definition: reconciling it drops every index and constraint.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares a range index on each of `properties`.
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether the index is on nodes or relationships.
+    /// * `label`: The label or relationship type to index.
+    /// * `properties`: The properties to index.
+    pub fn range_index<P: Display>(
+        self,
+        entity_type: EntityType,
+        label: &str,
+        properties: &[P],
+    ) -> Self {
+        self.indices(IndexType::Range, entity_type, label, properties, None)
+    }
+
+    /// Declares a full-text index on each of `properties`.
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether the index is on nodes or relationships.
+    /// * `label`: The label or relationship type to index.
+    /// * `properties`: The properties to index.
+    pub fn fulltext_index<P: Display>(
+        self,
+        entity_type: EntityType,
+        label: &str,
+        properties: &[P],
+    ) -> Self {
+        self.indices(IndexType::Fulltext, entity_type, label, properties, None)
+    }
+
+    /// Declares a vector index on `property`. An existing vector index with another dimension or
+    /// similarity function is dropped and created again.
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether the index is on nodes or relationships.
+    /// * `label`: The label or relationship type to index.
+    /// * `property`: The property holding the vectors.
+    /// * `dimension`: The length of the vectors.
+    /// * `similarity_function`: How the index compares vectors.
+    pub fn vector_index(
+        self,
+        entity_type: EntityType,
+        label: &str,
+        property: &str,
+        dimension: u32,
+        similarity_function: VectorSimilarity,
+    ) -> Self {
+        self.indices(
+            IndexType::Vector,
+            entity_type,
+            label,
+            &[property],
+            Some((dimension, similarity_function)),
+        )
+    }
+
+    /// Declares a unique constraint over `properties`, along with its backing range index.
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether the constraint is on nodes or relationships.
+    /// * `label`: The label or relationship type to constrain.
+    /// * `properties`: The properties whose combined values must be unique.
+    pub fn unique_constraint<P: Display>(
+        self,
+        entity_type: EntityType,
+        label: &str,
+        properties: &[P],
+    ) -> Self {
+        self.range_index(entity_type, label, properties).constraint(
+            ConstraintType::Unique,
+            entity_type,
+            label,
+            properties,
+        )
+    }
+
+    /// Declares a mandatory constraint over `properties`.
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether the constraint is on nodes or relationships.
+    /// * `label`: The label or relationship type to constrain.
+    /// * `properties`: The properties every entity must have.
+    pub fn mandatory_constraint<P: Display>(
+        self,
+        entity_type: EntityType,
+        label: &str,
+        properties: &[P],
+    ) -> Self {
+        self.constraint(ConstraintType::Mandatory, entity_type, label, properties)
+    }
+
+    /// Leaves the indices and constraints this definition does not list in place, so reconciling
+    /// only ever creates (or replaces) what it declares.
+    pub fn retain_unlisted(mut self) -> Self {
+        self.retain_unlisted = true;
+        self
+    }
+
+    fn indices<P: Display>(
+        mut self,
+        index_type: IndexType,
+        entity_type: EntityType,
+        label: &str,
+        properties: &[P],
+        vector: Option<(u32, VectorSimilarity)>,
+    ) -> Self {
+        for property in properties {
+            let field = IndexField {
+                index_type,
+                entity_type,
+                label: label.to_string(),
+                property: property.to_string(),
+                vector,
+            };
+            // A later definition of the same field replaces the earlier one.
+            self.indices.retain(|existing| !existing.same_field(&field));
+            self.indices.push(field);
+        }
+        self
+    }
+
+    fn constraint<P: Display>(
+        mut self,
+        constraint_type: ConstraintType,
+        entity_type: EntityType,
+        label: &str,
+        properties: &[P],
+    ) -> Self {
+        let mut properties = super::ops::owned_properties(properties);
+        properties.sort();
+        properties.dedup();
+        let constraint = ConstraintSpec {
+            constraint_type,
+            entity_type,
+            label: label.to_string(),
+            properties,
+        };
+        if !self.constraints.contains(&constraint) {
+            self.constraints.push(constraint);
+        }
+        self
+    }
+
+    /// The actions that turn `indices` and `constraints` into this definition.
+    fn plan(
+        &self,
+        indices: &[FalkorIndex],
+        constraints: &[Constraint],
+    ) -> SchemaPlan {
+        let mut drop_constraints = Vec::new();
+        let mut create_constraints = Vec::new();
+        for constraint in constraints {
+            match self
+                .constraints
+                .iter()
+                .find(|spec| spec.matches(constraint))
+            {
+                // A failed constraint enforces nothing; create it again over the current data.
+                Some(spec) if constraint.status == ConstraintStatus::Failed => {
+                    drop_constraints.push(SchemaAction::drop_constraint(constraint));
+                    create_constraints.push(spec.create());
+                }
+                Some(_) => {}
+                None if self.retain_unlisted => {}
+                None => drop_constraints.push(SchemaAction::drop_constraint(constraint)),
+            }
+        }
+        for spec in &self.constraints {
+            if !constraints
+                .iter()
+                .any(|constraint| spec.matches(constraint))
+            {
+                create_constraints.push(spec.create());
+            }
+        }
+
+        let current = current_fields(indices);
+        let mut drop_indices = Vec::new();
+        let mut create_indices = Vec::new();
+        for field in &current {
+            match self.indices.iter().find(|wanted| wanted.same_field(field)) {
+                // Settings that cannot be read back are not compared.
+                Some(wanted) if field.vector.is_some() && wanted.vector != field.vector => {
+                    drop_indices.push(field.drop());
+                    create_indices.push(wanted.create());
+                }
+                Some(_) => {}
+                None if self.retain_unlisted => {}
+                None => drop_indices.push(field.drop()),
+            }
+        }
+        for wanted in &self.indices {
+            if !current.iter().any(|field| field.same_field(wanted)) {
+                create_indices.push(wanted.create());
+            }
+        }
+
+        // Constraints go before the indices backing them, and come back after them.
+        let mut actions = drop_constraints;
+        actions.extend(drop_indices);
+        actions.extend(create_indices);
+        actions.extend(create_constraints);
+        SchemaPlan { actions }
+    }
+}
+
+/// Every indexed property of `indices`, once per index type.
+fn current_fields(indices: &[FalkorIndex]) -> Vec<IndexField> {
+    let mut fields = Vec::new();
+    for index in indices {
+        for property in &index.fields {
+            for index_type in index.field_types.get(property).into_iter().flatten() {
+                fields.push(IndexField {
+                    index_type: *index_type,
+                    entity_type: index.entity_type,
+                    label: index.index_label.clone(),
+                    property: property.clone(),
+                    vector: match index_type {
+                        IndexType::Vector => vector_index_settings(index),
+                        _ => None,
+                    },
+                });
+            }
+        }
+    }
+    fields
+}
+
+/// One change of a [`SchemaPlan`].
+///
+/// Displays as the statement or command it runs: Cypher for indices, and the arguments of
+/// `GRAPH.CONSTRAINT` for constraints.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum SchemaAction {
+    /// Create an index on one property, through [`SyncGraph::create_index`].
+    CreateIndex {
+        /// The kind of index.
+        index_type: IndexType,
+        /// Whether the index is on nodes or relationships.
+        entity_type: EntityType,
+        /// The label or relationship type.
+        label: String,
+        /// The indexed property.
+        property: String,
+        /// The `OPTIONS` of a vector index.
+        options: Option<HashMap<String, String>>,
+    },
+    /// Drop the index on one property, through [`SyncGraph::drop_index`].
+    DropIndex {
+        /// The kind of index.
+        index_type: IndexType,
+        /// Whether the index is on nodes or relationships.
+        entity_type: EntityType,
+        /// The label or relationship type.
+        label: String,
+        /// The indexed property.
+        property: String,
+    },
+    /// Create a constraint with `GRAPH.CONSTRAINT CREATE`; its backing index is a separate action.
+    CreateConstraint {
+        /// The kind of constraint.
+        constraint_type: ConstraintType,
+        /// Whether the constraint is on nodes or relationships.
+        entity_type: EntityType,
+        /// The label or relationship type.
+        label: String,
+        /// The constrained properties.
+        properties: Vec<String>,
+    },
+    /// Drop a constraint, through [`SyncGraph::drop_constraint`].
+    DropConstraint {
+        /// The kind of constraint.
+        constraint_type: ConstraintType,
+        /// Whether the constraint is on nodes or relationships.
+        entity_type: EntityType,
+        /// The label or relationship type.
+        label: String,
+        /// The constrained properties.
+        properties: Vec<String>,
+    },
+}
+
+impl SchemaAction {
+    fn drop_constraint(constraint: &Constraint) -> Self {
+        SchemaAction::DropConstraint {
+            constraint_type: constraint.constraint_type,
+            entity_type: constraint.entity_type,
+            label: constraint.label.clone(),
+            properties: constraint.properties.clone(),
+        }
+    }
+
+    /// The `GRAPH.CONSTRAINT` subcommand and arguments of a constraint action.
+    fn constraint_command(&self) -> Option<(&'static str, Vec<String>)> {
+        let (subcommand, constraint_type, entity_type, label, properties) = match self {
+            SchemaAction::CreateConstraint {
+                constraint_type,
+                entity_type,
+                label,
+                properties,
+            } => ("CREATE", constraint_type, entity_type, label, properties),
+            SchemaAction::DropConstraint {
+                constraint_type,
+                entity_type,
+                label,
+                properties,
+            } => ("DROP", constraint_type, entity_type, label, properties),
+            _ => return None,
+        };
+        let mut args = vec![
+            constraint_type.to_string(),
+            entity_type.to_string(),
+            label.clone(),
+            "PROPERTIES".to_string(),
+            properties.len().to_string(),
+        ];
+        args.extend(properties.iter().cloned());
+        Some((subcommand, args))
+    }
+
+    /// Runs the action on `graph`, then waits for it with `wait`, if any.
+    fn apply(
+        &self,
+        graph: &mut SyncGraph,
+        wait: Option<&WaitOptions>,
+    ) -> FalkorResult<()> {
+        match self {
+            SchemaAction::CreateIndex {
+                index_type,
+                entity_type,
+                label,
+                property,
+                options,
+            } => {
+                let op = graph.create_index_op(
+                    *index_type,
+                    *entity_type,
+                    label,
+                    &[property],
+                    options.as_ref(),
+                );
+                match wait {
+                    Some(wait) => op.wait_with(wait.clone()),
+                    None => op.execute().map(drop),
+                }
+            }
+            SchemaAction::DropIndex {
+                index_type,
+                entity_type,
+                label,
+                property,
+            } => {
+                let op = graph.drop_index_op(*index_type, *entity_type, label, &[property]);
+                match wait {
+                    Some(wait) => op.wait_with(wait.clone()),
+                    None => op.execute().map(drop),
+                }
+            }
+            SchemaAction::CreateConstraint {
+                constraint_type,
+                entity_type,
+                label,
+                properties,
+            }
+            | SchemaAction::DropConstraint {
+                constraint_type,
+                entity_type,
+                label,
+                properties,
+            } => {
+                let Some((subcommand, args)) = self.constraint_command() else {
+                    unreachable!("constraint actions have a constraint command")
+                };
+                let args = super::ops::property_refs(&args);
+                graph.execute_command("GRAPH.CONSTRAINT", Some(subcommand), Some(&args))?;
+                let Some(wait) = wait else {
+                    return Ok(());
+                };
+                let drop = subcommand == "DROP";
+                let operation = if drop {
+                    WaitOperation::ConstraintDrop
+                } else {
+                    WaitOperation::ConstraintCreation
+                };
+                poll_sync(wait, operation, || {
+                    Ok(constraint_step(
+                        &graph.list_constraints()?.data,
+                        drop,
+                        *constraint_type,
+                        *entity_type,
+                        label,
+                        properties,
+                    ))
+                })
+            }
+        }
+    }
+
+    /// Runs the action on `graph`, then waits for it with `wait`, if any.
+    #[cfg(feature = "tokio")]
+    async fn apply_async(
+        &self,
+        graph: &mut crate::AsyncGraph,
+        wait: Option<&WaitOptions>,
+    ) -> FalkorResult<()> {
+        match self {
+            SchemaAction::CreateIndex {
+                index_type,
+                entity_type,
+                label,
+                property,
+                options,
+            } => {
+                let op = graph.create_index_op(
+                    *index_type,
+                    *entity_type,
+                    label,
+                    &[property],
+                    options.as_ref(),
+                );
+                match wait {
+                    Some(wait) => op.wait_with(wait.clone()).await,
+                    None => op.execute().await.map(drop),
+                }
+            }
+            SchemaAction::DropIndex {
+                index_type,
+                entity_type,
+                label,
+                property,
+            } => {
+                let op = graph.drop_index_op(*index_type, *entity_type, label, &[property]);
+                match wait {
+                    Some(wait) => op.wait_with(wait.clone()).await,
+                    None => op.execute().await.map(drop),
+                }
+            }
+            SchemaAction::CreateConstraint {
+                constraint_type,
+                entity_type,
+                label,
+                properties,
+            }
+            | SchemaAction::DropConstraint {
+                constraint_type,
+                entity_type,
+                label,
+                properties,
+            } => {
+                let Some((subcommand, args)) = self.constraint_command() else {
+                    unreachable!("constraint actions have a constraint command")
+                };
+                let args = super::ops::property_refs(&args);
+                graph
+                    .execute_command("GRAPH.CONSTRAINT", Some(subcommand), Some(&args))
+                    .await?;
+                let Some(wait) = wait else {
+                    return Ok(());
+                };
+                let drop = subcommand == "DROP";
+                let operation = if drop {
+                    WaitOperation::ConstraintDrop
+                } else {
+                    WaitOperation::ConstraintCreation
+                };
+                let mut state = &mut *graph;
+                super::ops::poll_async(&mut state, wait, operation, |graph| {
+                    Box::pin(async move {
+                        Ok(constraint_step(
+                            &graph.list_constraints().await?.data,
+                            drop,
+                            *constraint_type,
+                            *entity_type,
+                            label,
+                            properties,
+                        ))
+                    })
+                })
+                .await
+            }
+        }
+    }
+}
+
+impl Display for SchemaAction {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            SchemaAction::CreateIndex {
+                index_type,
+                entity_type,
+                label,
+                property,
+                options,
+            } => {
+                let query = generate_create_index_query(
+                    *index_type,
+                    *entity_type,
+                    label,
+                    &[property],
+                    options.as_ref(),
+                )
+                .map_err(|_| fmt::Error)?;
+                f.write_str(&query)
+            }
+            SchemaAction::DropIndex {
+                index_type,
+                entity_type,
+                label,
+                property,
+            } => f.write_str(&generate_drop_index_query(
+                *index_type,
+                *entity_type,
+                label,
+                &[property],
+            )),
+            SchemaAction::CreateConstraint { .. } | SchemaAction::DropConstraint { .. } => {
+                let Some((subcommand, args)) = self.constraint_command() else {
+                    return Err(fmt::Error);
+                };
+                write!(f, "GRAPH.CONSTRAINT {subcommand} {}", args.join(" "))
+            }
+        }
+    }
+}
+
+/// The ordered changes that reconcile a graph with a [`DesiredSchema`].
+///
+/// Constraints are dropped first, then indices; indices are created before the constraints they
+/// back. Displays one action per line.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct SchemaPlan {
+    /// The actions, in the order they run.
+    pub actions: Vec<SchemaAction>,
+}
+
+impl SchemaPlan {
+    /// `true` if the graph already matches the definition.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+impl Display for SchemaPlan {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        for action in &self.actions {
+            writeln!(f, "{action}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Builder reconciling a [`SyncGraph`] with a [`DesiredSchema`].
+///
+/// Created via [`SyncGraph::reconcile_schema`]. [`plan`](Self::plan) only reads the current
+/// schema; [`execute`](Self::execute) also applies the plan without waiting, and
+/// [`wait`](Self::wait) / [`wait_with`](Self::wait_with) wait for each action before the next,
+/// like the index and constraint op builders. An action that fails stops the run; the actions
+/// before it stay applied, and reconciling again plans only what is still missing.
+#[must_use = "a schema reconcile builder does nothing unless `.plan()`, `.execute()` or `.wait()` is called"]
+pub struct SchemaReconcileBuilder<'a> {
+    graph: &'a mut SyncGraph,
+    desired: &'a DesiredSchema,
+}
+
+impl SchemaReconcileBuilder<'_> {
+    /// Computes the plan without changing the graph.
+    pub fn plan(self) -> FalkorResult<SchemaPlan> {
+        let indices = self.graph.list_indices()?.data;
+        let constraints = self.graph.list_constraints()?.data;
+        Ok(self.desired.plan(&indices, &constraints))
+    }
+
+    /// Computes and applies the plan without waiting for background index or constraint work.
+    ///
+    /// # Returns
+    /// The plan that was applied.
+    pub fn execute(self) -> FalkorResult<SchemaPlan> {
+        self.apply(None)
+    }
+
+    /// Computes and applies the plan, waiting (with default [`WaitOptions`]) for each action.
+    ///
+    /// # Returns
+    /// The plan that was applied.
+    pub fn wait(self) -> FalkorResult<SchemaPlan> {
+        self.wait_with(WaitOptions::default())
+    }
+
+    /// Computes and applies the plan, waiting for each action until `options.timeout` elapses.
+    ///
+    /// # Returns
+    /// The plan that was applied.
+    pub fn wait_with(
+        self,
+        options: WaitOptions,
+    ) -> FalkorResult<SchemaPlan> {
+        self.apply(Some(&options))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Reconcile Graph Schema", skip_all, level = "info")
+    )]
+    fn apply(
+        self,
+        wait: Option<&WaitOptions>,
+    ) -> FalkorResult<SchemaPlan> {
+        let graph = self.graph;
+        let indices = graph.list_indices()?.data;
+        let constraints = graph.list_constraints()?.data;
+        let plan = self.desired.plan(&indices, &constraints);
+        for action in &plan.actions {
+            action.apply(graph, wait)?;
+        }
+        Ok(plan)
+    }
+}
+
+/// Builder reconciling an [`AsyncGraph`](crate::AsyncGraph) with a [`DesiredSchema`].
+///
+/// Created via [`AsyncGraph::reconcile_schema`](crate::AsyncGraph::reconcile_schema); the async
+/// counterpart of [`SchemaReconcileBuilder`].
+#[cfg(feature = "tokio")]
+#[must_use = "a schema reconcile builder does nothing unless `.plan()`, `.execute()` or `.wait()` is awaited"]
+pub struct AsyncSchemaReconcileBuilder<'a> {
+    graph: &'a mut crate::AsyncGraph,
+    desired: &'a DesiredSchema,
+}
+
+#[cfg(feature = "tokio")]
+impl AsyncSchemaReconcileBuilder<'_> {
+    /// Computes the plan without changing the graph.
+    pub async fn plan(self) -> FalkorResult<SchemaPlan> {
+        let indices = self.graph.list_indices().await?.data;
+        let constraints = self.graph.list_constraints().await?.data;
+        Ok(self.desired.plan(&indices, &constraints))
+    }
+
+    /// Computes and applies the plan without waiting for background index or constraint work.
+    ///
+    /// # Returns
+    /// The plan that was applied.
+    pub async fn execute(self) -> FalkorResult<SchemaPlan> {
+        self.apply(None).await
+    }
+
+    /// Computes and applies the plan, waiting (with default [`WaitOptions`]) for each action.
+    ///
+    /// # Returns
+    /// The plan that was applied.
+    pub async fn wait(self) -> FalkorResult<SchemaPlan> {
+        self.wait_with(WaitOptions::default()).await
+    }
+
+    /// Computes and applies the plan, waiting for each action until `options.timeout` elapses.
+    ///
+    /// # Returns
+    /// The plan that was applied.
+    pub async fn wait_with(
+        self,
+        options: WaitOptions,
+    ) -> FalkorResult<SchemaPlan> {
+        self.apply(Some(&options)).await
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Reconcile Graph Schema Async", skip_all, level = "info")
+    )]
+    async fn apply(
+        self,
+        wait: Option<&WaitOptions>,
+    ) -> FalkorResult<SchemaPlan> {
+        let graph = self.graph;
+        let indices = graph.list_indices().await?.data;
+        let constraints = graph.list_constraints().await?.data;
+        let plan = self.desired.plan(&indices, &constraints);
+        for action in &plan.actions {
+            action.apply_async(graph, wait).await?;
+        }
+        Ok(plan)
+    }
+}
+
+impl SyncGraph {
+    /// Returns a builder that makes this graph's indices and constraints match `desired`,
+    /// supporting `.plan()` (dry run), `.execute()` and `.wait()`. Reconciling a graph that
+    /// already matches does nothing, so it is safe to run on every deployment.
+    ///
+    /// # Arguments
+    /// * `desired`: The indices and constraints the graph should have.
+    pub fn reconcile_schema<'a>(
+        &'a mut self,
+        desired: &'a DesiredSchema,
+    ) -> SchemaReconcileBuilder<'a> {
+        SchemaReconcileBuilder {
+            graph: self,
+            desired,
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl crate::AsyncGraph {
+    /// Returns a builder that makes this graph's indices and constraints match `desired`; see
+    /// [`SyncGraph::reconcile_schema`].
+    ///
+    /// # Arguments
+    /// * `desired`: The indices and constraints the graph should have.
+    pub fn reconcile_schema<'a>(
+        &'a mut self,
+        desired: &'a DesiredSchema,
+    ) -> AsyncSchemaReconcileBuilder<'a> {
+        AsyncSchemaReconcileBuilder {
+            graph: self,
+            desired,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FalkorValue, IndexStatus};
+
+    fn index(
+        label: &str,
+        fields: &[(&str, &[IndexType])],
+        options: &[(&str, FalkorValue)],
+    ) -> FalkorIndex {
+        FalkorIndex {
+            entity_type: EntityType::Node,
+            status: IndexStatus::Active,
+            index_label: label.to_string(),
+            fields: fields.iter().map(|(name, _)| name.to_string()).collect(),
+            field_types: fields
+                .iter()
+                .map(|(name, types)| (name.to_string(), types.to_vec()))
+                .collect(),
+            language: "english".to_string(),
+            stopwords: Vec::new(),
+            info: HashMap::new(),
+            options: options
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+        }
+    }
+
+    fn constraint(
+        constraint_type: ConstraintType,
+        label: &str,
+        properties: &[&str],
+        status: ConstraintStatus,
+    ) -> Constraint {
+        Constraint {
+            constraint_type,
+            label: label.to_string(),
+            properties: properties.iter().map(|p| p.to_string()).collect(),
+            entity_type: EntityType::Node,
+            status,
+        }
+    }
+
+    fn lines(plan: &SchemaPlan) -> Vec<String> {
+        plan.to_string().lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_plan_for_an_empty_graph_creates_indices_before_constraints() {
+        let desired = DesiredSchema::new()
+            .unique_constraint(EntityType::Node, "Person", &["email"])
+            .mandatory_constraint(EntityType::Node, "Person", &["name"])
+            .fulltext_index(EntityType::Node, "Movie", &["title"])
+            .vector_index(
+                EntityType::Node,
+                "Movie",
+                "embedding",
+                3,
+                VectorSimilarity::Cosine,
+            );
+        assert_eq!(
+            lines(&desired.plan(&[], &[])),
+            [
+                "CREATE INDEX FOR (l:Person) ON (l.email)",
+                "CREATE FULLTEXT INDEX FOR (l:Movie) ON (l.title)",
+                "CREATE VECTOR INDEX FOR (l:Movie) ON (l.embedding) OPTIONS { dimension: 3, similarityFunction: 'cosine' }",
+                "GRAPH.CONSTRAINT CREATE UNIQUE NODE Person PROPERTIES 1 email",
+                "GRAPH.CONSTRAINT CREATE MANDATORY NODE Person PROPERTIES 1 name",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_drops_what_is_not_listed_unless_retained() {
+        let indices = [
+            index("Person", &[("email", &[IndexType::Range])], &[]),
+            index(
+                "Person",
+                &[
+                    ("bio", &[IndexType::Fulltext]),
+                    ("age", &[IndexType::Range]),
+                ],
+                &[],
+            ),
+        ];
+        let constraints = [
+            constraint(
+                ConstraintType::Unique,
+                "Person",
+                &["email"],
+                ConstraintStatus::Active,
+            ),
+            constraint(
+                ConstraintType::Mandatory,
+                "Person",
+                &["age"],
+                ConstraintStatus::Active,
+            ),
+        ];
+        let desired = DesiredSchema::new()
+            .unique_constraint(EntityType::Node, "Person", &["email"])
+            .range_index(EntityType::Node, "Person", &["age"]);
+        assert_eq!(
+            lines(&desired.plan(&indices, &constraints)),
+            [
+                "GRAPH.CONSTRAINT DROP MANDATORY NODE Person PROPERTIES 1 age",
+                "DROP FULLTEXT INDEX for (e:Person) ON (e.bio)",
+            ]
+        );
+        assert!(desired
+            .retain_unlisted()
+            .plan(&indices, &constraints)
+            .is_empty());
+        assert_eq!(
+            DesiredSchema::new().plan(&indices, &constraints).actions[0],
+            SchemaAction::DropConstraint {
+                constraint_type: ConstraintType::Unique,
+                entity_type: EntityType::Node,
+                label: "Person".to_string(),
+                properties: vec!["email".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_plan_replaces_changed_vector_indices_and_failed_constraints() {
+        let indices = [index(
+            "Doc",
+            &[("embedding", &[IndexType::Vector])],
+            &[
+                ("dimension", FalkorValue::I64(3)),
+                (
+                    "similarityFunction",
+                    FalkorValue::String("euclidean".to_string()),
+                ),
+            ],
+        )];
+        let constraints = [constraint(
+            ConstraintType::Mandatory,
+            "Doc",
+            &["id"],
+            ConstraintStatus::Failed,
+        )];
+        let same = DesiredSchema::new()
+            .vector_index(
+                EntityType::Node,
+                "Doc",
+                "embedding",
+                3,
+                VectorSimilarity::Euclidean,
+            )
+            .mandatory_constraint(EntityType::Node, "Doc", &["id"]);
+        assert_eq!(
+            lines(&same.plan(&indices, &constraints)),
+            [
+                "GRAPH.CONSTRAINT DROP MANDATORY NODE Doc PROPERTIES 1 id",
+                "GRAPH.CONSTRAINT CREATE MANDATORY NODE Doc PROPERTIES 1 id",
+            ]
+        );
+        let wider = DesiredSchema::new().vector_index(
+            EntityType::Node,
+            "Doc",
+            "embedding",
+            8,
+            VectorSimilarity::Euclidean,
+        );
+        assert_eq!(
+            lines(&wider.plan(&indices, &[])),
+            [
+                "DROP VECTOR INDEX for (e:Doc) ON (e.embedding)",
+                "CREATE VECTOR INDEX FOR (l:Doc) ON (l.embedding) OPTIONS { dimension: 8, similarityFunction: 'euclidean' }",
+            ]
+        );
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Query generation for the typed spatial helpers, such as
+//! [`SyncGraph::nodes_within_radius`](crate::SyncGraph::nodes_within_radius).
+
+use super::quote_identifier;
+use crate::FalkorResult;
+
+/// The parameter the search center is bound to, as a `{latitude, longitude}` map.
+pub(crate) const CENTER_PARAM: &str = "center";
+/// The parameter the search radius is bound to, in meters.
+pub(crate) const RADIUS_PARAM: &str = "radius";
+
+/// Builds the read-only query behind `nodes_within_radius`.
+///
+/// The label and property are quoted with [`quote_identifier`]; the center and radius are never
+/// interpolated and must be bound as [`CENTER_PARAM`] and [`RADIUS_PARAM`]. The filter stays in
+/// the `MATCH ... WHERE` so FalkorDB can serve it from a range index on the point property.
+pub(crate) fn generate_nodes_within_radius_query(
+    label: &str,
+    property: &str,
+) -> FalkorResult<String> {
+    let label = quote_identifier(label)?;
+    let property = quote_identifier(property)?;
+    let distance = format!("distance(n.{property}, point(${CENTER_PARAM}))");
+    Ok(format!(
+        "MATCH (n:{label}) WHERE {distance} <= ${RADIUS_PARAM} \
+         RETURN n AS node, {distance} AS distance ORDER BY distance"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FalkorDBError;
+
+    #[test]
+    fn test_generate_nodes_within_radius_query() {
+        assert_eq!(
+            generate_nodes_within_radius_query("Depot", "location").unwrap(),
+            "MATCH (n:Depot) WHERE distance(n.location, point($center)) <= $radius \
+             RETURN n AS node, distance(n.location, point($center)) AS distance ORDER BY distance"
+        );
+    }
+
+    #[test]
+    fn test_generate_nodes_within_radius_query_quotes_identifiers() {
+        let query = generate_nodes_within_radius_query("Pickup Point", "geo-pos").unwrap();
+        assert!(query.starts_with("MATCH (n:`Pickup Point`) WHERE distance(n.`geo-pos`,"));
+
+        let query = generate_nodes_within_radius_query("Match", "order").unwrap();
+        assert!(query.starts_with("MATCH (n:`Match`) WHERE distance(n.`order`,"));
+    }
+
+    #[test]
+    fn test_generate_nodes_within_radius_query_rejects_unquotable_identifiers() {
+        assert!(matches!(
+            generate_nodes_within_radius_query("Depot) DETACH DELETE n //`", "location"),
+            Err(FalkorDBError::InvalidIdentifier { .. })
+        ));
+        assert!(matches!(
+            generate_nodes_within_radius_query("Depot", ""),
+            Err(FalkorDBError::InvalidIdentifier { .. })
+        ));
+    }
+}
@@ -0,0 +1,379 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Optimistic, `WATCH`-based graph transactions: read through one pinned connection, queue writes,
+//! and commit them with `MULTI`/`EXEC` only if no other client modified the graph in between.
+
+use crate::connection::blocking::BorrowedSyncConnection;
+use crate::graph::batch::{prepare_atomic, weave_exec_reply};
+use crate::graph::query_builder::{build_vec_rows, dispatch_query_response, unwrap_query_response};
+use crate::graph::HasGraphSchema;
+use crate::retry::run_transaction_blocking;
+use crate::{
+    AbortReason, AtomicBatchOutcome, BatchItemResult, BatchQuery, FalkorDBError, FalkorResult,
+    GraphSchema, QueryResult, Row, SyncGraph,
+};
+
+/// What a committed transaction produced: the closure's return value, and one result per queued
+/// write, in the order they were queued.
+#[derive(Debug)]
+pub struct TransactionOutput<T> {
+    /// The value returned by the transaction closure.
+    pub value: T,
+    /// One [`BatchItemResult`] per queued write. As with an
+    /// [atomic batch](crate::AtomicBatchOutcome::Committed), a write that fails while running is
+    /// its slot's `Err` and does not undo the others.
+    pub results: Vec<BatchItemResult>,
+}
+
+/// Parses the reply to a transaction read into eagerly decoded rows.
+fn parse_read_reply(
+    reply: redis::Value,
+    graph_schema: &mut GraphSchema,
+) -> FalkorResult<QueryResult<Vec<Row>>> {
+    unwrap_query_response(reply)
+        .and_then(|res| dispatch_query_response(res, graph_schema, build_vec_rows))
+}
+
+/// Turns the `EXEC` outcome of a transaction into its output, surfacing a changed graph as
+/// [`FalkorDBError::TransactionConflict`] so the attempt can be retried.
+fn commit_outcome<T>(
+    value: T,
+    outcome: AtomicBatchOutcome,
+) -> FalkorResult<TransactionOutput<T>> {
+    match outcome {
+        AtomicBatchOutcome::Committed(results) => Ok(TransactionOutput { value, results }),
+        AtomicBatchOutcome::Aborted(AbortReason::WatchedGraphChanged) => {
+            Err(FalkorDBError::TransactionConflict)
+        }
+        AtomicBatchOutcome::Aborted(AbortReason::Rejected(message)) => {
+            Err(FalkorDBError::RedisError(message))
+        }
+    }
+}
+
+/// The handle passed to a [`SyncGraph::transaction`] closure.
+///
+/// Reads run immediately on the transaction's connection, which `WATCH`es the graph key. Writes
+/// are only queued; they are sent as one `MULTI`/`EXEC` after the closure returns `Ok`, and are
+/// discarded if the closure fails or another client modified the graph in the meantime.
+pub struct Transaction<'t> {
+    conn: &'t mut BorrowedSyncConnection,
+    graph_name: &'t str,
+    graph_schema: &'t mut GraphSchema,
+    writes: Vec<BatchQuery>,
+}
+
+impl Transaction<'_> {
+    /// Run a read now, on the watched connection. It is always sent as `GRAPH.RO_QUERY`, so a
+    /// write passed here is rejected by the server instead of escaping the transaction.
+    ///
+    /// # Arguments
+    /// * `query`: the query to run, e.g. `BatchQuery::read("...").with_param("id", 1)`.
+    ///
+    /// # Returns
+    /// The query's eagerly parsed rows.
+    pub fn read(
+        &mut self,
+        query: &BatchQuery,
+    ) -> FalkorResult<QueryResult<Vec<Row>>> {
+        let args = query.encoded_args()?;
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let reply = self.conn.execute_command(
+            Some(self.graph_name),
+            "GRAPH.RO_QUERY",
+            None,
+            Some(&args),
+        )?;
+        parse_read_reply(reply, self.graph_schema)
+    }
+
+    /// Queue a write (`GRAPH.QUERY`) to commit with the transaction, and return a handle to set
+    /// its params/timeout.
+    pub fn query(
+        &mut self,
+        query_string: impl Into<String>,
+    ) -> &mut BatchQuery {
+        self.writes.push(BatchQuery::write(query_string));
+        self.writes.last_mut().expect("a query was just pushed")
+    }
+
+    /// Queue an already-built [`BatchQuery`] to commit with the transaction.
+    pub fn push(
+        &mut self,
+        query: BatchQuery,
+    ) -> &mut Self {
+        self.writes.push(query);
+        self
+    }
+}
+
+impl SyncGraph {
+    /// Run a read-modify-write transaction with optimistic concurrency control.
+    ///
+    /// The graph key is `WATCH`ed on a connection pinned for the whole attempt, then `f` runs:
+    /// its [`read`](Transaction::read)s see the current graph, and its writes are queued. When `f`
+    /// returns `Ok`, the writes are sent as one `MULTI`/`EXEC`. If another client modified the
+    /// graph since the `WATCH`, nothing is applied and `f` runs again, up to the client's
+    /// [`RetryPolicy`](crate::RetryPolicy) attempt budget and backoff (a disabled policy allows a
+    /// single attempt); once the budget is spent the call fails with
+    /// [`FalkorDBError::TransactionConflict`]. If `f` returns `Err`, the transaction is abandoned
+    /// and the error returned as is.
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), falkordb::FalkorDBError> {
+    /// use falkordb::{BatchQuery, FalkorClientBuilder};
+    /// # let info: falkordb::FalkorConnectionInfo = "falkor://127.0.0.1:6379".try_into()?;
+    /// let client = FalkorClientBuilder::new().with_connection_info(info).build()?;
+    /// let mut graph = client.select_graph("docs");
+    ///
+    /// let output = graph.transaction(|tx| {
+    ///     let current = tx.read(&BatchQuery::read("MATCH (d:Doc {id: 1}) RETURN d.version AS v"))?;
+    ///     let version: i64 = current.data[0].try_get("v")?;
+    ///     tx.query("MATCH (d:Doc {id: 1}) SET d.version = $next")
+    ///         .with_param("next", version + 1);
+    ///     Ok(version + 1)
+    /// })?;
+    /// assert_eq!(output.results.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Arguments
+    /// * `f`: the transaction body; it may run more than once, so it should have no side effects
+    ///   besides the reads and writes it issues through the [`Transaction`].
+    ///
+    /// # Returns
+    /// The closure's value and the results of the committed writes.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Transaction", skip_all, level = "info")
+    )]
+    pub fn transaction<T, F>(
+        &mut self,
+        mut f: F,
+    ) -> FalkorResult<TransactionOutput<T>>
+    where
+        F: FnMut(&mut Transaction<'_>) -> FalkorResult<T>,
+    {
+        let client = self.get_client().clone();
+        let policy = client.retry_policy();
+        let graph_name = self.graph_name().to_string();
+        let graph_schema = self.get_graph_schema_mut();
+
+        run_transaction_blocking(&policy, || {
+            let mut conn = client.borrow_connection(client.clone())?;
+            conn.execute_command(Some(&graph_name), "WATCH", None, None)?;
+
+            let mut tx = Transaction {
+                conn: &mut conn,
+                graph_name: &graph_name,
+                graph_schema: &mut *graph_schema,
+                writes: Vec::new(),
+            };
+            let body = f(&mut tx);
+            let writes = std::mem::take(&mut tx.writes);
+            let prepared = body.and_then(|value| {
+                if writes.is_empty() {
+                    return Ok((value, None));
+                }
+                prepare_atomic(&graph_name, &writes).map(|pipe| (value, Some(pipe)))
+            });
+
+            match prepared {
+                Ok((value, Some(pipe))) => {
+                    // `EXEC` clears the `WATCH` whether or not the transaction commits.
+                    let replies = conn.execute_pipeline(&pipe)?;
+                    let outcome = weave_exec_reply(replies, writes.len(), graph_schema)?;
+                    commit_outcome(value, outcome)
+                }
+                prepared => {
+                    // Nothing to commit: release the `WATCH` before the connection is reused. A
+                    // failing body's own error takes precedence over a failed `UNWATCH`.
+                    let unwatched = conn.execute_command(None, "UNWATCH", None, None);
+                    let (value, _) = prepared?;
+                    unwatched?;
+                    Ok(TransactionOutput {
+                        value,
+                        results: Vec::new(),
+                    })
+                }
+            }
+        })
+    }
+}
+
+#[cfg(feature = "tokio")]
+pub use asynchronous::AsyncTransaction;
+
+#[cfg(feature = "tokio")]
+mod asynchronous {
+    use super::{commit_outcome, parse_read_reply, TransactionOutput};
+    use crate::connection::asynchronous::BorrowedAsyncConnection;
+    use crate::graph::batch::{prepare_atomic, weave_exec_reply};
+    use crate::retry::run_transaction_async;
+    use crate::{AsyncGraph, BatchQuery, FalkorResult, GraphSchema, QueryResult, Row};
+    use parking_lot::RwLock;
+    use std::sync::Arc;
+
+    /// The handle passed to an [`AsyncGraph::transaction`] closure. See [`Transaction`](crate::Transaction)
+    /// for the semantics; only [`read`](Self::read) is async.
+    pub struct AsyncTransaction<'t> {
+        conn: &'t mut BorrowedAsyncConnection,
+        graph_name: &'t str,
+        graph_schema: Arc<RwLock<GraphSchema>>,
+        writes: Vec<BatchQuery>,
+    }
+
+    impl AsyncTransaction<'_> {
+        /// Run a read now, on the watched connection, as `GRAPH.RO_QUERY`.
+        ///
+        /// # Arguments
+        /// * `query`: the query to run, e.g. `BatchQuery::read("...").with_param("id", 1)`.
+        ///
+        /// # Returns
+        /// The query's eagerly parsed rows.
+        pub async fn read(
+            &mut self,
+            query: &BatchQuery,
+        ) -> FalkorResult<QueryResult<Vec<Row>>> {
+            let args = query.encoded_args()?;
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            let reply = self
+                .conn
+                .execute_command(Some(self.graph_name), "GRAPH.RO_QUERY", None, Some(&args))
+                .await?;
+            parse_read_reply(reply, &mut self.graph_schema.write())
+        }
+
+        /// Queue a write (`GRAPH.QUERY`) to commit with the transaction, and return a handle to
+        /// set its params/timeout.
+        pub fn query(
+            &mut self,
+            query_string: impl Into<String>,
+        ) -> &mut BatchQuery {
+            self.writes.push(BatchQuery::write(query_string));
+            self.writes.last_mut().expect("a query was just pushed")
+        }
+
+        /// Queue an already-built [`BatchQuery`] to commit with the transaction.
+        pub fn push(
+            &mut self,
+            query: BatchQuery,
+        ) -> &mut Self {
+            self.writes.push(query);
+            self
+        }
+    }
+
+    impl AsyncGraph {
+        /// Run a read-modify-write transaction with optimistic concurrency control. This is the
+        /// async counterpart of [`SyncGraph::transaction`](crate::SyncGraph::transaction), with
+        /// the same `WATCH`/`MULTI`/`EXEC` semantics and retry budget.
+        ///
+        /// With the multiplexed [`ConnectionStrategy`](crate::ConnectionStrategy) each attempt
+        /// opens a dedicated connection, since `WATCH` state cannot live on a shared socket.
+        ///
+        /// # Arguments
+        /// * `f`: the transaction body; it may run more than once, so it should have no side
+        ///   effects besides the reads and writes it issues through the [`AsyncTransaction`].
+        ///
+        /// # Returns
+        /// The closure's value and the results of the committed writes.
+        #[cfg_attr(
+            feature = "tracing",
+            tracing::instrument(name = "Graph Transaction", skip_all, level = "info")
+        )]
+        pub async fn transaction<T, F>(
+            &mut self,
+            mut f: F,
+        ) -> FalkorResult<TransactionOutput<T>>
+        where
+            F: AsyncFnMut(&mut AsyncTransaction<'_>) -> FalkorResult<T>,
+        {
+            let client = self.get_client().clone();
+            let policy = client.retry_policy();
+            let graph_name = self.graph_name().to_string();
+            let graph_schema = self.schema_handle();
+
+            run_transaction_async(&policy, async || {
+                let graph_name = graph_name.as_str();
+                let mut conn = client.borrow_exclusive_connection(client.clone()).await?;
+                conn.execute_command(Some(graph_name), "WATCH", None, None)
+                    .await?;
+
+                let mut tx = AsyncTransaction {
+                    conn: &mut conn,
+                    graph_name,
+                    graph_schema: graph_schema.clone(),
+                    writes: Vec::new(),
+                };
+                let body = f(&mut tx).await;
+                let writes = std::mem::take(&mut tx.writes);
+                let prepared = body.and_then(|value| {
+                    if writes.is_empty() {
+                        return Ok((value, None));
+                    }
+                    prepare_atomic(graph_name, &writes).map(|pipe| (value, Some(pipe)))
+                });
+
+                match prepared {
+                    Ok((value, Some(pipe))) => {
+                        // `EXEC` clears the `WATCH` whether or not the transaction commits.
+                        let replies = conn.execute_pipeline(&pipe).await?;
+                        let outcome =
+                            weave_exec_reply(replies, writes.len(), &mut graph_schema.write())?;
+                        commit_outcome(value, outcome)
+                    }
+                    prepared => {
+                        // Nothing to commit: release the `WATCH` before the connection is
+                        // reused. A failing body's own error takes precedence.
+                        let unwatched = conn.execute_command(None, "UNWATCH", None, None).await;
+                        let (value, _) = prepared?;
+                        unwatched?;
+                        Ok(TransactionOutput {
+                            value,
+                            results: Vec::new(),
+                        })
+                    }
+                }
+            })
+            .await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_outcome_committed_carries_value_and_results() {
+        let output = commit_outcome("done", AtomicBatchOutcome::Committed(Vec::new()))
+            .expect("committed transaction");
+        assert_eq!(output.value, "done");
+        assert!(output.results.is_empty());
+    }
+
+    #[test]
+    fn test_commit_outcome_changed_graph_is_a_conflict() {
+        let result = commit_outcome(
+            (),
+            AtomicBatchOutcome::Aborted(AbortReason::WatchedGraphChanged),
+        );
+        assert!(matches!(result, Err(FalkorDBError::TransactionConflict)));
+    }
+
+    #[test]
+    fn test_commit_outcome_rejected_surfaces_server_message() {
+        let result = commit_outcome(
+            (),
+            AtomicBatchOutcome::Aborted(AbortReason::Rejected("unknown command".to_string())),
+        );
+        assert!(
+            matches!(result, Err(FalkorDBError::RedisError(message)) if message == "unknown command")
+        );
+    }
+}
@@ -0,0 +1,579 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Chunked `UNWIND` writes: group a large number of records into `$rows` list parameters and run
+//! them through pipelined [`BatchBuilder`](crate::BatchBuilder)s, aggregating the statistics.
+
+use crate::{
+    BatchItemResult, BatchQuery, BatchResult, FalkorDBError, FalkorResult, IntoFalkorParam,
+    QueryResult, Row, SyncGraph,
+};
+use std::num::NonZeroUsize;
+
+/// The parameter each chunk's records are bound to.
+const ROWS_PARAM: &str = "rows";
+const DEFAULT_CHUNKS_PER_BATCH: usize = 4;
+
+/// The statistics of every chunk an [`UnwindWriter`] has written, summed.
+///
+/// Each field is the sum of the matching [`QueryResult`] statistic (for example
+/// [`get_nodes_created`](QueryResult::get_nodes_created)) over the chunks that succeeded.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct UnwindReport {
+    /// The number of chunks that succeeded.
+    pub chunks: usize,
+    /// The number of records in the chunks that succeeded.
+    pub rows: usize,
+    /// The number of labels added.
+    pub labels_added: i64,
+    /// The number of labels removed.
+    pub labels_removed: i64,
+    /// The number of nodes created.
+    pub nodes_created: i64,
+    /// The number of nodes deleted.
+    pub nodes_deleted: i64,
+    /// The number of properties set.
+    pub properties_set: i64,
+    /// The number of properties removed.
+    pub properties_removed: i64,
+    /// The number of relationships created.
+    pub relationships_created: i64,
+    /// The number of relationships deleted.
+    pub relationships_deleted: i64,
+    /// The number of indices created.
+    pub indices_created: i64,
+    /// The number of indices deleted.
+    pub indices_deleted: i64,
+    /// The server-side execution time of the chunks, in milliseconds.
+    pub internal_execution_time_ms: f64,
+}
+
+impl UnwindReport {
+    fn add(
+        &mut self,
+        chunk: &ChunkInfo,
+        result: &QueryResult<Vec<Row>>,
+    ) {
+        self.chunks += 1;
+        self.rows += chunk.rows;
+        self.labels_added += result.get_labels_added().unwrap_or_default();
+        self.labels_removed += result.get_labels_removed().unwrap_or_default();
+        self.nodes_created += result.get_nodes_created().unwrap_or_default();
+        self.nodes_deleted += result.get_nodes_deleted().unwrap_or_default();
+        self.properties_set += result.get_properties_set().unwrap_or_default();
+        self.properties_removed += result.get_properties_removed().unwrap_or_default();
+        self.relationships_created += result.get_relationship_created().unwrap_or_default();
+        self.relationships_deleted += result.get_relationship_deleted().unwrap_or_default();
+        self.indices_created += result.get_indices_created().unwrap_or_default();
+        self.indices_deleted += result.get_indices_deleted().unwrap_or_default();
+        self.internal_execution_time_ms += result.get_internal_execution_time().unwrap_or_default();
+    }
+}
+
+/// Where a chunk sits in the writer's input.
+#[derive(Clone, Copy, Debug)]
+struct ChunkInfo {
+    index: usize,
+    first_row: usize,
+    rows: usize,
+}
+
+impl ChunkInfo {
+    fn failed(
+        self,
+        source: FalkorDBError,
+    ) -> FalkorDBError {
+        FalkorDBError::UnwindChunkFailed {
+            chunk: self.index,
+            first_row: self.first_row,
+            rows: self.rows,
+            source: Box::new(source),
+        }
+    }
+}
+
+/// The chunks of one pipelined round-trip.
+struct PendingBatch {
+    chunks: Vec<ChunkInfo>,
+    queries: Vec<BatchQuery>,
+}
+
+/// Cuts incoming records into chunks, and chunks into batches.
+struct Assembler<R> {
+    query: String,
+    timeout: Option<i64>,
+    chunk_size: usize,
+    chunks_per_batch: usize,
+    rows: Vec<R>,
+    batch: Vec<(ChunkInfo, BatchQuery)>,
+    next_chunk: usize,
+    next_row: usize,
+}
+
+impl<R: IntoFalkorParam> Assembler<R> {
+    /// Adds a record, returning a batch once it is full.
+    fn push(
+        &mut self,
+        record: R,
+    ) -> Option<PendingBatch> {
+        self.rows.push(record);
+        if self.rows.len() == self.chunk_size {
+            self.seal_chunk();
+        }
+        (self.batch.len() == self.chunks_per_batch).then(|| self.take_batch())
+    }
+
+    /// Returns whatever is left over once the input is exhausted.
+    fn finish(&mut self) -> Option<PendingBatch> {
+        if !self.rows.is_empty() {
+            self.seal_chunk();
+        }
+        (!self.batch.is_empty()).then(|| self.take_batch())
+    }
+
+    fn seal_chunk(&mut self) {
+        let rows = std::mem::take(&mut self.rows);
+        let chunk = ChunkInfo {
+            index: self.next_chunk,
+            first_row: self.next_row,
+            rows: rows.len(),
+        };
+        self.next_chunk += 1;
+        self.next_row += rows.len();
+
+        let mut query = BatchQuery::write(self.query.as_str());
+        query.with_param(ROWS_PARAM, rows);
+        if let Some(timeout) = self.timeout {
+            query.with_timeout(timeout);
+        }
+        self.batch.push((chunk, query));
+    }
+
+    fn take_batch(&mut self) -> PendingBatch {
+        let (chunks, queries) = std::mem::take(&mut self.batch).into_iter().unzip();
+        PendingBatch { chunks, queries }
+    }
+}
+
+/// Writes a stream of records in `UNWIND $rows AS row ...` chunks. Create one with
+/// [`SyncGraph::unwind_writer`] or [`AsyncGraph::unwind_writer`](crate::AsyncGraph::unwind_writer).
+///
+/// Records are grouped `chunk_size` at a time into a `$rows` list parameter, so each record is one
+/// `row` of the query. Chunks are sent [`with_chunks_per_batch`](Self::with_chunks_per_batch) at a
+/// time over one pipelined round-trip, and the statistics of every chunk are summed into an
+/// [`UnwindReport`].
+///
+/// A pipeline is not a transaction: when a chunk fails, the other chunks of its batch still run,
+/// no further batch is sent, and the write returns
+/// [`FalkorDBError::UnwindChunkFailed`](crate::FalkorDBError::UnwindChunkFailed) naming the chunk.
+/// [`report`](Self::report) then describes the chunks that were applied.
+///
+/// ```no_run
+/// # fn main() -> Result<(), falkordb::FalkorDBError> {
+/// use falkordb::FalkorClientBuilder;
+/// use std::collections::BTreeMap;
+/// use std::num::NonZeroUsize;
+/// # let info: falkordb::FalkorConnectionInfo = "falkor://127.0.0.1:6379".try_into()?;
+/// let client = FalkorClientBuilder::new().with_connection_info(info).build()?;
+/// let mut graph = client.select_graph("social");
+///
+/// let people = (0..10_000).map(|id| BTreeMap::from([("id", id)]));
+/// let report = graph
+///     .unwind_writer("MERGE (:Person {id: row.id})", NonZeroUsize::new(1_000).unwrap())
+///     .write_all(people)?;
+/// assert_eq!(report.rows, 10_000);
+/// # Ok(())
+/// # }
+/// ```
+pub struct UnwindWriter<'a, G> {
+    graph: &'a mut G,
+    query: String,
+    chunk_size: usize,
+    chunks_per_batch: usize,
+    #[cfg_attr(not(feature = "tokio"), allow(dead_code))]
+    concurrency: usize,
+    timeout: Option<i64>,
+    report: UnwindReport,
+    next_chunk: usize,
+    next_row: usize,
+}
+
+impl<'a, G> UnwindWriter<'a, G> {
+    pub(crate) fn new(
+        graph: &'a mut G,
+        query_template: &str,
+        chunk_size: NonZeroUsize,
+    ) -> Self {
+        Self {
+            graph,
+            query: format!("UNWIND ${ROWS_PARAM} AS row {query_template}"),
+            chunk_size: chunk_size.get(),
+            chunks_per_batch: DEFAULT_CHUNKS_PER_BATCH,
+            concurrency: 1,
+            timeout: None,
+            report: UnwindReport::default(),
+            next_chunk: 0,
+            next_row: 0,
+        }
+    }
+
+    /// Sets how many chunks are pipelined in one round-trip (4 by default).
+    ///
+    /// # Arguments
+    /// * `chunks_per_batch`: The number of chunks per pipeline.
+    pub fn with_chunks_per_batch(
+        mut self,
+        chunks_per_batch: NonZeroUsize,
+    ) -> Self {
+        self.chunks_per_batch = chunks_per_batch.get();
+        self
+    }
+
+    /// Sets a server-side timeout, in milliseconds, for each chunk's query.
+    ///
+    /// # Arguments
+    /// * `timeout`: The timeout of one chunk, in milliseconds.
+    pub fn with_timeout(
+        mut self,
+        timeout: i64,
+    ) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The statistics of every chunk written so far, including by earlier `write_*` calls.
+    pub fn report(&self) -> &UnwindReport {
+        &self.report
+    }
+
+    fn assembler<R>(&self) -> Assembler<R> {
+        Assembler {
+            query: self.query.clone(),
+            timeout: self.timeout,
+            chunk_size: self.chunk_size,
+            chunks_per_batch: self.chunks_per_batch,
+            rows: Vec::with_capacity(self.chunk_size),
+            batch: Vec::new(),
+            next_chunk: self.next_chunk,
+            next_row: self.next_row,
+        }
+    }
+
+    /// Continues the chunk numbering of `assembler` in later writes.
+    fn resume_after<R>(
+        &mut self,
+        assembler: &Assembler<R>,
+    ) {
+        self.next_chunk = assembler.next_chunk;
+        self.next_row = assembler.next_row;
+    }
+
+    /// Adds the chunks that succeeded to the report, returning the earliest failed chunk.
+    fn record(
+        &mut self,
+        chunks: &[ChunkInfo],
+        result: BatchResult,
+    ) -> Option<(usize, FalkorDBError)> {
+        let items: Vec<BatchItemResult> = match result {
+            Ok(items) => items,
+            // The pipeline broke down as a whole: attribute it to its first chunk.
+            Err(err) => return Some((chunks[0].index, chunks[0].failed(err))),
+        };
+        let mut failure = None;
+        for (chunk, item) in chunks.iter().zip(items) {
+            match item {
+                Ok(result) => self.report.add(chunk, &result),
+                Err(err) if failure.is_none() => failure = Some((chunk.index, chunk.failed(err))),
+                Err(_) => {}
+            }
+        }
+        failure
+    }
+}
+
+impl UnwindWriter<'_, SyncGraph> {
+    /// Writes every record, one pipelined batch at a time.
+    ///
+    /// # Arguments
+    /// * `records`: The records; each becomes one `row` and must implement [`IntoFalkorParam`],
+    ///   typically a map or a struct deriving `IntoFalkorParam`.
+    ///
+    /// # Returns
+    /// The [`UnwindReport`] of everything this writer has written, or the first failed chunk.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Unwind Write", skip_all, level = "info")
+    )]
+    pub fn write_all<R: IntoFalkorParam>(
+        &mut self,
+        records: impl IntoIterator<Item = R>,
+    ) -> FalkorResult<UnwindReport> {
+        let mut assembler = self.assembler();
+        // Flushing the last batch polls the iterator again after its first `None`.
+        let mut records = records.into_iter().fuse();
+        let result = loop {
+            let batch = match records.next() {
+                Some(record) => match assembler.push(record) {
+                    Some(batch) => batch,
+                    None => continue,
+                },
+                None => match assembler.finish() {
+                    Some(batch) => batch,
+                    None => break Ok(self.report.clone()),
+                },
+            };
+            let mut builder = self.graph.batch();
+            for query in batch.queries {
+                builder.push(query);
+            }
+            let result = builder.execute();
+            if let Some((_, err)) = self.record(&batch.chunks, result) {
+                break Err(err);
+            }
+        };
+        self.resume_after(&assembler);
+        result
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl UnwindWriter<'_, crate::AsyncGraph> {
+    /// Sets how many pipelined batches may be in flight at once (1 by default). Each runs on its
+    /// own clone of the graph handle, so with a pooled or multiplexed client they overlap.
+    ///
+    /// # Arguments
+    /// * `concurrency`: The number of concurrent batches.
+    pub fn with_concurrency(
+        mut self,
+        concurrency: NonZeroUsize,
+    ) -> Self {
+        self.concurrency = concurrency.get();
+        self
+    }
+
+    /// Writes every record, keeping up to [`with_concurrency`](Self::with_concurrency) batches in
+    /// flight.
+    ///
+    /// # Arguments
+    /// * `records`: The records; each becomes one `row` and must implement [`IntoFalkorParam`].
+    ///
+    /// # Returns
+    /// The [`UnwindReport`] of everything this writer has written, or the first failed chunk.
+    pub async fn write_all<R: IntoFalkorParam>(
+        &mut self,
+        records: impl IntoIterator<Item = R>,
+    ) -> FalkorResult<UnwindReport> {
+        self.write_stream(AsStream(records.into_iter())).await
+    }
+
+    /// Writes every record of an async stream, keeping up to
+    /// [`with_concurrency`](Self::with_concurrency) batches in flight.
+    ///
+    /// # Arguments
+    /// * `records`: A [`Stream`](futures_core::Stream) of records; each becomes one `row`.
+    ///
+    /// # Returns
+    /// The [`UnwindReport`] of everything this writer has written, or the first failed chunk.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Unwind Write", skip_all, level = "info")
+    )]
+    pub async fn write_stream<R: IntoFalkorParam>(
+        &mut self,
+        records: impl futures_core::Stream<Item = R>,
+    ) -> FalkorResult<UnwindReport> {
+        let mut records = std::pin::pin!(records);
+        let mut assembler = self.assembler();
+        let mut in_flight = tokio::task::JoinSet::new();
+        let mut failure: Option<(usize, FalkorDBError)> = None;
+        let mut exhausted = false;
+
+        loop {
+            // Fill the free slots, unless a chunk already failed.
+            while failure.is_none() && !exhausted && in_flight.len() < self.concurrency {
+                let record = std::future::poll_fn(|cx| records.as_mut().poll_next(cx)).await;
+                let batch = match record {
+                    Some(record) => match assembler.push(record) {
+                        Some(batch) => batch,
+                        None => continue,
+                    },
+                    None => {
+                        exhausted = true;
+                        match assembler.finish() {
+                            Some(batch) => batch,
+                            None => break,
+                        }
+                    }
+                };
+                let mut graph = self.graph.clone();
+                in_flight.spawn(async move {
+                    let mut builder = graph.batch();
+                    for query in batch.queries {
+                        builder.push(query);
+                    }
+                    (batch.chunks, builder.execute().await)
+                });
+            }
+
+            let Some(joined) = in_flight.join_next().await else {
+                break;
+            };
+            let (chunks, result) =
+                joined.unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()));
+            if let Some((index, err)) = self.record(&chunks, result) {
+                if failure.as_ref().is_none_or(|(first, _)| index < *first) {
+                    failure = Some((index, err));
+                }
+            }
+        }
+
+        self.resume_after(&assembler);
+        match failure {
+            Some((_, err)) => Err(err),
+            None => Ok(self.report.clone()),
+        }
+    }
+}
+
+/// Adapts an iterator into a [`Stream`](futures_core::Stream) that is always ready.
+#[cfg(feature = "tokio")]
+struct AsStream<I>(I);
+
+// The iterator is never pinned in place.
+#[cfg(feature = "tokio")]
+impl<I> Unpin for AsStream<I> {}
+
+#[cfg(feature = "tokio")]
+impl<I: Iterator> futures_core::Stream for AsStream<I> {
+    type Item = I::Item;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        std::task::Poll::Ready(self.0.next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::blocking::create_empty_inner_sync_client;
+
+    fn assembler(
+        chunk_size: usize,
+        chunks_per_batch: usize,
+    ) -> Assembler<i64> {
+        Assembler {
+            query: "UNWIND $rows AS row CREATE (:N {v: row})".to_string(),
+            timeout: None,
+            chunk_size,
+            chunks_per_batch,
+            rows: Vec::new(),
+            batch: Vec::new(),
+            next_chunk: 0,
+            next_row: 0,
+        }
+    }
+
+    fn stats(
+        nodes: i64,
+        properties: i64,
+    ) -> BatchItemResult {
+        Ok(QueryResult {
+            header: Vec::<String>::new().into(),
+            data: Vec::new(),
+            stats: vec![
+                format!("Nodes created: {nodes}"),
+                format!("Properties set: {properties}"),
+            ],
+        })
+    }
+
+    #[test]
+    fn test_assembler_chunks_and_batches() {
+        let mut assembler = assembler(2, 2);
+        let mut batches = (0..7).filter_map(|v| assembler.push(v)).collect::<Vec<_>>();
+        batches.extend(assembler.finish());
+        assert_eq!(batches.len(), 2);
+        let rows = batches
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .chunks
+                    .iter()
+                    .map(|chunk| (chunk.first_row, chunk.rows))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(rows, [(0, 2), (2, 2), (4, 2), (6, 1)]);
+        assert_eq!(batches[1].chunks[1].index, 3);
+        assert_eq!(
+            batches[0].queries[1].encoded_args().unwrap()[0],
+            "CYPHER rows=[2, 3] UNWIND $rows AS row CREATE (:N {v: row})"
+        );
+        assert!(assembler.finish().is_none());
+    }
+
+    #[test]
+    fn test_record_sums_statistics_and_reports_the_failed_chunk() {
+        let mut graph = SyncGraph::new(create_empty_inner_sync_client(), "unwind");
+        let mut writer = UnwindWriter::new(
+            &mut graph,
+            "CREATE (:N {v: row})",
+            NonZeroUsize::new(10).unwrap(),
+        );
+        let chunks = [0, 1, 2].map(|index| ChunkInfo {
+            index,
+            first_row: index * 10,
+            rows: 10,
+        });
+        let failure = writer.record(
+            &chunks,
+            Ok(vec![
+                stats(10, 20),
+                Err(FalkorDBError::ParsingError("boom".to_string())),
+                stats(10, 5),
+            ]),
+        );
+        assert_eq!(writer.report().chunks, 2);
+        assert_eq!(writer.report().rows, 20);
+        assert_eq!(writer.report().nodes_created, 20);
+        assert_eq!(writer.report().properties_set, 25);
+        let (index, err) = failure.expect("chunk 1 failed");
+        assert_eq!(index, 1);
+        assert!(matches!(
+            err,
+            FalkorDBError::UnwindChunkFailed {
+                chunk: 1,
+                first_row: 10,
+                rows: 10,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_record_attributes_a_broken_pipeline_to_its_first_chunk() {
+        let mut graph = SyncGraph::new(create_empty_inner_sync_client(), "unwind");
+        let mut writer = UnwindWriter::new(
+            &mut graph,
+            "CREATE (:N {v: row})",
+            NonZeroUsize::new(10).unwrap(),
+        );
+        let chunks = [4, 5].map(|index| ChunkInfo {
+            index,
+            first_row: index * 10,
+            rows: 10,
+        });
+        let failure = writer.record(&chunks, Err(FalkorDBError::ConnectionDown));
+        assert!(matches!(
+            failure,
+            Some((4, FalkorDBError::UnwindChunkFailed { ref source, .. }))
+                if **source == FalkorDBError::ConnectionDown
+        ));
+        assert_eq!(writer.report(), &UnwindReport::default());
+    }
+}
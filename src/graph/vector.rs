@@ -0,0 +1,495 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Typed vector similarity search over the vector indices created by
+//! [`SyncGraph::create_node_vector_index`] and
+//! [`SyncGraph::create_edge_vector_index`], for [`SyncGraph::vector_search`].
+
+use super::vector_index_settings;
+use crate::{
+    Edge, EntityType, FalkorDBError, FalkorIndex, FalkorParams, FalkorResult, FromFalkorValue,
+    IndexType, IntoFalkorParam, IntoFalkorParams, Node, Row, SyncGraph,
+};
+use std::marker::PhantomData;
+
+/// The parameter the searched label or relationship type is bound to.
+const LABEL_PARAM: &str = "label";
+/// The parameter the searched property is bound to.
+const PROPERTY_PARAM: &str = "property";
+/// The parameter the number of neighbours is bound to.
+const K_PARAM: &str = "k";
+/// The parameter the query vector is bound to, as a float list wrapped in `vecf32`.
+const VECTOR_PARAM: &str = "vector";
+
+/// The column the matched entity is returned in, whatever the procedure yields it as.
+const ENTITY_COLUMN: &str = "entity";
+/// The column the score is returned in.
+const SCORE_COLUMN: &str = "score";
+
+/// The shared state of [`VectorSearchBuilder`] and [`AsyncVectorSearchBuilder`].
+struct VectorSearch {
+    entity_type: EntityType,
+    label: String,
+    property: String,
+    vector: Vec<f32>,
+    k: u32,
+    filter: Option<String>,
+    params: FalkorParams,
+}
+
+impl VectorSearch {
+    fn new(
+        entity_type: EntityType,
+        label: &str,
+        property: &str,
+        vector: &[f32],
+        k: u32,
+    ) -> Self {
+        Self {
+            entity_type,
+            label: label.to_string(),
+            property: property.to_string(),
+            vector: vector.to_vec(),
+            k,
+            filter: None,
+            params: FalkorParams::new(),
+        }
+    }
+
+    /// The variable the procedure yields the matched entity as, which the filter refers to.
+    fn variable(&self) -> &'static str {
+        match self.entity_type {
+            EntityType::Node => "node",
+            EntityType::Edge => "relationship",
+        }
+    }
+
+    /// Builds the read-only query; the label, property, `k` and vector are always parameters.
+    fn query(&self) -> String {
+        let procedure = match self.entity_type {
+            EntityType::Node => "queryNodes",
+            EntityType::Edge => "queryRelationships",
+        };
+        let variable = self.variable();
+        let filter = self
+            .filter
+            .as_ref()
+            .map(|filter| format!(" WHERE {filter}"))
+            .unwrap_or_default();
+        format!(
+            "CALL db.idx.vector.{procedure}(${LABEL_PARAM}, ${PROPERTY_PARAM}, ${K_PARAM}, \
+             vecf32(${VECTOR_PARAM})) YIELD {variable}, score{filter} \
+             RETURN {variable} AS {ENTITY_COLUMN}, score AS {SCORE_COLUMN} ORDER BY {SCORE_COLUMN}"
+        )
+    }
+
+    /// The caller's filter parameters, with the search's own parameters bound last so they cannot
+    /// be overridden.
+    fn params(self) -> FalkorParams {
+        let mut params = self.params;
+        params.add_param(LABEL_PARAM, self.label);
+        params.add_param(PROPERTY_PARAM, self.property);
+        params.add_param(K_PARAM, self.k);
+        params.add_param(VECTOR_PARAM, self.vector);
+        params
+    }
+
+    /// Checks the query vector against the dimension `DB.INDEXES` reports for the searched index.
+    ///
+    /// An index whose settings this client cannot read is searched without the check, leaving the
+    /// server to reject a mismatch.
+    fn validate(
+        &self,
+        indices: &[FalkorIndex],
+    ) -> FalkorResult<()> {
+        let index = indices
+            .iter()
+            .find(|index| {
+                index.entity_type == self.entity_type
+                    && index.index_label == self.label
+                    && index
+                        .field_types
+                        .get(&self.property)
+                        .is_some_and(|types| types.contains(&IndexType::Vector))
+            })
+            .ok_or_else(|| FalkorDBError::VectorIndexNotFound {
+                label: self.label.clone(),
+                property: self.property.clone(),
+            })?;
+        match vector_index_settings(index) {
+            Some((dimension, _)) if dimension as usize != self.vector.len() => {
+                Err(FalkorDBError::VectorDimensionMismatch {
+                    label: self.label.clone(),
+                    property: self.property.clone(),
+                    expected: dimension,
+                    actual: self.vector.len(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn parse_hit<T: FromFalkorValue>(row: Row) -> FalkorResult<(T, f64)> {
+    Ok((row.try_get(ENTITY_COLUMN)?, row.try_get(SCORE_COLUMN)?))
+}
+
+/// Builder for a vector similarity search on a [`SyncGraph`].
+///
+/// Created via [`SyncGraph::vector_search`] for nodes or [`SyncGraph::edge_vector_search`] for
+/// relationships. Executing it first reads the graph's indices to check that a vector index covers
+/// the label and property and that the query vector has its dimension, then returns the `k` nearest
+/// entities with their scores, nearest first.
+#[must_use = "a vector search builder does nothing unless `.execute()` is called"]
+pub struct VectorSearchBuilder<'a, T> {
+    graph: &'a mut SyncGraph,
+    search: VectorSearch,
+    entity: PhantomData<T>,
+}
+
+impl<T: FromFalkorValue> VectorSearchBuilder<'_, T> {
+    /// Keeps only the neighbours matching a Cypher predicate, applied after the `k` nearest are
+    /// found, so fewer than `k` may be returned.
+    ///
+    /// The predicate refers to the matched entity as `node` (or `relationship` for an edge search)
+    /// and to its distance as `score`, e.g. `node.in_stock AND node.price < $max_price`.
+    ///
+    /// # Arguments
+    /// * `filter`: A Cypher boolean expression, placed in a `WHERE` clause.
+    pub fn with_filter(
+        mut self,
+        filter: impl Into<String>,
+    ) -> Self {
+        self.search.filter = Some(filter.into());
+        self
+    }
+
+    /// Binds a parameter for the filter, referenced as `$key` (escaped for you). The `label`,
+    /// `property`, `k` and `vector` names are taken by the search itself.
+    ///
+    /// # Arguments
+    /// * `key`: The parameter name.
+    /// * `value`: The parameter value.
+    pub fn with_param<V: IntoFalkorParam>(
+        mut self,
+        key: &str,
+        value: V,
+    ) -> Self {
+        self.search.params.add_param(key, value);
+        self
+    }
+
+    /// Binds several parameters for the filter at once. See [`IntoFalkorParams`].
+    ///
+    /// # Arguments
+    /// * `params`: The parameters to bind.
+    pub fn with_params<P: IntoFalkorParams>(
+        mut self,
+        params: P,
+    ) -> Self {
+        self.search.params.merge(params.into_falkor_params());
+        self
+    }
+
+    /// Validates the query vector against the index and runs the search read-only.
+    ///
+    /// # Returns
+    /// The matched entities with their scores, nearest first, or
+    /// [`FalkorDBError::VectorIndexNotFound`] / [`FalkorDBError::VectorDimensionMismatch`] if the
+    /// search does not fit the graph's vector indices.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Vector Search", skip_all, level = "info")
+    )]
+    pub fn execute(self) -> FalkorResult<Vec<(T, f64)>> {
+        let graph = self.graph;
+        self.search.validate(&graph.list_indices()?.data)?;
+        let query = self.search.query();
+        graph
+            .ro_query(&query)
+            .with_params(self.search.params())
+            .execute()?
+            .data
+            .map(|row| parse_hit(row?))
+            .collect()
+    }
+}
+
+/// Builder for a vector similarity search on an [`AsyncGraph`](crate::AsyncGraph).
+///
+/// Created via [`AsyncGraph::vector_search`](crate::AsyncGraph::vector_search) or
+/// [`AsyncGraph::edge_vector_search`](crate::AsyncGraph::edge_vector_search); the async
+/// counterpart of [`VectorSearchBuilder`].
+#[cfg(feature = "tokio")]
+#[must_use = "a vector search builder does nothing unless `.execute()` is awaited"]
+pub struct AsyncVectorSearchBuilder<'a, T> {
+    graph: &'a mut crate::AsyncGraph,
+    search: VectorSearch,
+    entity: PhantomData<T>,
+}
+
+#[cfg(feature = "tokio")]
+impl<T: FromFalkorValue> AsyncVectorSearchBuilder<'_, T> {
+    /// Keeps only the neighbours matching a Cypher predicate; see
+    /// [`VectorSearchBuilder::with_filter`].
+    ///
+    /// # Arguments
+    /// * `filter`: A Cypher boolean expression, placed in a `WHERE` clause.
+    pub fn with_filter(
+        mut self,
+        filter: impl Into<String>,
+    ) -> Self {
+        self.search.filter = Some(filter.into());
+        self
+    }
+
+    /// Binds a parameter for the filter; see [`VectorSearchBuilder::with_param`].
+    ///
+    /// # Arguments
+    /// * `key`: The parameter name.
+    /// * `value`: The parameter value.
+    pub fn with_param<V: IntoFalkorParam>(
+        mut self,
+        key: &str,
+        value: V,
+    ) -> Self {
+        self.search.params.add_param(key, value);
+        self
+    }
+
+    /// Binds several parameters for the filter at once. See [`IntoFalkorParams`].
+    ///
+    /// # Arguments
+    /// * `params`: The parameters to bind.
+    pub fn with_params<P: IntoFalkorParams>(
+        mut self,
+        params: P,
+    ) -> Self {
+        self.search.params.merge(params.into_falkor_params());
+        self
+    }
+
+    /// Validates the query vector against the index and runs the search read-only.
+    ///
+    /// # Returns
+    /// The matched entities with their scores, nearest first, or
+    /// [`FalkorDBError::VectorIndexNotFound`] / [`FalkorDBError::VectorDimensionMismatch`] if the
+    /// search does not fit the graph's vector indices.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Graph Vector Search Async", skip_all, level = "info")
+    )]
+    pub async fn execute(self) -> FalkorResult<Vec<(T, f64)>> {
+        let graph = self.graph;
+        self.search.validate(&graph.list_indices().await?.data)?;
+        let query = self.search.query();
+        let mut rows = graph
+            .ro_query(&query)
+            .with_params(self.search.params())
+            .execute_single()
+            .await?
+            .data;
+        let mut hits = Vec::new();
+        while let Some(row) = rows.next_row().await {
+            hits.push(parse_hit(row?)?);
+        }
+        Ok(hits)
+    }
+}
+
+impl SyncGraph {
+    /// Returns a builder that finds the `k` nodes with `label` whose vector `property` is nearest
+    /// to `vector`, as `(Node, score)` pairs; the score is the distance under the index's
+    /// similarity function, so lower is closer.
+    ///
+    /// # Arguments
+    /// * `label`: The label of the node vector index to search.
+    /// * `property`: The indexed vector property.
+    /// * `vector`: The query vector, e.g. a `&[f32]`, a `Vec<f32>` or a [`Vec32`](crate::Vec32).
+    /// * `k`: The number of nearest neighbours to return.
+    pub fn vector_search(
+        &mut self,
+        label: &str,
+        property: &str,
+        vector: impl AsRef<[f32]>,
+        k: u32,
+    ) -> VectorSearchBuilder<'_, Node> {
+        VectorSearchBuilder {
+            graph: self,
+            search: VectorSearch::new(EntityType::Node, label, property, vector.as_ref(), k),
+            entity: PhantomData,
+        }
+    }
+
+    /// Returns a builder that finds the `k` relationships of type `relation` whose vector
+    /// `property` is nearest to `vector`, as `(Edge, score)` pairs; see
+    /// [`vector_search`](Self::vector_search).
+    ///
+    /// # Arguments
+    /// * `relation`: The relationship type of the edge vector index to search.
+    /// * `property`: The indexed vector property.
+    /// * `vector`: The query vector.
+    /// * `k`: The number of nearest neighbours to return.
+    pub fn edge_vector_search(
+        &mut self,
+        relation: &str,
+        property: &str,
+        vector: impl AsRef<[f32]>,
+        k: u32,
+    ) -> VectorSearchBuilder<'_, Edge> {
+        VectorSearchBuilder {
+            graph: self,
+            search: VectorSearch::new(EntityType::Edge, relation, property, vector.as_ref(), k),
+            entity: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl crate::AsyncGraph {
+    /// Returns a builder that finds the `k` nodes nearest to `vector`; see
+    /// [`SyncGraph::vector_search`].
+    ///
+    /// # Arguments
+    /// * `label`: The label of the node vector index to search.
+    /// * `property`: The indexed vector property.
+    /// * `vector`: The query vector.
+    /// * `k`: The number of nearest neighbours to return.
+    pub fn vector_search(
+        &mut self,
+        label: &str,
+        property: &str,
+        vector: impl AsRef<[f32]>,
+        k: u32,
+    ) -> AsyncVectorSearchBuilder<'_, Node> {
+        AsyncVectorSearchBuilder {
+            graph: self,
+            search: VectorSearch::new(EntityType::Node, label, property, vector.as_ref(), k),
+            entity: PhantomData,
+        }
+    }
+
+    /// Returns a builder that finds the `k` relationships nearest to `vector`; see
+    /// [`SyncGraph::edge_vector_search`].
+    ///
+    /// # Arguments
+    /// * `relation`: The relationship type of the edge vector index to search.
+    /// * `property`: The indexed vector property.
+    /// * `vector`: The query vector.
+    /// * `k`: The number of nearest neighbours to return.
+    pub fn edge_vector_search(
+        &mut self,
+        relation: &str,
+        property: &str,
+        vector: impl AsRef<[f32]>,
+        k: u32,
+    ) -> AsyncVectorSearchBuilder<'_, Edge> {
+        AsyncVectorSearchBuilder {
+            graph: self,
+            search: VectorSearch::new(EntityType::Edge, relation, property, vector.as_ref(), k),
+            entity: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FalkorValue, IndexStatus};
+    use std::collections::HashMap;
+
+    fn vector_index(
+        entity_type: EntityType,
+        label: &str,
+        property: &str,
+        dimension: i64,
+    ) -> FalkorIndex {
+        FalkorIndex {
+            entity_type,
+            status: IndexStatus::Active,
+            index_label: label.to_string(),
+            fields: vec![property.to_string()],
+            field_types: HashMap::from([(property.to_string(), vec![IndexType::Vector])]),
+            language: "english".to_string(),
+            stopwords: vec![],
+            info: HashMap::new(),
+            options: HashMap::from([
+                ("dimension".to_string(), FalkorValue::I64(dimension)),
+                (
+                    "similarityFunction".to_string(),
+                    FalkorValue::String("cosine".to_string()),
+                ),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_vector_search_query() {
+        let mut search = VectorSearch::new(EntityType::Node, "Doc", "embedding", &[0.5, 1.0], 3);
+        assert_eq!(
+            search.query(),
+            "CALL db.idx.vector.queryNodes($label, $property, $k, vecf32($vector)) \
+             YIELD node, score RETURN node AS entity, score AS score ORDER BY score"
+        );
+
+        search.entity_type = EntityType::Edge;
+        search.filter = Some("relationship.weight > $min".to_string());
+        assert_eq!(
+            search.query(),
+            "CALL db.idx.vector.queryRelationships($label, $property, $k, vecf32($vector)) \
+             YIELD relationship, score WHERE relationship.weight > $min \
+             RETURN relationship AS entity, score AS score ORDER BY score"
+        );
+    }
+
+    #[test]
+    fn test_vector_search_params_cannot_be_overridden() {
+        let mut search = VectorSearch::new(EntityType::Node, "Doc", "embedding", &[0.5, 1.0], 3);
+        search.params.add_param("k", 100);
+        search.params.add_param("min", 2);
+        let encoded = search.params().into_encoded().unwrap();
+        assert_eq!(
+            encoded,
+            [
+                ("k", "3"),
+                ("min", "2"),
+                ("label", "'Doc'"),
+                ("property", "'embedding'"),
+                ("vector", "[0.5, 1.0]"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_vector_search_validates_index_and_dimension() {
+        let indices = [
+            vector_index(EntityType::Node, "Doc", "embedding", 2),
+            vector_index(EntityType::Edge, "SIMILAR", "embedding", 4),
+        ];
+        let search = VectorSearch::new(EntityType::Node, "Doc", "embedding", &[0.5, 1.0], 3);
+        assert!(search.validate(&indices).is_ok());
+
+        let search = VectorSearch::new(EntityType::Edge, "SIMILAR", "embedding", &[0.5], 3);
+        assert_eq!(
+            search.validate(&indices),
+            Err(FalkorDBError::VectorDimensionMismatch {
+                label: "SIMILAR".to_string(),
+                property: "embedding".to_string(),
+                expected: 4,
+                actual: 1,
+            })
+        );
+
+        let search = VectorSearch::new(EntityType::Edge, "Doc", "embedding", &[0.5, 1.0], 3);
+        assert_eq!(
+            search.validate(&indices),
+            Err(FalkorDBError::VectorIndexNotFound {
+                label: "Doc".to_string(),
+                property: "embedding".to_string(),
+            })
+        );
+    }
+}
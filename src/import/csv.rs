@@ -0,0 +1,1816 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! CSV import: map the columns of CSV files to node and relationship properties, and load them
+//! through [`GRAPH.BULK`](crate::BulkLoader) or chunked [`UNWIND`](crate::UnwindWriter) writes.
+//!
+//! Each node file is described by a [`NodeMapping`] (its labels, typed columns and optional key
+//! property) and each relationship file by a [`RelationshipMapping`], whose two [`Endpoint`]s name
+//! the columns holding the keys of the nodes to connect. A [`CsvImport`] gathers the files and
+//! loads every node file, then every relationship file:
+//!
+//! ```no_run
+//! # fn main() -> Result<(), falkordb::FalkorDBError> {
+//! use falkordb::import::csv::{ColumnType, CsvImport, Endpoint, NodeMapping, RelationshipMapping};
+//! use falkordb::FalkorClientBuilder;
+//! use std::num::NonZeroUsize;
+//! # let info: falkordb::FalkorConnectionInfo = "falkor://127.0.0.1:6379".try_into()?;
+//! let client = FalkorClientBuilder::new().with_connection_info(info).build()?;
+//!
+//! let people = NodeMapping::new("Person")
+//!     .with_column("id", ColumnType::Int)
+//!     .with_column("name", ColumnType::String)
+//!     .with_column_as("born_on", "birthday", ColumnType::Date)
+//!     .with_key("id");
+//! let knows = RelationshipMapping::new(
+//!     "KNOWS",
+//!     Endpoint::new("from", "Person", "id", ColumnType::Int),
+//!     Endpoint::new("to", "Person", "id", ColumnType::Int),
+//! )
+//! .with_column("since", ColumnType::Int);
+//!
+//! let mut graph = client.select_graph("social");
+//! let report = CsvImport::new()
+//!     .with_node_file(people, "people.csv")
+//!     .with_relationship_file(knows, "knows.csv")
+//!     .write_unwind(&mut graph, NonZeroUsize::new(1_000).unwrap())?;
+//! println!("{} nodes, {} relationships", report.nodes_created, report.relationships_created);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Errors point at the file and line that caused them
+//! ([`FalkorDBError::CsvImport`]). Rows before a bad line may
+//! already be written, so run the same import with [`CsvImport::dry_run`] first to check every
+//! file without writing anything.
+
+use crate::value::vec32::Vec32;
+use crate::{
+    graph::quote_identifier, BulkLoader, BulkNodeId, BulkValue, FalkorDBError, FalkorResult,
+    FalkorValue, Point, SyncGraph,
+};
+use std::{collections::HashMap, fmt, fs::File, io::Read, num::NonZeroUsize, path::PathBuf};
+
+/// How the cells of a column are parsed. An empty cell is null, whatever the type: the property is
+/// not set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ColumnType {
+    /// A 64-bit integer.
+    Int,
+    /// A finite 64-bit float.
+    Float,
+    /// `true` or `false`, in any case.
+    Bool,
+    /// The cell's text, as is.
+    String,
+    /// A `latitude,longitude` pair (quote the cell, or separate the two with whitespace), stored
+    /// as a [`Point`].
+    Point,
+    /// A list of floats, optionally in brackets, separated by commas or whitespace and stored as a
+    /// `vecf32` vector.
+    VecF32,
+    /// A `YYYY-MM-DD` calendar date, stored as a `date`.
+    Date,
+}
+
+impl ColumnType {
+    fn name(self) -> &'static str {
+        match self {
+            ColumnType::Int => "int",
+            ColumnType::Float => "float",
+            ColumnType::Bool => "bool",
+            ColumnType::String => "string",
+            ColumnType::Point => "point",
+            ColumnType::VecF32 => "vecf32",
+            ColumnType::Date => "date",
+        }
+    }
+
+    /// Whether nodes can be keyed by columns of this type.
+    fn is_key(self) -> bool {
+        matches!(self, ColumnType::Int | ColumnType::String)
+    }
+
+    /// Whether `GRAPH.BULK` can store values of this type.
+    fn is_bulk_loadable(self) -> bool {
+        !matches!(
+            self,
+            ColumnType::Point | ColumnType::VecF32 | ColumnType::Date
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ColumnSpec {
+    column: String,
+    property: String,
+    ty: ColumnType,
+}
+
+/// Maps each row of a CSV file to a node.
+#[derive(Clone, Debug)]
+pub struct NodeMapping {
+    labels: Vec<String>,
+    columns: Vec<ColumnSpec>,
+    key: Option<String>,
+}
+
+impl NodeMapping {
+    /// Creates a mapping for nodes with the given label and no properties yet.
+    ///
+    /// # Arguments
+    /// * `label`: The label of every node in the file.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            labels: vec![label.into()],
+            columns: Vec::new(),
+            key: None,
+        }
+    }
+
+    /// Adds another label to every node in the file.
+    ///
+    /// # Arguments
+    /// * `label`: The extra label.
+    pub fn with_label(
+        mut self,
+        label: impl Into<String>,
+    ) -> Self {
+        self.labels.push(label.into());
+        self
+    }
+
+    /// Maps a column to the property of the same name.
+    ///
+    /// # Arguments
+    /// * `column`: The column's header, which is also the property's name.
+    /// * `ty`: How the column's cells are parsed.
+    pub fn with_column(
+        self,
+        column: impl Into<String>,
+        ty: ColumnType,
+    ) -> Self {
+        let column = column.into();
+        self.with_column_as(column.clone(), column, ty)
+    }
+
+    /// Maps a column to a property with another name.
+    ///
+    /// # Arguments
+    /// * `column`: The column's header.
+    /// * `property`: The property the column's cells are stored in.
+    /// * `ty`: How the column's cells are parsed.
+    pub fn with_column_as(
+        mut self,
+        column: impl Into<String>,
+        property: impl Into<String>,
+        ty: ColumnType,
+    ) -> Self {
+        self.columns.push(ColumnSpec {
+            column: column.into(),
+            property: property.into(),
+            ty,
+        });
+        self
+    }
+
+    /// Keys the nodes by one of their properties, so that relationships can refer to them. The
+    /// property must be mapped from an [`Int`](ColumnType::Int) or [`String`](ColumnType::String)
+    /// column, and every row must have a distinct, non-empty value for it.
+    ///
+    /// # Arguments
+    /// * `property`: The key property.
+    pub fn with_key(
+        mut self,
+        property: impl Into<String>,
+    ) -> Self {
+        self.key = Some(property.into());
+        self
+    }
+
+    fn describe(&self) -> String {
+        format!("{} nodes", self.labels.join(":"))
+    }
+}
+
+/// One end of a [`RelationshipMapping`]: a column holding the key of the node to connect.
+#[derive(Clone, Debug)]
+pub struct Endpoint {
+    column: String,
+    label: String,
+    key: String,
+    ty: ColumnType,
+}
+
+impl Endpoint {
+    /// Resolves the node whose `key` property equals the value of `column`.
+    ///
+    /// # Arguments
+    /// * `column`: The relationship file's column holding the node's key.
+    /// * `label`: The node's label.
+    /// * `key`: The node property the column is matched against.
+    /// * `ty`: How the column's cells are parsed: [`ColumnType::Int`] or [`ColumnType::String`].
+    pub fn new(
+        column: impl Into<String>,
+        label: impl Into<String>,
+        key: impl Into<String>,
+        ty: ColumnType,
+    ) -> Self {
+        Self {
+            column: column.into(),
+            label: label.into(),
+            key: key.into(),
+            ty,
+        }
+    }
+}
+
+/// Maps each row of a CSV file to a relationship between two keyed nodes.
+///
+/// When an endpoint's label and key are those of a [`NodeMapping::with_key`] in the same import,
+/// the row must name one of the nodes that import loads. Otherwise, [`CsvImport::write_unwind`]
+/// matches the endpoint against the nodes already in the graph, and a row whose endpoints do not
+/// match creates nothing; compare [`CsvImportReport::relationships_created`] with
+/// [`CsvImportReport::relationship_rows`] to find out. [`CsvImport::write_bulk`] creates a new
+/// graph, so it refuses endpoints outside the import.
+#[derive(Clone, Debug)]
+pub struct RelationshipMapping {
+    relationship_type: String,
+    src: Endpoint,
+    dst: Endpoint,
+    columns: Vec<ColumnSpec>,
+}
+
+impl RelationshipMapping {
+    /// Creates a mapping for relationships of the given type and no properties yet.
+    ///
+    /// # Arguments
+    /// * `relationship_type`: The type of every relationship in the file.
+    /// * `src`: The node each relationship starts from.
+    /// * `dst`: The node each relationship points to.
+    pub fn new(
+        relationship_type: impl Into<String>,
+        src: Endpoint,
+        dst: Endpoint,
+    ) -> Self {
+        Self {
+            relationship_type: relationship_type.into(),
+            src,
+            dst,
+            columns: Vec::new(),
+        }
+    }
+
+    /// Maps a column to the property of the same name.
+    ///
+    /// # Arguments
+    /// * `column`: The column's header, which is also the property's name.
+    /// * `ty`: How the column's cells are parsed.
+    pub fn with_column(
+        self,
+        column: impl Into<String>,
+        ty: ColumnType,
+    ) -> Self {
+        let column = column.into();
+        self.with_column_as(column.clone(), column, ty)
+    }
+
+    /// Maps a column to a property with another name.
+    ///
+    /// # Arguments
+    /// * `column`: The column's header.
+    /// * `property`: The property the column's cells are stored in.
+    /// * `ty`: How the column's cells are parsed.
+    pub fn with_column_as(
+        mut self,
+        column: impl Into<String>,
+        property: impl Into<String>,
+        ty: ColumnType,
+    ) -> Self {
+        self.columns.push(ColumnSpec {
+            column: column.into(),
+            property: property.into(),
+            ty,
+        });
+        self
+    }
+
+    fn describe(&self) -> String {
+        format!("{} relationships", self.relationship_type)
+    }
+}
+
+/// What a [`CsvImport`] read and wrote.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CsvImportReport {
+    /// The number of node rows read.
+    pub node_rows: usize,
+    /// The number of relationship rows read.
+    pub relationship_rows: usize,
+    /// The number of nodes created; 0 for a dry run.
+    pub nodes_created: i64,
+    /// The number of relationships created; 0 for a dry run.
+    pub relationships_created: i64,
+    /// The number of properties set; 0 for a dry run.
+    pub properties_set: i64,
+}
+
+enum Source<'a> {
+    Reader(Box<dyn Read + Send + 'a>),
+    Path(PathBuf),
+}
+
+struct Input<'a, M> {
+    mapping: M,
+    name: String,
+    source: Source<'a>,
+}
+
+/// Loads CSV files into a graph. Add the files with `with_nodes`/`with_node_file` and
+/// `with_relationships`/`with_relationship_file`, then run the import with
+/// [`write_bulk`](Self::write_bulk) (a new graph, through `GRAPH.BULK`) or
+/// [`write_unwind`](Self::write_unwind) (any graph, through chunked `UNWIND` queries).
+///
+/// Files must start with a header row; columns that no mapping mentions are ignored, and cells are
+/// trimmed. Every file is opened and its header checked before anything is written; node files are
+/// then loaded in the order they were added, followed by the relationship files.
+#[must_use = "nothing is read until the import is written"]
+pub struct CsvImport<'a> {
+    nodes: Vec<Input<'a, NodeMapping>>,
+    relationships: Vec<Input<'a, RelationshipMapping>>,
+    delimiter: u8,
+    dry_run: bool,
+}
+
+impl Default for CsvImport<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> CsvImport<'a> {
+    /// Creates an empty, comma-separated import.
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            relationships: Vec::new(),
+            delimiter: b',',
+            dry_run: false,
+        }
+    }
+
+    /// Adds CSV data to load as nodes. Errors name the data after the mapping's labels.
+    ///
+    /// # Arguments
+    /// * `mapping`: How each row becomes a node.
+    /// * `reader`: The CSV data.
+    pub fn with_nodes(
+        mut self,
+        mapping: NodeMapping,
+        reader: impl Read + Send + 'a,
+    ) -> Self {
+        self.nodes.push(Input {
+            name: mapping.describe(),
+            mapping,
+            source: Source::Reader(Box::new(reader)),
+        });
+        self
+    }
+
+    /// Adds a CSV file to load as nodes, opened when the import runs.
+    ///
+    /// # Arguments
+    /// * `mapping`: How each row becomes a node.
+    /// * `path`: The file's path.
+    pub fn with_node_file(
+        mut self,
+        mapping: NodeMapping,
+        path: impl Into<PathBuf>,
+    ) -> Self {
+        let path = path.into();
+        self.nodes.push(Input {
+            mapping,
+            name: path.display().to_string(),
+            source: Source::Path(path),
+        });
+        self
+    }
+
+    /// Adds CSV data to load as relationships. Errors name the data after the relationship type.
+    ///
+    /// # Arguments
+    /// * `mapping`: How each row becomes a relationship.
+    /// * `reader`: The CSV data.
+    pub fn with_relationships(
+        mut self,
+        mapping: RelationshipMapping,
+        reader: impl Read + Send + 'a,
+    ) -> Self {
+        self.relationships.push(Input {
+            name: mapping.describe(),
+            mapping,
+            source: Source::Reader(Box::new(reader)),
+        });
+        self
+    }
+
+    /// Adds a CSV file to load as relationships, opened when the import runs.
+    ///
+    /// # Arguments
+    /// * `mapping`: How each row becomes a relationship.
+    /// * `path`: The file's path.
+    pub fn with_relationship_file(
+        mut self,
+        mapping: RelationshipMapping,
+        path: impl Into<PathBuf>,
+    ) -> Self {
+        let path = path.into();
+        self.relationships.push(Input {
+            mapping,
+            name: path.display().to_string(),
+            source: Source::Path(path),
+        });
+        self
+    }
+
+    /// Sets the field delimiter (`,` by default), e.g. `b'\t'` for TSV files.
+    ///
+    /// # Arguments
+    /// * `delimiter`: The delimiter byte.
+    pub fn with_delimiter(
+        mut self,
+        delimiter: u8,
+    ) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Reads and checks every file as the real import would (headers, cells, duplicate keys and
+    /// endpoints within the import) without writing anything. The report counts the rows read.
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    fn prepare(
+        self,
+        target: Target,
+    ) -> FalkorResult<Prepared<'a>> {
+        let mut keys = NodeKeys::default();
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+        for input in self.nodes {
+            nodes.push(NodeInput::prepare(
+                input,
+                self.delimiter,
+                target,
+                &mut keys,
+            )?);
+        }
+        let relationships = self
+            .relationships
+            .into_iter()
+            .map(|input| RelationshipInput::prepare(input, self.delimiter, target, &keys))
+            .collect::<FalkorResult<_>>()?;
+        Ok(Prepared {
+            nodes,
+            relationships,
+            keys,
+            dry_run: self.dry_run,
+        })
+    }
+
+    /// Loads the files into a new graph through a [`BulkLoader`], finishing it.
+    ///
+    /// `GRAPH.BULK` cannot store [`Point`](ColumnType::Point), [`VecF32`](ColumnType::VecF32) or
+    /// [`Date`](ColumnType::Date) columns, and every relationship endpoint must be a node of this
+    /// import; use [`write_unwind`](Self::write_unwind) otherwise.
+    ///
+    /// # Arguments
+    /// * `loader`: The loader of the graph to create, from [`FalkorSyncClient::bulk_loader`](crate::FalkorSyncClient::bulk_loader).
+    ///
+    /// # Returns
+    /// A [`CsvImportReport`], or the first error, pointing at its file and line.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "CSV Bulk Import", skip_all, level = "info")
+    )]
+    pub fn write_bulk(
+        self,
+        mut loader: BulkLoader,
+    ) -> FalkorResult<CsvImportReport> {
+        let Prepared {
+            nodes,
+            relationships,
+            mut keys,
+            dry_run,
+        } = self.prepare(Target::Bulk)?;
+        let mut report = CsvImportReport::default();
+
+        for mut input in nodes {
+            let labels = input.mapping.labels.clone();
+            let labels = labels.iter().map(String::as_str).collect::<Vec<_>>();
+            while let Some(node) = input.next(&mut keys) {
+                let node = node?;
+                report.node_rows += 1;
+                if dry_run {
+                    continue;
+                }
+                report.properties_set += node.properties.len() as i64;
+                let id = loader.add_node(&labels, node.bulk_properties())?;
+                keys.set_id(input.slot, node.key, id);
+            }
+        }
+        for mut input in relationships {
+            while let Some(relationship) = input.next(&keys) {
+                let relationship = relationship?;
+                report.relationship_rows += 1;
+                if dry_run {
+                    continue;
+                }
+                report.properties_set += relationship.properties.len() as i64;
+                let (src, dst) = input.bulk_endpoints(&relationship)?;
+                loader.add_relationship(
+                    &input.mapping.relationship_type,
+                    src,
+                    dst,
+                    relationship.bulk_properties(),
+                )?;
+            }
+        }
+
+        if !dry_run {
+            let loaded = loader.finish()?;
+            report.nodes_created = loaded.nodes_created as i64;
+            report.relationships_created = loaded.relationships_created as i64;
+        }
+        Ok(report)
+    }
+
+    /// Loads the files into a graph through one [`UnwindWriter`](crate::UnwindWriter) per file:
+    /// `CREATE` for nodes, and `MATCH` on the endpoint keys then `CREATE` for relationships.
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to write to; it may already hold data.
+    /// * `chunk_size`: The number of rows per `UNWIND` chunk.
+    ///
+    /// # Returns
+    /// A [`CsvImportReport`], or the first error, pointing at its file and line.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "CSV UNWIND Import", skip_all, level = "info")
+    )]
+    pub fn write_unwind(
+        self,
+        graph: &mut SyncGraph,
+        chunk_size: NonZeroUsize,
+    ) -> FalkorResult<CsvImportReport> {
+        let Prepared {
+            nodes,
+            relationships,
+            mut keys,
+            dry_run,
+        } = self.prepare(Target::Unwind)?;
+        let mut report = CsvImportReport::default();
+
+        for mut input in nodes {
+            let template = std::mem::take(&mut input.template);
+            let mut failure = None;
+            let mut rows = 0;
+            let records = std::iter::from_fn(|| {
+                let row = stash_error(input.next(&mut keys), &mut failure)?;
+                rows += 1;
+                Some(row.unwind_row())
+            });
+            let written = if dry_run {
+                records.for_each(drop);
+                None
+            } else {
+                Some(
+                    graph
+                        .unwind_writer(&template, chunk_size)
+                        .write_all(records),
+                )
+            };
+            report.node_rows += rows;
+            if let Some(err) = failure {
+                return Err(err);
+            }
+            if let Some(written) = written.transpose()? {
+                report.nodes_created += written.nodes_created;
+                report.properties_set += written.properties_set;
+            }
+        }
+        for mut input in relationships {
+            let template = std::mem::take(&mut input.template);
+            let mut failure = None;
+            let mut rows = 0;
+            let records = std::iter::from_fn(|| {
+                let row = stash_error(input.next(&keys), &mut failure)?;
+                rows += 1;
+                Some(row.unwind_row())
+            });
+            let written = if dry_run {
+                records.for_each(drop);
+                None
+            } else {
+                Some(
+                    graph
+                        .unwind_writer(&template, chunk_size)
+                        .write_all(records),
+                )
+            };
+            report.relationship_rows += rows;
+            if let Some(err) = failure {
+                return Err(err);
+            }
+            if let Some(written) = written.transpose()? {
+                report.relationships_created += written.relationships_created;
+                report.properties_set += written.properties_set;
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl CsvImport<'_> {
+    /// The async counterpart of [`write_bulk`](Self::write_bulk), through an
+    /// [`AsyncBulkLoader`](crate::AsyncBulkLoader). The files are read synchronously.
+    ///
+    /// # Arguments
+    /// * `loader`: The loader of the graph to create, from [`FalkorAsyncClient::bulk_loader`](crate::FalkorAsyncClient::bulk_loader).
+    ///
+    /// # Returns
+    /// A [`CsvImportReport`], or the first error, pointing at its file and line.
+    pub async fn write_bulk_async(
+        self,
+        mut loader: crate::AsyncBulkLoader,
+    ) -> FalkorResult<CsvImportReport> {
+        let Prepared {
+            nodes,
+            relationships,
+            mut keys,
+            dry_run,
+        } = self.prepare(Target::Bulk)?;
+        let mut report = CsvImportReport::default();
+
+        for mut input in nodes {
+            let labels = input.mapping.labels.clone();
+            let labels = labels.iter().map(String::as_str).collect::<Vec<_>>();
+            while let Some(node) = input.next(&mut keys) {
+                let node = node?;
+                report.node_rows += 1;
+                if dry_run {
+                    continue;
+                }
+                report.properties_set += node.properties.len() as i64;
+                let id = loader.add_node(&labels, node.bulk_properties()).await?;
+                keys.set_id(input.slot, node.key, id);
+            }
+        }
+        for mut input in relationships {
+            while let Some(relationship) = input.next(&keys) {
+                let relationship = relationship?;
+                report.relationship_rows += 1;
+                if dry_run {
+                    continue;
+                }
+                report.properties_set += relationship.properties.len() as i64;
+                let (src, dst) = input.bulk_endpoints(&relationship)?;
+                loader
+                    .add_relationship(
+                        &input.mapping.relationship_type,
+                        src,
+                        dst,
+                        relationship.bulk_properties(),
+                    )
+                    .await?;
+            }
+        }
+
+        if !dry_run {
+            let loaded = loader.finish().await?;
+            report.nodes_created = loaded.nodes_created as i64;
+            report.relationships_created = loaded.relationships_created as i64;
+        }
+        Ok(report)
+    }
+
+    /// The async counterpart of [`write_unwind`](Self::write_unwind). The files are read
+    /// synchronously.
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to write to; it may already hold data.
+    /// * `chunk_size`: The number of rows per `UNWIND` chunk.
+    ///
+    /// # Returns
+    /// A [`CsvImportReport`], or the first error, pointing at its file and line.
+    pub async fn write_unwind_async(
+        self,
+        graph: &mut crate::AsyncGraph,
+        chunk_size: NonZeroUsize,
+    ) -> FalkorResult<CsvImportReport> {
+        let Prepared {
+            nodes,
+            relationships,
+            mut keys,
+            dry_run,
+        } = self.prepare(Target::Unwind)?;
+        let mut report = CsvImportReport::default();
+
+        for mut input in nodes {
+            let template = std::mem::take(&mut input.template);
+            let mut failure = None;
+            let mut rows = 0;
+            let records = std::iter::from_fn(|| {
+                let row = stash_error(input.next(&mut keys), &mut failure)?;
+                rows += 1;
+                Some(row.unwind_row())
+            });
+            let written = if dry_run {
+                records.for_each(drop);
+                None
+            } else {
+                Some(
+                    graph
+                        .unwind_writer(&template, chunk_size)
+                        .write_all(records)
+                        .await,
+                )
+            };
+            report.node_rows += rows;
+            if let Some(err) = failure {
+                return Err(err);
+            }
+            if let Some(written) = written.transpose()? {
+                report.nodes_created += written.nodes_created;
+                report.properties_set += written.properties_set;
+            }
+        }
+        for mut input in relationships {
+            let template = std::mem::take(&mut input.template);
+            let mut failure = None;
+            let mut rows = 0;
+            let records = std::iter::from_fn(|| {
+                let row = stash_error(input.next(&keys), &mut failure)?;
+                rows += 1;
+                Some(row.unwind_row())
+            });
+            let written = if dry_run {
+                records.for_each(drop);
+                None
+            } else {
+                Some(
+                    graph
+                        .unwind_writer(&template, chunk_size)
+                        .write_all(records)
+                        .await,
+                )
+            };
+            report.relationship_rows += rows;
+            if let Some(err) = failure {
+                return Err(err);
+            }
+            if let Some(written) = written.transpose()? {
+                report.relationships_created += written.relationships_created;
+                report.properties_set += written.properties_set;
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Ends an iteration at the first error, keeping it for after the write.
+fn stash_error<T>(
+    next: Option<FalkorResult<T>>,
+    failure: &mut Option<FalkorDBError>,
+) -> Option<T> {
+    match next? {
+        Ok(value) => Some(value),
+        Err(err) => {
+            *failure = Some(err);
+            None
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Target {
+    Bulk,
+    Unwind,
+}
+
+struct Prepared<'a> {
+    nodes: Vec<NodeInput<'a>>,
+    relationships: Vec<RelationshipInput<'a>>,
+    keys: NodeKeys,
+    dry_run: bool,
+}
+
+fn import_error(
+    input: &str,
+    line: Option<u64>,
+    message: impl Into<String>,
+) -> FalkorDBError {
+    FalkorDBError::CsvImport {
+        input: input.to_string(),
+        line,
+        message: message.into(),
+    }
+}
+
+/// A parsed cell.
+#[derive(Clone, Debug, PartialEq)]
+enum Cell {
+    Null,
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+    Point(Point),
+    VecF32(Vec32),
+    /// Validated `YYYY-MM-DD` text, turned into a date by `date()` in the query.
+    Date(String),
+}
+
+fn parse_cell(
+    text: &str,
+    ty: ColumnType,
+) -> Result<Cell, String> {
+    if text.is_empty() {
+        return Ok(Cell::Null);
+    }
+    let unexpected = || format!("expected {}, found '{text}'", ty.name());
+    match ty {
+        ColumnType::Int => text.parse().map(Cell::Int).map_err(|_| unexpected()),
+        ColumnType::Float => parse_finite(text).map(Cell::Float).ok_or_else(unexpected),
+        ColumnType::Bool => {
+            if text.eq_ignore_ascii_case("true") {
+                Ok(Cell::Bool(true))
+            } else if text.eq_ignore_ascii_case("false") {
+                Ok(Cell::Bool(false))
+            } else {
+                Err(unexpected())
+            }
+        }
+        ColumnType::String => Ok(Cell::String(text.to_string())),
+        ColumnType::Point => {
+            let coordinates = split_numbers(text)
+                .map(parse_finite)
+                .collect::<Option<Vec<f64>>>()
+                .ok_or_else(unexpected)?;
+            match coordinates[..] {
+                [latitude, longitude]
+                    if (-90.0..=90.0).contains(&latitude)
+                        && (-180.0..=180.0).contains(&longitude) =>
+                {
+                    Ok(Cell::Point(Point {
+                        latitude,
+                        longitude,
+                    }))
+                }
+                _ => Err(unexpected()),
+            }
+        }
+        ColumnType::VecF32 => {
+            let inner = text
+                .strip_prefix('[')
+                .and_then(|text| text.strip_suffix(']'))
+                .unwrap_or(text);
+            let values = split_numbers(inner)
+                .map(|value| value.parse::<f32>().ok().filter(|value| value.is_finite()))
+                .collect::<Option<Vec<f32>>>()
+                .filter(|values| !values.is_empty())
+                .ok_or_else(unexpected)?;
+            Ok(Cell::VecF32(Vec32 { values }))
+        }
+        ColumnType::Date => is_date(text)
+            .then(|| Cell::Date(text.to_string()))
+            .ok_or_else(unexpected),
+    }
+}
+
+fn parse_finite(text: &str) -> Option<f64> {
+    text.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+fn split_numbers(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+}
+
+/// Whether `text` is a valid `YYYY-MM-DD` date.
+fn is_date(text: &str) -> bool {
+    let mut parts = text.split('-');
+    let (Some(year), Some(month), Some(day), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return false;
+    };
+    let digits = |part: &str, len: usize| {
+        part.len() == len && part.bytes().all(|byte| byte.is_ascii_digit())
+    };
+    if !digits(year, 4) || !digits(month, 2) || !digits(day, 2) {
+        return false;
+    }
+    let (Ok(year), Ok(month), Ok(day)) = (
+        year.parse::<u32>(),
+        month.parse::<u32>(),
+        day.parse::<u32>(),
+    ) else {
+        return false;
+    };
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
+impl Cell {
+    fn into_value(self) -> FalkorValue {
+        match self {
+            Cell::Null => FalkorValue::None,
+            Cell::Int(value) => FalkorValue::I64(value),
+            Cell::Float(value) => FalkorValue::F64(value),
+            Cell::Bool(value) => FalkorValue::Bool(value),
+            Cell::String(value) | Cell::Date(value) => FalkorValue::String(value),
+            Cell::Point(value) => FalkorValue::Point(value),
+            Cell::VecF32(value) => FalkorValue::Vec32(value),
+        }
+    }
+
+    fn into_bulk_value(self) -> BulkValue {
+        match self {
+            Cell::Null => BulkValue::Null,
+            Cell::Int(value) => BulkValue::I64(value),
+            Cell::Float(value) => BulkValue::F64(value),
+            Cell::Bool(value) => BulkValue::Bool(value),
+            Cell::String(value) => BulkValue::String(value),
+            // Refused for `GRAPH.BULK` when the import is prepared.
+            Cell::Date(value) => BulkValue::String(value),
+            Cell::Point(point) => BulkValue::Array(vec![
+                BulkValue::F64(point.latitude),
+                BulkValue::F64(point.longitude),
+            ]),
+            Cell::VecF32(vec) => BulkValue::Array(
+                vec.values
+                    .into_iter()
+                    .map(|value| BulkValue::F64(value.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// A node key, as parsed from an [`Int`](ColumnType::Int) or [`String`](ColumnType::String) column.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Key {
+    Int(i64),
+    String(String),
+}
+
+impl Key {
+    fn from_cell(cell: Cell) -> Option<Key> {
+        match cell {
+            Cell::Int(value) => Some(Key::Int(value)),
+            Cell::String(value) => Some(Key::String(value)),
+            _ => None,
+        }
+    }
+
+    fn into_value(self) -> FalkorValue {
+        match self {
+            Key::Int(value) => FalkorValue::I64(value),
+            Key::String(value) => FalkorValue::String(value),
+        }
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Key::Int(value) => write!(f, "{value}"),
+            Key::String(value) => write!(f, "'{value}'"),
+        }
+    }
+}
+
+struct SeenNode {
+    line: u64,
+    id: Option<BulkNodeId>,
+}
+
+/// The keys of the nodes loaded by this import, per `(label, key property)`.
+#[derive(Default)]
+struct NodeKeys {
+    slots: HashMap<(String, String), usize>,
+    types: Vec<ColumnType>,
+    seen: Vec<HashMap<Key, SeenNode>>,
+}
+
+impl NodeKeys {
+    /// The slot holding the keys of nodes with `labels`, creating it if needed.
+    fn register(
+        &mut self,
+        labels: &[String],
+        key: &str,
+        ty: ColumnType,
+    ) -> Result<usize, String> {
+        let existing = labels
+            .iter()
+            .find_map(|label| self.slots.get(&(label.clone(), key.to_string())).copied());
+        let slot = existing.unwrap_or_else(|| {
+            self.types.push(ty);
+            self.seen.push(HashMap::new());
+            self.seen.len() - 1
+        });
+        if self.types[slot] != ty {
+            return Err(format!(
+                "key '{key}' is a {} column here but a {} column in another node file",
+                ty.name(),
+                self.types[slot].name()
+            ));
+        }
+        for label in labels {
+            self.slots
+                .entry((label.clone(), key.to_string()))
+                .or_insert(slot);
+        }
+        Ok(slot)
+    }
+
+    fn slot(
+        &self,
+        label: &str,
+        key: &str,
+    ) -> Option<usize> {
+        self.slots
+            .get(&(label.to_string(), key.to_string()))
+            .copied()
+    }
+
+    /// Records a node's key, refusing one that another row already has.
+    fn claim(
+        &mut self,
+        slot: usize,
+        key: &Key,
+        line: u64,
+    ) -> Result<(), String> {
+        if let Some(seen) = self.seen[slot].get(key) {
+            return Err(format!(
+                "duplicate key {key}, first seen on line {}",
+                seen.line
+            ));
+        }
+        self.seen[slot].insert(key.clone(), SeenNode { line, id: None });
+        Ok(())
+    }
+
+    fn set_id(
+        &mut self,
+        slot: Option<usize>,
+        key: Option<Key>,
+        id: BulkNodeId,
+    ) {
+        if let (Some(slot), Some(key)) = (slot, key) {
+            if let Some(seen) = self.seen[slot].get_mut(&key) {
+                seen.id = Some(id);
+            }
+        }
+    }
+}
+
+/// An open CSV file and the position of each mapped column.
+struct CsvFile<'a> {
+    name: String,
+    records: ::csv::StringRecordsIntoIter<Box<dyn Read + Send + 'a>>,
+}
+
+impl<'a> CsvFile<'a> {
+    fn open(
+        name: String,
+        source: Source<'a>,
+        delimiter: u8,
+        columns: &[&str],
+    ) -> FalkorResult<(Self, Vec<usize>)> {
+        let reader: Box<dyn Read + Send + 'a> = match source {
+            Source::Reader(reader) => reader,
+            Source::Path(path) => Box::new(
+                File::open(&path)
+                    .map_err(|err| import_error(&name, None, format!("cannot open: {err}")))?,
+            ),
+        };
+        let mut reader = ::csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .trim(::csv::Trim::All)
+            .from_reader(reader);
+        let headers = reader
+            .headers()
+            .map_err(|err| csv_error(&name, err))?
+            .clone();
+        let positions = columns
+            .iter()
+            .map(|column| {
+                headers
+                    .iter()
+                    .position(|header| header == *column)
+                    .ok_or_else(|| {
+                        import_error(&name, Some(1), format!("no column named '{column}'"))
+                    })
+            })
+            .collect::<FalkorResult<_>>()?;
+        Ok((
+            Self {
+                name,
+                records: reader.into_records(),
+            },
+            positions,
+        ))
+    }
+
+    fn next(&mut self) -> Option<FalkorResult<(u64, ::csv::StringRecord)>> {
+        let record = self.records.next()?;
+        Some(
+            record
+                .map(|record| (record.position().map_or(0, |pos| pos.line()), record))
+                .map_err(|err| csv_error(&self.name, err)),
+        )
+    }
+
+    fn error(
+        &self,
+        line: u64,
+        message: impl Into<String>,
+    ) -> FalkorDBError {
+        import_error(&self.name, Some(line), message)
+    }
+}
+
+fn csv_error(
+    name: &str,
+    err: ::csv::Error,
+) -> FalkorDBError {
+    let line = err.position().map(|pos| pos.line());
+    let message = match err.kind() {
+        ::csv::ErrorKind::UnequalLengths {
+            expected_len, len, ..
+        } => format!("expected {expected_len} fields, found {len}"),
+        ::csv::ErrorKind::Io(err) => format!("cannot read: {err}"),
+        ::csv::ErrorKind::Utf8 { err, .. } => format!("invalid UTF-8: {err}"),
+        _ => err.to_string(),
+    };
+    import_error(name, line, message)
+}
+
+/// Checks a file's property columns against the target.
+fn check_columns(
+    name: &str,
+    columns: &[ColumnSpec],
+    target: Target,
+) -> FalkorResult<()> {
+    for (index, spec) in columns.iter().enumerate() {
+        if columns[..index]
+            .iter()
+            .any(|other| other.property == spec.property)
+        {
+            return Err(import_error(
+                name,
+                None,
+                format!("property '{}' is mapped twice", spec.property),
+            ));
+        }
+        if target == Target::Bulk && !spec.ty.is_bulk_loadable() {
+            return Err(import_error(
+                name,
+                None,
+                format!(
+                    "column '{}': GRAPH.BULK cannot store {} values; import with write_unwind instead",
+                    spec.column,
+                    spec.ty.name()
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Parses the mapped cells of a row, leaving out the empty ones.
+fn parse_properties(
+    file: &CsvFile,
+    line: u64,
+    record: &::csv::StringRecord,
+    columns: &[ColumnSpec],
+    positions: &[usize],
+) -> FalkorResult<Vec<(String, Cell)>> {
+    let mut properties = Vec::with_capacity(columns.len());
+    for (spec, &position) in columns.iter().zip(positions) {
+        let cell = parse_cell(record.get(position).unwrap_or_default(), spec.ty)
+            .map_err(|message| file.error(line, format!("column '{}': {message}", spec.column)))?;
+        if cell != Cell::Null {
+            properties.push((spec.property.clone(), cell));
+        }
+    }
+    Ok(properties)
+}
+
+/// `{p: row.p, ...}` for the `UNWIND` templates, or nothing without properties.
+fn property_map(
+    columns: &[ColumnSpec],
+    row: &str,
+) -> FalkorResult<String> {
+    if columns.is_empty() {
+        return Ok(String::new());
+    }
+    let properties = columns
+        .iter()
+        .map(|spec| {
+            let property = quote_identifier(&spec.property)?;
+            Ok(match spec.ty {
+                // Dates travel as text, points as maps and vectors as lists.
+                ColumnType::Date => format!("{property}: date({row}.{property})"),
+                ColumnType::Point => format!("{property}: point({row}.{property})"),
+                ColumnType::VecF32 => format!("{property}: vecf32({row}.{property})"),
+                _ => format!("{property}: {row}.{property}"),
+            })
+        })
+        .collect::<FalkorResult<Vec<_>>>()?;
+    Ok(format!(" {{{}}}", properties.join(", ")))
+}
+
+struct ParsedNode {
+    key: Option<Key>,
+    properties: Vec<(String, Cell)>,
+}
+
+impl ParsedNode {
+    fn unwind_row(self) -> FalkorValue {
+        FalkorValue::Map(
+            self.properties
+                .into_iter()
+                .map(|(property, cell)| (property, cell.into_value()))
+                .collect(),
+        )
+    }
+
+    fn bulk_properties(&self) -> Vec<(String, BulkValue)> {
+        self.properties
+            .iter()
+            .map(|(property, cell)| (property.clone(), cell.clone().into_bulk_value()))
+            .collect()
+    }
+}
+
+struct NodeInput<'a> {
+    mapping: NodeMapping,
+    file: CsvFile<'a>,
+    positions: Vec<usize>,
+    /// The index of the key column in the mapping, and the slot of its keys.
+    key: Option<usize>,
+    slot: Option<usize>,
+    template: String,
+}
+
+impl<'a> NodeInput<'a> {
+    fn prepare(
+        input: Input<'a, NodeMapping>,
+        delimiter: u8,
+        target: Target,
+        keys: &mut NodeKeys,
+    ) -> FalkorResult<Self> {
+        let Input {
+            mapping,
+            name,
+            source,
+        } = input;
+        check_columns(&name, &mapping.columns, target)?;
+
+        let mut key = None;
+        let mut slot = None;
+        if let Some(property) = &mapping.key {
+            let index = mapping
+                .columns
+                .iter()
+                .position(|spec| &spec.property == property)
+                .ok_or_else(|| {
+                    import_error(
+                        &name,
+                        None,
+                        format!("key '{property}' is not a mapped property"),
+                    )
+                })?;
+            let ty = mapping.columns[index].ty;
+            if !ty.is_key() {
+                return Err(import_error(
+                    &name,
+                    None,
+                    format!(
+                        "key '{property}' must be an int or string column, not {}",
+                        ty.name()
+                    ),
+                ));
+            }
+            key = Some(index);
+            slot = Some(
+                keys.register(&mapping.labels, property, ty)
+                    .map_err(|message| import_error(&name, None, message))?,
+            );
+        }
+
+        let labels = mapping
+            .labels
+            .iter()
+            .map(|label| quote_identifier(label))
+            .collect::<FalkorResult<Vec<_>>>()?
+            .join(":");
+        let template = format!(
+            "CREATE (:{labels}{})",
+            property_map(&mapping.columns, "row")?
+        );
+
+        let columns = mapping
+            .columns
+            .iter()
+            .map(|spec| spec.column.as_str())
+            .collect::<Vec<_>>();
+        let (file, positions) = CsvFile::open(name, source, delimiter, &columns)?;
+        Ok(Self {
+            mapping,
+            file,
+            positions,
+            key,
+            slot,
+            template,
+        })
+    }
+
+    /// Parses the next row, claiming its key.
+    fn next(
+        &mut self,
+        keys: &mut NodeKeys,
+    ) -> Option<FalkorResult<ParsedNode>> {
+        let next = self.file.next()?;
+        Some(next.and_then(|(line, record)| {
+            let mut key = None;
+            if let (Some(index), Some(slot)) = (self.key, self.slot) {
+                let spec = &self.mapping.columns[index];
+                let cell = record.get(self.positions[index]).unwrap_or_default();
+                let parsed = parse_cell(cell, spec.ty).ok().and_then(Key::from_cell);
+                // Bad and empty cells are reported by `parse_properties`, or here when empty.
+                if let Some(parsed) = parsed {
+                    keys.claim(slot, &parsed, line)
+                        .map_err(|message| self.file.error(line, message))?;
+                    key = Some(parsed);
+                } else if cell.is_empty() {
+                    return Err(self.file.error(
+                        line,
+                        format!("column '{}': the key must not be empty", spec.column),
+                    ));
+                }
+            }
+            let properties = parse_properties(
+                &self.file,
+                line,
+                &record,
+                &self.mapping.columns,
+                &self.positions,
+            )?;
+            Ok(ParsedNode { key, properties })
+        }))
+    }
+}
+
+struct ParsedRelationship {
+    line: u64,
+    src: (Key, Option<BulkNodeId>),
+    dst: (Key, Option<BulkNodeId>),
+    properties: Vec<(String, Cell)>,
+}
+
+impl ParsedRelationship {
+    fn unwind_row(self) -> FalkorValue {
+        let properties = self
+            .properties
+            .into_iter()
+            .map(|(property, cell)| (property, cell.into_value()))
+            .collect();
+        FalkorValue::Map(HashMap::from([
+            ("src".to_string(), self.src.0.into_value()),
+            ("dst".to_string(), self.dst.0.into_value()),
+            ("props".to_string(), FalkorValue::Map(properties)),
+        ]))
+    }
+
+    fn bulk_properties(&self) -> Vec<(String, BulkValue)> {
+        self.properties
+            .iter()
+            .map(|(property, cell)| (property.clone(), cell.clone().into_bulk_value()))
+            .collect()
+    }
+}
+
+struct RelationshipInput<'a> {
+    mapping: RelationshipMapping,
+    file: CsvFile<'a>,
+    /// The positions of the source, destination and property columns.
+    src: usize,
+    dst: usize,
+    positions: Vec<usize>,
+    /// The slots of the endpoints' keys, when their nodes belong to this import.
+    src_slot: Option<usize>,
+    dst_slot: Option<usize>,
+    template: String,
+}
+
+impl<'a> RelationshipInput<'a> {
+    fn prepare(
+        input: Input<'a, RelationshipMapping>,
+        delimiter: u8,
+        target: Target,
+        keys: &NodeKeys,
+    ) -> FalkorResult<Self> {
+        let Input {
+            mapping,
+            name,
+            source,
+        } = input;
+        check_columns(&name, &mapping.columns, target)?;
+
+        let slot = |endpoint: &Endpoint| -> FalkorResult<Option<usize>> {
+            if !endpoint.ty.is_key() {
+                return Err(import_error(
+                    &name,
+                    None,
+                    format!(
+                        "endpoint column '{}' must be an int or string column, not {}",
+                        endpoint.column,
+                        endpoint.ty.name()
+                    ),
+                ));
+            }
+            let slot = keys.slot(&endpoint.label, &endpoint.key);
+            match slot {
+                Some(slot) if keys.types[slot] != endpoint.ty => Err(import_error(
+                    &name,
+                    None,
+                    format!(
+                        "endpoint column '{}' is {}, but the {} nodes are keyed by {} '{}'",
+                        endpoint.column,
+                        endpoint.ty.name(),
+                        endpoint.label,
+                        keys.types[slot].name(),
+                        endpoint.key
+                    ),
+                )),
+                None if target == Target::Bulk => Err(import_error(
+                    &name,
+                    None,
+                    format!(
+                        "no node file of this import is keyed by '{}' for label '{}', which GRAPH.BULK needs to resolve column '{}'",
+                        endpoint.key, endpoint.label, endpoint.column
+                    ),
+                )),
+                slot => Ok(slot),
+            }
+        };
+        let src_slot = slot(&mapping.src)?;
+        let dst_slot = slot(&mapping.dst)?;
+
+        let template = format!(
+            "MATCH (src:{} {{{}: row.src}}) MATCH (dst:{} {{{}: row.dst}}) CREATE (src)-[:{}{}]->(dst)",
+            quote_identifier(&mapping.src.label)?,
+            quote_identifier(&mapping.src.key)?,
+            quote_identifier(&mapping.dst.label)?,
+            quote_identifier(&mapping.dst.key)?,
+            quote_identifier(&mapping.relationship_type)?,
+            property_map(&mapping.columns, "row.props")?,
+        );
+
+        let mut columns = vec![mapping.src.column.as_str(), mapping.dst.column.as_str()];
+        columns.extend(mapping.columns.iter().map(|spec| spec.column.as_str()));
+        let (file, mut positions) = CsvFile::open(name, source, delimiter, &columns)?;
+        let properties = positions.split_off(2);
+        Ok(Self {
+            src: positions[0],
+            dst: positions[1],
+            positions: properties,
+            src_slot,
+            dst_slot,
+            template,
+            mapping,
+            file,
+        })
+    }
+
+    fn endpoint(
+        &self,
+        keys: &NodeKeys,
+        line: u64,
+        record: &::csv::StringRecord,
+        endpoint: &Endpoint,
+        position: usize,
+        slot: Option<usize>,
+    ) -> FalkorResult<(Key, Option<BulkNodeId>)> {
+        let cell = parse_cell(record.get(position).unwrap_or_default(), endpoint.ty).map_err(
+            |message| {
+                self.file
+                    .error(line, format!("column '{}': {message}", endpoint.column))
+            },
+        )?;
+        let key = Key::from_cell(cell).ok_or_else(|| {
+            self.file.error(
+                line,
+                format!(
+                    "column '{}': the endpoint key must not be empty",
+                    endpoint.column
+                ),
+            )
+        })?;
+        let Some(slot) = slot else {
+            return Ok((key, None));
+        };
+        match keys.seen[slot].get(&key) {
+            Some(seen) => Ok((key, seen.id)),
+            None => Err(self.file.error(
+                line,
+                format!(
+                    "column '{}': no {} node with {} = {key} in this import",
+                    endpoint.column, endpoint.label, endpoint.key
+                ),
+            )),
+        }
+    }
+
+    fn next(
+        &mut self,
+        keys: &NodeKeys,
+    ) -> Option<FalkorResult<ParsedRelationship>> {
+        let next = self.file.next()?;
+        Some(next.and_then(|(line, record)| {
+            let src = self.endpoint(
+                keys,
+                line,
+                &record,
+                &self.mapping.src,
+                self.src,
+                self.src_slot,
+            )?;
+            let dst = self.endpoint(
+                keys,
+                line,
+                &record,
+                &self.mapping.dst,
+                self.dst,
+                self.dst_slot,
+            )?;
+            let properties = parse_properties(
+                &self.file,
+                line,
+                &record,
+                &self.mapping.columns,
+                &self.positions,
+            )?;
+            Ok(ParsedRelationship {
+                line,
+                src,
+                dst,
+                properties,
+            })
+        }))
+    }
+
+    /// The loaded nodes a relationship connects.
+    fn bulk_endpoints(
+        &self,
+        relationship: &ParsedRelationship,
+    ) -> FalkorResult<(BulkNodeId, BulkNodeId)> {
+        relationship.src.1.zip(relationship.dst.1).ok_or_else(|| {
+            self.file
+                .error(relationship.line, "an endpoint was not loaded")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::blocking::create_empty_inner_sync_client;
+
+    const PEOPLE: &str = "id,name,score,active,loc,embedding,born\n\
+                          1,Alice,1.5,true,\"32.1,34.8\",[1 2],1990-02-28\n\
+                          2,Bob,,FALSE,,,\n";
+
+    fn people() -> NodeMapping {
+        NodeMapping::new("Person")
+            .with_column("id", ColumnType::Int)
+            .with_column("name", ColumnType::String)
+            .with_key("id")
+    }
+
+    fn knows() -> RelationshipMapping {
+        RelationshipMapping::new(
+            "KNOWS",
+            Endpoint::new("from", "Person", "id", ColumnType::Int),
+            Endpoint::new("to", "Person", "id", ColumnType::Int),
+        )
+        .with_column("since", ColumnType::Int)
+    }
+
+    fn dry_run_unwind(import: CsvImport) -> FalkorResult<CsvImportReport> {
+        let mut graph = SyncGraph::new(create_empty_inner_sync_client(), "csv");
+        import
+            .dry_run()
+            .write_unwind(&mut graph, NonZeroUsize::new(10).unwrap())
+    }
+
+    fn dry_run_bulk(import: CsvImport) -> FalkorResult<CsvImportReport> {
+        let loader = BulkLoader::new(create_empty_inner_sync_client(), "csv".to_string());
+        import.dry_run().write_bulk(loader)
+    }
+
+    #[test]
+    fn test_parse_cell() {
+        assert_eq!(parse_cell("", ColumnType::Int), Ok(Cell::Null));
+        assert_eq!(parse_cell("-7", ColumnType::Int), Ok(Cell::Int(-7)));
+        assert_eq!(parse_cell("2.5", ColumnType::Float), Ok(Cell::Float(2.5)));
+        assert_eq!(parse_cell("True", ColumnType::Bool), Ok(Cell::Bool(true)));
+        assert_eq!(
+            parse_cell("32.1 34.8", ColumnType::Point),
+            Ok(Cell::Point(Point {
+                latitude: 32.1,
+                longitude: 34.8
+            }))
+        );
+        assert_eq!(
+            parse_cell("[1, 2.5]", ColumnType::VecF32),
+            Ok(Cell::VecF32(Vec32 {
+                values: vec![1.0, 2.5]
+            }))
+        );
+        assert_eq!(
+            parse_cell("2024-02-29", ColumnType::Date),
+            Ok(Cell::Date("2024-02-29".to_string()))
+        );
+
+        assert_eq!(
+            parse_cell("1.5", ColumnType::Int),
+            Err("expected int, found '1.5'".to_string())
+        );
+        assert!(parse_cell("NaN", ColumnType::Float).is_err());
+        assert!(parse_cell("yes", ColumnType::Bool).is_err());
+        assert!(parse_cell("91,0", ColumnType::Point).is_err());
+        assert!(parse_cell("[]", ColumnType::VecF32).is_err());
+        assert!(parse_cell("2023-02-29", ColumnType::Date).is_err());
+        assert!(parse_cell("2023-2-01", ColumnType::Date).is_err());
+    }
+
+    #[test]
+    fn test_templates() {
+        let mapping = people()
+            .with_label("Employee")
+            .with_column_as("born", "birth date", ColumnType::Date)
+            .with_column("loc", ColumnType::Point)
+            .with_column("embedding", ColumnType::VecF32);
+        let input = NodeInput::prepare(
+            Input {
+                name: mapping.describe(),
+                mapping,
+                source: Source::Reader(Box::new(PEOPLE.as_bytes())),
+            },
+            b',',
+            Target::Unwind,
+            &mut NodeKeys::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            input.template,
+            "CREATE (:Person:Employee {id: row.id, name: row.name, `birth date`: date(row.`birth date`), loc: point(row.loc), embedding: vecf32(row.embedding)})"
+        );
+
+        let input = RelationshipInput::prepare(
+            Input {
+                name: "knows".to_string(),
+                mapping: knows(),
+                source: Source::Reader(Box::new("from,to,since\n".as_bytes())),
+            },
+            b',',
+            Target::Unwind,
+            &NodeKeys::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            input.template,
+            "MATCH (src:Person {id: row.src}) MATCH (dst:Person {id: row.dst}) CREATE (src)-[:KNOWS {since: row.props.since}]->(dst)"
+        );
+    }
+
+    #[test]
+    fn test_dry_run_counts_rows() {
+        let mapping = people()
+            .with_column("score", ColumnType::Float)
+            .with_column("active", ColumnType::Bool)
+            .with_column("loc", ColumnType::Point)
+            .with_column("embedding", ColumnType::VecF32)
+            .with_column("born", ColumnType::Date);
+        let report = dry_run_unwind(
+            CsvImport::new()
+                .with_nodes(mapping, PEOPLE.as_bytes())
+                .with_relationships(knows(), "from,to,since\n1,2,2020\n2,1,\n".as_bytes()),
+        )
+        .unwrap();
+        assert_eq!(report.node_rows, 2);
+        assert_eq!(report.relationship_rows, 2);
+        assert_eq!(report.nodes_created, 0);
+    }
+
+    #[test]
+    fn test_errors_name_the_line() {
+        let err = dry_run_unwind(
+            CsvImport::new().with_nodes(people(), "id,name\n1,Alice\nx,Bob\n".as_bytes()),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Person nodes, line 3: column 'id': expected int, found 'x'"
+        );
+
+        let err = dry_run_unwind(
+            CsvImport::new().with_nodes(people(), "id,name\n1,Alice\n1,Bob\n".as_bytes()),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            FalkorDBError::CsvImport {
+                input: "Person nodes".to_string(),
+                line: Some(3),
+                message: "duplicate key 1, first seen on line 2".to_string(),
+            }
+        );
+
+        let err = dry_run_unwind(CsvImport::new().with_nodes(people(), "id\n1\n".as_bytes()))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FalkorDBError::CsvImport { line: Some(1), ref message, .. } if message == "no column named 'name'"
+        ));
+
+        let err = dry_run_unwind(
+            CsvImport::new().with_nodes(people(), "id,name\n1,Alice,extra\n".as_bytes()),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            FalkorDBError::CsvImport { line: Some(2), .. }
+        ));
+    }
+
+    #[test]
+    fn test_endpoints_resolve_within_the_import() {
+        let import = || {
+            CsvImport::new()
+                .with_nodes(people(), "id,name\n1,Alice\n2,Bob\n".as_bytes())
+                .with_relationships(knows(), "from,to,since\n1,2,2020\n2,3,2021\n".as_bytes())
+        };
+        let err = dry_run_unwind(import()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "KNOWS relationships, line 3: column 'to': no Person node with id = 3 in this import"
+        );
+        assert_eq!(dry_run_bulk(import()).unwrap_err(), err);
+
+        // Endpoints outside the import are matched in the graph, which GRAPH.BULK cannot do.
+        let external =
+            || CsvImport::new().with_relationships(knows(), "from,to,since\n1,2,2020\n".as_bytes());
+        assert_eq!(dry_run_unwind(external()).unwrap().relationship_rows, 1);
+        assert!(matches!(
+            dry_run_bulk(external()),
+            Err(FalkorDBError::CsvImport { line: None, .. })
+        ));
+    }
+
+    #[test]
+    fn test_bulk_refuses_unsupported_columns() {
+        let mapping = people().with_column("loc", ColumnType::Point);
+        let err =
+            dry_run_bulk(CsvImport::new().with_nodes(mapping, PEOPLE.as_bytes())).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Person nodes: column 'loc': GRAPH.BULK cannot store point values; import with write_unwind instead"
+        );
+
+        let report =
+            dry_run_bulk(CsvImport::new().with_nodes(people(), PEOPLE.as_bytes())).unwrap();
+        assert_eq!(report.node_rows, 2);
+    }
+}
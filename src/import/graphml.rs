@@ -0,0 +1,605 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! GraphML import: load a GraphML document, such as one written by `falkordb::export::graphml`,
+//! into a graph.
+//!
+//! Nodes take their labels from the `labels` value (`:Person:Admin`) and relationships their type
+//! from the `label` value, as the exporter writes them; every other `<data>` value is a property,
+//! named by the `attr.name` of its `<key>` and typed by its `attr.type` (`boolean`, `int`, `long`,
+//! `float`, `double` or `string`) or `falkordb.type`. Key defaults apply to the elements without a
+//! value. Node ids only connect the elements of the document: every node gets a new id in the
+//! graph, and an edge must come after both of its nodes. The document is read as a stream and
+//! written in batches, so memory stays bounded by the batch size and the id of each node read so
+//! far:
+//!
+//! ```no_run
+//! # fn main() -> Result<(), falkordb::FalkorDBError> {
+//! use falkordb::import::graphml::GraphmlImporter;
+//! use falkordb::FalkorClientBuilder;
+//! use std::io::BufReader;
+//! # let info: falkordb::FalkorConnectionInfo = "falkor://127.0.0.1:6379".try_into()?;
+//! let client = FalkorClientBuilder::new().with_connection_info(info).build()?;
+//!
+//! let file = std::fs::File::open("social.graphml").expect("open file");
+//! let report = GraphmlImporter::new().import(&mut client.select_graph("social"), BufReader::new(file))?;
+//! println!("{} nodes, {} relationships", report.nodes_created, report.relationships_created);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Nested graphs and hyperedges have no FalkorDB form and are refused. Errors
+//! ([`FalkorDBError::GraphImport`](crate::FalkorDBError::GraphImport)) name the byte offset at fault. The batches before it stay
+//! written.
+
+use crate::export::graphml::{Kind, LABELS_KEY, TYPE_KEY};
+use crate::import::{EntityLoader, GraphImportReport};
+use crate::{Edge, FalkorResult, FalkorValue, Node, SyncGraph};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::io::BufRead;
+
+const FORMAT: &str = "GraphML";
+
+/// Imports a GraphML document; see the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct GraphmlImporter {
+    batch_size: usize,
+}
+
+impl Default for GraphmlImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphmlImporter {
+    /// Creates an importer writing 100 entities per statement.
+    pub fn new() -> Self {
+        Self { batch_size: 100 }
+    }
+
+    /// Sets the number of nodes or relationships created per statement (at least 1).
+    ///
+    /// # Arguments
+    /// * `batch_size`: The most entities one `CREATE` or `UNWIND` statement creates.
+    pub fn with_batch_size(
+        mut self,
+        batch_size: usize,
+    ) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Loads every node and edge of `input` into `graph`.
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to load into; it may already hold data.
+    /// * `input`: The document to read.
+    ///
+    /// # Returns
+    /// A [`GraphImportReport`], or the first error.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Import GraphML", skip_all, level = "info")
+    )]
+    pub fn import<R: BufRead>(
+        &self,
+        graph: &mut SyncGraph,
+        input: R,
+    ) -> FalkorResult<GraphImportReport> {
+        let mut loader = EntityLoader::new(FORMAT, self.batch_size)?;
+        let mut document = DocumentReader::new(input);
+        while document.feed(&mut loader)? {
+            loader.run(graph)?;
+        }
+        loader.finish();
+        loader.run(graph)?;
+        Ok(loader.report())
+    }
+
+    /// Loads every node and edge of `input` into `graph`; see [`GraphmlImporter::import`].
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to load into; it may already hold data.
+    /// * `input`: The document to read.
+    ///
+    /// # Returns
+    /// A [`GraphImportReport`], or the first error.
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Import GraphML Async", skip_all, level = "info")
+    )]
+    pub async fn import_async<R: BufRead>(
+        &self,
+        graph: &mut crate::AsyncGraph,
+        input: R,
+    ) -> FalkorResult<GraphImportReport> {
+        let mut loader = EntityLoader::new(FORMAT, self.batch_size)?;
+        let mut document = DocumentReader::new(input);
+        while document.feed(&mut loader)? {
+            loader.run_async(graph).await?;
+        }
+        loader.finish();
+        loader.run_async(graph).await?;
+        Ok(loader.report())
+    }
+}
+
+/// A `<key>`: the name and kind of the values it holds.
+struct Key {
+    /// The `for` attribute: `node`, `edge` or `all`.
+    domain: String,
+    name: String,
+    kind: Kind,
+    default: Option<String>,
+}
+
+/// The node or edge being read.
+struct Element {
+    edge: bool,
+    id: Option<String>,
+    source: Option<String>,
+    target: Option<String>,
+    values: HashMap<String, String>,
+}
+
+/// The parts of an XML event the document is read from.
+enum Markup {
+    Start {
+        name: String,
+        attributes: HashMap<String, String>,
+        empty: bool,
+    },
+    End(String),
+    Text(String),
+    Eof,
+}
+
+/// What an event of the document completed.
+enum Step {
+    Node(Node),
+    Edge(Edge),
+    Markup,
+    End,
+}
+
+/// Reads the document event by event, queueing its nodes and edges as they end.
+struct DocumentReader<R> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    keys: HashMap<String, Key>,
+    /// The key being declared.
+    key: Option<(String, Key)>,
+    /// The graph id of each node, by document id.
+    node_ids: HashMap<String, i64>,
+    edges: i64,
+    element: Option<Element>,
+    /// The key and text of the `<data>` or `<default>` being read.
+    value: Option<(String, String)>,
+}
+
+impl<R: BufRead> DocumentReader<R> {
+    fn new(input: R) -> Self {
+        Self {
+            reader: Reader::from_reader(input),
+            buf: Vec::new(),
+            keys: HashMap::new(),
+            key: None,
+            node_ids: HashMap::new(),
+            edges: 0,
+            element: None,
+            value: None,
+        }
+    }
+
+    fn position(&self) -> String {
+        format!("byte {}", self.reader.buffer_position())
+    }
+
+    /// Reads the next event and queues the node or edge it ends; returns `false` at the end of
+    /// the document.
+    fn feed(
+        &mut self,
+        loader: &mut EntityLoader,
+    ) -> FalkorResult<bool> {
+        let step = self
+            .step()
+            .map_err(|message| loader.error(self.position(), message))?;
+        match step {
+            Step::Node(node) => loader.node(node, || self.position())?,
+            Step::Edge(edge) => loader.edge(edge, || self.position())?,
+            Step::Markup => {}
+            Step::End => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn step(&mut self) -> Result<Step, String> {
+        match self.next_markup()? {
+            Markup::Start {
+                name,
+                attributes,
+                empty,
+            } => {
+                self.start(&name, attributes)?;
+                if empty {
+                    return self.end(&name);
+                }
+            }
+            Markup::End(name) => return self.end(&name),
+            Markup::Text(text) => {
+                if let Some((_, value)) = &mut self.value {
+                    value.push_str(&text);
+                }
+            }
+            Markup::Eof if self.element.is_some() => {
+                return Err("the document ends inside an element".to_string())
+            }
+            Markup::Eof => return Ok(Step::End),
+        }
+        Ok(Step::Markup)
+    }
+
+    fn next_markup(&mut self) -> Result<Markup, String> {
+        let invalid = |err: &dyn std::fmt::Display| format!("invalid XML: {err}");
+        self.buf.clear();
+        let event = self
+            .reader
+            .read_event_into(&mut self.buf)
+            .map_err(|err| invalid(&err))?;
+        let empty = matches!(event, Event::Empty(_));
+        Ok(match event {
+            Event::Start(start) | Event::Empty(start) => {
+                let mut attributes = HashMap::new();
+                for attribute in start.attributes() {
+                    let attribute = attribute.map_err(|err| invalid(&err))?;
+                    attributes.insert(
+                        String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned(),
+                        attribute
+                            .unescape_value()
+                            .map_err(|err| invalid(&err))?
+                            .into_owned(),
+                    );
+                }
+                Markup::Start {
+                    name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+                    attributes,
+                    empty,
+                }
+            }
+            Event::End(end) => {
+                Markup::End(String::from_utf8_lossy(end.local_name().as_ref()).into_owned())
+            }
+            Event::Text(text) => Markup::Text(
+                text.xml10_content()
+                    .map_err(|err| invalid(&err))?
+                    .into_owned(),
+            ),
+            Event::CData(text) => {
+                Markup::Text(text.decode().map_err(|err| invalid(&err))?.into_owned())
+            }
+            Event::GeneralRef(reference) => {
+                let text = match reference.resolve_char_ref().map_err(|err| invalid(&err))? {
+                    Some(c) => c.to_string(),
+                    None => {
+                        let name = reference.decode().map_err(|err| invalid(&err))?;
+                        quick_xml::escape::resolve_predefined_entity(&name)
+                            .ok_or_else(|| format!("unknown entity &{name};"))?
+                            .to_string()
+                    }
+                };
+                Markup::Text(text)
+            }
+            Event::Eof => Markup::Eof,
+            _ => Markup::Text(String::new()),
+        })
+    }
+
+    fn start(
+        &mut self,
+        name: &str,
+        mut attributes: HashMap<String, String>,
+    ) -> Result<(), String> {
+        match name {
+            "key" => {
+                let id = attributes
+                    .remove("id")
+                    .ok_or_else(|| "a <key> has no id".to_string())?;
+                let kind = Kind::of_key(
+                    attributes.get("attr.type").map(String::as_str),
+                    attributes.get("falkordb.type").map(String::as_str),
+                )
+                .map_err(|message| format!("key '{id}': {message}"))?;
+                let key = Key {
+                    domain: attributes
+                        .remove("for")
+                        .unwrap_or_else(|| "all".to_string()),
+                    name: attributes.remove("attr.name").unwrap_or_else(|| id.clone()),
+                    kind,
+                    default: None,
+                };
+                self.key = Some((id, key));
+            }
+            "default" if self.key.is_some() => self.value = Some((String::new(), String::new())),
+            "graph" if self.element.is_some() => {
+                return Err("nested graphs are not supported".to_string())
+            }
+            "hyperedge" => return Err("hyperedges are not supported".to_string()),
+            "node" | "edge" => {
+                if self.element.is_some() {
+                    return Err("nested graphs are not supported".to_string());
+                }
+                self.element = Some(Element {
+                    edge: name == "edge",
+                    id: attributes.remove("id"),
+                    source: attributes.remove("source"),
+                    target: attributes.remove("target"),
+                    values: HashMap::new(),
+                });
+            }
+            "data" if self.element.is_some() => {
+                let key = attributes
+                    .remove("key")
+                    .ok_or_else(|| "a <data> has no key".to_string())?;
+                self.value = Some((key, String::new()));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn end(
+        &mut self,
+        name: &str,
+    ) -> Result<Step, String> {
+        match name {
+            "key" => {
+                if let Some((id, key)) = self.key.take() {
+                    self.keys.insert(id, key);
+                }
+            }
+            "default" => {
+                if let (Some((_, key)), Some((_, text))) = (&mut self.key, self.value.take()) {
+                    key.default = Some(text);
+                }
+            }
+            "data" => {
+                if let (Some(element), Some((key, text))) = (&mut self.element, self.value.take()) {
+                    element.values.insert(key, text);
+                }
+            }
+            "node" | "edge" => match self.element.take() {
+                Some(element) if element.edge => return self.edge(element).map(Step::Edge),
+                Some(element) => return self.node(element).map(Step::Node),
+                None => {}
+            },
+            _ => {}
+        }
+        Ok(Step::Markup)
+    }
+
+    fn node(
+        &mut self,
+        mut element: Element,
+    ) -> Result<Node, String> {
+        let id = element
+            .id
+            .take()
+            .ok_or_else(|| "a <node> has no id".to_string())?;
+        let labels = element
+            .values
+            .remove(LABELS_KEY)
+            .map(|labels| {
+                labels
+                    .split(':')
+                    .filter(|label| !label.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let properties = self.properties("node", element.values)?;
+        let entity_id = self.node_ids.len() as i64;
+        if self.node_ids.insert(id.clone(), entity_id).is_some() {
+            return Err(format!("node '{id}' appears twice"));
+        }
+        Ok(Node {
+            entity_id,
+            labels,
+            properties,
+        })
+    }
+
+    fn edge(
+        &mut self,
+        mut element: Element,
+    ) -> Result<Edge, String> {
+        let endpoint = |id: Option<String>, role: &str| {
+            let id = id.ok_or_else(|| format!("an <edge> has no {role}"))?;
+            self.node_ids
+                .get(&id)
+                .copied()
+                .ok_or_else(|| format!("edge {role} '{id}' is not a node before it"))
+        };
+        let src_node_id = endpoint(element.source.take(), "source")?;
+        let dst_node_id = endpoint(element.target.take(), "target")?;
+        let relationship_type = element
+            .values
+            .remove(TYPE_KEY)
+            .filter(|label| !label.is_empty())
+            .ok_or_else(|| format!("an <edge> has no '{TYPE_KEY}' value for its type"))?;
+        let properties = self.properties("edge", element.values)?;
+        self.edges += 1;
+        Ok(Edge {
+            entity_id: self.edges,
+            relationship_type,
+            src_node_id,
+            dst_node_id,
+            properties,
+        })
+    }
+
+    /// The properties of a node or edge (`domain`), from its values and the key defaults.
+    fn properties(
+        &self,
+        domain: &str,
+        mut values: HashMap<String, String>,
+    ) -> Result<HashMap<String, FalkorValue>, String> {
+        for (id, key) in &self.keys {
+            if let Some(default) = &key.default {
+                if key.domain == domain || key.domain == "all" {
+                    values.entry(id.clone()).or_insert_with(|| default.clone());
+                }
+            }
+        }
+        values
+            .into_iter()
+            .map(|(id, text)| {
+                let key = self
+                    .keys
+                    .get(&id)
+                    .ok_or_else(|| format!("<data> refers to the undeclared key '{id}'"))?;
+                let value = key
+                    .kind
+                    .read(&text)
+                    .map_err(|message| format!("property '{}': {message}", key.name))?;
+                Ok((key.name.clone(), value))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::temporal::Date;
+    use crate::{FalkorDBError, Point};
+
+    fn read(document: &str) -> FalkorResult<()> {
+        let mut loader = EntityLoader::new(FORMAT, 10)?;
+        let mut reader = DocumentReader::new(document.as_bytes());
+        while reader.feed(&mut loader)? {}
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_typed_values_and_defaults() {
+        let document = r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="labels" for="node" attr.name="labels" attr.type="string"/>
+  <key id="label" for="edge" attr.name="label" attr.type="string"/>
+  <key id="d0" for="node" attr.name="home" attr.type="string" falkordb.type="point"/>
+  <key id="d1" for="node" attr.name="name" attr.type="string"/>
+  <key id="d2" for="node" attr.name="born" attr.type="string" falkordb.type="date">
+    <default>1970-01-01</default>
+  </key>
+  <key id="d3" for="edge" attr.name="weight" attr.type="double"/>
+  <graph id="g" edgedefault="directed">
+    <node id="a">
+      <data key="labels">:Person:Admin</data>
+      <data key="d0">{"latitude":1.5,"longitude":2.0}</data>
+      <data key="d1">A &amp; B&#10;<![CDATA[<c>]]></data>
+    </node>
+    <edge source="a" target="a">
+      <data key="label">SELF</data>
+      <data key="d3">INF</data>
+    </edge>
+  </graph>
+</graphml>"#;
+        let mut reader = DocumentReader::new(document.as_bytes());
+        let mut entities = Vec::new();
+        loop {
+            match reader.step().unwrap() {
+                Step::Node(node) => entities.push(FalkorValue::Node(node)),
+                Step::Edge(edge) => entities.push(FalkorValue::Edge(edge)),
+                Step::Markup => {}
+                Step::End => break,
+            }
+        }
+        assert_eq!(
+            entities,
+            vec![
+                FalkorValue::Node(Node {
+                    entity_id: 0,
+                    labels: vec!["Person".to_string(), "Admin".to_string()],
+                    properties: HashMap::from([
+                        (
+                            "home".to_string(),
+                            FalkorValue::Point(Point {
+                                latitude: 1.5,
+                                longitude: 2.0
+                            })
+                        ),
+                        (
+                            "name".to_string(),
+                            FalkorValue::String("A & B\n<c>".to_string())
+                        ),
+                        ("born".to_string(), FalkorValue::Date(Date::new(0))),
+                    ]),
+                }),
+                FalkorValue::Edge(Edge {
+                    entity_id: 1,
+                    relationship_type: "SELF".to_string(),
+                    src_node_id: 0,
+                    dst_node_id: 0,
+                    properties: HashMap::from([(
+                        "weight".to_string(),
+                        FalkorValue::F64(f64::INFINITY)
+                    )]),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_edge_before_its_nodes_names_the_byte() {
+        let err = read(
+            r#"<graphml><key id="label" for="edge"/><graph edgedefault="directed">
+<node id="a"/><edge source="a" target="b"><data key="label">R</data></edge></graph></graphml>"#,
+        )
+        .unwrap_err();
+        let FalkorDBError::GraphImport {
+            format,
+            position,
+            message,
+        } = err
+        else {
+            panic!("{err:?}");
+        };
+        assert_eq!(format, FORMAT);
+        assert!(position.starts_with("byte "));
+        assert_eq!(message, "edge target 'b' is not a node before it");
+    }
+
+    #[test]
+    fn test_unsupported_documents_are_refused() {
+        let message = |document| match read(document).unwrap_err() {
+            FalkorDBError::GraphImport { message, .. } => message,
+            err => panic!("{err:?}"),
+        };
+        assert_eq!(
+            message(r#"<graphml><graph><node id="a"><graph/></node></graph></graphml>"#),
+            "nested graphs are not supported"
+        );
+        assert_eq!(
+            message(
+                r#"<graphml><graph><node id="a"><data key="x">1</data></node></graph></graphml>"#
+            ),
+            "<data> refers to the undeclared key 'x'"
+        );
+        assert_eq!(
+            message(
+                r#"<graphml><graph><node id="a"/><edge source="a" target="a"/></graph></graphml>"#
+            ),
+            "an <edge> has no 'label' value for its type"
+        );
+        assert!(
+            message(r#"<graphml><graph><node id="a"></graph></graphml>"#)
+                .starts_with("invalid XML")
+        );
+    }
+}
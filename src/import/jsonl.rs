@@ -0,0 +1,301 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! JSON Lines import: load the lines written by `falkordb::export::jsonl` into a graph.
+//!
+//! Each line is a node object (`"type": "node"`, with `entity_id`, `labels` and `properties`) or a
+//! relationship object (`"type": "relationship"`, with `entity_id`, `relationship_type`,
+//! `src_node_id`, `dst_node_id` and `properties`); blank lines are skipped. Ids only connect the
+//! lines of the file: every node gets a new id in the graph, and a relationship must come after
+//! both of its nodes. Lines are read and written in batches, so memory stays bounded by the batch
+//! size and the id of each node read so far:
+//!
+//! ```no_run
+//! # fn main() -> Result<(), falkordb::FalkorDBError> {
+//! use falkordb::import::jsonl::JsonLinesImporter;
+//! use falkordb::FalkorClientBuilder;
+//! use std::io::BufReader;
+//! # let info: falkordb::FalkorConnectionInfo = "falkor://127.0.0.1:6379".try_into()?;
+//! let client = FalkorClientBuilder::new().with_connection_info(info).build()?;
+//!
+//! let file = std::fs::File::open("social.jsonl").expect("open file");
+//! let report = JsonLinesImporter::new()
+//!     .with_batch_size(500)
+//!     .import(&mut client.select_graph("social"), BufReader::new(file))?;
+//! println!("{} nodes, {} relationships", report.nodes_created, report.relationships_created);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Errors ([`FalkorDBError::GraphImport`](crate::FalkorDBError::GraphImport)) name the line at fault. The batches before it stay
+//! written.
+
+use crate::export::json_value;
+use crate::import::{EntityLoader, GraphImportReport};
+use crate::{Edge, FalkorResult, Node, SyncGraph};
+use serde_json::{Map, Value};
+use std::io::BufRead;
+
+const FORMAT: &str = "JSON Lines";
+
+/// Imports a JSON Lines file; see the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct JsonLinesImporter {
+    batch_size: usize,
+}
+
+impl Default for JsonLinesImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonLinesImporter {
+    /// Creates an importer writing 100 entities per statement.
+    pub fn new() -> Self {
+        Self { batch_size: 100 }
+    }
+
+    /// Sets the number of nodes or relationships created per statement (at least 1).
+    ///
+    /// # Arguments
+    /// * `batch_size`: The most entities one `CREATE` or `UNWIND` statement creates.
+    pub fn with_batch_size(
+        mut self,
+        batch_size: usize,
+    ) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Loads every line of `input` into `graph`.
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to load into; it may already hold data.
+    /// * `input`: The lines to read.
+    ///
+    /// # Returns
+    /// A [`GraphImportReport`], or the first error.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Import JSON Lines", skip_all, level = "info")
+    )]
+    pub fn import<R: BufRead>(
+        &self,
+        graph: &mut SyncGraph,
+        input: R,
+    ) -> FalkorResult<GraphImportReport> {
+        let mut loader = EntityLoader::new(FORMAT, self.batch_size)?;
+        for (index, line) in input.lines().enumerate() {
+            read_line(&mut loader, line, index as u64 + 1)?;
+            loader.run(graph)?;
+        }
+        loader.finish();
+        loader.run(graph)?;
+        Ok(loader.report())
+    }
+
+    /// Loads every line of `input` into `graph`; see [`JsonLinesImporter::import`].
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to load into; it may already hold data.
+    /// * `input`: The lines to read.
+    ///
+    /// # Returns
+    /// A [`GraphImportReport`], or the first error.
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Import JSON Lines Async", skip_all, level = "info")
+    )]
+    pub async fn import_async<R: BufRead>(
+        &self,
+        graph: &mut crate::AsyncGraph,
+        input: R,
+    ) -> FalkorResult<GraphImportReport> {
+        let mut loader = EntityLoader::new(FORMAT, self.batch_size)?;
+        for (index, line) in input.lines().enumerate() {
+            read_line(&mut loader, line, index as u64 + 1)?;
+            loader.run_async(graph).await?;
+        }
+        loader.finish();
+        loader.run_async(graph).await?;
+        Ok(loader.report())
+    }
+}
+
+/// A line of the file.
+#[derive(Debug, PartialEq)]
+enum Entity {
+    Node(Node),
+    Edge(Edge),
+}
+
+/// Parses line `number` and queues its entity.
+fn read_line(
+    loader: &mut EntityLoader,
+    line: std::io::Result<String>,
+    number: u64,
+) -> FalkorResult<()> {
+    let position = || format!("line {number}");
+    let line = line.map_err(|err| loader.error(position(), format!("cannot read: {err}")))?;
+    if line.trim().is_empty() {
+        return Ok(());
+    }
+    match parse_entity(&line).map_err(|message| loader.error(position(), message))? {
+        Entity::Node(node) => loader.node(node, position),
+        Entity::Edge(edge) => loader.edge(edge, position),
+    }
+}
+
+fn parse_entity(line: &str) -> Result<Entity, String> {
+    let Value::Object(mut object) =
+        serde_json::from_str(line).map_err(|err| format!("invalid JSON: {err}"))?
+    else {
+        return Err("expected a JSON object".to_string());
+    };
+    let kind = take_string(&mut object, "type")?;
+    let entity_id = take_id(&mut object, "entity_id")?;
+    let properties = match object.remove("properties") {
+        None | Some(Value::Null) => Default::default(),
+        Some(Value::Object(map)) => json_value::properties_from_json(map)?,
+        Some(_) => return Err("'properties' must be an object".to_string()),
+    };
+    match kind.as_str() {
+        "node" => {
+            let labels = match object.remove("labels") {
+                None | Some(Value::Null) => Vec::new(),
+                Some(Value::Array(labels)) => labels
+                    .into_iter()
+                    .map(|label| match label {
+                        Value::String(label) => Ok(label),
+                        _ => Err("'labels' must hold strings".to_string()),
+                    })
+                    .collect::<Result<_, _>>()?,
+                Some(_) => return Err("'labels' must be an array".to_string()),
+            };
+            Ok(Entity::Node(Node {
+                entity_id,
+                labels,
+                properties,
+            }))
+        }
+        "relationship" => Ok(Entity::Edge(Edge {
+            entity_id,
+            relationship_type: take_string(&mut object, "relationship_type")?,
+            src_node_id: take_id(&mut object, "src_node_id")?,
+            dst_node_id: take_id(&mut object, "dst_node_id")?,
+            properties,
+        })),
+        other => Err(format!(
+            "unknown type '{other}', expected 'node' or 'relationship'"
+        )),
+    }
+}
+
+fn take_string(
+    object: &mut Map<String, Value>,
+    field: &str,
+) -> Result<String, String> {
+    match object.remove(field) {
+        Some(Value::String(value)) => Ok(value),
+        Some(_) => Err(format!("'{field}' must be a string")),
+        None => Err(format!("missing '{field}'")),
+    }
+}
+
+fn take_id(
+    object: &mut Map<String, Value>,
+    field: &str,
+) -> Result<i64, String> {
+    match object.remove(field) {
+        Some(value) => value
+            .as_i64()
+            .ok_or_else(|| format!("'{field}' must be an integer")),
+        None => Err(format!("missing '{field}'")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::temporal::Date;
+    use crate::{FalkorDBError, FalkorValue};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_parse_node_and_relationship_lines() {
+        assert_eq!(
+            parse_entity(
+                r#"{"type":"node","entity_id":4,"labels":["Person"],"properties":{"born":{"$date":"1970-01-01"}}}"#
+            )
+            .unwrap(),
+            Entity::Node(Node {
+                entity_id: 4,
+                labels: vec!["Person".to_string()],
+                properties: HashMap::from([(
+                    "born".to_string(),
+                    FalkorValue::Date(Date::new(0))
+                )]),
+            })
+        );
+        assert_eq!(
+            parse_entity(
+                r#"{"type":"relationship","entity_id":1,"relationship_type":"KNOWS","src_node_id":4,"dst_node_id":5}"#
+            )
+            .unwrap(),
+            Entity::Edge(Edge {
+                entity_id: 1,
+                relationship_type: "KNOWS".to_string(),
+                src_node_id: 4,
+                dst_node_id: 5,
+                properties: HashMap::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_malformed_lines_are_refused() {
+        assert!(parse_entity("[1]").unwrap_err().contains("JSON object"));
+        assert!(parse_entity(r#"{"type":"node"}"#)
+            .unwrap_err()
+            .contains("missing 'entity_id'"));
+        assert!(parse_entity(r#"{"type":"way","entity_id":1}"#)
+            .unwrap_err()
+            .contains("unknown type 'way'"));
+        assert!(parse_entity(
+            r#"{"type":"relationship","entity_id":1,"relationship_type":"R","src_node_id":"a","dst_node_id":2}"#
+        )
+        .unwrap_err()
+        .contains("'src_node_id' must be an integer"));
+    }
+
+    #[test]
+    fn test_relationship_before_its_nodes_names_the_line() {
+        let mut loader = EntityLoader::new(FORMAT, 10).unwrap();
+        read_line(
+            &mut loader,
+            Ok(r#"{"type":"node","entity_id":1,"labels":["A"]}"#.to_string()),
+            1,
+        )
+        .unwrap();
+        read_line(&mut loader, Ok(String::new()), 2).unwrap();
+        let err = read_line(
+            &mut loader,
+            Ok(r#"{"type":"relationship","entity_id":1,"relationship_type":"R","src_node_id":1,"dst_node_id":2}"#.to_string()),
+            3,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            FalkorDBError::GraphImport {
+                format: FORMAT,
+                position: "line 3".to_string(),
+                message: "relationship 1 references node 2, which does not come before it"
+                    .to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,136 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Importers that load external data files into a graph: CSV through [`GRAPH.BULK`](crate::BulkLoader)
+//! or chunked [`UNWIND`](crate::UnwindWriter) writes, and the JSON Lines and GraphML files written by
+//! `falkordb::export` through batched Cypher statements.
+
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "graphml")]
+pub mod graphml;
+#[cfg(feature = "jsonl")]
+pub mod jsonl;
+
+#[cfg(any(feature = "jsonl", feature = "graphml"))]
+use crate::{
+    export::cypher::{DumpWriter, Replayer},
+    Edge, FalkorDBError, FalkorResult, Node, SyncGraph,
+};
+
+/// What a JSON Lines or GraphML importer created.
+#[cfg(any(feature = "jsonl", feature = "graphml"))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GraphImportReport {
+    /// The nodes created.
+    pub nodes_created: i64,
+    /// The relationships created.
+    pub relationships_created: i64,
+}
+
+/// Loads a stream of nodes, then relationships, through the statements of a Cypher dump: nodes are
+/// created in batches under a temporary dump id, which the relationship batches match on.
+#[cfg(any(feature = "jsonl", feature = "graphml"))]
+pub(crate) struct EntityLoader {
+    format: &'static str,
+    dump: DumpWriter,
+    replayer: Replayer,
+}
+
+#[cfg(any(feature = "jsonl", feature = "graphml"))]
+impl EntityLoader {
+    pub(crate) fn new(
+        format: &'static str,
+        batch_size: usize,
+    ) -> FalkorResult<Self> {
+        let mut dump = DumpWriter::new(batch_size);
+        dump.begin_nodes()?;
+        Ok(Self {
+            format,
+            dump,
+            replayer: Replayer::default(),
+        })
+    }
+
+    /// The error for a problem at `position` of the input.
+    pub(crate) fn error(
+        &self,
+        position: impl Into<String>,
+        message: impl Into<String>,
+    ) -> FalkorDBError {
+        FalkorDBError::GraphImport {
+            format: self.format,
+            position: position.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Queues `node`; a node that cannot be written is reported at `position`.
+    pub(crate) fn node(
+        &mut self,
+        node: Node,
+        position: impl FnOnce() -> String,
+    ) -> FalkorResult<()> {
+        let result = self.dump.node(node);
+        result.map_err(|err| self.locate(err, position))
+    }
+
+    /// Queues `edge`; an edge that cannot be written is reported at `position`.
+    pub(crate) fn edge(
+        &mut self,
+        edge: Edge,
+        position: impl FnOnce() -> String,
+    ) -> FalkorResult<()> {
+        let result = self.dump.edge(edge);
+        result.map_err(|err| self.locate(err, position))
+    }
+
+    fn locate(
+        &self,
+        err: FalkorDBError,
+        position: impl FnOnce() -> String,
+    ) -> FalkorDBError {
+        match err {
+            FalkorDBError::CypherDump { message } => self.error(position(), message),
+            err => err,
+        }
+    }
+
+    /// Runs the statements completed so far.
+    pub(crate) fn run(
+        &mut self,
+        graph: &mut SyncGraph,
+    ) -> FalkorResult<()> {
+        for line in self.dump.take_lines() {
+            self.replayer.run(graph, &line)?;
+        }
+        Ok(())
+    }
+
+    /// Runs the statements completed so far.
+    #[cfg(feature = "tokio")]
+    pub(crate) async fn run_async(
+        &mut self,
+        graph: &mut crate::AsyncGraph,
+    ) -> FalkorResult<()> {
+        for line in self.dump.take_lines() {
+            self.replayer.run_async(graph, &line).await?;
+        }
+        Ok(())
+    }
+
+    /// Closes the stream: the last batch and the removal of the dump ids are left to [`run`](Self::run).
+    pub(crate) fn finish(&mut self) {
+        self.dump.finish();
+    }
+
+    pub(crate) fn report(&self) -> GraphImportReport {
+        GraphImportReport {
+            nodes_created: self.replayer.report.nodes_created,
+            relationships_created: self.replayer.report.relationships_created,
+        }
+    }
+}
@@ -100,13 +100,25 @@
 //! | Feature | Enables |
 //! |---|---|
 //! | `tokio` | The async client and API on the `tokio` runtime (multi-threaded scheduler). |
-//! | `serde` | Map query results into your own `serde::Deserialize` types. |
+//! | `serde` | Map query results into your own `serde::Deserialize` types, serialize results with `serde::Serialize`, and bind any `Serialize` value as a parameter. |
+//! | `derive` | `#[derive(FromRow)]` / `#[derive(FromNode)]` to map rows and nodes onto structs without serde, and `#[derive(IntoFalkorParams)]` / `#[derive(IntoFalkorParam)]` to bind structs as parameters. |
+//! | `chrono` / `time` / `jiff` | Convert temporal values to and from those crates' date/time types, read them with `try_get` and bind them as parameters. |
+//! | `geo-types` | Convert `Point` to and from `geo_types::Point`, read it with `try_get` and bind it as a parameter. |
+//! | `csv` | Import CSV files into a graph with typed column mappings, through `GRAPH.BULK` or chunked `UNWIND` writes (`falkordb::import::csv`). |
+//! | `jsonl` | Export and import graphs as JSON Lines, one node or relationship per line (`falkordb::export::jsonl`, `falkordb::import::jsonl`). |
+//! | `graphml` | Export and import graphs as GraphML documents (`falkordb::export::graphml`, `falkordb::import::graphml`). |
+//! | `migrations` | Apply ordered, versioned Cypher migrations with checksum drift detection and dry runs (`falkordb::migrations`). |
+//! | `codegen` | Generate Rust structs with label and property name constants from a live graph schema (`falkordb::codegen`, `falkordb-codegen` binary). |
 //! | `tracing` | OpenTelemetry-aligned `tracing` spans with a privacy-safe query fingerprint. |
+//! | `opentelemetry` | Implies `tracing`; opt-in W3C `traceparent` propagation into queries via `with_trace_context`. |
 //! | `metrics` | Counters and histograms via the `metrics` facade (install any exporter). |
+//! | `query-stats` | In-process per-query statistics and a client-side slow query log with captured plans (`with_query_stats`). |
 //! | `embedded` | Run a self-contained embedded FalkorDB server (module downloaded at runtime). |
 //! | `embedded-bundle` | Embed the module at build time so the embedded server runs fully offline. |
 //! | `rustls` / `native-tls` | TLS for the sync client, via `rustls` or `native-tls`. |
 //! | `tokio-rustls` / `tokio-native-tls` | TLS for the async client. |
+//! | `cluster` | Connect to a Redis Cluster deployment from the sync client. |
+//! | `tokio-cluster` | Redis Cluster support for the async client as well (implies `tokio` and `cluster`). |
 //!
 //! ```bash
 //! cargo add falkordb --features tokio,serde
@@ -195,6 +207,39 @@
 //! graph.query("RETURN point($p)").with_param("p", coords).execute()?;
 //! ```
 //!
+//! With the `derive` feature, a struct can carry a query's parameters. `#[derive(IntoFalkorParams)]`
+//! binds one parameter per field, and `#[derive(IntoFalkorParam)]` encodes the struct as a single map
+//! parameter, so a `Vec` of them can feed an `UNWIND`. Fields can be renamed with
+//! `#[falkordb(rename = "...")]` or left out with `#[falkordb(skip)]`:
+//!
+//! ```ignore
+//! use falkordb::{IntoFalkorParam, IntoFalkorParams};
+//!
+//! #[derive(IntoFalkorParams)]
+//! struct NewUser {
+//!     name: String,
+//!     age: i64,
+//!     tags: Vec<String>,
+//!     #[falkordb(skip)]
+//!     password_hash: String,
+//! }
+//!
+//! #[derive(IntoFalkorParam)]
+//! struct Rating {
+//!     title: String,
+//!     stars: u8,
+//! }
+//!
+//! graph
+//!     .query("CREATE (:User {name: $name, age: $age, tags: $tags})")
+//!     .with_params(&new_user)
+//!     .execute()?;
+//! graph
+//!     .query("UNWIND $ratings AS r MATCH (m:Movie {title: r.title}) SET m.stars = r.stars")
+//!     .with_param("ratings", ratings) // a Vec<Rating>
+//!     .execute()?;
+//! ```
+//!
 //! If you really need a raw Cypher expression, `with_raw_param("key", "…")` is the explicit escape
 //! hatch — no escaping is applied to the value (the parameter name is still validated).
 //!
@@ -206,6 +251,39 @@
 //! cannot be bound back as parameters — build them in the query with the matching Cypher function (e.g.
 //! `date($s)`). A runnable version lives in [`examples/temporal.rs`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/temporal.rs).
 //!
+//! With the `chrono`, `time` or `jiff` feature, the matching types of those crates (for example
+//! `chrono::NaiveDate` or `jiff::Timestamp`) convert to and from these values with `From`/`TryFrom`,
+//! read straight from a column with `try_get`, and bind as parameters wrapped in the Cypher
+//! function of the matching type:
+//!
+//! ```ignore
+//! let row = graph
+//!     .query("RETURN date($day) AS day")
+//!     .with_param("day", chrono::NaiveDate::from_ymd_opt(1947, 11, 29).unwrap())
+//!     .execute()?
+//!     .data
+//!     .next()
+//!     .unwrap()?;
+//! let day: chrono::NaiveDate = row.try_get("day")?;
+//! ```
+//!
+//! For radius searches, `nodes_within_radius(label, property, center, radius_meters)` on a graph
+//! builds the `distance(n.property, point($center)) <= $radius` query for you: the label and
+//! property are quoted as identifiers and the center and radius are bound as parameters. Each row
+//! carries the `node` and its `distance` in meters, nearest first. With the `geo-types` feature,
+//! `geo_types::Point<f64>` (`x` is the longitude) converts to and from `Point`, reads from a point
+//! column with `try_get` and binds as a parameter wrapped in `point($p)`:
+//!
+//! ```ignore
+//! let depots = graph
+//!     .nodes_within_radius("Depot", "location", geo_types::Point::new(34.78, 32.08), 5_000.0)?
+//!     .execute()?;
+//! for row in depots.data {
+//!     let row = row?;
+//!     let (depot, meters): (Node, f64) = (row.try_get("node")?, row.try_get("distance")?);
+//! }
+//! ```
+//!
 //! #### Typed result mapping with serde
 //!
 //! Enable the optional `serde` feature to map query results straight into your own types instead of hand-matching every
@@ -269,6 +347,60 @@
 //! or yields the values in order for a tuple. The query `header` and `stats` remain available on the
 //! returned result.
 //!
+//! The same feature works in the other direction. `FalkorValue`, `Row`, the graph entities and the
+//! temporal types implement `serde::Serialize` with a stable shape, so results can be returned
+//! as JSON directly. Nodes become `{"id", "labels", "properties"}`, edges become
+//! `{"id", "relationship_type", "src_node_id", "dst_node_id", "properties"}`, and a `Row` becomes a
+//! map from column to value. Maps and properties are written in key order. Wrap any `Serialize`
+//! value in `SerdeParam` to bind it as a parameter with the usual escaping:
+//!
+//! ```ignore
+//! use falkordb::SerdeParam;
+//!
+//! let mut result = graph
+//!     .query("CREATE (m:Movie) SET m = $movie RETURN m")
+//!     .with_param("movie", SerdeParam(&new_movie)) // any `Serialize` struct
+//!     .execute()?;
+//! let rows: Vec<Row> = result.data.by_ref().collect::<Result<_, _>>()?;
+//! let body = serde_json::to_string(&rows)?;
+//! ```
+//!
+//! #### Typed result mapping without serde
+//!
+//! The optional `derive` feature maps rows and nodes onto your own structs through the same strict
+//! `FromFalkorValue` conversions as `Row::try_get`, without serde. `#[derive(FromRow)]` reads each
+//! field from the column of the same name, and `#[derive(FromNode)]` reads each field from a node
+//! property, so a `FromNode` struct can itself be a field of a `FromRow` struct:
+//!
+//! ```ignore
+//! use falkordb::{FromNode, FromRow};
+//!
+//! #[derive(FromNode)]
+//! struct Actor {
+//!     name: String,
+//!     #[falkordb(rename = "born")]
+//!     birth_year: Option<i64>, // an absent property is `None`
+//! }
+//!
+//! #[derive(FromRow)]
+//! struct Casting {
+//!     actor: Actor, // a node column
+//!     #[falkordb(rename = "movie_title")]
+//!     title: String,
+//! }
+//!
+//! let castings = graph
+//!     .query("MATCH (a:Actor)-[:ACTED_IN]->(m:Movie) RETURN a AS actor, m.title AS movie_title")
+//!     .execute()?
+//!     .data
+//!     .map(|row| row.and_then(Casting::from_row))
+//!     .collect::<Result<Vec<_>, _>>()?;
+//! ```
+//!
+//! A missing column fails with `FalkorDBError::MissingColumn`, a missing non-`Option` property with
+//! `FalkorDBError::MissingProperty`, and a value of the wrong type with `FalkorDBError::TypeError`.
+//! Tuple structs deriving `FromRow` read their fields by column index.
+//!
 //! ### Async
 //!
 //! #### tokio support
@@ -371,6 +503,12 @@
 //!
 //! A runnable version lives in [`examples/async_stream.rs`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/async_stream.rs).
 //!
+//! Every query arrives as a single reply by default. To bound memory for large ordered reads, opt in
+//! with `FalkorClientBuilder::with_stream_page_size`: an `ro_query` whose final `RETURN` has an
+//! `ORDER BY` (and no trailing `SKIP`/`LIMIT`, `UNION` or `CALL`) is then paged like `paginate`, the
+//! stream holding one page at a time. Each page re-runs the query, and pages are not one snapshot:
+//! concurrent writes or rows tied on the sort key can repeat or go missing between pages.
+//!
 //! #### Connection strategy and multiplexing
 //!
 //! The asynchronous client chooses how it manages its underlying Redis connections via a
@@ -416,10 +554,11 @@
 //! - **Backpressure:** multiplexed mode does not bound the number of outstanding requests
 //!   unless you set `with_max_inflight(n)` (where `n` is a `NonZeroUsize`; ignored by the
 //!   pooled strategy, whose pool size already caps in-flight commands).
-//! - **Sentinel:** a multiplexed connection built from a Sentinel-resolved node would not
-//!   re-resolve the master/replica on failover, so for Sentinel deployments the client
-//!   transparently falls back to the pooled strategy (which re-resolves on reconnect).
-//!   `connection_strategy()` returns this *effective* strategy.
+//! - **Sentinel:** multiplexed connections are resolved through Sentinel. When a command finds
+//!   its node down or demoted (`READONLY`) after a failover, the client asks Sentinel for the
+//!   current master/replica and swaps in new connections; callers already in flight finish on
+//!   the old ones. The failing command itself still returns `ConnectionDown`, so a configured
+//!   `RetryPolicy` retries eligible reads against the new node.
 //!
 //! A runnable example is provided in [`examples/multiplexed_async.rs`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/multiplexed_async.rs).
 //!
@@ -463,7 +602,15 @@
 //!   batch could not be completed — and if that happens *after* the pipeline was sent, the server may
 //!   have run some or all queries (the state is unknown), which matters for writes.
 //! - **Not a transaction.** A pipeline is not `MULTI`/`EXEC`: every queued query is executed, so a
-//!   failure in one does **not** roll back or stop the others.
+//!   failure in one does **not** roll back or stop the others. Call `batch.atomic()` to send the
+//!   queries as one `MULTI`/`EXEC` transaction instead: it returns `AtomicBatchOutcome::Committed`
+//!   with the per-query results, or `AtomicBatchOutcome::Aborted` when nothing was applied (because
+//!   the server rejected one of the queued queries).
+//! - **Read-modify-write.** When the writes depend on what you read, use `graph.transaction(|tx| ..)`
+//!   instead: it `WATCH`es the graph, runs the closure's `tx.read(..)`s on the same connection, and
+//!   commits its queued `tx.query(..)` writes with `MULTI`/`EXEC`. If another client changed the graph
+//!   in between, the closure re-runs within the `RetryPolicy` budget, then fails with
+//!   `FalkorDBError::TransactionConflict`.
 //! - **Results are eager.** Each query's rows are parsed up front into a `Vec<Row>` (the same `Row` as
 //!   elsewhere), since many result sets coexist in one batch.
 //! - **Owned queries.** To build queries ahead of time, construct `BatchQuery::write(..)` /
@@ -471,6 +618,236 @@
 //!
 //! A runnable version lives in [`examples/batch.rs`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/batch.rs).
 //!
+//! #### Bulk loading
+//!
+//! For large initial imports, `client.bulk_loader("graph")` creates a new graph through FalkorDB's
+//! `GRAPH.BULK` command, which takes binary-encoded nodes and relationships instead of Cypher and is
+//! much faster than `UNWIND` batches. `add_node` returns a `BulkNodeId` to connect relationships to;
+//! property values are `BulkValue`s (scalars, strings, `Option` and arrays), built with `From`:
+//!
+//! ```ignore
+//! let mut loader = client.bulk_loader("social");
+//! let alice = loader.add_node(&["Person"], [("name", BulkValue::from("Alice")), ("age", 33.into())])?;
+//! let bob = loader.add_node(&["Person"], [("name", BulkValue::from("Bob")), ("age", 35.into())])?;
+//! loader.add_relationship("KNOWS", alice, bob, [("since", 2020)])?;
+//! let report = loader.finish()?; // BulkLoadReport: totals and per-label counts
+//! ```
+//!
+//! Entities are encoded as they are added and sent in chunks of at most 32 MiB (see
+//! `with_max_chunk_bytes`), so memory stays bounded; `finish` sends the last chunk. The first chunk
+//! creates the graph, so loading into a graph that already exists fails. Each chunk commits on its
+//! own: after an error, `loader.report()` describes what was written. The async client's
+//! `AsyncBulkLoader` is the same with `.await`.
+//!
+//! #### Chunked `UNWIND` writes
+//!
+//! To write many records into an existing graph, `graph.unwind_writer(template, chunk_size)` (where
+//! `chunk_size` is a `NonZeroUsize`) groups them into `$rows` list parameters, runs
+//! `UNWIND $rows AS row <template>` once per chunk over pipelined batches, and sums the statistics
+//! of every chunk into an `UnwindReport`. Records are any `IntoFalkorParam` type, such as maps or
+//! structs deriving `IntoFalkorParam`:
+//!
+//! ```ignore
+//! let people = (0..100_000).map(|id| BTreeMap::from([("id", id)]));
+//! let report = graph
+//!     .unwind_writer("MERGE (:Person {id: row.id})", NonZeroUsize::new(1_000).unwrap())
+//!     .with_chunks_per_batch(NonZeroUsize::new(8).unwrap()) // chunks per pipelined round-trip
+//!     .write_all(people)?;
+//! println!("{} nodes created over {} chunks", report.nodes_created, report.chunks);
+//! ```
+//!
+//! When a chunk fails, no further batch is sent and the error is
+//! `FalkorDBError::UnwindChunkFailed`, naming the chunk and its rows; `writer.report()` sums the
+//! chunks that were applied. On `AsyncGraph`, `write_all` and `write_stream` (for a `Stream` of
+//! records) can keep several batches in flight with `with_concurrency`.
+//!
+//! #### CSV import
+//!
+//! With the `csv` feature, `falkordb::import::csv` loads CSV files with typed column mappings. A
+//! `NodeMapping` names a file's labels, its columns (`int`, `float`, `bool`, `string`, `point`,
+//! `vecf32` or `date`) and an optional key property; a `RelationshipMapping` resolves each row's
+//! endpoints by that key. `CsvImport` then writes everything through `GRAPH.BULK` (`write_bulk`, for
+//! a new graph) or chunked `UNWIND` queries (`write_unwind`, for any graph):
+//!
+//! ```ignore
+//! use falkordb::import::csv::{ColumnType, CsvImport, Endpoint, NodeMapping, RelationshipMapping};
+//!
+//! let people = NodeMapping::new("Person")
+//!     .with_column("id", ColumnType::Int)
+//!     .with_column("name", ColumnType::String)
+//!     .with_key("id");
+//! let knows = RelationshipMapping::new(
+//!     "KNOWS",
+//!     Endpoint::new("from", "Person", "id", ColumnType::Int),
+//!     Endpoint::new("to", "Person", "id", ColumnType::Int),
+//! );
+//! let import = || {
+//!     CsvImport::new()
+//!         .with_node_file(people.clone(), "people.csv")
+//!         .with_relationship_file(knows.clone(), "knows.csv")
+//! };
+//! let chunk_size = NonZeroUsize::new(1_000).unwrap();
+//! import().dry_run().write_unwind(&mut graph, chunk_size)?; // check every file, write nothing
+//! let report = import().write_unwind(&mut graph, chunk_size)?;
+//! ```
+//!
+//! Errors (`FalkorDBError::CsvImport`) name the file and line at fault, such as a cell that does not
+//! parse, a duplicate key or a relationship to a node the import does not contain.
+//!
+//! #### Cypher dump export
+//!
+//! `falkordb::export::cypher` writes a whole graph to any `std::io::Write` as a deterministic Cypher
+//! script: its indices, its constraints, then every node and relationship in id order, batched into
+//! `CREATE` and `UNWIND` statements and read through keyset-paged `MATCH` queries. `replay` loads the
+//! script into another graph, on the same or another instance:
+//!
+//! ```ignore
+//! use falkordb::export::cypher::{self, CypherExporter};
+//!
+//! let mut dump = Vec::new();
+//! let exported = CypherExporter::new()
+//!     .with_page_size(5_000)
+//!     .export(&mut graph, &mut dump)?;
+//! let replayed = cypher::replay(&mut client.select_graph("copy"), dump.as_slice())?;
+//! assert_eq!(replayed.nodes_created as usize, exported.nodes);
+//! ```
+//!
+//! Each statement is a single line ending with `;`. Constraints, which have no Cypher syntax, are
+//! written as `// CONSTRAINT …` comment lines that `replay` runs through `GRAPH.CONSTRAINT CREATE`.
+//!
+//! #### JSON Lines and GraphML
+//!
+//! With the `jsonl` and `graphml` features, `falkordb::export` writes a graph as JSON Lines (one node or
+//! relationship object per line, fields named after `Node` and `Edge`) or as a GraphML document, and
+//! `falkordb::import` loads either back. Both directions stream: exports read keyset-paged `MATCH`
+//! queries, imports write batched statements, and property types JSON and GraphML lack — points,
+//! `vecf32` vectors, dates, times and durations — survive the round trip:
+//!
+//! ```ignore
+//! use falkordb::export::graphml::GraphmlExporter;
+//! use falkordb::import::graphml::GraphmlImporter;
+//!
+//! let mut document = Vec::new();
+//! GraphmlExporter::new().export(&mut graph, &mut document)?;
+//! let report = GraphmlImporter::new()
+//!     .with_batch_size(500)
+//!     .import(&mut client.select_graph("copy"), document.as_slice())?;
+//! ```
+//!
+//! Imported nodes get new ids; the ids of the file only connect its relationships, which must come
+//! after both of their nodes.
+//!
+//! #### Schema migrations
+//!
+//! With the `migrations` feature, `falkordb::migrations` applies ordered, versioned migrations — Cypher
+//! statements and constraints — to a graph once each. Every applied migration is recorded as a
+//! `__FalkorMigration` node with the SHA-256 checksum of its steps; the runner refuses to run when an
+//! applied migration was edited since, and `dry_run` returns the `GRAPH.EXPLAIN` plan of each pending
+//! statement instead of running it:
+//!
+//! ```ignore
+//! use falkordb::migrations::{Migration, MigrationRunner};
+//! use falkordb::{EntityType, WaitOptions};
+//!
+//! let runner = MigrationRunner::new()
+//!     .with_migration(
+//!         Migration::new(1, "people")
+//!             .query("CREATE INDEX FOR (p:Person) ON (p.name)")
+//!             .unique_constraint(EntityType::Node, "Person", &["email"]),
+//!     )
+//!     .with_migration(Migration::from_script(2, "backfill", include_str!("002_backfill.cypher")))
+//!     .wait_for_schema(WaitOptions::default());
+//! runner.dry_run(&mut graph)?;
+//! let report = runner.run(&mut graph)?;
+//! ```
+//!
+//! With `wait_for_schema`, each migration waits for its constraints to be enforced and for the
+//! graph's indices to be operational before the next one starts.
+//!
+//! #### Declarative schema reconciliation
+//!
+//! [`DesiredSchema`] lists the indices and constraints a graph should have; `reconcile_schema` diffs
+//! it against `DB.INDEXES` and `DB.CONSTRAINTS` and returns a [`SchemaPlan`] of index and constraint
+//! actions, which prints as the statements it runs. Rerunning it against a matching graph plans
+//! nothing, so deployments can run it every time instead of a script that fails once applied:
+//!
+//! ```ignore
+//! use falkordb::{DesiredSchema, EntityType, VectorSimilarity, WaitOptions};
+//!
+//! let desired = DesiredSchema::new()
+//!     .unique_constraint(EntityType::Node, "Person", &["email"])
+//!     .mandatory_constraint(EntityType::Node, "Person", &["name"])
+//!     .fulltext_index(EntityType::Node, "Movie", &["title"])
+//!     .vector_index(EntityType::Node, "Movie", "embedding", 384, VectorSimilarity::Cosine);
+//! print!("{}", graph.reconcile_schema(&desired).plan()?);
+//! graph.reconcile_schema(&desired).wait_with(WaitOptions::default())?;
+//! ```
+//!
+//! Indices and constraints the definition does not list are dropped unless it calls
+//! `retain_unlisted()`; a vector index whose dimension or similarity function changed, and a
+//! constraint that failed, are dropped and created again.
+//!
+//! #### Schema introspection
+//!
+//! `describe()` reads a graph's schema with read-only queries: each label and relationship type with
+//! its entity count, the property keys seen on it with the types of their values (as Cypher's
+//! `typeOf` names them) and how often each occurs, the label pairs each relationship type connects,
+//! and the indices and constraints attached to it. On large graphs, `with_sample_size` looks at only
+//! the first entities of each label and relationship type for property types and endpoints:
+//!
+//! ```ignore
+//! let description = graph.describe().with_sample_size(10_000).execute()?;
+//! for label in &description.labels {
+//!     println!("{} ({} nodes)", label.name, label.count);
+//!     for property in &label.properties {
+//!         println!("  {}: {:?}", property.name, property.value_types);
+//!     }
+//! }
+//! ```
+//!
+//! #### Code generation
+//!
+//! With the `codegen` feature, `falkordb::codegen` turns the schema `describe()` reads into a Rust
+//! module: a struct per node label and relationship type, a field per property key typed after the
+//! values seen (an `Option` when some entities lack it), `LABEL`/`RELATIONSHIP_TYPE` and property
+//! name constants, and a `FromFalkorValue` implementation (node structs derive `FromNode`, so the
+//! generated code needs the `derive` feature). Check the output in and regenerate it in CI, and a
+//! schema change becomes a diff or a compile error instead of a runtime surprise:
+//!
+//! ```ignore
+//! use falkordb::codegen::CodeGenerator;
+//!
+//! let source = CodeGenerator::new().with_sample_size(10_000).generate(&mut graph)?;
+//! std::fs::write("src/schema.rs", source)?;
+//! ```
+//!
+//! The `falkordb-codegen` binary does the same from the command line:
+//! `cargo install falkordb --features codegen`, then
+//! `falkordb-codegen --url falkor://127.0.0.1:6379 --graph social --output src/schema.rs`.
+//!
+//! #### Fulltext search
+//!
+//! `create_fulltext_index(label, fields, options)` creates a fulltext index with typed settings:
+//! `FulltextIndexOptions` sets the language and stopwords, and each `FulltextField` can carry a
+//! weight, a phonetic matcher or `nostem()` (plain property names work too). `fulltext_search(label,
+//! query)` then returns the matching nodes best first, each row with a `node` and an `f64` `score`.
+//! The query is escaped with `escape_fulltext_query`, so punctuation in user input is searched as
+//! text rather than read as fulltext operators:
+//!
+//! ```ignore
+//! use falkordb::{FulltextField, FulltextIndexOptions};
+//!
+//! graph.create_fulltext_index(
+//!     "Movie",
+//!     &[FulltextField::new("title").with_weight(5.0), FulltextField::new("plot").nostem()],
+//!     &FulltextIndexOptions::new().with_language("English"),
+//! )?;
+//! for row in graph.fulltext_search("Movie", "the matrix: reloaded").execute()?.data {
+//!     let row = row?;
+//!     let (movie, score): (Node, f64) = (row.try_get("node")?, row.try_get("score")?);
+//! }
+//! ```
+//!
 //! #### Waiting for background operations
 //!
 //! Some FalkorDB operations finish **after** the command that starts them returns: when you create or
@@ -537,6 +914,21 @@
 //! equivalent). A runnable version lives in
 //! [`examples/vector_index.rs`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/vector_index.rs).
 //!
+//! To search a vector index, `vector_search(label, property, vector, k)` (and `edge_vector_search`
+//! for relationship indexes) takes the query vector as a `&[f32]`, `Vec<f32>` or `Vec32` and
+//! returns the `k` nearest entities as typed `(Node, f64)` / `(Edge, f64)` pairs, nearest first. It
+//! reads `list_indices` first and fails with `FalkorDBError::VectorIndexNotFound` or
+//! `FalkorDBError::VectorDimensionMismatch` instead of sending a query the index cannot serve. An
+//! optional `with_filter` predicate, with its own parameters, runs on the neighbours:
+//!
+//! ```ignore
+//! let hits: Vec<(Node, f64)> = graph
+//!     .vector_search("Product", "embedding", &query_embedding, 10)
+//!     .with_filter("node.in_stock AND node.price < $max_price")
+//!     .with_param("max_price", 50)
+//!     .execute()?;
+//! ```
+//!
 //! ### Connections and networking
 //!
 //! #### TLS support
@@ -664,6 +1056,26 @@
 //! use the primary. See [`examples/readonly_replica.rs`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/readonly_replica.rs)
 //! for a complete working example.
 //!
+//! #### Pinned sessions
+//!
+//! Each command normally borrows whichever pooled connection is free, so connection-scoped state does
+//! not survive from one command to the next. [`FalkorSyncClient::session`] (and the async
+//! `FalkorAsyncClient::session`) pins one connection for the lifetime of the returned session:
+//! graphs selected from it run every query, batch and transaction on that connection. Use it for
+//! `CLIENT SETNAME`, `WATCH`, or reading your own writes without any replica routing.
+//!
+//! ```no_run
+//! use falkordb::FalkorClientBuilder;
+//!
+//! let client = FalkorClientBuilder::new().build().expect("Failed to build client");
+//! let session = client.session().expect("Failed to open a session");
+//! session.set_client_name("importer").expect("Failed to name the connection");
+//!
+//! let mut graph = session.select_graph("imdb");
+//! graph.query("CREATE (:Actor {name: 'Meg Ryan'})").execute().expect("Failed to write");
+//! // The connection returns to the pool once `session` and `graph` are dropped.
+//! ```
+//!
 //! ### Resilience and observability
 //!
 //! #### Automatic retries
@@ -742,6 +1154,9 @@
 //! | `error.type` | `connection_down` | a bounded error kind, recorded on failure |
 //! | `db.response.returned_rows` | `42` | rows the server returned (on the outer `execute` span) |
 //! | `db.falkordb.server_time_ms` | `1.18` | the server's internal execution time, when reported |
+//! | `otel.kind` | `client` | the OpenTelemetry span kind |
+//! | `otel.name` | `GRAPH.QUERY` | the exported span name: the wire command, from a bounded allowlist |
+//! | `otel.status_code` | `error` | recorded on failure |
 //!
 //! **Privacy by default.** The raw query text and parameter values are **never** recorded by default —
 //! only the `db.query.fingerprint`, which is a hash of the query with all literals (strings, numbers,
@@ -771,6 +1186,26 @@
 //!
 //! See [`examples/observability.rs`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/observability.rs) for a complete, runnable example.
 //!
+//! #### OpenTelemetry trace context
+//!
+//! The `opentelemetry` feature (which implies `tracing`) connects the client spans to the work they
+//! cause. Install a [`tracing-opentelemetry`](https://docs.rs/tracing-opentelemetry) layer and the
+//! fields above become OpenTelemetry client-span attributes; opt in with `with_trace_context` and each
+//! query sent through `query`, `ro_query` or `call_procedure` (and the helpers built on them) ends
+//! with the W3C `traceparent` of the current span as a Cypher comment:
+//!
+//! ```ignore
+//! let client = FalkorClientBuilder::new()
+//!     .with_trace_context(true) // appends /*traceparent='00-<trace-id>-<span-id>-01'*/
+//!     .build()?;
+//! ```
+//!
+//! The comment shows up wherever the server exposes the query text, such as `GRAPH.SLOWLOG` and
+//! `MONITOR`, so a slow query can be followed back to the request that sent it. It is off by default
+//! because it makes every query text unique, which keeps FalkorDB's query plan cache from reusing
+//! plans. Batches and transactions are not annotated, and nothing is appended outside an
+//! OpenTelemetry span.
+//!
 //! #### Metrics
 //!
 //! Enable the `metrics` feature to emit counters and histograms through the
@@ -804,6 +1239,43 @@
 //! // ... use the client; metrics are now exported on the configured endpoint.
 //! ```
 //!
+//! #### Query statistics
+//!
+//! The `query-stats` feature keeps an in-process registry of the queries the client runs, grouped by
+//! the same redacted fingerprint the `tracing` spans carry. Opt in on the builder and read it back at
+//! runtime, e.g. from a "top queries" debug endpoint:
+//!
+//! ```ignore
+//! use falkordb::{QueryStatsConfig, QueryStatsOrder};
+//! use std::time::Duration;
+//!
+//! let client = FalkorClientBuilder::new()
+//!     .with_query_stats(QueryStatsConfig::new().with_slow_threshold(Duration::from_millis(250)))
+//!     .build()?;
+//!
+//! // ... run queries ...
+//!
+//! let stats = client.query_stats().expect("enabled on the builder");
+//! for entry in stats.top(10, QueryStatsOrder::TotalTime) {
+//!     println!(
+//!         "{} calls={} errors={} p99={:?} rows={} cache hits={:?}: {}",
+//!         entry.fingerprint, entry.calls, entry.errors, entry.p99, entry.rows_returned,
+//!         entry.cache_hit_ratio(), entry.query,
+//!     );
+//! }
+//! for slow in stats.slow_queries() {
+//!     println!("{:?} {} plan={:?}", slow.duration, slow.query, slow.plan);
+//! }
+//! ```
+//!
+//! Each fingerprint tracks its calls, errors, rows returned, plan cache hits (the server's `Cached
+//! execution` statistic) and p50/p95/p99/max latencies over its most recent calls. Calls at or above the
+//! slow threshold also go to a bounded slow query log; the first slow call of each fingerprint is
+//! followed by a `GRAPH.EXPLAIN`, and the plan is attached to the fingerprint and its slow log entries.
+//! Only redacted query text is stored, latencies are client-side round trips (retries included), and
+//! every collection is capped by `QueryStatsConfig`. `explain`, `profile` and procedure calls are not
+//! recorded.
+//!
 //! #### Actionable error hints
 //!
 //! `FalkorDBError::mitigation_hint()` turns common, recognizable failures into a short, actionable
@@ -947,7 +1419,7 @@
 //! | [`typed_params`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/typed_params.rs) | Type-safe, injection-proof query parameters | `--example typed_params` |
 //! | [`typed_mapping`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/typed_mapping.rs) | Map query results into your own `serde` types | `--features serde --example typed_mapping` |
 //! | [`temporal`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/temporal.rs) | Decode temporal values and use the type-safe `DateTime`/`Duration` algebra | `--example temporal` |
-//! | [`vector_index`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/vector_index.rs) | Create vector indexes with the typed helpers and `VectorSimilarity` | `--example vector_index` |
+//! | [`vector_index`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/vector_index.rs) | Create vector indexes with the typed helpers and `VectorSimilarity`, then search them | `--example vector_index` |
 //! | [`batch`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/batch.rs) | Batch / pipelined execution: many queries in one round-trip | `--example batch` |
 //! | [`waiting_ops`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/waiting_ops.rs) | Wait for background index / constraint / copy operations to take effect | `--example waiting_ops` |
 //! | [`udf_usage`](https://github.com/FalkorDB/falkordb-rs/blob/main/examples/udf_usage.rs) | Load a user-defined-function (UDF) library | `--example udf_usage` |
@@ -983,17 +1455,27 @@
 //!
 //! Licensed under the [MIT License](https://github.com/FalkorDB/falkordb-rs/blob/main/LICENSE).
 
+mod bulk;
 mod client;
+#[cfg(feature = "codegen")]
+pub mod codegen;
 mod connection;
 mod connection_info;
 #[cfg(feature = "embedded-core")]
 mod embedded;
 mod error;
+pub mod export;
 mod graph;
 mod graph_schema;
-#[cfg(any(feature = "tracing", feature = "metrics"))]
+#[cfg(any(feature = "csv", feature = "jsonl", feature = "graphml"))]
+pub mod import;
+#[cfg(feature = "migrations")]
+pub mod migrations;
+#[cfg(any(feature = "tracing", feature = "metrics", feature = "query-stats"))]
 mod observability;
 mod parser;
+#[cfg(feature = "query-stats")]
+mod query_stats;
 mod response;
 mod retry;
 mod value;
@@ -1001,16 +1483,32 @@ mod value;
 /// A [`Result`] which only returns [`FalkorDBError`] as its E type
 pub type FalkorResult<T> = Result<T, FalkorDBError>;
 
+pub use bulk::{blocking::BulkLoader, BulkLoadReport, BulkNodeId, BulkValue};
 pub use client::{
-    blocking::FalkorSyncClient, builder::FalkorClientBuilder, ConnectionStrategy, ReadPreference,
+    blocking::{FalkorSyncClient, FalkorSyncSession},
+    builder::FalkorClientBuilder,
+    ConnectionStrategy, ReadPreference,
 };
 pub use connection_info::FalkorConnectionInfo;
 pub use error::FalkorDBError;
 pub use graph::{
-    batch::{BatchBuilder, BatchItemResult, BatchQuery, BatchResult},
+    batch::{
+        AbortReason, AtomicBatchBuilder, AtomicBatchOutcome, AtomicBatchResult, BatchBuilder,
+        BatchItemResult, BatchQuery, BatchResult,
+    },
     blocking::SyncGraph,
+    describe::{
+        DescribeBuilder, EndpointDescription, GraphDescription, LabelDescription,
+        PropertyDescription, RelationshipTypeDescription, ValueTypeCount,
+    },
+    fulltext::{escape_fulltext_query, FulltextField, FulltextIndexOptions},
     ops::{ConstraintOpBuilder, CopyGraphBuilder, IndexOpBuilder, WaitOperation, WaitOptions},
+    paginate::{PagedResultSet, PaginatedQueryBuilder},
     query_builder::{ProcedureQueryBuilder, QueryBuilder},
+    reconcile::{DesiredSchema, SchemaAction, SchemaPlan, SchemaReconcileBuilder},
+    transaction::{Transaction, TransactionOutput},
+    unwind::{UnwindReport, UnwindWriter},
+    vector::VectorSearchBuilder,
     VectorSimilarity,
 };
 pub use graph_schema::{GraphSchema, SchemaType};
@@ -1019,7 +1517,7 @@ pub use response::{
     execution_plan::ExecutionPlan,
     index::{FalkorIndex, IndexStatus, IndexType},
     lazy_result_set::LazyResultSet,
-    row::Row,
+    row::{FromRow, Row},
     slowlog_entry::SlowlogEntry,
     QueryResult,
 };
@@ -1030,10 +1528,42 @@ pub use value::{
     path::Path,
     point::Point,
     temporal::{Date, DateTime, Duration, Seconds, Time},
-    to_cypher_param, FalkorParams, FalkorValue, FromFalkorValue, IntoFalkorParam, IntoFalkorParams,
+    to_cypher_param,
+    vec32::Vec32,
+    FalkorParams, FalkorValue, FromFalkorValue, FromNode, IntoFalkorParam, IntoFalkorParams,
     RawParam,
 };
 
+#[cfg(feature = "derive")]
+pub use falkordb_derive::{FromNode, FromRow, IntoFalkorParam, IntoFalkorParams};
+
+/// Support items for the `falkordb-derive` expansions. Not part of the public API.
+///
+/// The sealed supertraits of `IntoFalkorParam` and `IntoFalkorParams` are not reachable from here,
+/// so a hand-written impl cannot bypass the encoder:
+///
+/// ```compile_fail,E0405
+/// struct Evil;
+/// impl falkordb::__private::Sealed for Evil {}
+/// ```
+///
+/// ```compile_fail,E0277
+/// struct Evil;
+/// impl falkordb::IntoFalkorParam for Evil {
+///     fn encode_param(&self, out: &mut String) -> falkordb::FalkorResult<()> {
+///         out.push_str("1 MATCH (n) DETACH DELETE n");
+///         Ok(())
+///     }
+/// }
+/// ```
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::value::param::derive_support::{
+        EncodeParam, EncodeParams, ParamWriter, ParamsWriter,
+    };
+}
+
 #[cfg(feature = "tokio")]
 pub use response::row_stream::RowStream;
 #[cfg(feature = "serde")]
@@ -1041,18 +1571,31 @@ pub use response::typed_result_set::TypedLazyResultSet;
 #[cfg(all(feature = "serde", feature = "tokio"))]
 pub use response::typed_row_stream::TypedRowStream;
 #[cfg(feature = "serde")]
-pub use value::{from_falkor_row, from_falkor_value, FalkorValueDeserializer};
+pub use value::{from_falkor_row, from_falkor_value, FalkorValueDeserializer, SerdeParam};
 
 #[cfg(feature = "tokio")]
-pub use client::asynchronous::FalkorAsyncClient;
+pub use bulk::asynchronous::AsyncBulkLoader;
+#[cfg(feature = "tokio")]
+pub use client::asynchronous::{FalkorAsyncClient, FalkorAsyncSession};
 #[cfg(feature = "tokio")]
 pub use graph::asynchronous::AsyncGraph;
 #[cfg(feature = "tokio")]
+pub use graph::describe::AsyncDescribeBuilder;
+#[cfg(feature = "tokio")]
 pub use graph::ops::{AsyncConstraintOpBuilder, AsyncCopyGraphBuilder, AsyncIndexOpBuilder};
+#[cfg(feature = "tokio")]
+pub use graph::reconcile::AsyncSchemaReconcileBuilder;
+#[cfg(feature = "tokio")]
+pub use graph::transaction::AsyncTransaction;
+#[cfg(feature = "tokio")]
+pub use graph::vector::AsyncVectorSearchBuilder;
 
 #[cfg(feature = "embedded-core")]
 pub use embedded::{EmbeddedConfig, EmbeddedServer};
 
+#[cfg(feature = "query-stats")]
+pub use query_stats::{QueryStats, QueryStatsConfig, QueryStatsEntry, QueryStatsOrder, SlowQuery};
+
 #[cfg(test)]
 pub(crate) mod test_utils {
     use super::*;
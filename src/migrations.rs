@@ -0,0 +1,945 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Versioned schema migrations: apply an ordered list of Cypher migrations to a graph, once each.
+//!
+//! A [`Migration`] has a version, a description and a list of steps: Cypher statements (index
+//! creation, data backfills, …) and constraints, which have no Cypher syntax. A
+//! [`MigrationRunner`] applies the migrations a graph has not seen yet in version order, and
+//! records each one as a [`HISTORY_LABEL`] node holding its version, description and the SHA-256
+//! checksum of its steps:
+//!
+//! ```no_run
+//! # fn main() -> Result<(), falkordb::FalkorDBError> {
+//! use falkordb::migrations::{Migration, MigrationRunner};
+//! use falkordb::{EntityType, FalkorClientBuilder, WaitOptions};
+//! # let info: falkordb::FalkorConnectionInfo = "falkor://127.0.0.1:6379".try_into()?;
+//! let client = FalkorClientBuilder::new().with_connection_info(info).build()?;
+//!
+//! let runner = MigrationRunner::new()
+//!     .with_migration(
+//!         Migration::new(1, "people")
+//!             .query("CREATE INDEX FOR (p:Person) ON (p.name)")
+//!             .unique_constraint(EntityType::Node, "Person", &["email"]),
+//!     )
+//!     .with_migration(
+//!         Migration::new(2, "normalize emails")
+//!             .query("MATCH (p:Person) SET p.email = toLower(p.email)"),
+//!     )
+//!     .wait_for_schema(WaitOptions::default());
+//! let report = runner.run(&mut client.select_graph("social"))?;
+//! println!("applied {:?}", report.applied);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Before applying anything, the runner compares the recorded history with the migrations it was
+//! given, and refuses to run when an applied migration was edited since
+//! ([`FalkorDBError::MigrationChecksumMismatch`]), is missing, or when a new migration is older
+//! than one already applied. [`MigrationRunner::dry_run`] performs the same checks and returns the
+//! `GRAPH.EXPLAIN` plan of each pending statement without changing the graph.
+//!
+//! A migration is recorded only once all of its steps succeed, but its steps are not atomic: when
+//! one fails ([`FalkorDBError::MigrationFailed`]), the steps before it stay applied, so steps are
+//! best written to be safe to rerun. Runners must not run concurrently against the same graph.
+
+use crate::graph::ops::{self, property_refs};
+use crate::{
+    ConstraintType, EntityType, FalkorDBError, FalkorResult, SyncGraph, WaitOperation, WaitOptions,
+};
+use sha2::{Digest, Sha256};
+use std::fmt::{Display, Write as _};
+use std::sync::OnceLock;
+
+/// The label of the nodes recording applied migrations, one node per migration.
+pub const HISTORY_LABEL: &str = "__FalkorMigration";
+
+const HISTORY_QUERY: &str = "MATCH (m:__FalkorMigration) RETURN m.version AS version, \
+     m.description AS description, m.checksum AS checksum, m.applied_at AS applied_at \
+     ORDER BY m.version";
+const RECORD_QUERY: &str = "CREATE (:__FalkorMigration {version: $version, \
+     description: $description, checksum: $checksum, applied_at: timestamp()})";
+
+/// Whether `query` creates or drops an index, which `GRAPH.EXPLAIN` has no plan for.
+fn is_index_statement(query: &str) -> bool {
+    static INDEX_STATEMENT: OnceLock<regex::Regex> = OnceLock::new();
+    INDEX_STATEMENT
+        .get_or_init(|| {
+            regex::Regex::new(r"(?i)^\s*(CREATE|DROP)\s+(\w+\s+)?INDEX\b")
+                .expect("the index statement regex is a valid pattern")
+        })
+        .is_match(query)
+}
+
+/// One step of a [`Migration`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MigrationStep {
+    /// A Cypher statement, run with `GRAPH.QUERY`.
+    Query(String),
+    /// A constraint created with `GRAPH.CONSTRAINT CREATE`.
+    CreateConstraint {
+        /// Whether the constraint is unique or mandatory.
+        constraint_type: ConstraintType,
+        /// Whether the constraint applies to nodes or relationships.
+        entity_type: EntityType,
+        /// The label or relationship type it applies to.
+        label: String,
+        /// The properties it applies to.
+        properties: Vec<String>,
+    },
+    /// A constraint dropped with `GRAPH.CONSTRAINT DROP`.
+    DropConstraint {
+        /// Whether the constraint is unique or mandatory.
+        constraint_type: ConstraintType,
+        /// Whether the constraint applies to nodes or relationships.
+        entity_type: EntityType,
+        /// The label or relationship type it applies to.
+        label: String,
+        /// The properties it applies to.
+        properties: Vec<String>,
+    },
+}
+
+impl MigrationStep {
+    /// The text of the step that the checksum covers.
+    fn canonical(&self) -> String {
+        match self {
+            MigrationStep::Query(query) => format!("QUERY {query}"),
+            MigrationStep::CreateConstraint {
+                constraint_type,
+                entity_type,
+                label,
+                properties,
+            } => format!(
+                "CREATE CONSTRAINT {constraint_type} {entity_type} {label:?} {properties:?}"
+            ),
+            MigrationStep::DropConstraint {
+                constraint_type,
+                entity_type,
+                label,
+                properties,
+            } => {
+                format!("DROP CONSTRAINT {constraint_type} {entity_type} {label:?} {properties:?}")
+            }
+        }
+    }
+}
+
+/// A versioned list of steps, applied to a graph once by a [`MigrationRunner`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Migration {
+    version: u64,
+    description: String,
+    steps: Vec<MigrationStep>,
+}
+
+impl Migration {
+    /// Creates a migration with no steps.
+    ///
+    /// # Arguments
+    /// * `version`: Orders the migrations; each must be unique and at most `i64::MAX`.
+    /// * `description`: A short human-readable summary, recorded with the version.
+    pub fn new(
+        version: u64,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            version,
+            description: description.into(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Parses a script of Cypher statements into a migration, one [`MigrationStep::Query`] per
+    /// statement. A statement ends with a line ending in `;` (or with the script), so statements
+    /// may span several lines; each line is trimmed, and lines starting with `//` are comments.
+    ///
+    /// # Arguments
+    /// * `version`: Orders the migrations.
+    /// * `description`: A short human-readable summary.
+    /// * `script`: The statements.
+    pub fn from_script(
+        version: u64,
+        description: impl Into<String>,
+        script: &str,
+    ) -> Self {
+        let mut migration = Self::new(version, description);
+        let mut statement = String::new();
+        for line in script.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+            if !statement.is_empty() {
+                statement.push('\n');
+            }
+            match line.strip_suffix(';') {
+                Some(last) => {
+                    statement.push_str(last.trim_end());
+                    migration = migration.query(std::mem::take(&mut statement));
+                }
+                None => statement.push_str(line),
+            }
+        }
+        if !statement.is_empty() {
+            migration = migration.query(statement);
+        }
+        migration
+    }
+
+    /// Adds a Cypher statement.
+    ///
+    /// # Arguments
+    /// * `query`: The statement, run with `GRAPH.QUERY`.
+    pub fn query(
+        mut self,
+        query: impl Into<String>,
+    ) -> Self {
+        self.steps.push(MigrationStep::Query(query.into()));
+        self
+    }
+
+    /// Adds the creation of a unique constraint, along with the range index backing it, which must
+    /// not exist yet.
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether the constraint applies to nodes or relationships.
+    /// * `label`: The label or relationship type.
+    /// * `properties`: The properties whose combined values must be unique.
+    pub fn unique_constraint<P: Display>(
+        self,
+        entity_type: EntityType,
+        label: &str,
+        properties: &[P],
+    ) -> Self {
+        self.constraint(true, ConstraintType::Unique, entity_type, label, properties)
+    }
+
+    /// Adds the creation of a mandatory constraint.
+    ///
+    /// # Arguments
+    /// * `entity_type`: Whether the constraint applies to nodes or relationships.
+    /// * `label`: The label or relationship type.
+    /// * `properties`: The properties every entity must have.
+    pub fn mandatory_constraint<P: Display>(
+        self,
+        entity_type: EntityType,
+        label: &str,
+        properties: &[P],
+    ) -> Self {
+        self.constraint(
+            true,
+            ConstraintType::Mandatory,
+            entity_type,
+            label,
+            properties,
+        )
+    }
+
+    /// Adds the removal of a constraint.
+    ///
+    /// # Arguments
+    /// * `constraint_type`: Whether the constraint is unique or mandatory.
+    /// * `entity_type`: Whether the constraint applies to nodes or relationships.
+    /// * `label`: The label or relationship type.
+    /// * `properties`: The properties of the constraint.
+    pub fn drop_constraint<P: Display>(
+        self,
+        constraint_type: ConstraintType,
+        entity_type: EntityType,
+        label: &str,
+        properties: &[P],
+    ) -> Self {
+        self.constraint(false, constraint_type, entity_type, label, properties)
+    }
+
+    fn constraint<P: Display>(
+        mut self,
+        create: bool,
+        constraint_type: ConstraintType,
+        entity_type: EntityType,
+        label: &str,
+        properties: &[P],
+    ) -> Self {
+        let label = label.to_string();
+        let properties = ops::owned_properties(properties);
+        self.steps.push(if create {
+            MigrationStep::CreateConstraint {
+                constraint_type,
+                entity_type,
+                label,
+                properties,
+            }
+        } else {
+            MigrationStep::DropConstraint {
+                constraint_type,
+                entity_type,
+                label,
+                properties,
+            }
+        });
+        self
+    }
+
+    /// The version of the migration.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// The description of the migration.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The steps of the migration, in order.
+    pub fn steps(&self) -> &[MigrationStep] {
+        &self.steps
+    }
+
+    /// The lowercase hex SHA-256 of the steps, which changes whenever a step does. The description
+    /// is not part of it.
+    pub fn checksum(&self) -> String {
+        let mut hasher = Sha256::new();
+        for step in &self.steps {
+            let text = step.canonical();
+            hasher.update((text.len() as u64).to_le_bytes());
+            hasher.update(text.as_bytes());
+        }
+        hasher
+            .finalize()
+            .iter()
+            .fold(String::with_capacity(64), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02x}");
+                hex
+            })
+    }
+
+    fn failed(
+        &self,
+        message: impl Into<String>,
+    ) -> FalkorDBError {
+        FalkorDBError::MigrationFailed {
+            version: self.version,
+            message: message.into(),
+        }
+    }
+}
+
+/// A migration recorded as applied to a graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AppliedMigration {
+    /// The version of the migration.
+    pub version: u64,
+    /// The description it had when applied.
+    pub description: String,
+    /// The checksum of its steps when applied.
+    pub checksum: String,
+    /// When it was applied, in milliseconds since the Unix epoch (server clock).
+    pub applied_at: i64,
+}
+
+/// What [`MigrationRunner::run`] did.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MigrationReport {
+    /// The versions applied by this run, in order.
+    pub applied: Vec<u64>,
+    /// How many migrations were already applied.
+    pub already_applied: usize,
+}
+
+/// A pending migration, as [`MigrationRunner::dry_run`] would apply it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MigrationPlan {
+    /// The version of the migration.
+    pub version: u64,
+    /// The description of the migration.
+    pub description: String,
+    /// Each step with its plan.
+    pub steps: Vec<StepPlan>,
+}
+
+/// A step of a pending migration, with its execution plan.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StepPlan {
+    /// The step.
+    pub step: MigrationStep,
+    /// The `GRAPH.EXPLAIN` plan of a query step, one operation per line; `None` for index and
+    /// constraint steps, which have no plan.
+    pub plan: Option<Vec<String>>,
+}
+
+/// Applies [`Migration`]s to graphs; see the [module documentation](self).
+#[derive(Clone, Debug, Default)]
+pub struct MigrationRunner {
+    migrations: Vec<Migration>,
+    wait: Option<WaitOptions>,
+}
+
+impl MigrationRunner {
+    /// Creates a runner with no migrations, which does not wait for indices or constraints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a migration; migrations are applied in version order, whatever the order they are
+    /// added in.
+    ///
+    /// # Arguments
+    /// * `migration`: The migration.
+    pub fn with_migration(
+        mut self,
+        migration: Migration,
+    ) -> Self {
+        self.migrations.push(migration);
+        self
+    }
+
+    /// Adds several migrations; see [`MigrationRunner::with_migration`].
+    ///
+    /// # Arguments
+    /// * `migrations`: The migrations.
+    pub fn with_migrations(
+        mut self,
+        migrations: impl IntoIterator<Item = Migration>,
+    ) -> Self {
+        self.migrations.extend(migrations);
+        self
+    }
+
+    /// Makes every migration wait for its constraints to be enforced, and for every index of the
+    /// graph to be operational, before it is recorded and the next one starts. A constraint that
+    /// fails, or a wait that times out, fails the migration.
+    ///
+    /// # Arguments
+    /// * `options`: How long and how often to poll `DB.INDEXES` and `DB.CONSTRAINTS`.
+    pub fn wait_for_schema(
+        mut self,
+        options: WaitOptions,
+    ) -> Self {
+        self.wait = Some(options);
+        self
+    }
+
+    /// The migrations not applied yet, in version order, after checking `history` against the
+    /// migrations of the runner.
+    fn pending(
+        &self,
+        history: &[AppliedMigration],
+    ) -> FalkorResult<Vec<&Migration>> {
+        let mut migrations = self.migrations.iter().collect::<Vec<_>>();
+        migrations.sort_by_key(|migration| migration.version);
+        for pair in migrations.windows(2) {
+            if pair[0].version == pair[1].version {
+                return Err(pair[1].failed("more than one migration has this version"));
+            }
+        }
+        if let Some(migration) = migrations
+            .iter()
+            .find(|migration| i64::try_from(migration.version).is_err())
+        {
+            return Err(migration.failed("the version is larger than i64::MAX"));
+        }
+
+        for applied in history {
+            let migration = migrations
+                .binary_search_by_key(&applied.version, |migration| migration.version)
+                .map(|index| migrations[index])
+                .map_err(|_| FalkorDBError::MigrationFailed {
+                    version: applied.version,
+                    message: "the migration was applied, but the runner does not have it"
+                        .to_string(),
+                })?;
+            let checksum = migration.checksum();
+            if checksum != applied.checksum {
+                return Err(FalkorDBError::MigrationChecksumMismatch {
+                    version: applied.version,
+                    recorded: applied.checksum.clone(),
+                    current: checksum,
+                });
+            }
+        }
+
+        let latest = history.iter().map(|applied| applied.version).max();
+        let pending = migrations
+            .into_iter()
+            .filter(|migration| {
+                !history
+                    .iter()
+                    .any(|applied| applied.version == migration.version)
+            })
+            .collect::<Vec<_>>();
+        if let (Some(latest), Some(first)) = (latest, pending.first()) {
+            if first.version < latest {
+                return Err(first.failed(format!(
+                    "the migration is older than version {latest}, which is already applied"
+                )));
+            }
+        }
+        Ok(pending)
+    }
+
+    /// Applies every pending migration to `graph`, in version order.
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to migrate.
+    ///
+    /// # Returns
+    /// A [`MigrationReport`], or the first error: [`FalkorDBError::MigrationChecksumMismatch`]
+    /// or [`FalkorDBError::MigrationFailed`] before anything is applied when the history does not
+    /// match the migrations, and [`FalkorDBError::MigrationFailed`] when a step fails.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Run Migrations", skip_all, level = "info")
+    )]
+    pub fn run(
+        &self,
+        graph: &mut SyncGraph,
+    ) -> FalkorResult<MigrationReport> {
+        let history = history(graph)?;
+        let pending = self.pending(&history)?;
+        let mut report = MigrationReport {
+            applied: Vec::with_capacity(pending.len()),
+            already_applied: history.len(),
+        };
+        for migration in pending {
+            self.apply(graph, migration)?;
+            report.applied.push(migration.version);
+        }
+        Ok(report)
+    }
+
+    fn apply(
+        &self,
+        graph: &mut SyncGraph,
+        migration: &Migration,
+    ) -> FalkorResult<()> {
+        for (index, step) in migration.steps.iter().enumerate() {
+            self.apply_step(graph, step)
+                .map_err(|err| migration.failed(format!("step {} failed: {err}", index + 1)))?;
+        }
+        if let Some(options) = &self.wait {
+            ops::poll_sync(options, WaitOperation::IndexCreation, || {
+                Ok(ops::all_indices_ready(&graph.list_indices()?.data))
+            })
+            .map_err(|err| migration.failed(err.to_string()))?;
+        }
+        record(graph.query(RECORD_QUERY), migration)
+            .execute()
+            .map_err(|err| migration.failed(format!("recording the migration failed: {err}")))?;
+        Ok(())
+    }
+
+    fn apply_step(
+        &self,
+        graph: &mut SyncGraph,
+        step: &MigrationStep,
+    ) -> FalkorResult<()> {
+        let builder = match step {
+            MigrationStep::Query(query) => return graph.query(query).execute().map(drop),
+            MigrationStep::CreateConstraint {
+                constraint_type: ConstraintType::Unique,
+                entity_type,
+                label,
+                properties,
+            } => graph.create_unique_constraint_op(*entity_type, label, &property_refs(properties)),
+            MigrationStep::CreateConstraint {
+                constraint_type: ConstraintType::Mandatory,
+                entity_type,
+                label,
+                properties,
+            } => graph.create_mandatory_constraint_op(
+                *entity_type,
+                label,
+                &property_refs(properties),
+            ),
+            MigrationStep::DropConstraint {
+                constraint_type,
+                entity_type,
+                label,
+                properties,
+            } => graph.drop_constraint_op(
+                *constraint_type,
+                *entity_type,
+                label,
+                &property_refs(properties),
+            ),
+        };
+        match &self.wait {
+            Some(options) => builder.wait_with(options.clone()),
+            None => builder.execute().map(drop),
+        }
+    }
+
+    /// Checks the history of `graph` like [`MigrationRunner::run`], then explains the pending
+    /// migrations without applying them.
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to check.
+    ///
+    /// # Returns
+    /// The plan of each pending migration, in version order, or the first error. A statement the
+    /// server cannot parse fails with [`FalkorDBError::MigrationFailed`].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Dry Run Migrations", skip_all, level = "info")
+    )]
+    pub fn dry_run(
+        &self,
+        graph: &mut SyncGraph,
+    ) -> FalkorResult<Vec<MigrationPlan>> {
+        let history = history(graph)?;
+        self.pending(&history)?
+            .into_iter()
+            .map(|migration| {
+                let mut steps = Vec::with_capacity(migration.steps.len());
+                for (index, step) in migration.steps.iter().enumerate() {
+                    let plan = match step {
+                        MigrationStep::Query(query) if !is_index_statement(query) => {
+                            let plan = graph.explain(query).execute().map_err(|err| {
+                                migration.failed(format!("step {} failed: {err}", index + 1))
+                            })?;
+                            Some(plan.plan().to_vec())
+                        }
+                        _ => None,
+                    };
+                    steps.push(StepPlan {
+                        step: step.clone(),
+                        plan,
+                    });
+                }
+                Ok(MigrationPlan {
+                    version: migration.version,
+                    description: migration.description.clone(),
+                    steps,
+                })
+            })
+            .collect()
+    }
+
+    /// Applies every pending migration to `graph`; see [`MigrationRunner::run`].
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to migrate.
+    ///
+    /// # Returns
+    /// A [`MigrationReport`], or the first error.
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Run Migrations Async", skip_all, level = "info")
+    )]
+    pub async fn run_async(
+        &self,
+        graph: &mut crate::AsyncGraph,
+    ) -> FalkorResult<MigrationReport> {
+        let history = history_async(graph).await?;
+        let pending = self.pending(&history)?;
+        let mut report = MigrationReport {
+            applied: Vec::with_capacity(pending.len()),
+            already_applied: history.len(),
+        };
+        for migration in pending {
+            self.apply_async(graph, migration).await?;
+            report.applied.push(migration.version);
+        }
+        Ok(report)
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn apply_async(
+        &self,
+        graph: &mut crate::AsyncGraph,
+        migration: &Migration,
+    ) -> FalkorResult<()> {
+        for (index, step) in migration.steps.iter().enumerate() {
+            self.apply_step_async(graph, step)
+                .await
+                .map_err(|err| migration.failed(format!("step {} failed: {err}", index + 1)))?;
+        }
+        if let Some(options) = &self.wait {
+            let mut state = &mut *graph;
+            ops::poll_async(&mut state, options, WaitOperation::IndexCreation, |graph| {
+                Box::pin(
+                    async move { Ok(ops::all_indices_ready(&graph.list_indices().await?.data)) },
+                )
+            })
+            .await
+            .map_err(|err| migration.failed(err.to_string()))?;
+        }
+        record(graph.query(RECORD_QUERY), migration)
+            .execute()
+            .await
+            .map_err(|err| migration.failed(format!("recording the migration failed: {err}")))?;
+        Ok(())
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn apply_step_async(
+        &self,
+        graph: &mut crate::AsyncGraph,
+        step: &MigrationStep,
+    ) -> FalkorResult<()> {
+        let builder = match step {
+            MigrationStep::Query(query) => return graph.query(query).execute().await.map(drop),
+            MigrationStep::CreateConstraint {
+                constraint_type: ConstraintType::Unique,
+                entity_type,
+                label,
+                properties,
+            } => graph.create_unique_constraint_op(*entity_type, label, &property_refs(properties)),
+            MigrationStep::CreateConstraint {
+                constraint_type: ConstraintType::Mandatory,
+                entity_type,
+                label,
+                properties,
+            } => graph.create_mandatory_constraint_op(
+                *entity_type,
+                label,
+                &property_refs(properties),
+            ),
+            MigrationStep::DropConstraint {
+                constraint_type,
+                entity_type,
+                label,
+                properties,
+            } => graph.drop_constraint_op(
+                *constraint_type,
+                *entity_type,
+                label,
+                &property_refs(properties),
+            ),
+        };
+        match &self.wait {
+            Some(options) => builder.wait_with(options.clone()).await,
+            None => builder.execute().await.map(drop),
+        }
+    }
+
+    /// Checks the history of `graph` and explains the pending migrations; see
+    /// [`MigrationRunner::dry_run`].
+    ///
+    /// # Arguments
+    /// * `graph`: The graph to check.
+    ///
+    /// # Returns
+    /// The plan of each pending migration, in version order, or the first error.
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Dry Run Migrations Async", skip_all, level = "info")
+    )]
+    pub async fn dry_run_async(
+        &self,
+        graph: &mut crate::AsyncGraph,
+    ) -> FalkorResult<Vec<MigrationPlan>> {
+        let history = history_async(graph).await?;
+        let mut plans = Vec::new();
+        for migration in self.pending(&history)? {
+            let mut steps = Vec::with_capacity(migration.steps.len());
+            for (index, step) in migration.steps.iter().enumerate() {
+                let plan = match step {
+                    MigrationStep::Query(query) if !is_index_statement(query) => {
+                        let plan = graph.explain(query).execute().await.map_err(|err| {
+                            migration.failed(format!("step {} failed: {err}", index + 1))
+                        })?;
+                        Some(plan.plan().to_vec())
+                    }
+                    _ => None,
+                };
+                steps.push(StepPlan {
+                    step: step.clone(),
+                    plan,
+                });
+            }
+            plans.push(MigrationPlan {
+                version: migration.version,
+                description: migration.description.clone(),
+                steps,
+            });
+        }
+        Ok(plans)
+    }
+}
+
+/// Binds the parameters of [`RECORD_QUERY`] for `migration`.
+fn record<'a, R, T: Display, G>(
+    query: crate::QueryBuilder<'a, R, T, G>,
+    migration: &Migration,
+) -> crate::QueryBuilder<'a, R, T, G> {
+    query
+        .with_param("version", migration.version as i64)
+        .with_param("description", migration.description.as_str())
+        .with_param("checksum", migration.checksum())
+}
+
+fn parse_history(row: crate::Row) -> FalkorResult<AppliedMigration> {
+    let version: i64 = row.try_get("version")?;
+    Ok(AppliedMigration {
+        version: u64::try_from(version).map_err(|_| FalkorDBError::MigrationFailed {
+            version: 0,
+            message: format!("the history records the negative version {version}"),
+        })?,
+        description: row.try_get("description")?,
+        checksum: row.try_get("checksum")?,
+        applied_at: row.try_get("applied_at")?,
+    })
+}
+
+/// The migrations recorded as applied to `graph`, in version order.
+///
+/// # Arguments
+/// * `graph`: The graph to read the [`HISTORY_LABEL`] nodes of.
+///
+/// # Returns
+/// The applied migrations, or the first error.
+pub fn history(graph: &mut SyncGraph) -> FalkorResult<Vec<AppliedMigration>> {
+    graph
+        .ro_query(HISTORY_QUERY)
+        .execute()?
+        .data
+        .map(|row| parse_history(row?))
+        .collect()
+}
+
+/// The migrations recorded as applied to `graph`; see [`history`].
+///
+/// # Arguments
+/// * `graph`: The graph to read the [`HISTORY_LABEL`] nodes of.
+///
+/// # Returns
+/// The applied migrations, or the first error.
+#[cfg(feature = "tokio")]
+pub async fn history_async(graph: &mut crate::AsyncGraph) -> FalkorResult<Vec<AppliedMigration>> {
+    // One reply, never paged, so the history is a single consistent read.
+    let mut rows = graph.ro_query(HISTORY_QUERY).execute_single().await?.data;
+    let mut history = Vec::new();
+    while let Some(row) = rows.next_row().await {
+        history.push(parse_history(row?)?);
+    }
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn applied(migration: &Migration) -> AppliedMigration {
+        AppliedMigration {
+            version: migration.version,
+            description: migration.description.clone(),
+            checksum: migration.checksum(),
+            applied_at: 0,
+        }
+    }
+
+    fn runner() -> MigrationRunner {
+        MigrationRunner::new().with_migrations([
+            Migration::new(3, "third").query("MATCH (n) SET n.c = 1"),
+            Migration::new(1, "first")
+                .query("CREATE INDEX FOR (p:Person) ON (p.name)")
+                .unique_constraint(EntityType::Node, "Person", &["name"]),
+            Migration::new(2, "second").query("MATCH (n) SET n.b = 1"),
+        ])
+    }
+
+    fn versions(pending: FalkorResult<Vec<&Migration>>) -> Vec<u64> {
+        pending
+            .unwrap()
+            .iter()
+            .map(|migration| migration.version)
+            .collect()
+    }
+
+    #[test]
+    fn test_pending_migrations_run_in_version_order() {
+        let runner = runner();
+        assert_eq!(versions(runner.pending(&[])), vec![1, 2, 3]);
+        let first = applied(&runner.migrations[1]);
+        assert_eq!(versions(runner.pending(&[first])), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_history_drift_is_refused() {
+        let runner = runner();
+        let mut first = applied(&runner.migrations[1]);
+        first.checksum = "0".repeat(64);
+        assert!(matches!(
+            runner.pending(&[first]),
+            Err(FalkorDBError::MigrationChecksumMismatch { version: 1, .. })
+        ));
+
+        let unknown = AppliedMigration {
+            version: 9,
+            ..applied(&runner.migrations[0])
+        };
+        assert!(matches!(
+            runner.pending(&[unknown]),
+            Err(FalkorDBError::MigrationFailed { version: 9, .. })
+        ));
+
+        let third = applied(&runner.migrations[0]);
+        assert_eq!(
+            runner.pending(&[third]).unwrap_err(),
+            FalkorDBError::MigrationFailed {
+                version: 1,
+                message: "the migration is older than version 3, which is already applied"
+                    .to_string(),
+            }
+        );
+
+        let duplicate = runner.with_migration(Migration::new(2, "again"));
+        assert!(matches!(
+            duplicate.pending(&[]),
+            Err(FalkorDBError::MigrationFailed { version: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_checksum_covers_the_steps_only() {
+        let migration = Migration::new(1, "a").query("RETURN 1");
+        assert_eq!(migration.checksum().len(), 64);
+        assert_eq!(
+            migration.checksum(),
+            Migration::new(1, "renamed").query("RETURN 1").checksum()
+        );
+        assert_ne!(
+            migration.checksum(),
+            Migration::new(1, "a").query("RETURN 2").checksum()
+        );
+        assert_ne!(
+            Migration::new(1, "a")
+                .query("RETURN 1")
+                .query("RETURN 2")
+                .checksum(),
+            Migration::new(1, "a")
+                .query("RETURN 1\nRETURN 2")
+                .checksum()
+        );
+    }
+
+    #[test]
+    fn test_script_splits_on_line_ending_semicolons() {
+        let migration = Migration::from_script(
+            1,
+            "script",
+            "// people\nCREATE INDEX FOR (p:Person) ON (p.name);\n\nMATCH (p:Person)\n  SET p.tag = 'a;b';\nRETURN 1",
+        );
+        assert_eq!(
+            migration.steps(),
+            [
+                MigrationStep::Query("CREATE INDEX FOR (p:Person) ON (p.name)".to_string()),
+                MigrationStep::Query("MATCH (p:Person)\nSET p.tag = 'a;b'".to_string()),
+                MigrationStep::Query("RETURN 1".to_string()),
+            ]
+        );
+        assert!(is_index_statement("create vector index for (n:N) on (n.v)"));
+        assert!(!is_index_statement("MATCH (n) RETURN n"));
+    }
+}
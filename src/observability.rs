@@ -3,7 +3,8 @@
  * Licensed under the MIT License.
  */
 
-//! Internal helpers for the optional `tracing` (and, later, `metrics`) instrumentation.
+//! Internal helpers for the optional `tracing`, `opentelemetry`, `metrics` and `query-stats`
+//! instrumentation.
 //!
 //! Everything here is compiled only when an observability feature is enabled, so the hot path does
 //! no instrumentation work when they are off. The headline safety property is that **no raw query
@@ -11,10 +12,11 @@
 //! [`query_fingerprint`] (a hash of the query *template* with literals redacted), and the raw query
 //! is recorded only behind the opt-in `with_query_logging` builder flag.
 
-#[cfg(feature = "tokio")]
+#[cfg(all(feature = "tokio", any(feature = "tracing", feature = "metrics")))]
 use crate::ConnectionStrategy;
+#[cfg(any(feature = "tracing", feature = "metrics"))]
 use crate::FalkorDBError;
-#[cfg(feature = "tracing")]
+#[cfg(any(feature = "tracing", feature = "query-stats"))]
 use std::sync::OnceLock;
 
 /// Compute a privacy-safe, stable fingerprint of a Cypher query.
@@ -24,7 +26,7 @@ use std::sync::OnceLock;
 /// or parameter values share a fingerprint, and no sensitive value enters the hash. Redaction is
 /// best-effort (a regex, not a full Cypher parser). The value is an FNV-1a hash rendered as 16 hex
 /// digits; it is **not** guaranteed stable across crate versions (group within a deployment).
-#[cfg(feature = "tracing")]
+#[cfg(any(feature = "tracing", feature = "query-stats"))]
 pub(crate) fn query_fingerprint(query: &str) -> String {
     let normalized = redact_literals(query);
     format!("{:016x}", fnv1a_64(normalized.as_bytes()))
@@ -32,8 +34,8 @@ pub(crate) fn query_fingerprint(query: &str) -> String {
 
 /// Replace string / numeric / boolean / null literals with `?`. Identifiers, labels, property
 /// names, keywords and structure are preserved, so the redacted text captures the query shape.
-#[cfg(feature = "tracing")]
-fn redact_literals(query: &str) -> String {
+#[cfg(any(feature = "tracing", feature = "query-stats"))]
+pub(crate) fn redact_literals(query: &str) -> String {
     static LITERAL: OnceLock<regex::Regex> = OnceLock::new();
     let re = LITERAL.get_or_init(|| {
         // Order matters: match whole quoted strings first so literals inside them are not matched
@@ -47,7 +49,7 @@ fn redact_literals(query: &str) -> String {
 }
 
 /// FNV-1a 64-bit hash. Deterministic and dependency-free; adequate for a grouping fingerprint.
-#[cfg(feature = "tracing")]
+#[cfg(any(feature = "tracing", feature = "query-stats"))]
 fn fnv1a_64(bytes: &[u8]) -> u64 {
     const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
     const PRIME: u64 = 0x0000_0100_0000_01b3;
@@ -62,6 +64,7 @@ fn fnv1a_64(bytes: &[u8]) -> u64 {
 /// A bounded, payload-free label for an error, safe to use as a span field or (later) a metric
 /// label. Matches on the variant only — never on any carried `String` — so it can never echo a
 /// query, parameter, graph name, or server message.
+#[cfg(any(feature = "tracing", feature = "metrics"))]
 pub(crate) fn error_kind(error: &FalkorDBError) -> &'static str {
     match error {
         FalkorDBError::ConnectionDown => "connection_down",
@@ -88,7 +91,7 @@ pub(crate) fn error_kind(error: &FalkorDBError) -> &'static str {
 }
 
 /// A bounded label for the active connection strategy. The sync client is always pooled.
-#[cfg(feature = "tokio")]
+#[cfg(all(feature = "tokio", any(feature = "tracing", feature = "metrics")))]
 pub(crate) fn strategy_label(strategy: &ConnectionStrategy) -> &'static str {
     match strategy {
         ConnectionStrategy::Pooled { .. } => "pooled",
@@ -97,20 +100,24 @@ pub(crate) fn strategy_label(strategy: &ConnectionStrategy) -> &'static str {
 }
 
 /// The strategy label for the (always pooled) sync client.
+#[cfg(any(feature = "tracing", feature = "metrics"))]
 pub(crate) const SYNC_STRATEGY: &str = "pooled";
 
-/// Record the request-side operational fields on the current span: the connection strategy, whether
-/// the operation is read-only, and the privacy-safe query fingerprint — plus the raw query template
-/// **only** when `log_raw` is set (the opt-in `with_query_logging` flag). Parameter values are never
-/// recorded — they live in the query preamble, not in `query_template`.
+/// Record the request-side operational fields on the current span: the bounded `otel.name` of the
+/// wire command, the connection strategy, whether the operation is read-only, and the privacy-safe
+/// query fingerprint — plus the raw query template **only** when `log_raw` is set (the opt-in
+/// `with_query_logging` flag). Parameter values are never recorded — they live in the query
+/// preamble, not in `query_template`.
 #[cfg(feature = "tracing")]
 pub(crate) fn record_request(
+    command: &str,
     strategy: &'static str,
     read_only: bool,
     query_template: &str,
     log_raw: bool,
 ) {
     let span = tracing::Span::current();
+    span.record("otel.name", command_label(command));
     span.record("db.falkordb.strategy", strategy);
     span.record("db.falkordb.read_only", read_only);
     span.record(
@@ -125,7 +132,47 @@ pub(crate) fn record_request(
 /// Record the bounded error kind on the current span when an operation fails.
 #[cfg(feature = "tracing")]
 pub(crate) fn record_error(error: &FalkorDBError) {
-    tracing::Span::current().record("error.type", error_kind(error));
+    let span = tracing::Span::current();
+    span.record("error.type", error_kind(error));
+    span.record("otel.status_code", "error");
+}
+
+/// The W3C `traceparent` header value of an OpenTelemetry span, or `None` for an invalid (absent)
+/// span context.
+#[cfg(feature = "opentelemetry")]
+fn traceparent(span_context: &opentelemetry::trace::SpanContext) -> Option<String> {
+    span_context.is_valid().then(|| {
+        format!(
+            "00-{:032x}-{:016x}-{:02x}",
+            span_context.trace_id(),
+            span_context.span_id(),
+            span_context.trace_flags().to_u8()
+        )
+    })
+}
+
+/// Append the current span's `traceparent` to an encoded query as a Cypher comment, when `enabled`
+/// (the opt-in `with_trace_context` flag) and an OpenTelemetry span is active. The comment starts
+/// on a new line so a trailing `//` comment in the query cannot swallow it, and it never carries
+/// anything but the hex trace id, span id and flags.
+#[cfg(feature = "opentelemetry")]
+pub(crate) fn with_trace_context(
+    mut query: String,
+    enabled: bool,
+) -> String {
+    use opentelemetry::trace::TraceContextExt;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    if !enabled {
+        return query;
+    }
+    let context = tracing::Span::current().context();
+    if let Some(traceparent) = traceparent(context.span().span_context()) {
+        query.push_str("\n/*traceparent='");
+        query.push_str(&traceparent);
+        query.push_str("'*/");
+    }
+    query
 }
 
 /// Record the result-side fields on the current (outer `execute`) span, once the response has been
@@ -152,8 +199,8 @@ pub(crate) fn record_result(
 /// A bounded label for a wire command, for use as a metric label. An allowlist of known commands
 /// (unknown ⇒ `"other"`), so a metric label can never carry a user-controlled or high-cardinality
 /// string. Procedure calls are labeled by their wire command (`GRAPH.QUERY`/`GRAPH.RO_QUERY`), never
-/// by the procedure name.
-#[cfg(feature = "metrics")]
+/// by the procedure name. Also the `otel.name` of the execution spans.
+#[cfg(any(feature = "tracing", feature = "metrics"))]
 pub(crate) fn command_label(command: &str) -> &'static str {
     match command {
         "GRAPH.QUERY" => "GRAPH.QUERY",
@@ -283,7 +330,7 @@ pub(crate) fn record_pool_wait(
 mod tests {
     use super::*;
 
-    #[cfg(feature = "tracing")]
+    #[cfg(any(feature = "tracing", feature = "query-stats"))]
     #[test]
     fn fingerprint_is_stable_for_same_query() {
         assert_eq!(
@@ -292,7 +339,7 @@ mod tests {
         );
     }
 
-    #[cfg(feature = "tracing")]
+    #[cfg(any(feature = "tracing", feature = "query-stats"))]
     #[test]
     fn fingerprint_is_value_independent_for_inlined_literals() {
         // The whole point of redaction: differing literal values must not change the fingerprint.
@@ -308,7 +355,7 @@ mod tests {
         assert_eq!(n1, n2, "numeric literals must be redacted before hashing");
     }
 
-    #[cfg(feature = "tracing")]
+    #[cfg(any(feature = "tracing", feature = "query-stats"))]
     #[test]
     fn fingerprint_distinguishes_query_shape() {
         assert_ne!(
@@ -317,7 +364,7 @@ mod tests {
         );
     }
 
-    #[cfg(feature = "tracing")]
+    #[cfg(any(feature = "tracing", feature = "query-stats"))]
     #[test]
     fn redaction_removes_literal_values_and_fingerprint_is_hex() {
         let query = "MATCH (u {ssn: '123-45-6789', name: 'secret'}) RETURN u";
@@ -343,7 +390,7 @@ mod tests {
         assert!(fingerprint.bytes().all(|b| b.is_ascii_hexdigit()));
     }
 
-    #[cfg(feature = "tracing")]
+    #[cfg(any(feature = "tracing", feature = "query-stats"))]
     #[test]
     fn redaction_preserves_shape_and_strips_literals() {
         assert_eq!(
@@ -352,6 +399,7 @@ mod tests {
         );
     }
 
+    #[cfg(any(feature = "tracing", feature = "metrics"))]
     #[test]
     fn error_kind_is_bounded_and_payload_free() {
         // A String-carrying variant must map to a fixed label, never echoing the payload.
@@ -369,6 +417,7 @@ mod tests {
         );
     }
 
+    #[cfg(any(feature = "tracing", feature = "metrics"))]
     #[test]
     fn error_kind_is_a_bounded_lowercase_label_for_every_constructed_variant() {
         let cases: Vec<FalkorDBError> = vec![
@@ -498,9 +547,12 @@ mod span_capture_tests {
                 db.query.fingerprint = tracing::field::Empty,
                 db.query.text = tracing::field::Empty,
                 error.type = tracing::field::Empty,
+                otel.name = tracing::field::Empty,
+                otel.status_code = tracing::field::Empty,
             );
             let _enter = span.enter();
             super::record_request(
+                "GRAPH.RO_QUERY",
                 "multiplexed",
                 true,
                 "MATCH (n {x: 'secret'}) RETURN n",
@@ -528,6 +580,14 @@ mod span_capture_tests {
             fields.get("error.type").map(String::as_str),
             Some("connection_down")
         );
+        assert_eq!(
+            fields.get("otel.name").map(String::as_str),
+            Some("GRAPH.RO_QUERY")
+        );
+        assert_eq!(
+            fields.get("otel.status_code").map(String::as_str),
+            Some("error")
+        );
     }
 
     #[test]
@@ -541,7 +601,7 @@ mod span_capture_tests {
                 db.query.text = tracing::field::Empty,
             );
             let _enter = span.enter();
-            super::record_request("pooled", false, "MATCH (n) RETURN n", true);
+            super::record_request("GRAPH.QUERY", "pooled", false, "MATCH (n) RETURN n", true);
         });
         assert_eq!(
             fields.get("db.query.text").map(String::as_str),
@@ -678,3 +738,56 @@ mod metrics_tests {
             .any(|n| n == "falkordb_connection_pool_wait_seconds"));
     }
 }
+
+/// Checks that [`with_trace_context`] appends the W3C `traceparent` of the active OpenTelemetry span,
+/// and leaves the query untouched when disabled or outside any span.
+#[cfg(all(test, feature = "opentelemetry"))]
+mod trace_context_tests {
+    use opentelemetry::trace::{TraceContextExt, TracerProvider};
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn with_otel_subscriber(body: impl FnOnce()) {
+        let provider = SdkTracerProvider::builder().build();
+        let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer("falkordb-tests"));
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, body);
+    }
+
+    #[test]
+    fn appends_the_traceparent_of_the_current_span() {
+        with_otel_subscriber(|| {
+            let span = tracing::info_span!("query");
+            let _enter = span.enter();
+            let query = super::with_trace_context("MATCH (n) RETURN n // all".to_string(), true);
+
+            let context = span.context();
+            let span_context = context.span().span_context().clone();
+            assert_eq!(
+                query,
+                format!(
+                    "MATCH (n) RETURN n // all\n/*traceparent='00-{:032x}-{:016x}-01'*/",
+                    span_context.trace_id(),
+                    span_context.span_id()
+                )
+            );
+        });
+    }
+
+    #[test]
+    fn leaves_the_query_untouched_when_disabled_or_outside_a_span() {
+        with_otel_subscriber(|| {
+            assert_eq!(
+                super::with_trace_context("RETURN 1".to_string(), true),
+                "RETURN 1"
+            );
+            let span = tracing::info_span!("query");
+            let _enter = span.enter();
+            assert_eq!(
+                super::with_trace_context("RETURN 1".to_string(), false),
+                "RETURN 1"
+            );
+        });
+    }
+}
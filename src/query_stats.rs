@@ -0,0 +1,631 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! The in-process query statistics registry enabled with
+//! [`FalkorClientBuilder::with_query_stats`](crate::FalkorClientBuilder::with_query_stats).
+//!
+//! Queries are grouped by the same privacy-safe fingerprint the `tracing` spans carry, so the
+//! registry only ever holds redacted query text: literals are replaced by `?` and parameter values
+//! are never seen.
+
+use crate::observability::{query_fingerprint, redact_literals};
+use crate::parser::redis_value_as_untyped_string_vec;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// Settings for a [`QueryStats`] registry, passed to
+/// [`FalkorClientBuilder::with_query_stats`](crate::FalkorClientBuilder::with_query_stats).
+///
+/// Every limit bounds the memory the registry can use, however many distinct queries the
+/// application runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[must_use = "query stats settings do nothing unless passed to with_query_stats"]
+pub struct QueryStatsConfig {
+    slow_threshold: Duration,
+    max_fingerprints: usize,
+    latency_samples: usize,
+    slow_log_capacity: usize,
+    explain_plans: bool,
+}
+
+impl Default for QueryStatsConfig {
+    fn default() -> Self {
+        Self {
+            slow_threshold: Duration::from_secs(1),
+            max_fingerprints: 1000,
+            latency_samples: 1024,
+            slow_log_capacity: 128,
+            explain_plans: true,
+        }
+    }
+}
+
+impl QueryStatsConfig {
+    /// Creates the default settings: a one second slow query threshold, up to 1000 tracked
+    /// fingerprints with 1024 latency samples each, a slow query log of 128 entries, and plan
+    /// capture enabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the round-trip time at or above which a query is added to the slow query log.
+    ///
+    /// # Arguments
+    /// * `threshold`: The slowest acceptable round trip, retries included.
+    pub fn with_slow_threshold(
+        self,
+        threshold: Duration,
+    ) -> Self {
+        Self {
+            slow_threshold: threshold,
+            ..self
+        }
+    }
+
+    /// Caps the number of distinct fingerprints tracked. Once the cap is reached, calls to queries
+    /// with a new fingerprint are only counted in [`QueryStats::untracked_calls`].
+    ///
+    /// # Arguments
+    /// * `max_fingerprints`: The maximum number of tracked fingerprints.
+    pub fn with_max_fingerprints(
+        self,
+        max_fingerprints: usize,
+    ) -> Self {
+        Self {
+            max_fingerprints,
+            ..self
+        }
+    }
+
+    /// Sets how many of the most recent latencies of each fingerprint the percentiles are computed
+    /// over.
+    ///
+    /// # Arguments
+    /// * `latency_samples`: The number of samples kept per fingerprint, at least 1.
+    pub fn with_latency_samples(
+        self,
+        latency_samples: usize,
+    ) -> Self {
+        Self {
+            latency_samples: latency_samples.max(1),
+            ..self
+        }
+    }
+
+    /// Sets how many slow queries are kept; the oldest entry is dropped once the log is full.
+    ///
+    /// # Arguments
+    /// * `capacity`: The maximum number of entries in the slow query log.
+    pub fn with_slow_log_capacity(
+        self,
+        capacity: usize,
+    ) -> Self {
+        Self {
+            slow_log_capacity: capacity,
+            ..self
+        }
+    }
+
+    /// Enables or disables capturing the execution plan of slow queries.
+    ///
+    /// When enabled, the first slow call of each fingerprint is followed by a `GRAPH.EXPLAIN` of the
+    /// same query on the primary, and the plan is attached to that fingerprint and to its slow query
+    /// log entries. Enabled by default.
+    ///
+    /// # Arguments
+    /// * `enabled`: Whether to run `GRAPH.EXPLAIN` for slow queries.
+    pub fn with_explain_plans(
+        self,
+        enabled: bool,
+    ) -> Self {
+        Self {
+            explain_plans: enabled,
+            ..self
+        }
+    }
+}
+
+/// The order [`QueryStats::top`] ranks fingerprints by, highest first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QueryStatsOrder {
+    /// The total time spent in the query, across all its calls. **Default.**
+    #[default]
+    TotalTime,
+    /// The number of calls.
+    Calls,
+    /// The number of failed calls.
+    Errors,
+    /// The 99th percentile latency.
+    P99,
+}
+
+/// The statistics of one query fingerprint, as returned by [`QueryStats::entries`] and
+/// [`QueryStats::top`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct QueryStatsEntry {
+    /// The privacy-safe fingerprint of the query, as recorded on the `db.query.fingerprint` span
+    /// field.
+    pub fingerprint: String,
+    /// The query text with its literals redacted to `?`.
+    pub query: String,
+    /// How many times the query was executed.
+    pub calls: u64,
+    /// How many of those calls failed.
+    pub errors: u64,
+    /// The total number of rows returned by the successful calls.
+    pub rows_returned: u64,
+    /// How many successful calls reused a cached execution plan.
+    pub cache_hits: u64,
+    /// How many successful calls reported that they did not reuse a cached execution plan.
+    pub cache_misses: u64,
+    /// The total round-trip time of all calls.
+    pub total_time: Duration,
+    /// The median latency of the most recent calls.
+    pub p50: Duration,
+    /// The 95th percentile latency of the most recent calls.
+    pub p95: Duration,
+    /// The 99th percentile latency of the most recent calls.
+    pub p99: Duration,
+    /// The slowest call seen.
+    pub max: Duration,
+    /// The execution plan captured after the first slow call, if any.
+    pub plan: Option<Vec<String>>,
+}
+
+impl QueryStatsEntry {
+    /// The mean round-trip time per call.
+    pub fn mean(&self) -> Duration {
+        u32::try_from(self.calls)
+            .ok()
+            .and_then(|calls| self.total_time.checked_div(calls))
+            .unwrap_or_default()
+    }
+
+    /// The fraction of calls reporting a cache status that reused a cached execution plan, or
+    /// `None` before any call reported one.
+    pub fn cache_hit_ratio(&self) -> Option<f64> {
+        let reported = self.cache_hits + self.cache_misses;
+        (reported > 0).then(|| self.cache_hits as f64 / reported as f64)
+    }
+}
+
+/// One entry of the client-side slow query log, as returned by [`QueryStats::slow_queries`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct SlowQuery {
+    /// The privacy-safe fingerprint of the query.
+    pub fingerprint: String,
+    /// The query text with its literals redacted to `?`.
+    pub query: String,
+    /// The round-trip time of the call, retries included.
+    pub duration: Duration,
+    /// When the call finished.
+    pub finished_at: SystemTime,
+    /// Whether the call failed, e.g. because it timed out.
+    pub failed: bool,
+    /// The execution plan of the query, when plan capture is enabled and `GRAPH.EXPLAIN` succeeded.
+    pub plan: Option<Vec<String>>,
+}
+
+/// The running totals of one fingerprint.
+struct Tracked {
+    query: String,
+    calls: u64,
+    errors: u64,
+    rows_returned: u64,
+    cache_hits: u64,
+    cache_misses: u64,
+    total_time: Duration,
+    max: Duration,
+    samples: VecDeque<Duration>,
+    /// Set once a plan has been requested, so each fingerprint is explained at most once.
+    plan_requested: bool,
+    plan: Option<Arc<[String]>>,
+}
+
+impl Tracked {
+    fn new(query: String) -> Self {
+        Self {
+            query,
+            calls: 0,
+            errors: 0,
+            rows_returned: 0,
+            cache_hits: 0,
+            cache_misses: 0,
+            total_time: Duration::ZERO,
+            max: Duration::ZERO,
+            samples: VecDeque::new(),
+            plan_requested: false,
+            plan: None,
+        }
+    }
+
+    fn to_entry(
+        &self,
+        fingerprint: &str,
+    ) -> QueryStatsEntry {
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        QueryStatsEntry {
+            fingerprint: fingerprint.to_string(),
+            query: self.query.clone(),
+            calls: self.calls,
+            errors: self.errors,
+            rows_returned: self.rows_returned,
+            cache_hits: self.cache_hits,
+            cache_misses: self.cache_misses,
+            total_time: self.total_time,
+            p50: percentile(&sorted, 0.50),
+            p95: percentile(&sorted, 0.95),
+            p99: percentile(&sorted, 0.99),
+            max: self.max,
+            plan: self.plan.as_deref().map(<[String]>::to_vec),
+        }
+    }
+}
+
+/// The nearest-rank percentile of already sorted samples, or zero when there are none.
+fn percentile(
+    sorted: &[Duration],
+    quantile: f64,
+) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (quantile * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[derive(Default)]
+struct Registry {
+    tracked: HashMap<String, Tracked>,
+    slow: VecDeque<SlowQuery>,
+    untracked_calls: u64,
+}
+
+/// The row count and plan cache status read from a successful query reply.
+struct ReplySummary {
+    rows: u64,
+    cached: Option<bool>,
+}
+
+/// Reads the row count and the `Cached execution` statistic of a raw `--compact` query reply
+/// without decoding any row. Returns `None` for a server error reply.
+fn summarize_reply(reply: &redis::Value) -> Option<ReplySummary> {
+    let redis::Value::Array(elements) = reply else {
+        return None;
+    };
+    let rows = match elements.as_slice() {
+        [_, redis::Value::Array(rows), _] => rows.len() as u64,
+        _ => 0,
+    };
+    let cached = elements
+        .last()
+        .and_then(|stats| redis_value_as_untyped_string_vec(stats.clone()).ok())
+        .and_then(|stats| {
+            stats.iter().find_map(|stat| {
+                stat.strip_prefix("Cached execution: ")
+                    .and_then(|value| value.trim().parse::<i64>().ok())
+                    .map(|value| value != 0)
+            })
+        });
+    Some(ReplySummary { rows, cached })
+}
+
+/// A slow call recorded by [`QueryStats::record`], to be added to the slow query log with
+/// [`QueryStats::finish_slow`] once its plan (if needed) has been fetched.
+pub(crate) struct SlowCapture {
+    fingerprint: String,
+    query: String,
+    duration: Duration,
+    failed: bool,
+    plan: Option<Arc<[String]>>,
+    needs_plan: bool,
+}
+
+impl SlowCapture {
+    /// Whether the caller should run `GRAPH.EXPLAIN` and pass the plan to `finish_slow`.
+    pub(crate) fn needs_plan(&self) -> bool {
+        self.needs_plan
+    }
+}
+
+/// A shared, thread-safe registry of per-query statistics and a client-side slow query log.
+///
+/// Enable it with
+/// [`FalkorClientBuilder::with_query_stats`](crate::FalkorClientBuilder::with_query_stats) and read
+/// it back at runtime through the client's `query_stats()`, e.g. to serve a "top queries" debug
+/// endpoint. Cloning the handle is cheap, and every clone reads the same registry. Only queries run
+/// through `query`, `ro_query` and the helpers built on them are recorded; `explain`, `profile` and
+/// procedure calls are not.
+///
+/// Latencies are client-side round trips, retries included, so they cover network time and
+/// connection waits that the server's own `GRAPH.SLOWLOG` does not.
+#[derive(Clone)]
+pub struct QueryStats {
+    config: QueryStatsConfig,
+    registry: Arc<Mutex<Registry>>,
+}
+
+impl std::fmt::Debug for QueryStats {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("QueryStats")
+            .field("config", &self.config)
+            .field("fingerprints", &self.registry.lock().tracked.len())
+            .finish()
+    }
+}
+
+impl QueryStats {
+    pub(crate) fn new(config: QueryStatsConfig) -> Self {
+        Self {
+            config,
+            registry: Arc::default(),
+        }
+    }
+
+    /// The settings this registry was created with.
+    pub fn config(&self) -> QueryStatsConfig {
+        self.config
+    }
+
+    /// Returns the statistics of every tracked fingerprint, ordered by total time, highest first.
+    pub fn entries(&self) -> Vec<QueryStatsEntry> {
+        self.top(usize::MAX, QueryStatsOrder::TotalTime)
+    }
+
+    /// Returns the statistics of the `n` highest ranked fingerprints.
+    ///
+    /// # Arguments
+    /// * `n`: The maximum number of entries to return.
+    /// * `order`: What to rank the fingerprints by.
+    ///
+    /// # Returns
+    /// At most `n` [`QueryStatsEntry`]s, highest first.
+    pub fn top(
+        &self,
+        n: usize,
+        order: QueryStatsOrder,
+    ) -> Vec<QueryStatsEntry> {
+        let mut entries: Vec<QueryStatsEntry> = self
+            .registry
+            .lock()
+            .tracked
+            .iter()
+            .map(|(fingerprint, tracked)| tracked.to_entry(fingerprint))
+            .collect();
+        entries.sort_by(|a, b| {
+            let ranked = match order {
+                QueryStatsOrder::TotalTime => b.total_time.cmp(&a.total_time),
+                QueryStatsOrder::Calls => b.calls.cmp(&a.calls),
+                QueryStatsOrder::Errors => b.errors.cmp(&a.errors),
+                QueryStatsOrder::P99 => b.p99.cmp(&a.p99),
+            };
+            ranked.then_with(|| a.fingerprint.cmp(&b.fingerprint))
+        });
+        entries.truncate(n);
+        entries
+    }
+
+    /// Returns the client-side slow query log, oldest entry first.
+    pub fn slow_queries(&self) -> Vec<SlowQuery> {
+        self.registry.lock().slow.iter().cloned().collect()
+    }
+
+    /// The number of calls not attributed to any fingerprint because the
+    /// [`with_max_fingerprints`](QueryStatsConfig::with_max_fingerprints) cap was reached.
+    pub fn untracked_calls(&self) -> u64 {
+        self.registry.lock().untracked_calls
+    }
+
+    /// Clears every statistic, captured plan and slow query log entry.
+    pub fn reset(&self) {
+        *self.registry.lock() = Registry::default();
+    }
+
+    /// Records one call of `query_template`, with its raw reply (`None` when it failed), and
+    /// returns a [`SlowCapture`] when the call reached the slow query threshold.
+    pub(crate) fn record(
+        &self,
+        query_template: &str,
+        duration: Duration,
+        reply: Option<&redis::Value>,
+    ) -> Option<SlowCapture> {
+        let fingerprint = query_fingerprint(query_template);
+        let summary = reply.and_then(summarize_reply);
+        let slow = duration >= self.config.slow_threshold;
+
+        let mut registry = self.registry.lock();
+        if !registry.tracked.contains_key(&fingerprint)
+            && registry.tracked.len() >= self.config.max_fingerprints
+        {
+            registry.untracked_calls += 1;
+            return slow.then(|| SlowCapture {
+                query: redact_literals(query_template),
+                fingerprint,
+                duration,
+                failed: summary.is_none(),
+                plan: None,
+                needs_plan: false,
+            });
+        }
+
+        let tracked = registry
+            .tracked
+            .entry(fingerprint.clone())
+            .or_insert_with(|| Tracked::new(redact_literals(query_template)));
+        tracked.calls += 1;
+        tracked.total_time += duration;
+        tracked.max = tracked.max.max(duration);
+        if tracked.samples.len() == self.config.latency_samples {
+            tracked.samples.pop_front();
+        }
+        tracked.samples.push_back(duration);
+        match &summary {
+            Some(summary) => {
+                tracked.rows_returned += summary.rows;
+                match summary.cached {
+                    Some(true) => tracked.cache_hits += 1,
+                    Some(false) => tracked.cache_misses += 1,
+                    None => {}
+                }
+            }
+            None => tracked.errors += 1,
+        }
+
+        if !slow {
+            return None;
+        }
+        let needs_plan = self.config.explain_plans && !tracked.plan_requested;
+        tracked.plan_requested |= needs_plan;
+        Some(SlowCapture {
+            query: tracked.query.clone(),
+            fingerprint,
+            duration,
+            failed: summary.is_none(),
+            plan: tracked.plan.clone(),
+            needs_plan,
+        })
+    }
+
+    /// Adds a slow call to the slow query log, attaching `plan` to its fingerprint when the capture
+    /// asked for one.
+    pub(crate) fn finish_slow(
+        &self,
+        capture: SlowCapture,
+        plan: Option<Vec<String>>,
+    ) {
+        let mut registry = self.registry.lock();
+        let plan = match plan {
+            Some(plan) if capture.needs_plan => {
+                let plan: Arc<[String]> = plan.into();
+                if let Some(tracked) = registry.tracked.get_mut(&capture.fingerprint) {
+                    tracked.plan = Some(Arc::clone(&plan));
+                }
+                Some(plan)
+            }
+            _ => capture.plan,
+        };
+        if self.config.slow_log_capacity == 0 {
+            return;
+        }
+        if registry.slow.len() == self.config.slow_log_capacity {
+            registry.slow.pop_front();
+        }
+        registry.slow.push_back(SlowQuery {
+            fingerprint: capture.fingerprint,
+            query: capture.query,
+            duration: capture.duration,
+            finished_at: SystemTime::now(),
+            failed: capture.failed,
+            plan: plan.as_deref().map(<[String]>::to_vec),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reply(
+        rows: usize,
+        cached: bool,
+    ) -> redis::Value {
+        let stat = |text: &str| redis::Value::SimpleString(text.to_string());
+        redis::Value::Array(vec![
+            redis::Value::Array(vec![]),
+            redis::Value::Array(vec![redis::Value::Array(vec![]); rows]),
+            redis::Value::Array(vec![
+                stat(&format!("Cached execution: {}", u8::from(cached))),
+                stat("Query internal execution time: 0.1 milliseconds"),
+            ]),
+        ])
+    }
+
+    #[test]
+    fn test_record_groups_calls_by_fingerprint() {
+        let stats = QueryStats::new(QueryStatsConfig::new());
+        let ms = Duration::from_millis;
+        assert!(stats
+            .record("MATCH (n {id: 1}) RETURN n", ms(10), Some(&reply(2, false)))
+            .is_none());
+        stats.record("MATCH (n {id: 2}) RETURN n", ms(30), Some(&reply(3, true)));
+        stats.record("MATCH (n {id: 3}) RETURN n", ms(20), None);
+        stats.record("RETURN 'secret'", ms(1), Some(&reply(1, true)));
+
+        let entries = stats.entries();
+        assert_eq!(entries.len(), 2);
+        let entry = &entries[0];
+        assert_eq!(entry.query, "MATCH (n {id: ?}) RETURN n");
+        assert_eq!((entry.calls, entry.errors, entry.rows_returned), (3, 1, 5));
+        assert_eq!(entry.cache_hit_ratio(), Some(0.5));
+        assert_eq!((entry.p50, entry.p99, entry.max), (ms(20), ms(30), ms(30)));
+        assert_eq!(entry.mean(), ms(20));
+        assert_eq!(entries[1].query, "RETURN ?");
+
+        let top = stats.top(1, QueryStatsOrder::Calls);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].fingerprint, entry.fingerprint);
+        stats.reset();
+        assert!(stats.entries().is_empty());
+    }
+
+    #[test]
+    fn test_slow_queries_request_each_plan_once() {
+        let stats = QueryStats::new(
+            QueryStatsConfig::new()
+                .with_slow_threshold(Duration::from_millis(100))
+                .with_slow_log_capacity(2),
+        );
+        let slow = Duration::from_millis(150);
+        let plan = vec!["Results".to_string(), "    Project".to_string()];
+
+        let first = stats
+            .record("RETURN 1", slow, Some(&reply(1, false)))
+            .unwrap();
+        assert!(first.needs_plan());
+        stats.finish_slow(first, Some(plan.clone()));
+        let second = stats.record("RETURN 2", slow, None).unwrap();
+        assert!(!second.needs_plan());
+        stats.finish_slow(second, None);
+        let third = stats
+            .record("RETURN 3", slow, Some(&reply(1, true)))
+            .unwrap();
+        stats.finish_slow(third, None);
+
+        let log = stats.slow_queries();
+        assert_eq!(log.len(), 2);
+        assert!(log[0].failed && !log[1].failed);
+        assert!(log.iter().all(|entry| entry.plan.as_ref() == Some(&plan)));
+        assert!(log.iter().all(|entry| entry.query == "RETURN ?"));
+        assert_eq!(stats.entries()[0].plan, Some(plan));
+    }
+
+    #[test]
+    fn test_fingerprint_cap_and_sample_window() {
+        let stats = QueryStats::new(
+            QueryStatsConfig::new()
+                .with_max_fingerprints(1)
+                .with_latency_samples(2),
+        );
+        for ms in [50, 10, 20] {
+            stats.record("MATCH (n) RETURN n", Duration::from_millis(ms), None);
+        }
+        stats.record("MATCH (m) RETURN m", Duration::from_millis(1), None);
+
+        assert_eq!(stats.untracked_calls(), 1);
+        let entries = stats.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].p99, Duration::from_millis(20));
+        assert_eq!(entries[0].max, Duration::from_millis(50));
+    }
+}
@@ -189,6 +189,59 @@ impl Row {
     }
 }
 
+/// Serializes as a map from column name to value, in column order. A duplicated column name is
+/// written once per occurrence, so most readers keep the last one, as [`Row::into_map`] does.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Row {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.header.iter().zip(&self.values))
+    }
+}
+
+/// A type that can be built from a whole result [`Row`].
+///
+/// With the `derive` feature, `#[derive(FromRow)]` implements it for a struct by reading each named
+/// field from the column of the same name (or each tuple field by column index) through
+/// [`Row::try_get`], so every field type needs [`FromFalkorValue`]:
+///
+/// ```ignore
+/// use falkordb::FromRow;
+///
+/// #[derive(FromRow)]
+/// struct Movie {
+///     title: String,
+///     #[falkordb(rename = "released")]
+///     year: i64,
+///     rating: Option<f64>,
+/// }
+///
+/// let result = graph
+///     .query("MATCH (m:Movie) RETURN m.title AS title, m.released AS released, m.rating AS rating")
+///     .execute()?;
+/// let movies = result
+///     .data
+///     .map(|row| row.and_then(Movie::from_row))
+///     .collect::<Result<Vec<_>, _>>()?;
+/// ```
+pub trait FromRow: Sized {
+    /// Build `Self` from `row`.
+    ///
+    /// # Errors
+    ///
+    /// Derived implementations return [`FalkorDBError::MissingColumn`] for an absent column and
+    /// [`FalkorDBError::TypeError`] for a value of the wrong type.
+    fn from_row(row: Row) -> FalkorResult<Self>;
+}
+
+impl FromRow for Row {
+    fn from_row(row: Row) -> FalkorResult<Self> {
+        Ok(row)
+    }
+}
+
 impl IntoIterator for Row {
     type Item = FalkorValue;
     type IntoIter = std::vec::IntoIter<FalkorValue>;
@@ -212,33 +265,27 @@ impl FromIterator<(String, FalkorValue)> for Row {
     }
 }
 
-/// Parses every raw row of a query reply into a [`Row`], resolving compact ids against
-/// `graph_schema` (refreshing it once on a cache miss). Per-row parse failures are surfaced as `Err`
-/// items rather than aborting the whole set, so callers choose whether to keep going
-/// ([`RowStream`](crate::RowStream)) or collapse to the first error (the batch API).
+/// Parses one raw row of a query reply into a [`Row`], resolving compact ids against
+/// `graph_schema` (refreshing it once on a cache miss) and checking its width against `header`.
 ///
-/// Shared by [`RowStream::parse`](crate::RowStream) (async, lazy-yielding) and the batch builder
-/// (eager `Vec<Row>`), so the row-decoding logic lives in exactly one place.
-pub(crate) fn parse_rows(
-    header: Arc<[String]>,
-    raw_rows: Vec<redis::Value>,
+/// Shared by [`RowStream`](crate::RowStream) (async, decoding one row per poll) and the batch
+/// builder (eager `Vec<Row>`), so the row-decoding logic lives in exactly
+/// one place.
+pub(crate) fn parse_row(
+    header: &Arc<[String]>,
+    raw: redis::Value,
     graph_schema: &mut crate::GraphSchema,
-) -> Vec<FalkorResult<Row>> {
+) -> FalkorResult<Row> {
     use crate::parser::{parse_type, ParserTypeMarker};
-    raw_rows
-        .into_iter()
-        .map(|raw| {
-            let values = parse_type(ParserTypeMarker::Array, raw, graph_schema)
-                .and_then(FalkorValue::into_vec)?;
-            if values.len() != header.len() {
-                return Err(FalkorDBError::RowShapeMismatch {
-                    header_len: header.len(),
-                    value_len: values.len(),
-                });
-            }
-            Ok(Row::new(Arc::clone(&header), values))
-        })
-        .collect()
+    let values =
+        parse_type(ParserTypeMarker::Array, raw, graph_schema).and_then(FalkorValue::into_vec)?;
+    if values.len() != header.len() {
+        return Err(FalkorDBError::RowShapeMismatch {
+            header_len: header.len(),
+            value_len: values.len(),
+        });
+    }
+    Ok(Row::new(Arc::clone(header), values))
 }
 
 #[cfg(test)]
@@ -285,6 +332,15 @@ mod tests {
         assert_eq!(row.try_get::<Option<String>>("sequel").unwrap(), None);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_as_a_map_in_column_order() {
+        assert_eq!(
+            serde_json::to_string(&sample()).unwrap(),
+            r#"{"title":"Dune","year":1965,"rating":8.2,"sequel":null}"#
+        );
+    }
+
     #[test]
     fn try_get_missing_vs_null() {
         let row = sample();
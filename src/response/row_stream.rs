@@ -6,7 +6,9 @@
 //! An owned, `Send + 'static` async result set implementing [`futures_core::Stream`], available
 //! with the `tokio` feature.
 
-use crate::{FalkorResult, FalkorValue, GraphSchema, Row};
+use crate::{
+    graph::paginate::AsyncPager, FalkorDBError, FalkorResult, FalkorValue, GraphSchema, Row,
+};
 use std::collections::VecDeque;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -14,16 +16,19 @@ use std::task::{Context, Poll};
 
 /// The owned result set produced by an [`AsyncGraph`](crate::AsyncGraph) query.
 ///
-/// Each row of the `GRAPH.QUERY` reply is parsed **eagerly** when the query runs (resolving compact
-/// ids against the graph schema, refreshing it once on a cache miss) into a `FalkorResult<Row>`, so
-/// a `RowStream` is a self-contained, `Send + 'static` buffer of already-decoded rows. It can be
-/// moved into a `tokio::spawn` task and composed with `futures::StreamExt`/`futures::TryStreamExt`,
-/// and it stays valid even if the graph is mutated or deleted before the stream is consumed.
+/// Rows arrive one page at a time. Each page is decoded when it is received (resolving compact ids
+/// against the graph schema, refreshing it once on a cache miss), so a `RowStream` never depends on
+/// the schema cache afterwards and stays valid even if the graph is mutated or deleted before it is
+/// drained. It is `Send + 'static`: it can be moved into a `tokio::spawn` task and composed with
+/// `futures::StreamExt`/`futures::TryStreamExt`.
 ///
-/// It implements [`futures_core::Stream`], yielding `FalkorResult<Row>`. The rows are already in
-/// memory, so this is a buffered stream — `poll_next` never returns `Poll::Pending`. Drain it with
-/// `StreamExt`/`TryStreamExt` (`try_next().await`, `try_collect().await`,
-/// `map(..).buffer_unordered(..)`, …).
+/// Queries run through [`QueryBuilder::paginate`](crate::QueryBuilder::paginate), and ordered
+/// read-only queries on a client that opted in with
+/// [`with_stream_page_size`](crate::FalkorClientBuilder::with_stream_page_size), are paged: the
+/// stream holds only
+/// the current page, fetches the next one once it is drained, and returns `Poll::Pending` while
+/// that fetch is in flight, so a slow consumer applies backpressure instead of buffering the whole
+/// result set. Any other query arrives as one reply, held whole.
 ///
 /// ```rust,no_run
 /// # #![recursion_limit = "256"]
@@ -41,11 +46,13 @@ use std::task::{Context, Poll};
 /// ```
 pub struct RowStream {
     rows: VecDeque<FalkorResult<Row>>,
+    pager: Option<AsyncPager>,
 }
 
 impl RowStream {
-    /// Parses every raw row of a reply into a [`Row`], surfacing per-row parse failures as `Err`
-    /// items, while resolving compact ids against `graph_schema` (refreshing it on a cache miss).
+    /// Decodes every raw row of a received page into a [`Row`], surfacing per-row parse failures
+    /// as `Err` items, while resolving compact ids against `graph_schema` (refreshing it on a cache
+    /// miss).
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(name = "Parse Row Stream", skip_all, level = "debug")
@@ -56,16 +63,38 @@ impl RowStream {
         graph_schema: &mut GraphSchema,
     ) -> Self {
         Self {
-            rows: crate::response::row::parse_rows(header, raw_rows, graph_schema).into(),
+            rows: raw_rows
+                .into_iter()
+                .map(|raw| crate::response::row::parse_row(&header, raw, graph_schema))
+                .collect(),
+            pager: None,
         }
     }
 
-    /// Returns the number of rows remaining in the result set.
+    /// Attaches a pager that fetches further pages of a paginated query once this one is drained.
+    pub(crate) fn with_pager(
+        self,
+        pager: AsyncPager,
+    ) -> Self {
+        Self {
+            pager: Some(pager),
+            ..self
+        }
+    }
+
+    /// Awaits the next row, fetching the following page once the current one is drained.
+    pub(crate) async fn next_row(&mut self) -> Option<FalkorResult<Row>> {
+        use futures_core::Stream;
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+
+    /// Returns the number of rows already received and not yet consumed. A paged stream may
+    /// fetch more pages after these; see [`Stream::size_hint`](futures_core::Stream::size_hint).
     pub fn len(&self) -> usize {
         self.rows.len()
     }
 
-    /// Returns whether this result set is empty or depleted.
+    /// Returns whether no received rows remain. A paged stream may still fetch more pages.
     pub fn is_empty(&self) -> bool {
         self.rows.is_empty()
     }
@@ -73,11 +102,20 @@ impl RowStream {
     /// Iterates the rows as bare `Vec<FalkorValue>`, reproducing the pre-0.7 behaviour in which a
     /// row that fails to parse is yielded as a single `[FalkorValue::Unparseable]` element instead
     /// of surfacing the error. Prefer the default fallible iteration.
-    pub fn into_values_lossy(self) -> impl Iterator<Item = Vec<FalkorValue>> {
-        self.rows.into_iter().map(|row| match row {
+    ///
+    /// # Returns
+    /// The rows, or [`FalkorDBError::InvalidPagination`] for a paged stream, whose later pages
+    /// can only be fetched by polling it as a [`Stream`](futures_core::Stream).
+    pub fn into_values_lossy(self) -> FalkorResult<impl Iterator<Item = Vec<FalkorValue>>> {
+        if self.pager.is_some() {
+            return Err(FalkorDBError::InvalidPagination {
+                reason: "into_values_lossy cannot fetch the later pages of a paged stream",
+            });
+        }
+        Ok(self.rows.into_iter().map(|row| match row {
             Ok(row) => row.into_values(),
             Err(err) => vec![FalkorValue::Unparseable(err.to_string())],
-        })
+        }))
     }
 }
 
@@ -86,15 +124,36 @@ impl futures_core::Stream for RowStream {
 
     fn poll_next(
         self: Pin<&mut Self>,
-        _cx: &mut Context<'_>,
+        cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
-        // Rows are already parsed and buffered in memory, so a row is always immediately ready;
-        // this never parks the task.
-        Poll::Ready(self.get_mut().rows.pop_front())
+        let this = self.get_mut();
+        loop {
+            // The current page is already decoded, so its next row is always immediately ready.
+            if let Some(row) = this.rows.pop_front() {
+                return Poll::Ready(Some(row));
+            }
+            let Some(pager) = this.pager.as_mut() else {
+                return Poll::Ready(None);
+            };
+            match pager.poll_next_page(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Ok((page, more))) => {
+                    this.rows = page.rows;
+                    if !more {
+                        this.pager = None;
+                    }
+                }
+                Poll::Ready(Err(err)) => {
+                    this.pager = None;
+                    return Poll::Ready(Some(Err(err)));
+                }
+            }
+        }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.rows.len(), Some(self.rows.len()))
+        let upper = self.pager.is_none().then_some(self.rows.len());
+        (self.rows.len(), upper)
     }
 }
 
@@ -102,7 +161,6 @@ impl futures_core::Stream for RowStream {
 mod tests {
     use super::*;
     use crate::client::blocking::create_empty_inner_sync_client;
-    use crate::FalkorDBError;
 
     /// A single-column row holding one scalar `i64` (`ParserTypeMarker::I64 == 3`), which parses
     /// without needing any schema lookup.
@@ -113,14 +171,14 @@ mod tests {
         ])])
     }
 
-    fn stream_of(values: &[i64]) -> RowStream {
+    fn parse_stream(raw_rows: Vec<redis::Value>) -> RowStream {
         let header: Arc<[String]> = Arc::from(vec!["n".to_string()]);
         let mut schema = GraphSchema::new("test", create_empty_inner_sync_client());
-        RowStream::parse(
-            header,
-            values.iter().map(|&n| scalar_row(n)).collect(),
-            &mut schema,
-        )
+        RowStream::parse(header, raw_rows, &mut schema)
+    }
+
+    fn stream_of(values: &[i64]) -> RowStream {
+        parse_stream(values.iter().map(|&n| scalar_row(n)).collect())
     }
 
     fn assert_send_static<T: Send + 'static>() {}
@@ -162,7 +220,7 @@ mod tests {
 
     #[test]
     fn into_values_lossy_yields_bare_rows() {
-        let rows: Vec<Vec<FalkorValue>> = stream_of(&[5]).into_values_lossy().collect();
+        let rows: Vec<Vec<FalkorValue>> = stream_of(&[5]).into_values_lossy().unwrap().collect();
         assert_eq!(rows, vec![vec![FalkorValue::I64(5)]]);
     }
 
@@ -173,9 +231,7 @@ mod tests {
             redis::Value::Array(vec![redis::Value::Int(3), redis::Value::Int(1)]),
             redis::Value::Array(vec![redis::Value::Int(3), redis::Value::Int(2)]),
         ]);
-        let header: Arc<[String]> = Arc::from(vec!["n".to_string()]);
-        let mut schema = GraphSchema::new("test", create_empty_inner_sync_client());
-        let mut stream = RowStream::parse(header, vec![two_value_row], &mut schema);
+        let mut stream = parse_stream(vec![two_value_row]);
 
         let err = stream.rows.pop_front().unwrap().unwrap_err();
         assert!(matches!(
@@ -195,11 +251,9 @@ mod tests {
             redis::Value::Int(9999),
             redis::Value::Int(1),
         ])]);
-        let header: Arc<[String]> = Arc::from(vec!["n".to_string()]);
-        let mut schema = GraphSchema::new("test", create_empty_inner_sync_client());
-        let stream = RowStream::parse(header, vec![bad_row], &mut schema);
+        let stream = parse_stream(vec![bad_row]);
 
-        let rows: Vec<Vec<FalkorValue>> = stream.into_values_lossy().collect();
+        let rows: Vec<Vec<FalkorValue>> = stream.into_values_lossy().unwrap().collect();
         assert_eq!(rows.len(), 1);
         assert!(matches!(rows[0].as_slice(), [FalkorValue::Unparseable(_)]));
     }
@@ -211,4 +265,22 @@ mod tests {
         let stream = stream_of(&[1, 2, 3]);
         assert_eq!(Stream::size_hint(&stream), (3, Some(3)));
     }
+
+    #[test]
+    fn parse_errors_are_yielded_in_row_order() {
+        // The second raw row is malformed; it must not affect the rows around it.
+        let bad_row = redis::Value::Array(vec![redis::Value::Array(vec![
+            redis::Value::Int(9999),
+            redis::Value::Int(1),
+        ])]);
+        let mut stream = parse_stream(vec![scalar_row(1), bad_row, scalar_row(3)]);
+
+        let first = stream.rows.pop_front().unwrap().unwrap();
+        assert_eq!(first.try_get_at::<i64>(0).unwrap(), 1);
+        assert_eq!(stream.len(), 2);
+        assert!(stream.rows.pop_front().unwrap().is_err());
+        let last = stream.rows.pop_front().unwrap().unwrap();
+        assert_eq!(last.try_get_at::<i64>(0).unwrap(), 3);
+        assert!(stream.rows.pop_front().is_none());
+    }
 }
@@ -48,12 +48,13 @@ impl<T> TypedRowStream<T> {
         }
     }
 
-    /// Returns the number of rows remaining in the result set.
+    /// Returns the number of rows already received and not yet consumed. A paged stream may
+    /// fetch more pages after these; see [`Stream::size_hint`](futures_core::Stream::size_hint).
     pub fn len(&self) -> usize {
         self.inner.len()
     }
 
-    /// Returns whether this result set is empty or depleted.
+    /// Returns whether no received rows remain. A paged stream may still fetch more pages.
     pub fn is_empty(&self) -> bool {
         self.inner.is_empty()
     }
@@ -75,7 +76,7 @@ where
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.inner.len(), Some(self.inner.len()))
+        self.inner.size_hint()
     }
 }
 
@@ -424,6 +424,46 @@ where
         .await
 }
 
+/// Run a `WATCH`-based graph transaction under `policy`'s attempt budget and backoff, re-running
+/// `attempt` only when the watched graph changed
+/// ([`TransactionConflict`](FalkorDBError::TransactionConflict)). Unlike the query seams this
+/// ignores the policy's [scope](RetryPolicy::scope): an aborted transaction applied nothing, so
+/// running it again cannot duplicate a write.
+pub(crate) fn run_transaction_blocking<T>(
+    policy: &RetryPolicy,
+    mut attempt: impl FnMut() -> FalkorResult<T>,
+) -> FalkorResult<T> {
+    let mut delays = backon::BackoffBuilder::build(policy.backon_builder());
+    loop {
+        match attempt() {
+            Err(FalkorDBError::TransactionConflict) => match delays.next() {
+                Some(delay) => std::thread::sleep(delay),
+                None => return Err(FalkorDBError::TransactionConflict),
+            },
+            result => return result,
+        }
+    }
+}
+
+/// Async counterpart of [`run_transaction_blocking`]. Takes an async closure rather than going
+/// through `backon`, so each attempt can borrow the caller's transaction body mutably.
+#[cfg(feature = "tokio")]
+pub(crate) async fn run_transaction_async<T>(
+    policy: &RetryPolicy,
+    mut attempt: impl AsyncFnMut() -> FalkorResult<T>,
+) -> FalkorResult<T> {
+    let mut delays = backon::BackoffBuilder::build(policy.backon_builder());
+    loop {
+        match attempt().await {
+            Err(FalkorDBError::TransactionConflict) => match delays.next() {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Err(FalkorDBError::TransactionConflict),
+            },
+            result => return result,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -715,6 +755,39 @@ mod blocking_runner_tests {
         assert_eq!(result.expect("should succeed after healing"), "ok");
         assert_eq!(calls.get(), 3);
     }
+
+    #[test]
+    fn transaction_conflict_reruns_until_budget_is_spent() {
+        let calls = Cell::new(0);
+        let result: FalkorResult<()> = run_transaction_blocking(&fast_read_only(3), || {
+            calls.set(calls.get() + 1);
+            Err(FalkorDBError::TransactionConflict)
+        });
+        assert!(matches!(result, Err(FalkorDBError::TransactionConflict)));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn transaction_under_disabled_policy_runs_once() {
+        let calls = Cell::new(0);
+        let result: FalkorResult<()> = run_transaction_blocking(&RetryPolicy::disabled(), || {
+            calls.set(calls.get() + 1);
+            Err(FalkorDBError::TransactionConflict)
+        });
+        assert!(matches!(result, Err(FalkorDBError::TransactionConflict)));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn transaction_other_errors_are_not_rerun() {
+        let calls = Cell::new(0);
+        let result: FalkorResult<()> = run_transaction_blocking(&fast_read_only(5), || {
+            calls.set(calls.get() + 1);
+            Err(FalkorDBError::ConnectionDown)
+        });
+        assert!(matches!(result, Err(FalkorDBError::ConnectionDown)));
+        assert_eq!(calls.get(), 1, "only conflicts re-run the transaction");
+    }
 }
 
 /// Async parity of [`blocking_runner_tests`].
@@ -786,4 +859,20 @@ mod async_runner_tests {
         assert!(matches!(result, Err(FalkorDBError::ConnectionDown)));
         assert_eq!(calls.get(), 4);
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn transaction_conflict_reruns_until_success() {
+        let calls = Cell::new(0);
+        let result = run_transaction_async(&fast_read_only(3), async || {
+            calls.set(calls.get() + 1);
+            if calls.get() < 3 {
+                Err(FalkorDBError::TransactionConflict)
+            } else {
+                Ok(9)
+            }
+        })
+        .await;
+        assert_eq!(result.expect("should commit on the third attempt"), 9);
+        assert_eq!(calls.get(), 3);
+    }
 }
@@ -40,6 +40,42 @@ pub trait FromFalkorValue: Sized {
     fn from_falkor_value(value: FalkorValue) -> FalkorResult<Self>;
 }
 
+/// A type that can be built from a [`Node`]'s properties.
+///
+/// With the `derive` feature, `#[derive(FromNode)]` implements it for a struct with named fields,
+/// reading each from the property of the same name, together with [`FromFalkorValue`] so the
+/// struct can be read straight from a node column (for example as a field of a `FromRow` struct).
+/// FalkorDB does not store `null` properties, so an absent property becomes `None` for an `Option`
+/// field and [`FalkorDBError::MissingProperty`] otherwise.
+///
+/// ```ignore
+/// use falkordb::FromNode;
+///
+/// #[derive(FromNode)]
+/// struct Actor {
+///     name: String,
+///     #[falkordb(rename = "born")]
+///     birth_year: Option<i64>,
+/// }
+///
+/// let actor: Actor = row.try_get("a")?;
+/// ```
+pub trait FromNode: Sized {
+    /// Build `Self` from `node`'s properties.
+    ///
+    /// # Errors
+    ///
+    /// Derived implementations return [`FalkorDBError::MissingProperty`] for an absent property of a
+    /// non-`Option` field and [`FalkorDBError::TypeError`] for a value of the wrong type.
+    fn from_node(node: Node) -> FalkorResult<Self>;
+}
+
+impl FromNode for Node {
+    fn from_node(node: Node) -> FalkorResult<Self> {
+        Ok(node)
+    }
+}
+
 /// The name of a [`FalkorValue`] variant, for error messages.
 pub(crate) fn variant_name(value: &FalkorValue) -> &'static str {
     match value {
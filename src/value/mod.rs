@@ -22,6 +22,8 @@ pub(crate) mod vec32;
 
 #[cfg(feature = "serde")]
 mod de;
+#[cfg(feature = "serde")]
+mod ser;
 
 #[cfg(all(test, feature = "serde"))]
 mod de_proptest;
@@ -31,12 +33,31 @@ mod param_proptest;
 
 pub use param::{to_cypher_param, FalkorParams, IntoFalkorParam, IntoFalkorParams, RawParam};
 
-pub use from_value::FromFalkorValue;
+pub use from_value::{FromFalkorValue, FromNode};
 
 #[cfg(feature = "serde")]
 pub use de::{from_falkor_row, from_falkor_value, FalkorValueDeserializer};
+#[cfg(feature = "serde")]
+pub use param::SerdeParam;
 
 /// An enum of all the supported Falkor types
+///
+/// With the `serde` feature, this and the types it carries implement `serde::Serialize` as plain,
+/// untagged data, so a result can be handed straight to a JSON (or any other serde) encoder.
+/// Every map, including entity `properties`, is written in key order:
+///
+/// | Value                                | Serialized as                                                              |
+/// |--------------------------------------|----------------------------------------------------------------------------|
+/// | `None`                               | unit (`null`)                                                              |
+/// | `Bool` / `I64` / `F64` / `String`    | the matching primitive                                                     |
+/// | `Array` / `Vec32`                    | a sequence                                                                 |
+/// | `Map`                                | a map, in key order                                                        |
+/// | [`Node`]                             | `{"id", "labels", "properties"}`                                           |
+/// | [`Edge`]                             | `{"id", "relationship_type", "src_node_id", "dst_node_id", "properties"}`  |
+/// | [`Path`]                             | `{"nodes", "relationships"}`                                               |
+/// | [`Point`]                            | `{"latitude", "longitude"}`                                                |
+/// | `DateTime`/`Date`/`Time`/`Duration`  | the raw temporal scalar in seconds, as an `i64` (as when deserializing)    |
+/// | `Unparseable`                        | an error                                                                   |
 #[derive(Clone, Debug, PartialEq)]
 #[non_exhaustive]
 pub enum FalkorValue {
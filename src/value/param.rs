@@ -20,8 +20,16 @@ use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write;
 
-mod sealed {
+#[cfg(feature = "serde")]
+mod serde_param;
+
+#[cfg(feature = "serde")]
+pub use serde_param::SerdeParam;
+
+pub(crate) mod sealed {
+    /// Supertrait of [`IntoFalkorParam`](super::IntoFalkorParam).
     pub trait Sealed {}
+    /// Supertrait of [`IntoFalkorParams`](super::IntoFalkorParams).
     pub trait SealedParams {}
 }
 
@@ -31,7 +39,8 @@ mod sealed {
 /// safe Cypher literal, which is what makes parameters injection-proof. To pass a value whose
 /// type does not implement it, either convert it to a supported type (for example a map or a
 /// list) or use [`QueryBuilder::with_raw_param`](crate::QueryBuilder::with_raw_param) for a raw,
-/// already-valid Cypher expression.
+/// already-valid Cypher expression. With the `derive` feature, `#[derive(IntoFalkorParam)]`
+/// implements it for a struct whose fields all implement it, encoding the struct as a map.
 ///
 /// Encoding is fallible: a few values have no Cypher representation — non-finite floats, strings
 /// containing a NUL byte, integers outside the `i64` range, map keys containing a backtick, and
@@ -329,23 +338,72 @@ impl IntoFalkorParam for char {
 
 // ---- reference / smart-pointer impls --------------------------------------------------------
 
-impl<T: sealed::Sealed + ?Sized> sealed::Sealed for &T {}
-impl<T: IntoFalkorParam + ?Sized> IntoFalkorParam for &T {
-    fn encode_param(
+/// Encodes a value by handing already-encodable values to a [`ParamWriter`], which can emit
+/// nothing else.
+///
+/// A single blanket impl makes every `EncodeParam` type an [`IntoFalkorParam`]; references,
+/// boxes and `#[derive(IntoFalkorParam)]` structs are encoded this way, so the sealed supertrait
+/// never has to leave the crate. A hand-written impl gains nothing over the derive: the writer
+/// only accepts values that are themselves [`IntoFalkorParam`].
+#[doc(hidden)]
+pub trait EncodeParam {
+    /// Writes `self` through `out`.
+    fn write_param(
         &self,
-        out: &mut String,
+        out: &mut ParamWriter<'_>,
+    ) -> FalkorResult<()>;
+}
+
+/// The output of an [`EncodeParam`] impl: the parameter text, writable only with values that
+/// encode themselves.
+#[doc(hidden)]
+pub struct ParamWriter<'a> {
+    out: &'a mut String,
+}
+
+impl ParamWriter<'_> {
+    /// Writes one value.
+    pub fn value<V: IntoFalkorParam + ?Sized>(
+        &mut self,
+        value: &V,
+    ) -> FalkorResult<()> {
+        value.encode_param(self.out)
+    }
+
+    /// Writes the named values as one map literal, in order.
+    pub fn map(
+        &mut self,
+        fields: &[(&str, &dyn IntoFalkorParam)],
     ) -> FalkorResult<()> {
-        (**self).encode_param(out)
+        encode_map(fields.iter().map(|(name, value)| (name, value)), self.out)
     }
 }
 
-impl<T: sealed::Sealed + ?Sized> sealed::Sealed for Box<T> {}
-impl<T: IntoFalkorParam + ?Sized> IntoFalkorParam for Box<T> {
+impl<T: EncodeParam + ?Sized> sealed::Sealed for T {}
+impl<T: EncodeParam + ?Sized> IntoFalkorParam for T {
     fn encode_param(
         &self,
         out: &mut String,
     ) -> FalkorResult<()> {
-        (**self).encode_param(out)
+        self.write_param(&mut ParamWriter { out })
+    }
+}
+
+impl<T: IntoFalkorParam + ?Sized> EncodeParam for &T {
+    fn write_param(
+        &self,
+        out: &mut ParamWriter<'_>,
+    ) -> FalkorResult<()> {
+        out.value(*self)
+    }
+}
+
+impl<T: IntoFalkorParam + ?Sized> EncodeParam for Box<T> {
+    fn write_param(
+        &self,
+        out: &mut ParamWriter<'_>,
+    ) -> FalkorResult<()> {
+        out.value(&**self)
     }
 }
 
@@ -613,6 +671,24 @@ impl FalkorParams {
         }
     }
 
+    /// Consumes the set, returning each `(name, encoded_value)` pair in insertion order, or the
+    /// first parameter encoding error.
+    pub(crate) fn into_encoded(self) -> FalkorResult<Vec<(String, String)>> {
+        self.entries
+            .into_iter()
+            .map(|(name, encoded)| encoded.map(|value| (name, value)))
+            .collect()
+    }
+
+    /// Returns whether a parameter named `name` has been added.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn contains(
+        &self,
+        name: &str,
+    ) -> bool {
+        self.entries.iter().any(|(existing, _)| existing == name)
+    }
+
     /// Returns whether there are no parameters.
     pub fn is_empty(&self) -> bool {
         self.entries.is_empty()
@@ -649,7 +725,8 @@ impl FalkorParams {
 /// [`QueryBuilder::with_params`](crate::QueryBuilder::with_params).
 ///
 /// Implemented for [`FalkorParams`], `()` (no parameters), `Vec<(K, V)>`, `[(K, V); N]`, and
-/// `HashMap`/`BTreeMap<K, V>`, where `K: AsRef<str>` and `V: IntoFalkorParam`.
+/// `HashMap`/`BTreeMap<K, V>`, where `K: AsRef<str>` and `V: IntoFalkorParam`. With the `derive`
+/// feature, `#[derive(IntoFalkorParams)]` implements it for a struct and references to it.
 pub trait IntoFalkorParams: sealed::SealedParams {
     /// Encode all entries into a [`FalkorParams`].
     #[doc(hidden)]
@@ -715,6 +792,66 @@ impl<K: AsRef<str>, V: IntoFalkorParam> IntoFalkorParams for BTreeMap<K, V> {
     }
 }
 
+// ---- derive support -------------------------------------------------------------------------
+
+/// What `#[derive(IntoFalkorParam)]` / `#[derive(IntoFalkorParams)]` expand to. Derived impls
+/// implement [`EncodeParam`] or [`EncodeParams`](derive_support::EncodeParams), whose writers only
+/// accept fields that already implement [`IntoFalkorParam`], so they keep the sealing guarantee of
+/// emitting nothing but safe Cypher literals without the sealed traits being exported.
+#[cfg(feature = "derive")]
+pub(crate) mod derive_support {
+    use super::{sealed, FalkorParams, IntoFalkorParam, IntoFalkorParams};
+
+    pub use super::{EncodeParam, ParamWriter};
+
+    /// Binds a struct's fields as query parameters through a [`ParamsWriter`]; every
+    /// `EncodeParams` type, and every reference to one, is an [`IntoFalkorParams`].
+    pub trait EncodeParams {
+        /// Writes the parameters of `self` through `out`.
+        fn write_params(
+            &self,
+            out: &mut ParamsWriter<'_>,
+        );
+    }
+
+    /// The output of an [`EncodeParams`] impl: named parameters whose values encode themselves.
+    pub struct ParamsWriter<'a> {
+        pub(super) params: &'a mut FalkorParams,
+    }
+
+    impl ParamsWriter<'_> {
+        /// Binds one parameter per field, in declaration order.
+        pub fn fields(
+            &mut self,
+            fields: &[(&str, &dyn IntoFalkorParam)],
+        ) {
+            for (name, value) in fields {
+                self.params.add_param(name, value);
+            }
+        }
+    }
+
+    impl<T: EncodeParams + ?Sized> sealed::SealedParams for T {}
+    impl<T: EncodeParams> IntoFalkorParams for T {
+        fn into_falkor_params(self) -> FalkorParams {
+            let mut params = FalkorParams::new();
+            self.write_params(&mut ParamsWriter {
+                params: &mut params,
+            });
+            params
+        }
+    }
+
+    impl<T: EncodeParams + ?Sized> EncodeParams for &T {
+        fn write_params(
+            &self,
+            out: &mut ParamsWriter<'_>,
+        ) {
+            (**self).write_params(out);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -894,6 +1031,27 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_support_encodes_fields_in_order() {
+        let tags = vec!["a", "b"];
+        let fields: [(&str, &dyn IntoFalkorParam); 3] =
+            [("name", &"Ada"), ("age", &36i64), ("tags", &tags)];
+
+        let mut params = FalkorParams::new();
+        derive_support::ParamsWriter {
+            params: &mut params,
+        }
+        .fields(&fields);
+        let mut out = String::new();
+        params.encode_preamble(&mut out).unwrap();
+        assert_eq!(out, "CYPHER name='Ada' age=36 tags=['a', 'b'] ");
+
+        let mut map = String::new();
+        ParamWriter { out: &mut map }.map(&fields).unwrap();
+        assert_eq!(map, "{name: 'Ada', age: 36, tags: ['a', 'b']}");
+    }
+
     #[test]
     fn test_empty_params_no_preamble() {
         let params = FalkorParams::new();
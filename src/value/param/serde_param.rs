@@ -0,0 +1,765 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Binding any `serde::Serialize` value as a query parameter, through a [`serde::Serializer`]
+//! that writes Cypher literals with the same encoders as the typed [`IntoFalkorParam`] impls.
+
+use super::{encode_list, encode_map_key, encode_str, param_err, sealed, IntoFalkorParam};
+use crate::{FalkorDBError, FalkorResult};
+use serde::ser::{self, Impossible, Serialize};
+
+/// Binds any [`serde::Serialize`] value as a query parameter.
+///
+/// The value is encoded with the same escaping as the typed parameters, so it stays
+/// injection-proof. Structs and maps become Cypher maps, sequences and tuples become lists,
+/// `None` and unit become `null`, and enums follow serde's externally tagged form: a unit variant
+/// is its name as a string, and any other variant is a one-entry map `{Variant: ...}`.
+///
+/// Map keys must serialize as strings, characters or integers (the latter become their decimal
+/// form). The encoding errors of the typed parameters apply too, such as non-finite floats or
+/// integers outside the `i64` range.
+///
+/// ```
+/// # use falkordb::{to_cypher_param, SerdeParam};
+/// #[derive(serde::Serialize)]
+/// struct Rating {
+///     title: &'static str,
+///     stars: u8,
+/// }
+///
+/// let literal = to_cypher_param(&SerdeParam(Rating { title: "Heat", stars: 5 })).unwrap();
+/// assert_eq!(literal, "{title: 'Heat', stars: 5}");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SerdeParam<T>(pub T);
+
+impl<T: Serialize> sealed::Sealed for SerdeParam<T> {}
+impl<T: Serialize> IntoFalkorParam for SerdeParam<T> {
+    fn encode_param(
+        &self,
+        out: &mut String,
+    ) -> FalkorResult<()> {
+        self.0.serialize(CypherSerializer { out })
+    }
+}
+
+/// Writes one Cypher literal per serialized value into `out`.
+struct CypherSerializer<'a> {
+    out: &'a mut String,
+}
+
+impl<'a> CypherSerializer<'a> {
+    /// Open `{Variant: ` for a non-unit enum variant; the caller closes it with `}`.
+    fn open_variant(
+        &mut self,
+        variant: &str,
+    ) -> FalkorResult<()> {
+        self.out.push('{');
+        encode_map_key(variant, self.out)?;
+        self.out.push_str(": ");
+        Ok(())
+    }
+
+    fn list(
+        self,
+        close: &'static str,
+    ) -> Compound<'a> {
+        self.out.push('[');
+        Compound {
+            out: self.out,
+            first: true,
+            close,
+        }
+    }
+
+    fn map(
+        self,
+        close: &'static str,
+    ) -> Compound<'a> {
+        self.out.push('{');
+        Compound {
+            out: self.out,
+            first: true,
+            close,
+        }
+    }
+}
+
+impl<'a> ser::Serializer for CypherSerializer<'a> {
+    type Ok = ();
+    type Error = FalkorDBError;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(
+        self,
+        v: bool,
+    ) -> FalkorResult<()> {
+        v.encode_param(self.out)
+    }
+
+    fn serialize_i8(
+        self,
+        v: i8,
+    ) -> FalkorResult<()> {
+        v.encode_param(self.out)
+    }
+
+    fn serialize_i16(
+        self,
+        v: i16,
+    ) -> FalkorResult<()> {
+        v.encode_param(self.out)
+    }
+
+    fn serialize_i32(
+        self,
+        v: i32,
+    ) -> FalkorResult<()> {
+        v.encode_param(self.out)
+    }
+
+    fn serialize_i64(
+        self,
+        v: i64,
+    ) -> FalkorResult<()> {
+        v.encode_param(self.out)
+    }
+
+    fn serialize_i128(
+        self,
+        v: i128,
+    ) -> FalkorResult<()> {
+        v.encode_param(self.out)
+    }
+
+    fn serialize_u8(
+        self,
+        v: u8,
+    ) -> FalkorResult<()> {
+        v.encode_param(self.out)
+    }
+
+    fn serialize_u16(
+        self,
+        v: u16,
+    ) -> FalkorResult<()> {
+        v.encode_param(self.out)
+    }
+
+    fn serialize_u32(
+        self,
+        v: u32,
+    ) -> FalkorResult<()> {
+        v.encode_param(self.out)
+    }
+
+    fn serialize_u64(
+        self,
+        v: u64,
+    ) -> FalkorResult<()> {
+        v.encode_param(self.out)
+    }
+
+    fn serialize_u128(
+        self,
+        v: u128,
+    ) -> FalkorResult<()> {
+        v.encode_param(self.out)
+    }
+
+    fn serialize_f32(
+        self,
+        v: f32,
+    ) -> FalkorResult<()> {
+        v.encode_param(self.out)
+    }
+
+    fn serialize_f64(
+        self,
+        v: f64,
+    ) -> FalkorResult<()> {
+        v.encode_param(self.out)
+    }
+
+    fn serialize_char(
+        self,
+        v: char,
+    ) -> FalkorResult<()> {
+        v.encode_param(self.out)
+    }
+
+    fn serialize_str(
+        self,
+        v: &str,
+    ) -> FalkorResult<()> {
+        encode_str(v, self.out)
+    }
+
+    fn serialize_bytes(
+        self,
+        v: &[u8],
+    ) -> FalkorResult<()> {
+        encode_list(v, self.out)
+    }
+
+    fn serialize_none(self) -> FalkorResult<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(
+        self,
+        value: &T,
+    ) -> FalkorResult<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> FalkorResult<()> {
+        self.out.push_str("null");
+        Ok(())
+    }
+
+    fn serialize_unit_struct(
+        self,
+        _name: &'static str,
+    ) -> FalkorResult<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> FalkorResult<()> {
+        encode_str(variant, self.out)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> FalkorResult<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        mut self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> FalkorResult<()> {
+        self.open_variant(variant)?;
+        value.serialize(CypherSerializer {
+            out: &mut *self.out,
+        })?;
+        self.out.push('}');
+        Ok(())
+    }
+
+    fn serialize_seq(
+        self,
+        _len: Option<usize>,
+    ) -> FalkorResult<Compound<'a>> {
+        Ok(self.list("]"))
+    }
+
+    fn serialize_tuple(
+        self,
+        _len: usize,
+    ) -> FalkorResult<Compound<'a>> {
+        Ok(self.list("]"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> FalkorResult<Compound<'a>> {
+        Ok(self.list("]"))
+    }
+
+    fn serialize_tuple_variant(
+        mut self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> FalkorResult<Compound<'a>> {
+        self.open_variant(variant)?;
+        Ok(self.list("]}"))
+    }
+
+    fn serialize_map(
+        self,
+        _len: Option<usize>,
+    ) -> FalkorResult<Compound<'a>> {
+        Ok(self.map("}"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> FalkorResult<Compound<'a>> {
+        Ok(self.map("}"))
+    }
+
+    fn serialize_struct_variant(
+        mut self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> FalkorResult<Compound<'a>> {
+        self.open_variant(variant)?;
+        Ok(self.map("}}"))
+    }
+}
+
+/// An open list or map literal; `close` also closes an enclosing `{Variant: ` if there is one.
+struct Compound<'a> {
+    out: &'a mut String,
+    first: bool,
+    close: &'static str,
+}
+
+impl Compound<'_> {
+    fn separator(&mut self) {
+        if !std::mem::take(&mut self.first) {
+            self.out.push_str(", ");
+        }
+    }
+
+    fn element<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> FalkorResult<()> {
+        self.separator();
+        value.serialize(CypherSerializer {
+            out: &mut *self.out,
+        })
+    }
+
+    fn field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &str,
+        value: &T,
+    ) -> FalkorResult<()> {
+        self.separator();
+        encode_map_key(key, self.out)?;
+        self.out.push_str(": ");
+        value.serialize(CypherSerializer {
+            out: &mut *self.out,
+        })
+    }
+
+    fn finish(self) -> FalkorResult<()> {
+        self.out.push_str(self.close);
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = FalkorDBError;
+
+    fn serialize_element<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> FalkorResult<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> FalkorResult<()> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = FalkorDBError;
+
+    fn serialize_element<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> FalkorResult<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> FalkorResult<()> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = FalkorDBError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> FalkorResult<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> FalkorResult<()> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = FalkorDBError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> FalkorResult<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> FalkorResult<()> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = FalkorDBError;
+
+    fn serialize_key<T: ?Sized + Serialize>(
+        &mut self,
+        key: &T,
+    ) -> FalkorResult<()> {
+        let key = key.serialize(MapKeySerializer)?;
+        self.separator();
+        encode_map_key(&key, self.out)?;
+        self.out.push_str(": ");
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+    ) -> FalkorResult<()> {
+        value.serialize(CypherSerializer {
+            out: &mut *self.out,
+        })
+    }
+
+    fn end(self) -> FalkorResult<()> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = FalkorDBError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> FalkorResult<()> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> FalkorResult<()> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = FalkorDBError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> FalkorResult<()> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> FalkorResult<()> {
+        self.finish()
+    }
+}
+
+/// Turns a map key into the string [`encode_map_key`] quotes; Cypher map keys are names.
+struct MapKeySerializer;
+
+fn key_err() -> FalkorDBError {
+    param_err("map parameter keys must be strings, characters or integers")
+}
+
+macro_rules! serialize_int_key {
+    ($($method:ident: $t:ty),*) => {$(
+        fn $method(
+            self,
+            v: $t,
+        ) -> FalkorResult<String> {
+            Ok(v.to_string())
+        }
+    )*};
+}
+
+impl ser::Serializer for MapKeySerializer {
+    type Ok = String;
+    type Error = FalkorDBError;
+    type SerializeSeq = Impossible<String, FalkorDBError>;
+    type SerializeTuple = Impossible<String, FalkorDBError>;
+    type SerializeTupleStruct = Impossible<String, FalkorDBError>;
+    type SerializeTupleVariant = Impossible<String, FalkorDBError>;
+    type SerializeMap = Impossible<String, FalkorDBError>;
+    type SerializeStruct = Impossible<String, FalkorDBError>;
+    type SerializeStructVariant = Impossible<String, FalkorDBError>;
+
+    serialize_int_key!(
+        serialize_i8: i8, serialize_i16: i16, serialize_i32: i32, serialize_i64: i64,
+        serialize_i128: i128, serialize_u8: u8, serialize_u16: u16, serialize_u32: u32,
+        serialize_u64: u64, serialize_u128: u128
+    );
+
+    fn serialize_str(
+        self,
+        v: &str,
+    ) -> FalkorResult<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_char(
+        self,
+        v: char,
+    ) -> FalkorResult<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> FalkorResult<String> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> FalkorResult<String> {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(
+        self,
+        _v: bool,
+    ) -> FalkorResult<String> {
+        Err(key_err())
+    }
+
+    fn serialize_f32(
+        self,
+        _v: f32,
+    ) -> FalkorResult<String> {
+        Err(key_err())
+    }
+
+    fn serialize_f64(
+        self,
+        _v: f64,
+    ) -> FalkorResult<String> {
+        Err(key_err())
+    }
+
+    fn serialize_bytes(
+        self,
+        _v: &[u8],
+    ) -> FalkorResult<String> {
+        Err(key_err())
+    }
+
+    fn serialize_none(self) -> FalkorResult<String> {
+        Err(key_err())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(
+        self,
+        _value: &T,
+    ) -> FalkorResult<String> {
+        Err(key_err())
+    }
+
+    fn serialize_unit(self) -> FalkorResult<String> {
+        Err(key_err())
+    }
+
+    fn serialize_unit_struct(
+        self,
+        _name: &'static str,
+    ) -> FalkorResult<String> {
+        Err(key_err())
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> FalkorResult<String> {
+        Err(key_err())
+    }
+
+    fn serialize_seq(
+        self,
+        _len: Option<usize>,
+    ) -> FalkorResult<Self::SerializeSeq> {
+        Err(key_err())
+    }
+
+    fn serialize_tuple(
+        self,
+        _len: usize,
+    ) -> FalkorResult<Self::SerializeTuple> {
+        Err(key_err())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> FalkorResult<Self::SerializeTupleStruct> {
+        Err(key_err())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> FalkorResult<Self::SerializeTupleVariant> {
+        Err(key_err())
+    }
+
+    fn serialize_map(
+        self,
+        _len: Option<usize>,
+    ) -> FalkorResult<Self::SerializeMap> {
+        Err(key_err())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> FalkorResult<Self::SerializeStruct> {
+        Err(key_err())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> FalkorResult<Self::SerializeStructVariant> {
+        Err(key_err())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_cypher_param;
+    use std::collections::BTreeMap;
+
+    #[derive(serde::Serialize)]
+    struct Movie<'a> {
+        title: &'a str,
+        year: u16,
+        rating: Option<f64>,
+        genres: Vec<&'a str>,
+        #[serde(rename = "box office")]
+        box_office: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        sequel: Option<Box<Movie<'a>>>,
+    }
+
+    #[derive(serde::Serialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rect(u8, u8),
+        Named { name: String },
+    }
+
+    fn enc<T: Serialize>(value: T) -> String {
+        to_cypher_param(&SerdeParam(value)).expect("should encode")
+    }
+
+    #[test]
+    fn test_serde_param_struct_is_a_map() {
+        let movie = Movie {
+            title: "It's",
+            year: 1999,
+            rating: None,
+            genres: vec!["sci-fi"],
+            box_office: 467_000_000,
+            sequel: None,
+        };
+        assert_eq!(
+            enc(&movie),
+            "{title: 'It\\'s', year: 1999, rating: null, genres: ['sci-fi'], \
+             `box office`: 467000000}"
+        );
+    }
+
+    #[test]
+    fn test_serde_param_enums_are_externally_tagged() {
+        assert_eq!(enc(Shape::Empty), "'Empty'");
+        assert_eq!(enc(Shape::Circle(1.5)), "{Circle: 1.5}");
+        assert_eq!(enc(Shape::Rect(2, 3)), "{Rect: [2, 3]}");
+        assert_eq!(
+            enc(Shape::Named {
+                name: "x".to_string()
+            }),
+            "{Named: {name: 'x'}}"
+        );
+    }
+
+    #[test]
+    fn test_serde_param_maps_and_sequences() {
+        assert_eq!(
+            enc(BTreeMap::from([(1, "a"), (2, "b")])),
+            "{`1`: 'a', `2`: 'b'}"
+        );
+        assert_eq!(enc((1, "two", [3.0])), "[1, 'two', [3.0]]");
+        assert_eq!(enc(()), "null");
+        assert_eq!(enc(Some(true)), "true");
+    }
+
+    #[test]
+    fn test_serde_param_reports_unencodable_values() {
+        for err in [
+            to_cypher_param(&SerdeParam(f64::NAN)),
+            to_cypher_param(&SerdeParam(u64::MAX)),
+            to_cypher_param(&SerdeParam("nul\0")),
+            to_cypher_param(&SerdeParam(BTreeMap::from([((1, 2), 3)]))),
+            to_cypher_param(&SerdeParam(BTreeMap::from([("a`b", 3)]))),
+        ] {
+            assert!(matches!(err, Err(FalkorDBError::ParamEncoding { .. })));
+        }
+    }
+}
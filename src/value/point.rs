@@ -8,7 +8,13 @@ use crate::{
     FalkorDBError, FalkorResult,
 };
 
+#[cfg(feature = "geo-types")]
+mod geo_interop;
+
 /// A point in the world.
+///
+/// With the `geo-types` feature, converts to and from `geo_types::Point<f64>` (and `Coord<f64>`),
+/// taking `x` as the longitude and `y` as the latitude.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Point {
     /// The latitude coordinate
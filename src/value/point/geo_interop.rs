@@ -0,0 +1,97 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Conversions between [`Point`] and `geo-types`. `geo-types` stores a position as `x`/`y`, so
+//! `x` is the longitude and `y` the latitude.
+
+use super::Point;
+use crate::{value::param::sealed, FalkorResult, FalkorValue, FromFalkorValue, IntoFalkorParam};
+
+impl From<geo_types::Point<f64>> for Point {
+    fn from(value: geo_types::Point<f64>) -> Self {
+        value.0.into()
+    }
+}
+
+impl From<geo_types::Coord<f64>> for Point {
+    fn from(value: geo_types::Coord<f64>) -> Self {
+        Point {
+            latitude: value.y,
+            longitude: value.x,
+        }
+    }
+}
+
+impl From<Point> for geo_types::Point<f64> {
+    fn from(value: Point) -> Self {
+        geo_types::Point::new(value.longitude, value.latitude)
+    }
+}
+
+impl From<Point> for geo_types::Coord<f64> {
+    fn from(value: Point) -> Self {
+        geo_types::coord! { x: value.longitude, y: value.latitude }
+    }
+}
+
+impl FromFalkorValue for geo_types::Point<f64> {
+    fn from_falkor_value(value: FalkorValue) -> FalkorResult<Self> {
+        Point::from_falkor_value(value).map(Into::into)
+    }
+}
+
+impl sealed::Sealed for geo_types::Point<f64> {}
+impl IntoFalkorParam for geo_types::Point<f64> {
+    /// Encodes like [`Point`]: wrap the parameter with `point($p)` in your query.
+    fn encode_param(
+        &self,
+        out: &mut String,
+    ) -> FalkorResult<()> {
+        Point::from(*self).encode_param(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geo_point_maps_x_to_longitude() {
+        let point = Point::from(geo_types::Point::new(34.79, 32.07));
+        assert_eq!(
+            point,
+            Point {
+                latitude: 32.07,
+                longitude: 34.79
+            }
+        );
+        assert_eq!(
+            geo_types::Point::from(point),
+            geo_types::Point::new(34.79, 32.07)
+        );
+    }
+
+    #[test]
+    fn test_geo_point_from_falkor_value() {
+        let value = FalkorValue::Point(Point {
+            latitude: 51.5,
+            longitude: -0.12,
+        });
+        assert_eq!(
+            geo_types::Point::<f64>::from_falkor_value(value).unwrap(),
+            geo_types::Point::new(-0.12, 51.5)
+        );
+        assert!(geo_types::Point::<f64>::from_falkor_value(FalkorValue::I64(1)).is_err());
+    }
+
+    #[test]
+    fn test_geo_point_encodes_like_point() {
+        let mut out = String::new();
+        geo_types::Point::new(-0.12, 51.5)
+            .encode_param(&mut out)
+            .unwrap();
+        assert_eq!(out, "{latitude: 51.5, longitude: -0.12}");
+    }
+}
@@ -0,0 +1,256 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! `serde::Serialize` implementations for [`FalkorValue`] and the graph types it carries. The
+//! output shape is documented on [`FalkorValue`]. Only available when the `serde` feature is
+//! enabled.
+
+use crate::{Date, DateTime, Duration, Edge, FalkorValue, Node, Path, Point, Seconds, Time};
+use serde::ser::{Error as _, SerializeStruct, Serializer};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use super::vec32::Vec32;
+
+/// Serializes a string-keyed map in key order, whatever the iteration order of the `HashMap`.
+struct Sorted<'a>(&'a HashMap<String, FalkorValue>);
+
+impl Serialize for Sorted<'_> {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().collect::<BTreeMap<_, _>>())
+    }
+}
+
+impl Serialize for FalkorValue {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match self {
+            FalkorValue::None => serializer.serialize_unit(),
+            FalkorValue::Bool(value) => serializer.serialize_bool(*value),
+            FalkorValue::I64(value) => serializer.serialize_i64(*value),
+            FalkorValue::F64(value) => serializer.serialize_f64(*value),
+            FalkorValue::String(value) => serializer.serialize_str(value),
+            FalkorValue::Array(values) => serializer.collect_seq(values),
+            FalkorValue::Map(map) => Sorted(map).serialize(serializer),
+            FalkorValue::Vec32(vec) => vec.serialize(serializer),
+            FalkorValue::Node(node) => node.serialize(serializer),
+            FalkorValue::Edge(edge) => edge.serialize(serializer),
+            FalkorValue::Path(path) => path.serialize(serializer),
+            FalkorValue::Point(point) => point.serialize(serializer),
+            FalkorValue::DateTime(datetime) => datetime.serialize(serializer),
+            FalkorValue::Date(date) => date.serialize(serializer),
+            FalkorValue::Time(time) => time.serialize(serializer),
+            FalkorValue::Duration(duration) => duration.serialize(serializer),
+            FalkorValue::Unparseable(reason) => Err(S::Error::custom(format!(
+                "cannot serialize an unparseable value: {reason}"
+            ))),
+        }
+    }
+}
+
+impl Serialize for Node {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut node = serializer.serialize_struct("Node", 3)?;
+        node.serialize_field("id", &self.entity_id)?;
+        node.serialize_field("labels", &self.labels)?;
+        node.serialize_field("properties", &Sorted(&self.properties))?;
+        node.end()
+    }
+}
+
+impl Serialize for Edge {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut edge = serializer.serialize_struct("Edge", 5)?;
+        edge.serialize_field("id", &self.entity_id)?;
+        edge.serialize_field("relationship_type", &self.relationship_type)?;
+        edge.serialize_field("src_node_id", &self.src_node_id)?;
+        edge.serialize_field("dst_node_id", &self.dst_node_id)?;
+        edge.serialize_field("properties", &Sorted(&self.properties))?;
+        edge.end()
+    }
+}
+
+impl Serialize for Path {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut path = serializer.serialize_struct("Path", 2)?;
+        path.serialize_field("nodes", &self.nodes)?;
+        path.serialize_field("relationships", &self.relationships)?;
+        path.end()
+    }
+}
+
+impl Serialize for Point {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut point = serializer.serialize_struct("Point", 2)?;
+        point.serialize_field("latitude", &self.latitude)?;
+        point.serialize_field("longitude", &self.longitude)?;
+        point.end()
+    }
+}
+
+impl Serialize for Vec32 {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.values)
+    }
+}
+
+impl Serialize for Seconds {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.get())
+    }
+}
+
+macro_rules! serialize_temporal {
+    ($($name:ident),*) => {$(
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(
+                &self,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                self.seconds().serialize(serializer)
+            }
+        }
+    )*};
+}
+serialize_temporal!(DateTime, Date, Time, Duration);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn node() -> Node {
+        Node {
+            entity_id: 1,
+            labels: vec!["Person".to_string()],
+            properties: HashMap::from([
+                ("name".to_string(), FalkorValue::from("Ada")),
+                ("age".to_string(), FalkorValue::I64(36)),
+            ]),
+        }
+    }
+
+    fn edge() -> Edge {
+        Edge {
+            entity_id: 9,
+            relationship_type: "KNOWS".to_string(),
+            src_node_id: 1,
+            dst_node_id: 2,
+            properties: HashMap::from([("since".to_string(), FalkorValue::I64(1843))]),
+        }
+    }
+
+    #[test]
+    fn test_serialize_scalars() {
+        assert_eq!(
+            serde_json::to_value(FalkorValue::None).unwrap(),
+            json!(null)
+        );
+        assert_eq!(
+            serde_json::to_value(FalkorValue::Bool(true)).unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            serde_json::to_value(FalkorValue::I64(-3)).unwrap(),
+            json!(-3)
+        );
+        assert_eq!(
+            serde_json::to_value(FalkorValue::F64(2.5)).unwrap(),
+            json!(2.5)
+        );
+        assert_eq!(
+            serde_json::to_value(FalkorValue::from("hi")).unwrap(),
+            json!("hi")
+        );
+        assert_eq!(
+            serde_json::to_value(FalkorValue::DateTime(DateTime::new(60))).unwrap(),
+            json!(60)
+        );
+        assert_eq!(
+            serde_json::to_value(FalkorValue::Vec32(Vec32 {
+                values: vec![0.5, 1.0]
+            }))
+            .unwrap(),
+            json!([0.5, 1.0])
+        );
+    }
+
+    #[test]
+    fn test_serialize_entities() {
+        assert_eq!(
+            serde_json::to_value(FalkorValue::Node(node())).unwrap(),
+            json!({"id": 1, "labels": ["Person"], "properties": {"age": 36, "name": "Ada"}})
+        );
+        assert_eq!(
+            serde_json::to_value(FalkorValue::Edge(edge())).unwrap(),
+            json!({
+                "id": 9,
+                "relationship_type": "KNOWS",
+                "src_node_id": 1,
+                "dst_node_id": 2,
+                "properties": {"since": 1843}
+            })
+        );
+        let path = Path {
+            nodes: vec![node()],
+            relationships: vec![edge()],
+        };
+        let value = serde_json::to_value(FalkorValue::Path(path)).unwrap();
+        assert_eq!(value["nodes"][0]["id"], json!(1));
+        assert_eq!(
+            value["relationships"][0]["relationship_type"],
+            json!("KNOWS")
+        );
+        assert_eq!(
+            serde_json::to_value(FalkorValue::Point(Point {
+                latitude: 32.07,
+                longitude: 34.79
+            }))
+            .unwrap(),
+            json!({"latitude": 32.07, "longitude": 34.79})
+        );
+    }
+
+    #[test]
+    fn test_serialize_maps_in_key_order() {
+        let map = FalkorValue::Map(HashMap::from([
+            ("b".to_string(), FalkorValue::I64(2)),
+            ("c".to_string(), FalkorValue::I64(3)),
+            ("a".to_string(), FalkorValue::I64(1)),
+        ]));
+        assert_eq!(
+            serde_json::to_string(&map).unwrap(),
+            r#"{"a":1,"b":2,"c":3}"#
+        );
+    }
+
+    #[test]
+    fn test_serialize_unparseable_is_error() {
+        assert!(serde_json::to_string(&FalkorValue::Unparseable("?".to_string())).is_err());
+    }
+}
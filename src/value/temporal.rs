@@ -17,6 +17,29 @@
 //!
 //! As observed from the server: `date` is seconds since the Unix epoch at UTC midnight (negative
 //! before 1970), `time`/`localtime` is a number of seconds, and `duration` is a span in seconds.
+//!
+//! The optional `chrono`, `time` and `jiff` features convert these values to and from the
+//! matching types of those crates:
+//!
+//! | FalkorDB     | `chrono`                | `time`                 | `jiff`                 |
+//! |--------------|-------------------------|------------------------|------------------------|
+//! | [`DateTime`] | `DateTime<Utc>`         | `OffsetDateTime`       | `Timestamp`            |
+//! | [`Date`]     | `NaiveDate`             | `Date`                 | `civil::Date`          |
+//! | [`Time`]     | `NaiveTime`             | `Time`                 | `civil::Time`          |
+//! | [`Duration`] | `TimeDelta`             | `Duration`             | `SignedDuration`       |
+//!
+//! Converting *into* a FalkorDB type is infallible but keeps whole seconds only: instants are
+//! rounded down (towards the past, also before 1970), times of day drop their fraction, and
+//! durations are truncated towards zero. Converting *out of* one fails with
+//! [`FalkorDBError::TemporalOutOfRange`](crate::FalkorDBError::TemporalOutOfRange) when the target
+//! type cannot hold the value, such as a `time` outside `0..86400` seconds or an instant beyond the
+//! library's supported years. A `date` that is not at UTC midnight maps to the day it falls on.
+//!
+//! The library types also implement [`FromFalkorValue`](crate::FromFalkorValue) and
+//! [`IntoFalkorParam`](crate::IntoFalkorParam). FalkorDB cannot bind a temporal value directly, so
+//! an instant, date or time of day is encoded as an ISO-8601 string (`'2024-05-01T12:00:00'` in
+//! UTC, `'2024-05-01'`, `'12:00:00'`) and a duration as a `{seconds: 90}` map; wrap the parameter
+//! with `localdatetime($p)`, `date($p)`, `localtime($p)` or `duration($p)` in the query.
 
 use crate::{parser::redis_value_as_int, FalkorResult};
 use std::fmt;
@@ -317,6 +340,318 @@ impl Neg for Duration {
     }
 }
 
+// ---- date/time library interop ---------------------------------------------------------------
+
+/// `FromFalkorValue` and `IntoFalkorParam` for a date/time library type, through the FalkorDB
+/// type it converts to and from. `$from_falkor` turns the FalkorDB value into the library type.
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+macro_rules! impl_temporal_interop {
+    ($($falkor:ident => $ext:ty = $from_falkor:expr),* $(,)?) => {$(
+        impl crate::FromFalkorValue for $ext {
+            fn from_falkor_value(value: crate::FalkorValue) -> crate::FalkorResult<Self> {
+                let value = <super::$falkor as crate::FromFalkorValue>::from_falkor_value(value)?;
+                ($from_falkor)(value)
+            }
+        }
+
+        impl crate::value::param::sealed::Sealed for $ext {}
+        impl crate::IntoFalkorParam for $ext {
+            fn encode_param(
+                &self,
+                out: &mut String,
+            ) -> crate::FalkorResult<()> {
+                super::$falkor::from(*self).encode_iso_param(out)
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_interop;
+#[cfg(feature = "jiff")]
+mod jiff_interop;
+#[cfg(feature = "time")]
+mod time_interop;
+
+/// Calendar arithmetic shared by the date/time library conversions, and the ISO 8601 literals that
+/// encode the FalkorDB temporal types for Cypher (query parameters and dumps).
+mod interop {
+    use super::{Date, DateTime, Duration, Time};
+    use crate::value::param::encode_str;
+    use crate::{FalkorDBError, FalkorResult};
+    use std::fmt::Write as _;
+
+    pub(super) const SECS_PER_DAY: i64 = 86_400;
+
+    pub(super) fn out_of_range(
+        seconds: i64,
+        target: &'static str,
+    ) -> FalkorDBError {
+        FalkorDBError::TemporalOutOfRange { seconds, target }
+    }
+
+    /// Days since 1970-01-01 of a proleptic Gregorian date (Howard Hinnant's `days_from_civil`).
+    #[cfg(any(
+        test,
+        feature = "chrono",
+        feature = "time",
+        feature = "jiff",
+        feature = "jsonl",
+        feature = "graphml"
+    ))]
+    pub(super) fn days_from_civil(
+        year: i64,
+        month: u32,
+        day: u32,
+    ) -> i64 {
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = i64::from(month);
+        let day_of_year =
+            (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The `(year, month, day)` of a number of days since 1970-01-01; the inverse of
+    /// [`days_from_civil`].
+    pub(super) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        (year, month, day)
+    }
+
+    /// `(hour, minute, second)` of a `time` scalar, or an error outside one day.
+    pub(super) fn hms(
+        time: Time,
+        target: &'static str,
+    ) -> FalkorResult<(u32, u32, u32)> {
+        let secs = time.secs;
+        if !(0..SECS_PER_DAY).contains(&secs) {
+            return Err(out_of_range(secs, target));
+        }
+        let secs = secs as u32;
+        Ok((secs / 3600, secs / 60 % 60, secs % 60))
+    }
+
+    /// A four-digit year, or a signed expanded year outside `0..=9999` (ISO 8601).
+    fn iso_year(year: i64) -> String {
+        if (0..=9999).contains(&year) {
+            format!("{year:04}")
+        } else {
+            format!("{year:+05}")
+        }
+    }
+
+    fn iso_date(secs: i64) -> String {
+        let (year, month, day) = civil_from_days(secs.div_euclid(SECS_PER_DAY));
+        format!("{}-{month:02}-{day:02}", iso_year(year))
+    }
+
+    fn iso_time(secs_of_day: i64) -> String {
+        format!(
+            "{:02}:{:02}:{:02}",
+            secs_of_day / 3600,
+            secs_of_day / 60 % 60,
+            secs_of_day % 60
+        )
+    }
+
+    /// Seconds since 1970-01-01 of an ISO 8601 `YYYY-MM-DD` date, as written by [`iso_date`].
+    #[cfg(any(test, feature = "jsonl", feature = "graphml"))]
+    fn parse_iso_date(date: &str) -> Option<i64> {
+        let mut parts = date.rsplitn(3, '-');
+        let (day, month, year) = (parts.next()?, parts.next()?, parts.next()?);
+        if day.len() != 2 || month.len() != 2 || year.trim_start_matches(['+', '-']).len() < 4 {
+            return None;
+        }
+        let (year, month, day) = (year.parse().ok()?, month.parse().ok()?, day.parse().ok()?);
+        let days = days_from_civil(year, month, day);
+        // Out-of-range months and days do not survive the round trip.
+        (civil_from_days(days) == (year, month, day)).then_some(days * SECS_PER_DAY)
+    }
+
+    /// Seconds since midnight of an ISO 8601 `hh:mm:ss` time, as written by [`iso_time`].
+    #[cfg(any(test, feature = "jsonl", feature = "graphml"))]
+    fn parse_iso_time(time: &str) -> Option<i64> {
+        let mut parts = time.split(':');
+        let mut next = |limit: i64| {
+            let part = parts.next().filter(|part| part.len() == 2)?;
+            part.parse::<i64>()
+                .ok()
+                .filter(|value| (0..limit).contains(value))
+        };
+        let secs = next(24)? * 3600 + next(60)? * 60 + next(60)?;
+        parts.next().is_none().then_some(secs)
+    }
+
+    impl DateTime {
+        /// The ISO 8601 `YYYY-MM-DDThh:mm:ss` form of this date-time.
+        pub(crate) fn to_iso(self) -> String {
+            let time = iso_time(self.secs.rem_euclid(SECS_PER_DAY));
+            format!("{}T{time}", iso_date(self.secs))
+        }
+
+        /// Parses the form written by [`DateTime::to_iso`].
+        #[cfg(any(test, feature = "jsonl", feature = "graphml"))]
+        pub(crate) fn parse_iso(value: &str) -> Option<Self> {
+            let (date, time) = value.split_once('T')?;
+            Some(Self::new(parse_iso_date(date)? + parse_iso_time(time)?))
+        }
+
+        pub(crate) fn encode_iso_param(
+            self,
+            out: &mut String,
+        ) -> FalkorResult<()> {
+            encode_str(&self.to_iso(), out)
+        }
+    }
+
+    impl Date {
+        /// The ISO 8601 `YYYY-MM-DD` form of this date.
+        pub(crate) fn to_iso(self) -> String {
+            iso_date(self.secs)
+        }
+
+        /// Parses the form written by [`Date::to_iso`].
+        #[cfg(any(test, feature = "jsonl", feature = "graphml"))]
+        pub(crate) fn parse_iso(value: &str) -> Option<Self> {
+            parse_iso_date(value).map(Self::new)
+        }
+
+        pub(crate) fn encode_iso_param(
+            self,
+            out: &mut String,
+        ) -> FalkorResult<()> {
+            encode_str(&self.to_iso(), out)
+        }
+    }
+
+    impl Time {
+        /// The ISO 8601 `hh:mm:ss` form of this time, or an error outside one day.
+        pub(crate) fn to_iso(self) -> FalkorResult<String> {
+            let (hour, minute, second) = hms(self, "a time of day")?;
+            Ok(format!("{hour:02}:{minute:02}:{second:02}"))
+        }
+
+        /// Parses the form written by [`Time::to_iso`].
+        #[cfg(any(test, feature = "jsonl", feature = "graphml"))]
+        pub(crate) fn parse_iso(value: &str) -> Option<Self> {
+            parse_iso_time(value).map(Self::new)
+        }
+
+        pub(crate) fn encode_iso_param(
+            self,
+            out: &mut String,
+        ) -> FalkorResult<()> {
+            encode_str(&self.to_iso()?, out)
+        }
+    }
+
+    impl Duration {
+        pub(crate) fn encode_iso_param(
+            self,
+            out: &mut String,
+        ) -> FalkorResult<()> {
+            let _ = write!(out, "{{seconds: {}}}", self.secs);
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_civil_days_round_trip() {
+            assert_eq!(days_from_civil(1970, 1, 1), 0);
+            assert_eq!(days_from_civil(1969, 12, 31), -1);
+            assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+            assert_eq!(civil_from_days(-719_468), (0, 3, 1));
+            for days in (-1_000_000..1_000_000).step_by(997) {
+                let (year, month, day) = civil_from_days(days);
+                assert_eq!(days_from_civil(year, month, day), days);
+            }
+        }
+
+        #[test]
+        fn test_iso_strings_round_trip() {
+            for secs in [
+                -62_198_755_200,
+                -697_161_600,
+                -1,
+                0,
+                1_700_000_000,
+                400_000_000_000,
+            ] {
+                let datetime = DateTime::new(secs);
+                assert_eq!(DateTime::parse_iso(&datetime.to_iso()), Some(datetime));
+                let date = Date::new(secs.div_euclid(SECS_PER_DAY) * SECS_PER_DAY);
+                assert_eq!(Date::parse_iso(&date.to_iso()), Some(date));
+            }
+            assert_eq!(Time::parse_iso("12:34:56"), Some(Time::new(45_296)));
+            assert_eq!(
+                Date::parse_iso("+12345-01-02").map(Date::to_iso).as_deref(),
+                Some("+12345-01-02")
+            );
+            for invalid in ["2023-02-29", "2023-13-01", "23-01-01", "2023-1-01", "x"] {
+                assert_eq!(Date::parse_iso(invalid), None, "{invalid}");
+            }
+            for invalid in ["24:00:00", "12:60:00", "1:00:00", "12:00", "12:00:00:00"] {
+                assert_eq!(Time::parse_iso(invalid), None, "{invalid}");
+            }
+        }
+
+        #[test]
+        fn test_iso_param_literals() {
+            let enc = |f: &dyn Fn(&mut String) -> FalkorResult<()>| {
+                let mut out = String::new();
+                f(&mut out).map(|()| out)
+            };
+            assert_eq!(
+                enc(&|out| DateTime::new(-1).encode_iso_param(out)).unwrap(),
+                "'1969-12-31T23:59:59'"
+            );
+            assert_eq!(
+                enc(&|out| Date::new(-697_161_600).encode_iso_param(out)).unwrap(),
+                "'1947-11-29'"
+            );
+            assert_eq!(
+                enc(&|out| Date::new(-62_198_755_200).encode_iso_param(out)).unwrap(),
+                "'-0001-01-01'"
+            );
+            assert_eq!(
+                enc(&|out| Time::new(45_296).encode_iso_param(out)).unwrap(),
+                "'12:34:56'"
+            );
+            assert_eq!(
+                enc(&|out| Duration::new(-90).encode_iso_param(out)).unwrap(),
+                "{seconds: -90}"
+            );
+            assert!(matches!(
+                enc(&|out| Time::new(86_400).encode_iso_param(out)),
+                Err(FalkorDBError::TemporalOutOfRange {
+                    seconds: 86_400,
+                    ..
+                })
+            ));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
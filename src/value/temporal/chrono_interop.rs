@@ -0,0 +1,166 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Conversions between the FalkorDB temporal types and `chrono`.
+
+use super::interop::{civil_from_days, days_from_civil, hms, out_of_range, SECS_PER_DAY};
+use super::{Date, DateTime, Duration, Time};
+use crate::{FalkorDBError, FalkorResult};
+use chrono::{Datelike as _, TimeZone, Timelike as _, Utc};
+
+/// Rounds down to the whole second, also before 1970.
+impl<Tz: TimeZone> From<chrono::DateTime<Tz>> for DateTime {
+    fn from(value: chrono::DateTime<Tz>) -> Self {
+        DateTime::new(value.timestamp())
+    }
+}
+
+impl TryFrom<DateTime> for chrono::DateTime<Utc> {
+    type Error = FalkorDBError;
+
+    fn try_from(value: DateTime) -> FalkorResult<Self> {
+        chrono::DateTime::from_timestamp(value.secs, 0)
+            .ok_or_else(|| out_of_range(value.secs, "chrono::DateTime<Utc>"))
+    }
+}
+
+impl From<chrono::NaiveDate> for Date {
+    fn from(value: chrono::NaiveDate) -> Self {
+        let days = days_from_civil(i64::from(value.year()), value.month(), value.day());
+        Date::new(days * SECS_PER_DAY)
+    }
+}
+
+impl TryFrom<Date> for chrono::NaiveDate {
+    type Error = FalkorDBError;
+
+    fn try_from(value: Date) -> FalkorResult<Self> {
+        let (year, month, day) = civil_from_days(value.secs.div_euclid(SECS_PER_DAY));
+        i32::try_from(year)
+            .ok()
+            .and_then(|year| chrono::NaiveDate::from_ymd_opt(year, month, day))
+            .ok_or_else(|| out_of_range(value.secs, "chrono::NaiveDate"))
+    }
+}
+
+/// Drops any fraction of a second.
+impl From<chrono::NaiveTime> for Time {
+    fn from(value: chrono::NaiveTime) -> Self {
+        Time::new(i64::from(value.num_seconds_from_midnight()))
+    }
+}
+
+impl TryFrom<Time> for chrono::NaiveTime {
+    type Error = FalkorDBError;
+
+    fn try_from(value: Time) -> FalkorResult<Self> {
+        let (hour, minute, second) = hms(value, "chrono::NaiveTime")?;
+        chrono::NaiveTime::from_hms_opt(hour, minute, second)
+            .ok_or_else(|| out_of_range(value.secs, "chrono::NaiveTime"))
+    }
+}
+
+/// Truncates towards zero to whole seconds.
+impl From<chrono::TimeDelta> for Duration {
+    fn from(value: chrono::TimeDelta) -> Self {
+        Duration::new(value.num_seconds())
+    }
+}
+
+impl TryFrom<Duration> for chrono::TimeDelta {
+    type Error = FalkorDBError;
+
+    fn try_from(value: Duration) -> FalkorResult<Self> {
+        chrono::TimeDelta::try_seconds(value.secs)
+            .ok_or_else(|| out_of_range(value.secs, "chrono::TimeDelta"))
+    }
+}
+
+impl_temporal_interop!(
+    DateTime => chrono::DateTime<Utc> = chrono::DateTime::<Utc>::try_from,
+    Date => chrono::NaiveDate = chrono::NaiveDate::try_from,
+    Time => chrono::NaiveTime = chrono::NaiveTime::try_from,
+    Duration => chrono::TimeDelta = chrono::TimeDelta::try_from,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{to_cypher_param, FalkorValue, FromFalkorValue};
+    use chrono::{NaiveDate, NaiveTime, TimeDelta};
+
+    #[test]
+    fn test_datetime_round_trip_and_rounding() {
+        let instant =
+            Utc.with_ymd_and_hms(1969, 12, 31, 23, 59, 59).unwrap() + TimeDelta::milliseconds(500);
+        assert_eq!(DateTime::from(instant), DateTime::new(-1));
+        assert_eq!(
+            chrono::DateTime::<Utc>::try_from(DateTime::new(1_700_000_000)).unwrap(),
+            Utc.with_ymd_and_hms(2023, 11, 14, 22, 13, 20).unwrap()
+        );
+        assert!(matches!(
+            chrono::DateTime::<Utc>::try_from(DateTime::new(i64::MAX)),
+            Err(FalkorDBError::TemporalOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_date_time_and_duration_conversions() {
+        let date = NaiveDate::from_ymd_opt(1947, 11, 29).unwrap();
+        assert_eq!(Date::from(date), Date::new(-697_161_600));
+        assert_eq!(NaiveDate::try_from(Date::new(-697_161_600)).unwrap(), date);
+        // A date scalar that is not at midnight maps to the day it falls on.
+        assert_eq!(NaiveDate::try_from(Date::new(-697_161_599)).unwrap(), date);
+
+        let time = NaiveTime::from_hms_milli_opt(12, 34, 56, 789).unwrap();
+        assert_eq!(Time::from(time), Time::new(45_296));
+        assert_eq!(
+            NaiveTime::try_from(Time::new(45_296)).unwrap(),
+            NaiveTime::from_hms_opt(12, 34, 56).unwrap()
+        );
+        assert!(NaiveTime::try_from(Time::new(-1)).is_err());
+
+        assert_eq!(
+            Duration::from(TimeDelta::milliseconds(-1_500)),
+            Duration::new(-1)
+        );
+        assert_eq!(
+            TimeDelta::try_from(Duration::new(90)).unwrap(),
+            TimeDelta::seconds(90)
+        );
+        assert!(TimeDelta::try_from(Duration::new(i64::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_from_falkor_value_and_params() {
+        assert_eq!(
+            NaiveDate::from_falkor_value(FalkorValue::Date(Date::new(0))).unwrap(),
+            NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()
+        );
+        assert!(matches!(
+            NaiveDate::from_falkor_value(FalkorValue::I64(0)),
+            Err(FalkorDBError::TypeError {
+                expected: "Date",
+                got: "I64"
+            })
+        ));
+        assert_eq!(
+            to_cypher_param(&Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap()).unwrap(),
+            "'2024-05-01T12:00:00'"
+        );
+        assert_eq!(
+            to_cypher_param(&NaiveDate::from_ymd_opt(2024, 5, 1).unwrap()).unwrap(),
+            "'2024-05-01'"
+        );
+        assert_eq!(
+            to_cypher_param(&NaiveTime::from_hms_opt(8, 5, 0).unwrap()).unwrap(),
+            "'08:05:00'"
+        );
+        assert_eq!(
+            to_cypher_param(&TimeDelta::minutes(2)).unwrap(),
+            "{seconds: 120}"
+        );
+    }
+}
@@ -0,0 +1,161 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Conversions between the FalkorDB temporal types and `jiff`.
+
+use super::interop::{civil_from_days, days_from_civil, hms, out_of_range, SECS_PER_DAY};
+use super::{Date, DateTime, Duration, Time};
+use crate::{FalkorDBError, FalkorResult};
+use jiff::civil;
+
+/// Rounds down to the whole second, also before 1970.
+impl From<jiff::Timestamp> for DateTime {
+    fn from(value: jiff::Timestamp) -> Self {
+        // `as_second` truncates towards zero; a negative fraction belongs to the earlier second.
+        let secs = value.as_second();
+        DateTime::new(if value.subsec_nanosecond() < 0 {
+            secs - 1
+        } else {
+            secs
+        })
+    }
+}
+
+impl TryFrom<DateTime> for jiff::Timestamp {
+    type Error = FalkorDBError;
+
+    fn try_from(value: DateTime) -> FalkorResult<Self> {
+        jiff::Timestamp::from_second(value.secs)
+            .map_err(|_| out_of_range(value.secs, "jiff::Timestamp"))
+    }
+}
+
+impl From<civil::Date> for Date {
+    fn from(value: civil::Date) -> Self {
+        let days = days_from_civil(
+            i64::from(value.year()),
+            value.month() as u32,
+            value.day() as u32,
+        );
+        Date::new(days * SECS_PER_DAY)
+    }
+}
+
+impl TryFrom<Date> for civil::Date {
+    type Error = FalkorDBError;
+
+    fn try_from(value: Date) -> FalkorResult<Self> {
+        let (year, month, day) = civil_from_days(value.secs.div_euclid(SECS_PER_DAY));
+        let year =
+            i16::try_from(year).map_err(|_| out_of_range(value.secs, "jiff::civil::Date"))?;
+        // `civil_from_days` always yields a valid month (1-12) and day (1-31).
+        civil::Date::new(year, month as i8, day as i8)
+            .map_err(|_| out_of_range(value.secs, "jiff::civil::Date"))
+    }
+}
+
+/// Drops any fraction of a second.
+impl From<civil::Time> for Time {
+    fn from(value: civil::Time) -> Self {
+        Time::new(
+            i64::from(value.hour()) * 3600
+                + i64::from(value.minute()) * 60
+                + i64::from(value.second()),
+        )
+    }
+}
+
+impl TryFrom<Time> for civil::Time {
+    type Error = FalkorDBError;
+
+    fn try_from(value: Time) -> FalkorResult<Self> {
+        let (hour, minute, second) = hms(value, "jiff::civil::Time")?;
+        civil::Time::new(hour as i8, minute as i8, second as i8, 0)
+            .map_err(|_| out_of_range(value.secs, "jiff::civil::Time"))
+    }
+}
+
+/// Truncates towards zero to whole seconds.
+impl From<jiff::SignedDuration> for Duration {
+    fn from(value: jiff::SignedDuration) -> Self {
+        Duration::new(value.as_secs())
+    }
+}
+
+impl From<Duration> for jiff::SignedDuration {
+    fn from(value: Duration) -> Self {
+        jiff::SignedDuration::from_secs(value.secs)
+    }
+}
+
+impl_temporal_interop!(
+    DateTime => jiff::Timestamp = jiff::Timestamp::try_from,
+    Date => civil::Date = civil::Date::try_from,
+    Time => civil::Time = civil::Time::try_from,
+    Duration => jiff::SignedDuration = |value: Duration| Ok(jiff::SignedDuration::from(value)),
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{to_cypher_param, FalkorValue, FromFalkorValue};
+
+    #[test]
+    fn test_datetime_round_trip_and_rounding() {
+        let instant = jiff::Timestamp::from_millisecond(-500).unwrap();
+        assert_eq!(DateTime::from(instant), DateTime::new(-1));
+        assert_eq!(
+            jiff::Timestamp::try_from(DateTime::new(1_700_000_000)).unwrap(),
+            jiff::Timestamp::from_second(1_700_000_000).unwrap()
+        );
+        assert!(matches!(
+            jiff::Timestamp::try_from(DateTime::new(i64::MAX)),
+            Err(FalkorDBError::TemporalOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_date_time_and_duration_conversions() {
+        let day = civil::date(1947, 11, 29);
+        assert_eq!(Date::from(day), Date::new(-697_161_600));
+        assert_eq!(civil::Date::try_from(Date::new(-697_161_600)).unwrap(), day);
+        assert!(civil::Date::try_from(Date::new(i64::MIN)).is_err());
+
+        assert_eq!(
+            Time::from(civil::time(12, 34, 56, 789_000_000)),
+            Time::new(45_296)
+        );
+        assert_eq!(
+            civil::Time::try_from(Time::new(45_296)).unwrap(),
+            civil::time(12, 34, 56, 0)
+        );
+        assert!(civil::Time::try_from(Time::new(-1)).is_err());
+
+        assert_eq!(
+            Duration::from(jiff::SignedDuration::from_millis(-1_500)),
+            Duration::new(-1)
+        );
+        assert_eq!(
+            jiff::SignedDuration::from(Duration::new(90)),
+            jiff::SignedDuration::from_secs(90)
+        );
+    }
+
+    #[test]
+    fn test_from_falkor_value_and_params() {
+        assert_eq!(
+            civil::Time::from_falkor_value(FalkorValue::Time(Time::new(60))).unwrap(),
+            civil::time(0, 1, 0, 0)
+        );
+        assert_eq!(
+            to_cypher_param(&jiff::Timestamp::UNIX_EPOCH).unwrap(),
+            "'1970-01-01T00:00:00'"
+        );
+        assert_eq!(
+            to_cypher_param(&jiff::SignedDuration::from_secs(-5)).unwrap(),
+            "{seconds: -5}"
+        );
+    }
+}
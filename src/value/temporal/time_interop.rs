@@ -0,0 +1,159 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! Conversions between the FalkorDB temporal types and `time`.
+
+use super::interop::{civil_from_days, days_from_civil, hms, out_of_range, SECS_PER_DAY};
+use super::{Date, DateTime, Duration, Time};
+use crate::{FalkorDBError, FalkorResult};
+
+/// Rounds down to the whole second, also before 1970.
+impl From<time::OffsetDateTime> for DateTime {
+    fn from(value: time::OffsetDateTime) -> Self {
+        DateTime::new(value.unix_timestamp())
+    }
+}
+
+impl TryFrom<DateTime> for time::OffsetDateTime {
+    type Error = FalkorDBError;
+
+    fn try_from(value: DateTime) -> FalkorResult<Self> {
+        time::OffsetDateTime::from_unix_timestamp(value.secs)
+            .map_err(|_| out_of_range(value.secs, "time::OffsetDateTime"))
+    }
+}
+
+impl From<time::Date> for Date {
+    fn from(value: time::Date) -> Self {
+        let days = days_from_civil(
+            i64::from(value.year()),
+            u32::from(u8::from(value.month())),
+            u32::from(value.day()),
+        );
+        Date::new(days * SECS_PER_DAY)
+    }
+}
+
+impl TryFrom<Date> for time::Date {
+    type Error = FalkorDBError;
+
+    fn try_from(value: Date) -> FalkorResult<Self> {
+        let (year, month, day) = civil_from_days(value.secs.div_euclid(SECS_PER_DAY));
+        let year = i32::try_from(year).map_err(|_| out_of_range(value.secs, "time::Date"))?;
+        // `civil_from_days` always yields a valid month (1-12) and day (1-31).
+        let month = time::Month::try_from(month as u8)
+            .map_err(|_| out_of_range(value.secs, "time::Date"))?;
+        time::Date::from_calendar_date(year, month, day as u8)
+            .map_err(|_| out_of_range(value.secs, "time::Date"))
+    }
+}
+
+/// Drops any fraction of a second.
+impl From<time::Time> for Time {
+    fn from(value: time::Time) -> Self {
+        let (hour, minute, second) = value.as_hms();
+        Time::new(i64::from(hour) * 3600 + i64::from(minute) * 60 + i64::from(second))
+    }
+}
+
+impl TryFrom<Time> for time::Time {
+    type Error = FalkorDBError;
+
+    fn try_from(value: Time) -> FalkorResult<Self> {
+        let (hour, minute, second) = hms(value, "time::Time")?;
+        time::Time::from_hms(hour as u8, minute as u8, second as u8)
+            .map_err(|_| out_of_range(value.secs, "time::Time"))
+    }
+}
+
+/// Truncates towards zero to whole seconds.
+impl From<time::Duration> for Duration {
+    fn from(value: time::Duration) -> Self {
+        Duration::new(value.whole_seconds())
+    }
+}
+
+impl From<Duration> for time::Duration {
+    fn from(value: Duration) -> Self {
+        time::Duration::seconds(value.secs)
+    }
+}
+
+impl_temporal_interop!(
+    DateTime => time::OffsetDateTime = time::OffsetDateTime::try_from,
+    Date => time::Date = time::Date::try_from,
+    Time => time::Time = time::Time::try_from,
+    Duration => time::Duration = |value: Duration| Ok(time::Duration::from(value)),
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{to_cypher_param, FalkorValue, FromFalkorValue};
+    use time::Month;
+
+    fn date(
+        year: i32,
+        month: Month,
+        day: u8,
+    ) -> time::Date {
+        time::Date::from_calendar_date(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_datetime_round_trip_and_rounding() {
+        let instant = time::OffsetDateTime::from_unix_timestamp_nanos(-500_000_000).unwrap();
+        assert_eq!(DateTime::from(instant), DateTime::new(-1));
+        assert_eq!(
+            time::OffsetDateTime::try_from(DateTime::new(1_700_000_000)).unwrap(),
+            time::OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap()
+        );
+        assert!(matches!(
+            time::OffsetDateTime::try_from(DateTime::new(i64::MAX)),
+            Err(FalkorDBError::TemporalOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_date_time_and_duration_conversions() {
+        let day = date(1947, Month::November, 29);
+        assert_eq!(Date::from(day), Date::new(-697_161_600));
+        assert_eq!(time::Date::try_from(Date::new(-697_161_600)).unwrap(), day);
+        assert!(time::Date::try_from(Date::new(i64::MIN)).is_err());
+
+        let time = time::Time::from_hms_milli(12, 34, 56, 789).unwrap();
+        assert_eq!(Time::from(time), Time::new(45_296));
+        assert_eq!(
+            time::Time::try_from(Time::new(45_296)).unwrap(),
+            time::Time::from_hms(12, 34, 56).unwrap()
+        );
+        assert!(time::Time::try_from(Time::new(86_400)).is_err());
+
+        assert_eq!(
+            Duration::from(time::Duration::milliseconds(-1_500)),
+            Duration::new(-1)
+        );
+        assert_eq!(
+            time::Duration::from(Duration::new(-90)),
+            time::Duration::seconds(-90)
+        );
+    }
+
+    #[test]
+    fn test_from_falkor_value_and_params() {
+        assert_eq!(
+            time::Duration::from_falkor_value(FalkorValue::Duration(Duration::new(3))).unwrap(),
+            time::Duration::seconds(3)
+        );
+        assert_eq!(
+            to_cypher_param(&date(2024, Month::May, 1)).unwrap(),
+            "'2024-05-01'"
+        );
+        assert_eq!(
+            to_cypher_param(&time::OffsetDateTime::UNIX_EPOCH).unwrap(),
+            "'1970-01-01T00:00:00'"
+        );
+    }
+}
@@ -9,6 +9,7 @@ use crate::{
     FalkorResult,
 };
 
+/// A vector of 32-bit floats, as stored by `vecf32` and searched by vector indices.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Vec32 {
     /// The values of the vector
@@ -41,6 +42,13 @@ impl Vec32 {
         Ok(vec32)
     }
 }
+
+impl AsRef<[f32]> for Vec32 {
+    fn as_ref(&self) -> &[f32] {
+        &self.values
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
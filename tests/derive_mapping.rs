@@ -0,0 +1,138 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! `#[derive(FromRow)]` / `#[derive(FromNode)]` over hand-built rows and nodes. These tests need
+//! no server; they only run with the `derive` feature.
+
+#![cfg(feature = "derive")]
+
+use falkordb::{FalkorDBError, FalkorValue, FromNode, FromRow, Node, Row};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, FromNode)]
+struct Actor {
+    name: String,
+    #[falkordb(rename = "born")]
+    birth_year: Option<i64>,
+}
+
+#[derive(Debug, PartialEq, FromRow)]
+struct Casting {
+    actor: Actor,
+    #[falkordb(rename = "movie_title")]
+    title: String,
+    rating: Option<f64>,
+    r#type: String,
+}
+
+#[derive(Debug, PartialEq, FromRow)]
+struct Pair(String, i64);
+
+fn node(properties: &[(&str, FalkorValue)]) -> Node {
+    Node {
+        entity_id: 7,
+        labels: vec!["Actor".to_string()],
+        properties: properties
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect::<HashMap<_, _>>(),
+    }
+}
+
+fn casting_row(actor: Node) -> Row {
+    Row::from_iter([
+        ("actor".to_string(), FalkorValue::Node(actor)),
+        ("movie_title".to_string(), FalkorValue::from("The Matrix")),
+        ("rating".to_string(), FalkorValue::None),
+        ("type".to_string(), FalkorValue::from("lead")),
+    ])
+}
+
+#[test]
+fn from_row_reads_renamed_optional_and_nested_node_columns() {
+    let row = casting_row(node(&[
+        ("name", FalkorValue::from("Keanu Reeves")),
+        ("born", FalkorValue::I64(1964)),
+    ]));
+    let casting = Casting::from_row(row).expect("row maps");
+    assert_eq!(
+        casting,
+        Casting {
+            actor: Actor {
+                name: "Keanu Reeves".to_string(),
+                birth_year: Some(1964),
+            },
+            title: "The Matrix".to_string(),
+            rating: None,
+            r#type: "lead".to_string(),
+        }
+    );
+}
+
+#[test]
+fn from_row_reports_the_missing_column() {
+    let row = Row::from_iter([("title".to_string(), FalkorValue::from("The Matrix"))]);
+    let err = Casting::from_row(row).unwrap_err();
+    assert!(matches!(err, FalkorDBError::MissingColumn { ref name } if name == "actor"));
+}
+
+#[test]
+fn from_row_reports_a_type_mismatch() {
+    let row = Row::from_iter([
+        ("actor".to_string(), FalkorValue::from("not a node")),
+        ("movie_title".to_string(), FalkorValue::from("The Matrix")),
+        ("rating".to_string(), FalkorValue::None),
+        ("type".to_string(), FalkorValue::from("lead")),
+    ]);
+    let err = Casting::from_row(row).unwrap_err();
+    assert!(matches!(
+        err,
+        FalkorDBError::TypeError {
+            expected: "Node",
+            got: "String"
+        }
+    ));
+}
+
+#[test]
+fn from_row_tuple_struct_reads_by_index() {
+    let row = Row::from_iter([
+        ("name".to_string(), FalkorValue::from("Trinity")),
+        ("age".to_string(), FalkorValue::I64(37)),
+    ]);
+    assert_eq!(
+        Pair::from_row(row).unwrap(),
+        Pair("Trinity".to_string(), 37)
+    );
+}
+
+#[test]
+fn from_node_absent_optional_property_is_none() {
+    let actor = Actor::from_node(node(&[("name", FalkorValue::from("Carrie-Anne Moss"))]))
+        .expect("node maps");
+    assert_eq!(actor.birth_year, None);
+}
+
+#[test]
+fn from_node_reports_the_missing_property() {
+    let err = Actor::from_node(node(&[("born", FalkorValue::I64(1967))])).unwrap_err();
+    assert!(matches!(err, FalkorDBError::MissingProperty { ref name } if name == "name"));
+}
+
+#[test]
+fn from_node_reports_a_property_type_mismatch() {
+    let err = Actor::from_node(node(&[
+        ("name", FalkorValue::from("Laurence Fishburne")),
+        ("born", FalkorValue::from("1961")),
+    ]))
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        FalkorDBError::TypeError {
+            expected: "i64",
+            got: "String"
+        }
+    ));
+}
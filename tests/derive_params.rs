@@ -0,0 +1,99 @@
+/*
+ * Copyright FalkorDB Ltd. 2023 - present
+ * Licensed under the MIT License.
+ */
+
+//! `#[derive(IntoFalkorParam)]` / `#[derive(IntoFalkorParams)]` encoding. These tests need no
+//! server; they only run with the `derive` feature.
+
+#![cfg(feature = "derive")]
+
+use falkordb::{to_cypher_param, FalkorDBError, IntoFalkorParam, IntoFalkorParams};
+
+#[derive(IntoFalkorParam)]
+struct Address {
+    city: String,
+    #[falkordb(rename = "zip code")]
+    zip: Option<String>,
+}
+
+#[derive(IntoFalkorParam, IntoFalkorParams)]
+struct NewUser {
+    name: String,
+    age: i64,
+    tags: Vec<String>,
+    address: Address,
+    #[falkordb(rename = "kind")]
+    r#type: &'static str,
+    #[falkordb(skip)]
+    #[allow(dead_code)]
+    password: String,
+}
+
+#[derive(IntoFalkorParams)]
+struct Skipped {
+    #[falkordb(skip)]
+    #[allow(dead_code)]
+    ignored: i64,
+}
+
+fn new_user() -> NewUser {
+    NewUser {
+        name: "Ada".to_string(),
+        age: 36,
+        tags: vec!["admin".to_string()],
+        address: Address {
+            city: "London".to_string(),
+            zip: None,
+        },
+        r#type: "person",
+        password: "hunter2".to_string(),
+    }
+}
+
+#[test]
+fn into_falkor_param_encodes_a_map_in_field_order() {
+    assert_eq!(
+        to_cypher_param(&new_user()).unwrap(),
+        "{name: 'Ada', age: 36, tags: ['admin'], address: {city: 'London', `zip code`: null}, \
+         kind: 'person'}"
+    );
+}
+
+#[test]
+fn into_falkor_param_nests_in_lists() {
+    let rows = vec![
+        Address {
+            city: "Paris".to_string(),
+            zip: Some("75001".to_string()),
+        },
+        Address {
+            city: "Rome".to_string(),
+            zip: None,
+        },
+    ];
+    assert_eq!(
+        to_cypher_param(&rows).unwrap(),
+        "[{city: 'Paris', `zip code`: '75001'}, {city: 'Rome', `zip code`: null}]"
+    );
+}
+
+#[test]
+fn into_falkor_param_reports_the_unencodable_field() {
+    let address = Address {
+        city: "nul\0".to_string(),
+        zip: None,
+    };
+    assert!(matches!(
+        to_cypher_param(&address),
+        Err(FalkorDBError::ParamEncoding { .. })
+    ));
+}
+
+#[test]
+fn into_falkor_params_binds_owned_and_borrowed_structs() {
+    let user = new_user();
+    assert!(!(&user).into_falkor_params().is_empty());
+    assert!(!user.into_falkor_params().is_empty());
+    assert!(Skipped { ignored: 1 }.into_falkor_params().is_empty());
+}
@@ -216,8 +216,7 @@ mod async_flavours {
                 .await
                 .expect("embedded async client should build for an available module");
 
-            // Embedded is non-Sentinel, so the requested strategy is used as-is (no
-            // downgrade) and reported back.
+            // The requested strategy is used as-is and reported back.
             assert_eq!(client.connection_strategy(), strategy);
 
             let graph_name = format!("embedded_async_{}", strategy.connection_count().get());
@@ -549,6 +549,43 @@ mod typed_params {
         let _ = graph.delete();
     }
 
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_with_params_from_derived_struct() {
+        #[derive(falkordb::IntoFalkorParams)]
+        struct NewUser {
+            name: String,
+            #[falkordb(rename = "years")]
+            age: i64,
+            #[falkordb(skip)]
+            #[allow(dead_code)]
+            password: String,
+        }
+
+        let Some(mut graph) = graph_for("test_params_derived") else {
+            return;
+        };
+        let user = NewUser {
+            name: "Ada".to_string(),
+            age: 36,
+            password: "hunter2".to_string(),
+        };
+        let mut result = graph
+            .query("RETURN $name + ':' + toString($years)")
+            .with_params(&user)
+            .execute()
+            .expect("query should succeed");
+        let value = result
+            .data
+            .next()
+            .expect("expected a row")
+            .expect("row should parse")
+            .try_get_at::<String>(0)
+            .expect("column 0 should be a string");
+        assert_eq!(value, "Ada:36");
+        let _ = graph.delete();
+    }
+
     #[test]
     fn test_with_raw_param() {
         let Some(mut graph) = graph_for("test_params_raw") else {
@@ -1166,6 +1203,60 @@ mod serde_typed_mapping {
 
         assert_eq!(counts, vec![1]);
 
+        let _ = graph.delete();
+    }
+    #[test]
+    fn test_serde_param_round_trips_through_a_node() {
+        if skip_if_no_server() {
+            return;
+        }
+
+        let conn_info = match get_test_connection_info() {
+            Ok(info) => info,
+            Err(_) => return,
+        };
+
+        let client = match FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .build()
+        {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+
+        #[derive(serde::Serialize)]
+        struct NewMovie<'a> {
+            title: &'a str,
+            year: i64,
+            genres: Vec<&'a str>,
+        }
+
+        let mut graph = client.select_graph("test_serde_param_node");
+        let _ = graph.delete();
+
+        let movie = NewMovie {
+            title: "Heat",
+            year: 1995,
+            genres: vec!["crime", "thriller"],
+        };
+        let mut result = graph
+            .query("CREATE (m:Movie) SET m = $movie RETURN m")
+            .with_param("movie", falkordb::SerdeParam(&movie))
+            .execute()
+            .expect("create should succeed");
+
+        let row = result
+            .data
+            .next()
+            .expect("expected a row")
+            .expect("row should parse");
+        let json = serde_json::to_value(&row).expect("row should serialize");
+        assert_eq!(json["m"]["labels"], serde_json::json!(["Movie"]));
+        assert_eq!(
+            json["m"]["properties"],
+            serde_json::json!({"genres": ["crime", "thriller"], "title": "Heat", "year": 1995})
+        );
+
         let _ = graph.delete();
     }
 }
@@ -1429,11 +1520,79 @@ mod async_streaming {
             Some("Heat")
         );
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ordered_ro_query_is_paged_transparently() {
+        if skip_if_no_server() {
+            return;
+        }
+        let Ok(conn_info) = get_test_connection_info() else {
+            return;
+        };
+        let Ok(client) = FalkorClientBuilder::new_async()
+            .with_connection_info(conn_info)
+            .with_stream_page_size(std::num::NonZeroUsize::new(10))
+            .build()
+            .await
+        else {
+            return;
+        };
+        let mut graph = client.select_graph("test_async_stream_transparent_paging");
+        let _ = graph.delete().await;
+        graph
+            .query("UNWIND range(1, 25) AS i CREATE (:N {v: i})")
+            .execute()
+            .await
+            .expect("create");
+
+        let result = graph
+            .ro_query("MATCH (n:N) RETURN n.v AS v ORDER BY v")
+            .execute()
+            .await
+            .expect("query");
+        // Only the first page is resident; the rest are fetched as the stream is drained.
+        assert_eq!(result.data.len(), 10);
+        let values: Vec<i64> = result
+            .data
+            .map(|row| row?.try_get::<i64>("v"))
+            .try_collect()
+            .await
+            .expect("collect");
+        assert_eq!(values, (1..=25).collect::<Vec<_>>());
+
+        // An unordered query cannot be paged safely and arrives as one reply.
+        let result = graph
+            .ro_query("MATCH (n:N) RETURN n.v AS v")
+            .execute()
+            .await
+            .expect("query");
+        assert_eq!(result.data.len(), 25);
+
+        // Paging is opt-in: a client without a stream page size fetches the ordered query whole.
+        let Ok(default_client) = FalkorClientBuilder::new_async()
+            .with_connection_info(get_test_connection_info().expect("connection info"))
+            .build()
+            .await
+        else {
+            return;
+        };
+        let result = default_client
+            .select_graph("test_async_stream_transparent_paging")
+            .ro_query("MATCH (n:N) RETURN n.v AS v ORDER BY v")
+            .execute()
+            .await
+            .expect("query");
+        assert_eq!(result.data.len(), 25);
+        let _ = graph.delete().await;
+    }
 }
 
 mod batch_pipelining {
     use super::{get_test_connection_info, skip_if_no_server};
-    use falkordb::{BatchQuery, FalkorClientBuilder, FalkorDBError};
+    use falkordb::{
+        AtomicBatchOutcome, Backoff, BatchQuery, FalkorClientBuilder, FalkorDBError, RetryPolicy,
+    };
+    use std::time::Duration;
 
     fn graph_for(name: &str) -> Option<falkordb::SyncGraph> {
         if skip_if_no_server() {
@@ -1607,13 +1766,217 @@ mod batch_pipelining {
         assert_eq!(n, 1);
         graph.delete().expect("delete");
     }
+
+    #[test]
+    fn atomic_batch_commits_all_queries() {
+        let Some(mut graph) = graph_for("test_batch_atomic") else {
+            return;
+        };
+        let mut batch = graph.batch();
+        batch.query("CREATE (:N {v: 1})");
+        batch.query("CREATE (:N {v: 2})");
+        batch.ro_query("MATCH (n:N) RETURN count(n) AS n");
+        let outcome = batch.atomic().execute().expect("transaction dispatched");
+
+        let AtomicBatchOutcome::Committed(results) = outcome else {
+            panic!("an uncontended transaction must commit, got {outcome:?}");
+        };
+        assert_eq!(results.len(), 3);
+        let n: i64 = results[2].as_ref().expect("count ok").data[0]
+            .try_get("n")
+            .expect("n");
+        assert_eq!(n, 2);
+        graph.delete().expect("delete");
+    }
+
+    #[test]
+    fn atomic_batch_encode_error_sends_nothing() {
+        let Some(mut graph) = graph_for("test_batch_atomic_encode") else {
+            return;
+        };
+        graph.query("CREATE (:Seed)").execute().expect("seed");
+        let mut batch = graph.batch();
+        batch.query("CREATE (:N {v: 1})");
+        batch
+            .query("CREATE (:N {v: $v})")
+            .with_param("invalid name!", 1);
+        assert!(batch.atomic().execute().is_err());
+
+        let mut count = graph
+            .ro_query("MATCH (n:N) RETURN count(n) AS n")
+            .execute()
+            .expect("count");
+        let n: i64 = count.data.next().unwrap().unwrap().try_get("n").unwrap();
+        assert_eq!(n, 0, "no query of a failed transaction may apply");
+        graph.delete().expect("delete");
+    }
+
+    #[test]
+    fn transaction_reads_then_commits_queued_writes() {
+        let Some(mut graph) = graph_for("test_transaction_commit") else {
+            return;
+        };
+        graph
+            .query("CREATE (:Doc {id: 1, version: 1})")
+            .execute()
+            .expect("seed");
+        let output = graph
+            .transaction(|tx| {
+                let current = tx.read(&BatchQuery::read(
+                    "MATCH (d:Doc {id: 1}) RETURN d.version AS v",
+                ))?;
+                let version: i64 = current.data[0].try_get("v")?;
+                tx.query("MATCH (d:Doc {id: 1}) SET d.version = $next")
+                    .with_param("next", version + 1);
+                Ok(version + 1)
+            })
+            .expect("uncontended transaction commits");
+        assert_eq!(output.value, 2);
+        assert_eq!(output.results.len(), 1);
+        assert!(output.results[0].is_ok());
+
+        let mut check = graph
+            .ro_query("MATCH (d:Doc {id: 1}) RETURN d.version AS v")
+            .execute()
+            .expect("check");
+        let v: i64 = check.data.next().unwrap().unwrap().try_get("v").unwrap();
+        assert_eq!(v, 2);
+        graph.delete().expect("delete");
+    }
+
+    #[test]
+    fn transaction_body_error_applies_nothing() {
+        let Some(mut graph) = graph_for("test_transaction_body_err") else {
+            return;
+        };
+        graph.query("CREATE (:Seed)").execute().expect("seed");
+        let result = graph.transaction(|tx| {
+            tx.query("CREATE (:N)");
+            Err::<(), _>(FalkorDBError::ParsingError("abandon".to_string()))
+        });
+        assert!(matches!(result, Err(FalkorDBError::ParsingError(_))));
+
+        let mut count = graph
+            .ro_query("MATCH (n:N) RETURN count(n) AS n")
+            .execute()
+            .expect("count");
+        let n: i64 = count.data.next().unwrap().unwrap().try_get("n").unwrap();
+        assert_eq!(n, 0, "writes of an abandoned transaction must not apply");
+        graph.delete().expect("delete");
+    }
+
+    /// A client whose transactions get `max_attempts` attempts, 10ms apart, plus a second client
+    /// on the same graph to write from while a transaction is in flight.
+    fn contended_graphs(
+        name: &str,
+        max_attempts: u32,
+    ) -> Option<(falkordb::SyncGraph, falkordb::SyncGraph)> {
+        if skip_if_no_server() {
+            return None;
+        }
+        let policy = RetryPolicy::read_only()
+            .max_attempts(max_attempts)
+            .backoff(Backoff::fixed(Duration::from_millis(10)));
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(get_test_connection_info().ok()?)
+            .with_retry_policy(policy)
+            .build()
+            .ok()?;
+        let other = FalkorClientBuilder::new()
+            .with_connection_info(get_test_connection_info().ok()?)
+            .build()
+            .ok()?;
+        let mut graph = client.select_graph(name);
+        let _ = graph.delete();
+        graph
+            .query("CREATE (:Doc {id: 1, version: 1})")
+            .execute()
+            .ok()?;
+        Some((graph, other.select_graph(name)))
+    }
+
+    fn doc_version(graph: &mut falkordb::SyncGraph) -> i64 {
+        let mut check = graph
+            .ro_query("MATCH (d:Doc {id: 1}) RETURN d.version AS v")
+            .execute()
+            .expect("check");
+        check.data.next().unwrap().unwrap().try_get("v").unwrap()
+    }
+
+    #[test]
+    fn transaction_reruns_after_a_concurrent_write() {
+        let Some((mut graph, mut other)) = contended_graphs("test_transaction_contended", 3) else {
+            return;
+        };
+        let mut attempts = 0;
+        let output = graph
+            .transaction(|tx| {
+                attempts += 1;
+                let current = tx.read(&BatchQuery::read(
+                    "MATCH (d:Doc {id: 1}) RETURN d.version AS v",
+                ))?;
+                let version: i64 = current.data[0].try_get("v")?;
+                if attempts == 1 {
+                    // Another client changes the graph between the read and the commit.
+                    other
+                        .query("MATCH (d:Doc {id: 1}) SET d.version = d.version + 10")
+                        .execute()?;
+                }
+                tx.query("MATCH (d:Doc {id: 1}) SET d.version = $next")
+                    .with_param("next", version + 1);
+                Ok(version)
+            })
+            .expect("the second attempt commits");
+
+        assert_eq!(
+            attempts, 2,
+            "the conflicting attempt must re-run the closure"
+        );
+        assert_eq!(output.value, 11, "the re-run reads the concurrent write");
+        assert_eq!(doc_version(&mut graph), 12);
+        graph.delete().expect("delete");
+    }
+
+    #[test]
+    fn transaction_conflict_after_the_attempt_budget() {
+        let Some((mut graph, mut other)) = contended_graphs("test_transaction_always_contended", 2)
+        else {
+            return;
+        };
+        let mut attempts = 0;
+        let result = graph.transaction(|tx| {
+            attempts += 1;
+            tx.read(&BatchQuery::read(
+                "MATCH (d:Doc {id: 1}) RETURN d.version AS v",
+            ))?;
+            other
+                .query("MATCH (d:Doc {id: 1}) SET d.version = d.version + 10")
+                .execute()?;
+            tx.query("MATCH (d:Doc {id: 1}) SET d.version = 0");
+            Ok(())
+        });
+
+        assert!(matches!(result, Err(FalkorDBError::TransactionConflict)));
+        assert_eq!(attempts, 2, "the RetryPolicy bounds the number of attempts");
+        assert_eq!(
+            doc_version(&mut graph),
+            21,
+            "only the concurrent writes applied, none of the transaction's"
+        );
+        graph.delete().expect("delete");
+    }
 }
 
 #[cfg(feature = "tokio")]
 mod async_batch_pipelining {
     use super::{get_test_connection_info, skip_if_no_server};
-    use falkordb::{AsyncGraph, ConnectionStrategy, FalkorClientBuilder, FalkorDBError};
+    use falkordb::{
+        AsyncGraph, Backoff, BatchQuery, ConnectionStrategy, FalkorClientBuilder, FalkorDBError,
+        RetryPolicy,
+    };
+    use futures::TryStreamExt;
     use std::num::NonZeroU8;
+    use std::time::Duration;
 
     async fn async_graph_for(name: &str) -> Option<AsyncGraph> {
         if skip_if_no_server() {
@@ -1708,7 +2071,6 @@ mod async_batch_pipelining {
 
     #[tokio::test(flavor = "multi_thread")]
     async fn async_query_with_timeout_executes() {
-        use futures::TryStreamExt;
         let Some(mut graph) = async_graph_for("test_async_query_timeout").await else {
             return;
         };
@@ -1753,53 +2115,280 @@ mod async_batch_pipelining {
         assert_eq!(n, 1);
         graph.delete().await.expect("delete");
     }
-}
 
-mod temporal_values {
-    use super::{get_test_connection_info, skip_if_no_server};
-    use falkordb::{Date, DateTime, Duration, FalkorClientBuilder, Time};
+    #[tokio::test(flavor = "multi_thread")]
+    async fn async_transaction_over_multiplexed_connection() {
+        // The default multiplexed strategy cannot WATCH on its shared socket, so this exercises
+        // the dedicated connection each transaction attempt opens.
+        let Some(mut graph) = async_graph_for("test_async_transaction").await else {
+            return;
+        };
+        graph
+            .query("CREATE (:Doc {id: 1, version: 1})")
+            .execute()
+            .await
+            .expect("seed");
 
-    fn graph_for(name: &str) -> Option<falkordb::SyncGraph> {
+        let output = graph
+            .transaction(async |tx| {
+                let current = tx
+                    .read(&BatchQuery::read(
+                        "MATCH (d:Doc {id: 1}) RETURN d.version AS v",
+                    ))
+                    .await?;
+                let version: i64 = current.data[0].try_get("v")?;
+                tx.query("MATCH (d:Doc {id: 1}) SET d.version = $next")
+                    .with_param("next", version + 1);
+                Ok(version + 1)
+            })
+            .await
+            .expect("uncontended transaction commits");
+        assert_eq!(output.value, 2);
+        assert!(output.results[0].is_ok());
+        graph.delete().await.expect("delete");
+    }
+
+    /// Async counterpart of the sync `contended_graphs`.
+    async fn contended_async_graphs(
+        name: &str,
+        max_attempts: u32,
+    ) -> Option<(AsyncGraph, AsyncGraph)> {
         if skip_if_no_server() {
             return None;
         }
-        let conn_info = get_test_connection_info().ok()?;
-        let client = FalkorClientBuilder::new()
-            .with_connection_info(conn_info)
+        let policy = RetryPolicy::read_only()
+            .max_attempts(max_attempts)
+            .backoff(Backoff::fixed(Duration::from_millis(10)));
+        let client = FalkorClientBuilder::new_async()
+            .with_connection_info(get_test_connection_info().ok()?)
+            .with_retry_policy(policy)
+            .build()
+            .await
+            .ok()?;
+        let other = FalkorClientBuilder::new_async()
+            .with_connection_info(get_test_connection_info().ok()?)
             .build()
+            .await
             .ok()?;
         let mut graph = client.select_graph(name);
-        let _ = graph.delete();
-        Some(graph)
+        let _ = graph.delete().await;
+        graph
+            .query("CREATE (:Doc {id: 1, version: 1})")
+            .execute()
+            .await
+            .ok()?;
+        Some((graph, other.select_graph(name)))
     }
 
-    /// End-to-end: FalkorDB temporal scalars (markers 13/14/15/16) decode into the typed
-    /// `DateTime` / `Date` / `Time` / `Duration` values rather than surfacing as `Unparseable`.
-    #[test]
-    fn test_temporal_values_round_trip() {
-        let Some(mut graph) = graph_for("test_temporal_round_trip") else {
-            return;
-        };
-        let mut result = graph
-            .query(
-                "RETURN date('1947-11-29') AS d, localdatetime('1947-11-29T00:00:00') AS dt, \
-                 localtime() AS t, duration({days: 3}) AS dur",
-            )
+    async fn doc_version(graph: &mut AsyncGraph) -> i64 {
+        let mut check = graph
+            .ro_query("MATCH (d:Doc {id: 1}) RETURN d.version AS v")
             .execute()
-            .expect("temporal query should succeed");
-        let row = result
+            .await
+            .expect("check");
+        check
             .data
-            .next()
-            .expect("expected a row")
-            .expect("row should parse");
-
-        // `date('1947-11-29')` is seconds since the Unix epoch at UTC midnight (negative, pre-1970).
-        let d: Date = row.try_get("d").expect("date column");
-        assert_eq!(d.seconds().get(), -697_161_600);
+            .try_next()
+            .await
+            .unwrap()
+            .unwrap()
+            .try_get("v")
+            .unwrap()
+    }
 
-        // `localdatetime('1947-11-29T00:00:00')` is the same instant decoded as a `DateTime` (marker 13).
-        let dt: DateTime = row.try_get("dt").expect("datetime column");
-        assert_eq!(dt.seconds().get(), -697_161_600);
+    #[tokio::test(flavor = "multi_thread")]
+    async fn async_transaction_reruns_after_a_concurrent_write() {
+        let Some((mut graph, mut other)) =
+            contended_async_graphs("test_async_transaction_contended", 3).await
+        else {
+            return;
+        };
+        let mut attempts = 0;
+        let output = graph
+            .transaction(async |tx| {
+                attempts += 1;
+                let current = tx
+                    .read(&BatchQuery::read(
+                        "MATCH (d:Doc {id: 1}) RETURN d.version AS v",
+                    ))
+                    .await?;
+                let version: i64 = current.data[0].try_get("v")?;
+                if attempts == 1 {
+                    // Another client changes the graph between the read and the commit.
+                    other
+                        .query("MATCH (d:Doc {id: 1}) SET d.version = d.version + 10")
+                        .execute()
+                        .await?;
+                }
+                tx.query("MATCH (d:Doc {id: 1}) SET d.version = $next")
+                    .with_param("next", version + 1);
+                Ok(version)
+            })
+            .await
+            .expect("the second attempt commits");
+
+        assert_eq!(
+            attempts, 2,
+            "the conflicting attempt must re-run the closure"
+        );
+        assert_eq!(output.value, 11, "the re-run reads the concurrent write");
+        assert_eq!(doc_version(&mut graph).await, 12);
+        graph.delete().await.expect("delete");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn async_transaction_conflict_after_the_attempt_budget() {
+        let Some((mut graph, mut other)) =
+            contended_async_graphs("test_async_transaction_always_contended", 2).await
+        else {
+            return;
+        };
+        let mut attempts = 0;
+        let result = graph
+            .transaction(async |tx| {
+                attempts += 1;
+                tx.read(&BatchQuery::read(
+                    "MATCH (d:Doc {id: 1}) RETURN d.version AS v",
+                ))
+                .await?;
+                other
+                    .query("MATCH (d:Doc {id: 1}) SET d.version = d.version + 10")
+                    .execute()
+                    .await?;
+                tx.query("MATCH (d:Doc {id: 1}) SET d.version = 0");
+                Ok(())
+            })
+            .await;
+
+        assert!(matches!(result, Err(FalkorDBError::TransactionConflict)));
+        assert_eq!(attempts, 2, "the RetryPolicy bounds the number of attempts");
+        assert_eq!(
+            doc_version(&mut graph).await,
+            21,
+            "only the concurrent writes applied, none of the transaction's"
+        );
+        graph.delete().await.expect("delete");
+    }
+}
+
+mod sessions {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::{BatchQuery, FalkorClientBuilder};
+    use std::num::NonZeroU8;
+
+    #[test]
+    fn session_graph_reads_its_own_writes() {
+        if skip_if_no_server() {
+            return;
+        }
+        let conn_info = get_test_connection_info().expect("conn info");
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .with_num_connections(NonZeroU8::new(2).expect("valid pool size"))
+            .build()
+            .expect("client");
+
+        let session = client.session().expect("session");
+        session
+            .set_client_name("integration-session")
+            .expect("set name");
+        let mut graph = session.select_graph("test_session_graph");
+        let _ = graph.delete();
+        graph.query("CREATE (:N {v: 1})").execute().expect("create");
+
+        let mut batch = graph.batch();
+        batch.push(BatchQuery::read("MATCH (n:N) RETURN count(n) AS n"));
+        let results = batch.execute().expect("batch dispatched");
+        let n: i64 = results[0].as_ref().unwrap().data[0].try_get("n").unwrap();
+        assert_eq!(n, 1);
+
+        // The rest of the pool keeps serving the client while the session is open.
+        assert!(client
+            .list_graphs()
+            .expect("list")
+            .contains(&"test_session_graph".to_string()));
+        graph.delete().expect("delete");
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn async_session_over_multiplexed_client() {
+        if skip_if_no_server() {
+            return;
+        }
+        let conn_info = get_test_connection_info().expect("conn info");
+        let client = FalkorClientBuilder::new_async()
+            .with_connection_info(conn_info)
+            .build()
+            .await
+            .expect("client");
+
+        let session = client.session().await.expect("session");
+        session
+            .set_client_name("integration-async-session")
+            .await
+            .expect("set name");
+        let mut graph = session.select_graph("test_async_session_graph");
+        let _ = graph.delete().await;
+        graph
+            .query("CREATE (:N {v: 1})")
+            .execute()
+            .await
+            .expect("create");
+        let mut batch = graph.batch();
+        batch.ro_query("MATCH (n:N) RETURN count(n) AS n");
+        let results = batch.execute().await.expect("batch dispatched");
+        let n: i64 = results[0].as_ref().unwrap().data[0].try_get("n").unwrap();
+        assert_eq!(n, 1);
+        graph.delete().await.expect("delete");
+    }
+}
+
+mod temporal_values {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::{Date, DateTime, Duration, FalkorClientBuilder, Time};
+
+    fn graph_for(name: &str) -> Option<falkordb::SyncGraph> {
+        if skip_if_no_server() {
+            return None;
+        }
+        let conn_info = get_test_connection_info().ok()?;
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .build()
+            .ok()?;
+        let mut graph = client.select_graph(name);
+        let _ = graph.delete();
+        Some(graph)
+    }
+
+    /// End-to-end: FalkorDB temporal scalars (markers 13/14/15/16) decode into the typed
+    /// `DateTime` / `Date` / `Time` / `Duration` values rather than surfacing as `Unparseable`.
+    #[test]
+    fn test_temporal_values_round_trip() {
+        let Some(mut graph) = graph_for("test_temporal_round_trip") else {
+            return;
+        };
+        let mut result = graph
+            .query(
+                "RETURN date('1947-11-29') AS d, localdatetime('1947-11-29T00:00:00') AS dt, \
+                 localtime() AS t, duration({days: 3}) AS dur",
+            )
+            .execute()
+            .expect("temporal query should succeed");
+        let row = result
+            .data
+            .next()
+            .expect("expected a row")
+            .expect("row should parse");
+
+        // `date('1947-11-29')` is seconds since the Unix epoch at UTC midnight (negative, pre-1970).
+        let d: Date = row.try_get("d").expect("date column");
+        assert_eq!(d.seconds().get(), -697_161_600);
+
+        // `localdatetime('1947-11-29T00:00:00')` is the same instant decoded as a `DateTime` (marker 13).
+        let dt: DateTime = row.try_get("dt").expect("datetime column");
+        assert_eq!(dt.seconds().get(), -697_161_600);
 
         // `localtime()` is a `Time`; its exact value is dynamic, so just assert it decoded into the
         // typed value (rather than `Unparseable`). A `localtime`/`time` scalar is non-negative —
@@ -1823,4 +2412,1349 @@ mod temporal_values {
 
         let _ = graph.delete();
     }
+    /// `chrono` values bind as parameters and decode back from the matching temporal columns.
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_values_round_trip_through_params() {
+        use chrono::{NaiveDate, TimeDelta, TimeZone, Utc};
+
+        let Some(mut graph) = graph_for("test_temporal_chrono") else {
+            return;
+        };
+        let date = NaiveDate::from_ymd_opt(1947, 11, 29).unwrap();
+        let instant = Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap();
+        let mut result = graph
+            .query("RETURN date($d) AS d, localdatetime($dt) AS dt, duration($dur) AS dur")
+            .with_param("d", date)
+            .with_param("dt", instant)
+            .with_param("dur", TimeDelta::days(3))
+            .execute()
+            .expect("temporal query should succeed");
+        let row = result
+            .data
+            .next()
+            .expect("expected a row")
+            .expect("row should parse");
+
+        assert_eq!(row.try_get::<NaiveDate>("d").expect("date column"), date);
+        assert_eq!(
+            row.try_get::<chrono::DateTime<Utc>>("dt")
+                .expect("datetime column"),
+            instant
+        );
+        assert_eq!(
+            row.try_get::<TimeDelta>("dur").expect("duration column"),
+            TimeDelta::days(3)
+        );
+        let _ = graph.delete();
+    }
+}
+
+mod spatial_queries {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::{FalkorClientBuilder, Node, Point};
+
+    fn graph_for(name: &str) -> Option<falkordb::SyncGraph> {
+        if skip_if_no_server() {
+            return None;
+        }
+        let conn_info = get_test_connection_info().ok()?;
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .build()
+            .ok()?;
+        let mut graph = client.select_graph(name);
+        let _ = graph.delete();
+        Some(graph)
+    }
+
+    /// `nodes_within_radius` keeps the nodes inside the radius, nearest first, with their distance.
+    #[test]
+    fn test_nodes_within_radius() {
+        let Some(mut graph) = graph_for("test_spatial_within_radius") else {
+            return;
+        };
+        graph
+            .query(
+                "CREATE (:Depot {name: 'jaffa', location: point({latitude: 32.0504, longitude: 34.7522})}), \
+                 (:Depot {name: 'center', location: point({latitude: 32.0853, longitude: 34.7818})}), \
+                 (:Depot {name: 'haifa', location: point({latitude: 32.7940, longitude: 34.9896})})",
+            )
+            .execute()
+            .expect("seeding depots should succeed");
+
+        let center = Point {
+            latitude: 32.0853,
+            longitude: 34.7818,
+        };
+        let result = graph
+            .nodes_within_radius("Depot", "location", center, 10_000.0)
+            .expect("identifiers are valid")
+            .execute()
+            .expect("radius query should succeed");
+        let rows = result
+            .data
+            .collect::<Result<Vec<_>, _>>()
+            .expect("rows should parse");
+        let names = rows
+            .iter()
+            .map(|row| {
+                let node: Node = row.try_get("node").expect("node column");
+                node.properties["name"]
+                    .as_string()
+                    .expect("name is a string")
+                    .clone()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["center", "jaffa"]);
+        let nearest: f64 = rows[0].try_get("distance").expect("distance column");
+        assert!(nearest < 1.0);
+
+        let _ = graph.delete();
+    }
+
+    /// A `geo_types::Point` binds as a parameter and decodes back from a point column.
+    #[cfg(feature = "geo-types")]
+    #[test]
+    fn test_geo_types_point_round_trips_through_params() {
+        let Some(mut graph) = graph_for("test_spatial_geo_types") else {
+            return;
+        };
+        let location = geo_types::Point::new(34.7818, 32.0853);
+        let mut result = graph
+            .query("RETURN point($p) AS p")
+            .with_param("p", location)
+            .execute()
+            .expect("point query should succeed");
+        let row = result
+            .data
+            .next()
+            .expect("expected a row")
+            .expect("row should parse");
+        let decoded: geo_types::Point<f64> = row.try_get("p").expect("point column");
+        approx::assert_abs_diff_eq!(decoded.x(), location.x(), epsilon = 1e-4);
+        approx::assert_abs_diff_eq!(decoded.y(), location.y(), epsilon = 1e-4);
+
+        let _ = graph.delete();
+    }
+}
+
+mod fulltext_search {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::{FalkorClientBuilder, FulltextField, FulltextIndexOptions, Node};
+
+    fn graph_for(name: &str) -> Option<falkordb::SyncGraph> {
+        if skip_if_no_server() {
+            return None;
+        }
+        let conn_info = get_test_connection_info().ok()?;
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .build()
+            .ok()?;
+        let mut graph = client.select_graph(name);
+        let _ = graph.delete();
+        Some(graph)
+    }
+
+    fn titles(
+        graph: &mut falkordb::SyncGraph,
+        query: &str,
+    ) -> Vec<(String, f64)> {
+        let result = graph
+            .fulltext_search("Movie", query)
+            .execute()
+            .expect("fulltext search should succeed");
+        result
+            .data
+            .map(|row| {
+                let row = row.expect("row should parse");
+                let node: Node = row.try_get("node").expect("node column");
+                let title = node.properties["title"]
+                    .as_string()
+                    .expect("title is a string")
+                    .clone();
+                (title, row.try_get("score").expect("score column"))
+            })
+            .collect()
+    }
+
+    /// A weighted field ranks its matches first, and punctuation in the query is matched as text.
+    #[test]
+    fn test_fulltext_search_with_weighted_fields() {
+        let Some(mut graph) = graph_for("test_fulltext_weighted") else {
+            return;
+        };
+        graph
+            .query(
+                "CREATE (:Movie {title: 'The Matrix', plot: 'A hacker learns the truth'}), \
+                 (:Movie {title: 'Hackers', plot: 'Teenagers uncover a matrix of fraud'}), \
+                 (:Movie {title: 'Up', plot: 'A balloon adventure'})",
+            )
+            .execute()
+            .expect("seeding movies should succeed");
+        graph
+            .create_fulltext_index(
+                "Movie",
+                &[
+                    FulltextField::new("title").with_weight(5.0),
+                    FulltextField::new("plot"),
+                ],
+                &FulltextIndexOptions::new().with_stopwords(["a", "the"]),
+            )
+            .expect("fulltext index creation should succeed");
+
+        let hits = titles(&mut graph, "matrix");
+        assert_eq!(
+            hits.iter()
+                .map(|(title, _)| title.as_str())
+                .collect::<Vec<_>>(),
+            ["The Matrix", "Hackers"]
+        );
+        assert!(hits[0].1 > hits[1].1);
+
+        // Unescaped, this is a fulltext syntax error; escaped, it is searched as plain text.
+        titles(&mut graph, "matrix) | (@title:");
+
+        let _ = graph.delete();
+    }
+}
+
+mod vector_search {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::{Edge, FalkorClientBuilder, FalkorDBError, Node, VectorSimilarity};
+
+    fn graph_for(name: &str) -> Option<falkordb::SyncGraph> {
+        if skip_if_no_server() {
+            return None;
+        }
+        let conn_info = get_test_connection_info().ok()?;
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .build()
+            .ok()?;
+        let mut graph = client.select_graph(name);
+        let _ = graph.delete();
+        Some(graph)
+    }
+
+    fn name(node: &Node) -> String {
+        node.properties["name"]
+            .as_string()
+            .expect("name is a string")
+            .clone()
+    }
+
+    /// Nodes come back nearest first, the filter drops neighbours, and mismatches fail client-side.
+    #[test]
+    fn test_vector_search_nodes() {
+        let Some(mut graph) = graph_for("test_vector_search_nodes") else {
+            return;
+        };
+        graph
+            .query(
+                "CREATE (:Doc {name: 'near', draft: false, embedding: vecf32([1.0, 0.0])}), \
+                 (:Doc {name: 'draft', draft: true, embedding: vecf32([0.9, 0.1])}), \
+                 (:Doc {name: 'far', draft: false, embedding: vecf32([0.0, 1.0])})",
+            )
+            .execute()
+            .expect("seeding docs should succeed");
+        graph
+            .create_node_vector_index_op("Doc", &["embedding"], 2, VectorSimilarity::Euclidean)
+            .wait()
+            .expect("vector index should become operational");
+
+        let hits = graph
+            .vector_search("Doc", "embedding", [1.0f32, 0.0], 3)
+            .execute()
+            .expect("vector search should succeed");
+        assert_eq!(
+            hits.iter().map(|(node, _)| name(node)).collect::<Vec<_>>(),
+            ["near", "draft", "far"]
+        );
+        assert!(hits[0].1 <= hits[1].1 && hits[1].1 <= hits[2].1);
+
+        let hits = graph
+            .vector_search("Doc", "embedding", vec![1.0, 0.0], 2)
+            .with_filter("node.draft = $draft")
+            .with_param("draft", false)
+            .execute()
+            .expect("filtered vector search should succeed");
+        assert_eq!(
+            hits.iter().map(|(node, _)| name(node)).collect::<Vec<_>>(),
+            ["near"]
+        );
+
+        assert!(matches!(
+            graph
+                .vector_search("Doc", "embedding", [1.0, 0.0, 0.0], 1)
+                .execute(),
+            Err(FalkorDBError::VectorDimensionMismatch {
+                expected: 2,
+                actual: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            graph.vector_search("Doc", "title", [1.0, 0.0], 1).execute(),
+            Err(FalkorDBError::VectorIndexNotFound { .. })
+        ));
+
+        let _ = graph.delete();
+    }
+
+    /// Relationship indices are searched through `edge_vector_search`.
+    #[test]
+    fn test_edge_vector_search() {
+        let Some(mut graph) = graph_for("test_vector_search_edges") else {
+            return;
+        };
+        graph
+            .query(
+                "CREATE (a:Doc)-[:SIMILAR {v: vecf32([0.0, 1.0])}]->(b:Doc), \
+                 (a)-[:SIMILAR {v: vecf32([1.0, 0.0])}]->(b)",
+            )
+            .execute()
+            .expect("seeding relationships should succeed");
+        graph
+            .create_edge_vector_index_op("SIMILAR", &["v"], 2, VectorSimilarity::Cosine)
+            .wait()
+            .expect("vector index should become operational");
+
+        let hits: Vec<(Edge, f64)> = graph
+            .edge_vector_search("SIMILAR", "v", [0.0, 1.0], 1)
+            .execute()
+            .expect("edge vector search should succeed");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0.relationship_type, "SIMILAR");
+
+        let _ = graph.delete();
+    }
+}
+
+mod bulk_loading {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::{BulkValue, FalkorClientBuilder, FalkorSyncClient};
+
+    fn client_for(name: &str) -> Option<FalkorSyncClient> {
+        if skip_if_no_server() {
+            return None;
+        }
+        let conn_info = get_test_connection_info().ok()?;
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .build()
+            .ok()?;
+        let _ = client.select_graph(name).delete();
+        Some(client)
+    }
+
+    /// Nodes and relationships loaded over several small chunks all land in the new graph, with
+    /// the node IDs the loader handed out.
+    #[test]
+    fn test_bulk_load_across_chunks() {
+        let Some(client) = client_for("test_bulk_load_chunks") else {
+            return;
+        };
+        let mut loader = client
+            .bulk_loader("test_bulk_load_chunks")
+            .with_max_chunk_bytes(256);
+        let mut people = Vec::new();
+        for index in 0..50_i64 {
+            let person = loader
+                .add_node(
+                    &["Person"],
+                    [
+                        ("name", BulkValue::from(format!("p{index}"))),
+                        ("age", BulkValue::from(index)),
+                        ("tags", BulkValue::from(vec!["a", "b"])),
+                    ],
+                )
+                .expect("node encodes");
+            people.push(person);
+        }
+        let city = loader
+            .add_node(&["City", "Place"], [("name", "Haifa")])
+            .expect("node encodes");
+        for person in &people {
+            loader
+                .add_relationship("LIVES_IN", *person, city, [("since", 2020)])
+                .expect("relationship encodes");
+        }
+        let report = loader.finish().expect("bulk load succeeds");
+        assert_eq!(report.nodes_created, 51);
+        assert_eq!(report.relationships_created, 50);
+        assert_eq!(report.labels["Person"], 50);
+        assert_eq!(report.labels["Place"], 1);
+        assert_eq!(report.relationship_types["LIVES_IN"], 50);
+        assert!(report.commands > 1);
+
+        let mut graph = client.select_graph("test_bulk_load_chunks");
+        let rows = graph
+            .query(
+                "MATCH (p:Person {name: 'p7'})-[r:LIVES_IN]->(c:City:Place) \
+                 RETURN ID(p) AS id, p.age AS age, p.tags AS tags, r.since AS since, c.name AS city",
+            )
+            .execute()
+            .expect("query succeeds")
+            .data
+            .collect::<Result<Vec<_>, _>>()
+            .expect("rows parse");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].try_get::<i64>("id").unwrap() as u64, people[7].id());
+        assert_eq!(rows[0].try_get::<i64>("age").unwrap(), 7);
+        assert_eq!(rows[0].try_get::<Vec<String>>("tags").unwrap(), ["a", "b"]);
+        assert_eq!(rows[0].try_get::<i64>("since").unwrap(), 2020);
+        assert_eq!(rows[0].try_get::<String>("city").unwrap(), "Haifa");
+
+        let _ = graph.delete();
+    }
+
+    /// The first chunk carries `BEGIN`, so loading into an existing graph is refused.
+    #[test]
+    fn test_bulk_load_refuses_existing_graph() {
+        let Some(client) = client_for("test_bulk_load_existing") else {
+            return;
+        };
+        let mut graph = client.select_graph("test_bulk_load_existing");
+        graph
+            .query("CREATE (:Existing)")
+            .execute()
+            .expect("seeding succeeds");
+
+        let mut loader = client.bulk_loader("test_bulk_load_existing");
+        loader.add_node(&["New"], [("n", 1)]).expect("node encodes");
+        assert!(loader.finish().is_err());
+
+        let _ = graph.delete();
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_async_bulk_load() {
+        use futures::TryStreamExt;
+
+        if skip_if_no_server() {
+            return;
+        }
+        let Ok(conn_info) = get_test_connection_info() else {
+            return;
+        };
+        let Ok(client) = FalkorClientBuilder::new_async()
+            .with_connection_info(conn_info)
+            .build()
+            .await
+        else {
+            return;
+        };
+        let mut graph = client.select_graph("test_async_bulk_load");
+        let _ = graph.delete().await;
+
+        let mut loader = client.bulk_loader("test_async_bulk_load");
+        let a = loader
+            .add_node(&["N"], [("v", 1)])
+            .await
+            .expect("node encodes");
+        let b = loader
+            .add_node(&["N"], [("v", 2)])
+            .await
+            .expect("node encodes");
+        loader
+            .add_relationship("NEXT", a, b, Vec::<(&str, BulkValue)>::new())
+            .await
+            .expect("relationship encodes");
+        let report = loader.finish().await.expect("bulk load succeeds");
+        assert_eq!((report.nodes_created, report.relationships_created), (2, 1));
+
+        let count: i64 = graph
+            .query("MATCH (:N)-[:NEXT]->(:N) RETURN count(*) AS c")
+            .execute()
+            .await
+            .expect("query succeeds")
+            .data
+            .try_next()
+            .await
+            .expect("row parses")
+            .expect("a row")
+            .try_get("c")
+            .expect("count column");
+        assert_eq!(count, 1);
+
+        let _ = graph.delete().await;
+    }
+}
+
+mod unwind_writes {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::{FalkorClientBuilder, FalkorDBError, FalkorValue, SyncGraph};
+    use std::collections::BTreeMap;
+    use std::num::NonZeroUsize;
+
+    fn graph_for(name: &str) -> Option<SyncGraph> {
+        if skip_if_no_server() {
+            return None;
+        }
+        let conn_info = get_test_connection_info().ok()?;
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .build()
+            .ok()?;
+        let mut graph = client.select_graph(name);
+        let _ = graph.delete();
+        Some(graph)
+    }
+
+    fn count_nodes(graph: &mut SyncGraph) -> i64 {
+        graph
+            .ro_query("MATCH (n:N) RETURN count(n) AS c")
+            .execute()
+            .expect("query succeeds")
+            .data
+            .next()
+            .expect("a row")
+            .expect("row parses")
+            .try_get("c")
+            .expect("count column")
+    }
+
+    #[test]
+    fn test_unwind_writer_sums_statistics_across_chunks() {
+        let Some(mut graph) = graph_for("test_unwind_writer_stats") else {
+            return;
+        };
+        let records = (0..95_i64).map(|v| BTreeMap::from([("v", v), ("w", v * 2)]));
+        let report = graph
+            .unwind_writer(
+                "CREATE (:N {v: row.v, w: row.w})",
+                NonZeroUsize::new(10).unwrap(),
+            )
+            .with_chunks_per_batch(NonZeroUsize::new(3).unwrap())
+            .write_all(records)
+            .expect("every chunk succeeds");
+        assert_eq!((report.chunks, report.rows), (10, 95));
+        assert_eq!(report.nodes_created, 95);
+        assert_eq!(report.properties_set, 190);
+        assert_eq!(report.labels_added, 1);
+        assert_eq!(count_nodes(&mut graph), 95);
+
+        let _ = graph.delete();
+    }
+
+    /// A failing chunk is named in the error, the rest of its pipeline still runs, and no further
+    /// batch is sent.
+    #[test]
+    fn test_unwind_writer_reports_the_failed_chunk() {
+        let Some(mut graph) = graph_for("test_unwind_writer_failure") else {
+            return;
+        };
+        let records = (0..50_i64).map(|v| match v {
+            25 => FalkorValue::String("not a number".to_string()),
+            v => FalkorValue::I64(v),
+        });
+        let mut writer = graph
+            .unwind_writer("CREATE (:N {v: row * 2})", NonZeroUsize::new(10).unwrap())
+            .with_chunks_per_batch(NonZeroUsize::new(2).unwrap());
+        let err = writer.write_all(records).expect_err("chunk 2 fails");
+        assert!(matches!(
+            err,
+            FalkorDBError::UnwindChunkFailed {
+                chunk: 2,
+                first_row: 20,
+                rows: 10,
+                ..
+            }
+        ));
+        let report = writer.report().clone();
+        assert_eq!((report.chunks, report.rows), (3, 30));
+        assert_eq!(report.nodes_created, 30);
+        assert_eq!(count_nodes(&mut graph), 30);
+
+        let _ = graph.delete();
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_async_unwind_writer_streams_concurrent_batches() {
+        use futures::TryStreamExt;
+
+        if skip_if_no_server() {
+            return;
+        }
+        let Ok(conn_info) = get_test_connection_info() else {
+            return;
+        };
+        let Ok(client) = FalkorClientBuilder::new_async()
+            .with_connection_info(conn_info)
+            .build()
+            .await
+        else {
+            return;
+        };
+        let mut graph = client.select_graph("test_async_unwind_writer");
+        let _ = graph.delete().await;
+
+        let records = futures::stream::iter(0..500_i64);
+        let report = graph
+            .unwind_writer("CREATE (:N {v: row})", NonZeroUsize::new(25).unwrap())
+            .with_chunks_per_batch(NonZeroUsize::new(2).unwrap())
+            .with_concurrency(NonZeroUsize::new(4).unwrap())
+            .write_stream(records)
+            .await
+            .expect("every chunk succeeds");
+        assert_eq!(
+            (report.chunks, report.rows, report.nodes_created),
+            (20, 500, 500)
+        );
+
+        let sum: i64 = graph
+            .ro_query("MATCH (n:N) RETURN sum(n.v) AS s")
+            .execute()
+            .await
+            .expect("query succeeds")
+            .data
+            .try_next()
+            .await
+            .expect("row parses")
+            .expect("a row")
+            .try_get("s")
+            .expect("sum column");
+        assert_eq!(sum, (0..500).sum::<i64>());
+
+        let _ = graph.delete().await;
+    }
+}
+
+#[cfg(feature = "csv")]
+mod csv_import {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::import::csv::{
+        ColumnType, CsvImport, Endpoint, NodeMapping, RelationshipMapping,
+    };
+    use falkordb::{FalkorClientBuilder, FalkorSyncClient};
+
+    const PEOPLE: &str = "id,name,born,home\n\
+                          1,Alice,1990-04-01,\"32.08,34.78\"\n\
+                          2,Bob,,\n\
+                          3,Carol,1985-12-24,\"31.77,35.21\"\n";
+    const KNOWS: &str = "from,to,since\n1,2,2015\n2,3,\n3,1,2020\n";
+
+    fn client_for(name: &str) -> Option<FalkorSyncClient> {
+        if skip_if_no_server() {
+            return None;
+        }
+        let conn_info = get_test_connection_info().ok()?;
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .build()
+            .ok()?;
+        let _ = client.select_graph(name).delete();
+        Some(client)
+    }
+
+    fn people() -> NodeMapping {
+        NodeMapping::new("Person")
+            .with_column("id", ColumnType::Int)
+            .with_column("name", ColumnType::String)
+            .with_key("id")
+    }
+
+    fn knows() -> RelationshipMapping {
+        RelationshipMapping::new(
+            "KNOWS",
+            Endpoint::new("from", "Person", "id", ColumnType::Int),
+            Endpoint::new("to", "Person", "id", ColumnType::Int),
+        )
+        .with_column("since", ColumnType::Int)
+    }
+
+    #[test]
+    fn test_csv_import_through_unwind() {
+        let Some(client) = client_for("test_csv_import_unwind") else {
+            return;
+        };
+        let mut graph = client.select_graph("test_csv_import_unwind");
+        let mapping = people()
+            .with_column("born", ColumnType::Date)
+            .with_column("home", ColumnType::Point);
+        let report = CsvImport::new()
+            .with_nodes(mapping, PEOPLE.as_bytes())
+            .with_relationships(knows(), KNOWS.as_bytes())
+            .write_unwind(&mut graph, std::num::NonZeroUsize::new(2).unwrap())
+            .expect("import succeeds");
+        assert_eq!((report.node_rows, report.nodes_created), (3, 3));
+        assert_eq!(
+            (report.relationship_rows, report.relationships_created),
+            (3, 3)
+        );
+
+        let row = graph
+            .ro_query(
+                "MATCH (a:Person {name: 'Alice'})-[k:KNOWS]->(b:Person) \
+                 RETURN b.name AS friend, k.since AS since, a.born.year AS year, a.home.latitude AS lat, typeOf(a.home) AS home_type",
+            )
+            .execute()
+            .expect("query succeeds")
+            .data
+            .next()
+            .expect("a row")
+            .expect("row parses");
+        assert_eq!(row.try_get::<String>("friend").unwrap(), "Bob");
+        assert_eq!(row.try_get::<i64>("since").unwrap(), 2015);
+        assert_eq!(row.try_get::<i64>("year").unwrap(), 1990);
+        assert!((row.try_get::<f64>("lat").unwrap() - 32.08).abs() < 1e-4);
+        assert_eq!(row.try_get::<String>("home_type").unwrap(), "Point");
+
+        let _ = graph.delete();
+    }
+
+    #[test]
+    fn test_csv_import_through_bulk() {
+        let Some(client) = client_for("test_csv_import_bulk") else {
+            return;
+        };
+        let report = CsvImport::new()
+            .with_nodes(people(), PEOPLE.as_bytes())
+            .with_relationships(knows(), KNOWS.as_bytes())
+            .write_bulk(client.bulk_loader("test_csv_import_bulk"))
+            .expect("import succeeds");
+        assert_eq!((report.nodes_created, report.relationships_created), (3, 3));
+
+        let mut graph = client.select_graph("test_csv_import_bulk");
+        let count: i64 = graph
+            .ro_query("MATCH (:Person)-[:KNOWS]->(:Person {name: 'Carol'}) RETURN count(*) AS c")
+            .execute()
+            .expect("query succeeds")
+            .data
+            .next()
+            .expect("a row")
+            .expect("row parses")
+            .try_get("c")
+            .expect("count column");
+        assert_eq!(count, 1);
+
+        let _ = graph.delete();
+    }
+
+    /// A dry run reads every line but writes nothing, not even the graph.
+    #[test]
+    fn test_csv_dry_run_writes_nothing() {
+        let Some(client) = client_for("test_csv_import_dry_run") else {
+            return;
+        };
+        let mut graph = client.select_graph("test_csv_import_dry_run");
+        let report = CsvImport::new()
+            .with_nodes(people(), PEOPLE.as_bytes())
+            .dry_run()
+            .write_unwind(&mut graph, std::num::NonZeroUsize::new(10).unwrap())
+            .expect("the file is valid");
+        assert_eq!((report.node_rows, report.nodes_created), (3, 0));
+        assert!(!client
+            .list_graphs()
+            .expect("graphs listed")
+            .contains(&"test_csv_import_dry_run".to_string()));
+    }
+}
+
+mod cypher_dump {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::export::cypher::{self, CypherExporter};
+    use falkordb::{FalkorClientBuilder, FalkorSyncClient, SyncGraph};
+
+    fn client_for(names: &[&str]) -> Option<FalkorSyncClient> {
+        if skip_if_no_server() {
+            return None;
+        }
+        let conn_info = get_test_connection_info().ok()?;
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .build()
+            .ok()?;
+        for name in names {
+            let _ = client.select_graph(name).delete();
+        }
+        Some(client)
+    }
+
+    fn count(
+        graph: &mut SyncGraph,
+        query: &str,
+    ) -> i64 {
+        graph
+            .ro_query(query)
+            .execute()
+            .expect("query succeeds")
+            .data
+            .next()
+            .expect("a row")
+            .expect("row parses")
+            .try_get("c")
+            .expect("count column")
+    }
+
+    fn export(
+        graph: &mut SyncGraph,
+        batch_size: usize,
+    ) -> String {
+        let mut out = Vec::new();
+        CypherExporter::new()
+            .with_page_size(2)
+            .with_batch_size(batch_size)
+            .export(graph, &mut out)
+            .expect("export succeeds");
+        String::from_utf8(out).expect("the dump is UTF-8")
+    }
+
+    /// Replaying a dump gives a graph whose own dump is identical, apart from the graph name.
+    #[test]
+    fn test_dump_round_trips_through_replay() {
+        let Some(client) = client_for(&["test_cypher_dump_src", "test_cypher_dump_dst"]) else {
+            return;
+        };
+        let mut source = client.select_graph("test_cypher_dump_src");
+        source
+            .query(
+                "CREATE (a:Person {name: 'Alice', born: date('1990-04-01'), home: point({latitude: 32.0, longitude: 34.5})}), \
+                 (b:Person:Admin {name: 'Bob\\'s', tags: ['x', 'y']}), (c:City {name: 'Haifa'}), \
+                 (a)-[:KNOWS {since: 2015}]->(b), (b)-[:KNOWS {since: 2019}]->(a), (a)-[:LIVES_IN]->(c), (c)-[:NEAR]->(c)",
+            )
+            .execute()
+            .expect("graph created");
+        source
+            .create_unique_constraint(falkordb::EntityType::Node, "Person".to_string(), &["name"])
+            .expect("constraint created");
+
+        let dump = export(&mut source, 2);
+        let mut copy = client.select_graph("test_cypher_dump_dst");
+        let report = cypher::replay(&mut copy, dump.as_bytes()).expect("replay succeeds");
+        assert_eq!((report.nodes_created, report.relationships_created), (3, 4));
+        assert_eq!(report.constraints, 1);
+        assert_eq!(
+            count(&mut copy, "MATCH (n:__FalkorDumpNode) RETURN count(n) AS c"),
+            0
+        );
+        assert_eq!(
+            count(
+                &mut copy,
+                "MATCH (n) WHERE n.__falkordb_dump_id IS NOT NULL RETURN count(n) AS c"
+            ),
+            0
+        );
+
+        let body = |dump: &str| dump.lines().skip(1).map(String::from).collect::<Vec<_>>();
+        assert_eq!(body(&export(&mut copy, 2)), body(&dump));
+
+        let _ = source.delete();
+        let _ = copy.delete();
+    }
+
+    #[test]
+    fn test_dump_is_deterministic() {
+        let Some(client) = client_for(&["test_cypher_dump_stable"]) else {
+            return;
+        };
+        let mut graph = client.select_graph("test_cypher_dump_stable");
+        graph
+            .query("UNWIND range(1, 25) AS i CREATE (:N {i: i, half: i / 2.0})")
+            .execute()
+            .expect("graph created");
+        assert_eq!(export(&mut graph, 7), export(&mut graph, 7));
+
+        let _ = graph.delete();
+    }
+}
+
+#[cfg(any(feature = "jsonl", feature = "graphml"))]
+mod graph_exchange {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::{FalkorClientBuilder, FalkorSyncClient, FalkorValue, SyncGraph};
+
+    fn client_for(names: &[&str]) -> Option<FalkorSyncClient> {
+        if skip_if_no_server() {
+            return None;
+        }
+        let conn_info = get_test_connection_info().ok()?;
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .build()
+            .ok()?;
+        for name in names {
+            let _ = client.select_graph(name).delete();
+        }
+        Some(client)
+    }
+
+    fn create_source(graph: &mut SyncGraph) {
+        graph
+            .query(
+                "CREATE (a:Person {name: 'Alice', born: date('1990-04-01'), home: point({latitude: 32.0, longitude: 34.5}), \
+                 embedding: vecf32([0.5, 1.5])}), (b:Person:Admin {name: 'Bob <&>', tags: ['x', 'y']}), \
+                 (a)-[:KNOWS {since: 2015, weight: 0.5}]->(b), (b)-[:KNOWS]->(a), (a)-[:SELF]->(a)",
+            )
+            .execute()
+            .expect("graph created");
+    }
+
+    /// The copy holds the same entities, with the property types of the source.
+    fn assert_copied(graph: &mut SyncGraph) {
+        let row = graph
+            .ro_query(
+                "MATCH (a:Person {name: 'Alice'})-[k:KNOWS]->(b:Person:Admin) \
+                 RETURN a.born AS born, a.home AS home, a.embedding AS embedding, k.weight AS weight, b.tags AS tags",
+            )
+            .execute()
+            .expect("query succeeds")
+            .data
+            .next()
+            .expect("the copied pattern matches")
+            .expect("row parses");
+        assert!(matches!(
+            row.try_get::<FalkorValue>("born").unwrap(),
+            FalkorValue::Date(_)
+        ));
+        assert!(matches!(
+            row.try_get::<FalkorValue>("home").unwrap(),
+            FalkorValue::Point(_)
+        ));
+        assert!(matches!(
+            row.try_get::<FalkorValue>("embedding").unwrap(),
+            FalkorValue::Vec32(_)
+        ));
+        assert_eq!(row.try_get::<f64>("weight").unwrap(), 0.5);
+        assert_eq!(
+            row.try_get::<Vec<String>>("tags").unwrap(),
+            vec!["x".to_string(), "y".to_string()]
+        );
+        let relationships: i64 = graph
+            .ro_query("MATCH ()-[r]->() RETURN count(r) AS c")
+            .execute()
+            .expect("query succeeds")
+            .data
+            .next()
+            .expect("a row")
+            .expect("row parses")
+            .try_get("c")
+            .expect("count column");
+        assert_eq!(relationships, 3);
+    }
+
+    #[cfg(feature = "jsonl")]
+    #[test]
+    fn test_json_lines_round_trip() {
+        use falkordb::export::jsonl::JsonLinesExporter;
+        use falkordb::import::jsonl::JsonLinesImporter;
+
+        let Some(client) = client_for(&["test_jsonl_src", "test_jsonl_dst"]) else {
+            return;
+        };
+        let mut source = client.select_graph("test_jsonl_src");
+        create_source(&mut source);
+        let mut lines = Vec::new();
+        let exported = JsonLinesExporter::new()
+            .with_page_size(1)
+            .export(&mut source, &mut lines)
+            .expect("export succeeds");
+        assert_eq!((exported.nodes, exported.relationships), (2, 3));
+
+        let mut copy = client.select_graph("test_jsonl_dst");
+        let imported = JsonLinesImporter::new()
+            .with_batch_size(2)
+            .import(&mut copy, lines.as_slice())
+            .expect("import succeeds");
+        assert_eq!(
+            (imported.nodes_created, imported.relationships_created),
+            (2, 3)
+        );
+        assert_copied(&mut copy);
+
+        let _ = source.delete();
+        let _ = copy.delete();
+    }
+
+    #[cfg(feature = "graphml")]
+    #[test]
+    fn test_graphml_round_trip() {
+        use falkordb::export::graphml::GraphmlExporter;
+        use falkordb::import::graphml::GraphmlImporter;
+
+        let Some(client) = client_for(&["test_graphml_src", "test_graphml_dst"]) else {
+            return;
+        };
+        let mut source = client.select_graph("test_graphml_src");
+        create_source(&mut source);
+        let mut document = Vec::new();
+        let exported = GraphmlExporter::new()
+            .with_page_size(1)
+            .export(&mut source, &mut document)
+            .expect("export succeeds");
+        assert_eq!((exported.nodes, exported.relationships), (2, 3));
+
+        let mut copy = client.select_graph("test_graphml_dst");
+        let imported = GraphmlImporter::new()
+            .with_batch_size(2)
+            .import(&mut copy, document.as_slice())
+            .expect("import succeeds");
+        assert_eq!(
+            (imported.nodes_created, imported.relationships_created),
+            (2, 3)
+        );
+        assert_copied(&mut copy);
+
+        let _ = source.delete();
+        let _ = copy.delete();
+    }
+}
+
+#[cfg(feature = "migrations")]
+mod schema_migrations {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::migrations::{self, Migration, MigrationRunner};
+    use falkordb::{EntityType, FalkorClientBuilder, FalkorDBError, SyncGraph, WaitOptions};
+    use std::time::Duration;
+
+    fn graph(name: &str) -> Option<SyncGraph> {
+        if skip_if_no_server() {
+            return None;
+        }
+        let conn_info = get_test_connection_info().ok()?;
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .build()
+            .ok()?;
+        let mut graph = client.select_graph(name);
+        let _ = graph.delete();
+        Some(graph)
+    }
+
+    fn runner() -> MigrationRunner {
+        MigrationRunner::new()
+            .with_migration(
+                Migration::new(1, "people")
+                    .query("CREATE (:Person {email: 'A@X.COM'})")
+                    .query("CREATE INDEX FOR (p:Person) ON (p.name)")
+                    .unique_constraint(EntityType::Node, "Person", &["email"]),
+            )
+            .with_migration(
+                Migration::new(2, "lowercase emails")
+                    .query("MATCH (p:Person) SET p.email = toLower(p.email)"),
+            )
+            .wait_for_schema(WaitOptions::with_timeout(Duration::from_secs(10)))
+    }
+
+    #[test]
+    fn test_migrations_apply_once_and_detect_drift() {
+        let Some(mut graph) = graph("test_schema_migrations") else {
+            return;
+        };
+        let plans = runner().dry_run(&mut graph).expect("dry run succeeds");
+        assert_eq!(plans.len(), 2);
+        assert!(plans[0].steps[0].plan.is_some());
+        assert!(plans[0].steps[1].plan.is_none());
+        assert!(migrations::history(&mut graph)
+            .expect("history reads")
+            .is_empty());
+
+        let report = runner().run(&mut graph).expect("migrations apply");
+        assert_eq!(report.applied, vec![1, 2]);
+        let report = runner().run(&mut graph).expect("nothing left to apply");
+        assert!(report.applied.is_empty());
+        assert_eq!(report.already_applied, 2);
+
+        let history = migrations::history(&mut graph).expect("history reads");
+        assert_eq!(
+            history.iter().map(|m| m.version).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(graph
+            .list_constraints()
+            .expect("constraints list")
+            .data
+            .iter()
+            .any(|constraint| constraint.label == "Person"));
+
+        let edited = MigrationRunner::new()
+            .with_migration(Migration::new(1, "people").query("RETURN 1"))
+            .with_migration(
+                Migration::new(2, "lowercase emails")
+                    .query("MATCH (p:Person) SET p.email = toLower(p.email)"),
+            );
+        assert!(matches!(
+            edited.run(&mut graph),
+            Err(FalkorDBError::MigrationChecksumMismatch { version: 1, .. })
+        ));
+
+        let _ = graph.delete();
+    }
+
+    #[test]
+    fn test_failed_step_is_not_recorded() {
+        let Some(mut graph) = graph("test_schema_migrations_failure") else {
+            return;
+        };
+        let runner = MigrationRunner::new()
+            .with_migration(Migration::new(1, "ok").query("CREATE (:N)"))
+            .with_migration(Migration::new(2, "broken").query("THIS IS NOT CYPHER"));
+        assert!(matches!(
+            runner.run(&mut graph),
+            Err(FalkorDBError::MigrationFailed { version: 2, .. })
+        ));
+        let history = migrations::history(&mut graph).expect("history reads");
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].version, 1);
+
+        let _ = graph.delete();
+    }
+}
+
+mod schema_reconcile {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::{
+        DesiredSchema, EntityType, FalkorClientBuilder, SchemaAction, SyncGraph, VectorSimilarity,
+        WaitOptions,
+    };
+    use std::time::Duration;
+
+    fn graph(name: &str) -> Option<SyncGraph> {
+        if skip_if_no_server() {
+            return None;
+        }
+        let conn_info = get_test_connection_info().ok()?;
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .build()
+            .ok()?;
+        let mut graph = client.select_graph(name);
+        let _ = graph.delete();
+        graph
+            .query("CREATE (:Person {email: 'a@x.com', name: 'A', bio: 'hello'})")
+            .execute()
+            .ok()?;
+        Some(graph)
+    }
+
+    fn desired() -> DesiredSchema {
+        DesiredSchema::new()
+            .unique_constraint(EntityType::Node, "Person", &["email"])
+            .mandatory_constraint(EntityType::Node, "Person", &["name"])
+            .fulltext_index(EntityType::Node, "Person", &["bio"])
+            .vector_index(
+                EntityType::Node,
+                "Person",
+                "embedding",
+                3,
+                VectorSimilarity::Cosine,
+            )
+    }
+
+    #[test]
+    fn test_reconcile_is_idempotent_and_drops_unlisted_items() {
+        let Some(mut graph) = graph("test_schema_reconcile") else {
+            return;
+        };
+        let wait = WaitOptions::with_timeout(Duration::from_secs(10));
+        let desired = desired();
+        let plan = graph
+            .reconcile_schema(&desired)
+            .plan()
+            .expect("plan reads the schema");
+        assert_eq!(plan.actions.len(), 5);
+        assert!(graph.list_indices().expect("indices list").data.is_empty());
+
+        graph
+            .reconcile_schema(&desired)
+            .wait_with(wait.clone())
+            .expect("schema is applied");
+        assert!(graph
+            .reconcile_schema(&desired)
+            .plan()
+            .expect("plan reads the schema")
+            .is_empty());
+
+        let smaller =
+            DesiredSchema::new().unique_constraint(EntityType::Node, "Person", &["email"]);
+        let applied = graph
+            .reconcile_schema(&smaller)
+            .wait_with(wait)
+            .expect("unlisted items are dropped");
+        assert!(matches!(
+            applied.actions[0],
+            SchemaAction::DropConstraint { .. }
+        ));
+        assert_eq!(
+            graph
+                .list_constraints()
+                .expect("constraints list")
+                .data
+                .len(),
+            1
+        );
+        assert!(graph
+            .reconcile_schema(&smaller)
+            .plan()
+            .expect("plan reads the schema")
+            .is_empty());
+        let _ = graph.delete();
+    }
+}
+
+mod schema_describe {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::{EntityType, FalkorClientBuilder, IndexType, SyncGraph};
+
+    fn graph(name: &str) -> Option<SyncGraph> {
+        if skip_if_no_server() {
+            return None;
+        }
+        let conn_info = get_test_connection_info().ok()?;
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .build()
+            .ok()?;
+        let mut graph = client.select_graph(name);
+        let _ = graph.delete();
+        Some(graph)
+    }
+
+    #[test]
+    fn test_describe_reports_properties_endpoints_and_indices() {
+        let Some(mut graph) = graph("test_schema_describe") else {
+            return;
+        };
+        graph
+            .query(
+                "CREATE (a:Person {name: 'A', age: 30})-[:KNOWS {since: 2020}]->(:Person {name: 'B', age: '40'}), \
+                 (a)-[:KNOWS]->(:Robot {name: 'R'})",
+            )
+            .execute()
+            .expect("graph is created");
+        graph
+            .create_index(
+                IndexType::Range,
+                EntityType::Node,
+                "Person",
+                &["name"],
+                None,
+            )
+            .expect("index is created");
+
+        let description = graph.describe().execute().expect("schema is read");
+        let person = description
+            .labels
+            .iter()
+            .find(|label| label.name == "Person")
+            .expect("Person is described");
+        assert_eq!(person.count, 2);
+        let age = person
+            .properties
+            .iter()
+            .find(|property| property.name == "age")
+            .expect("age is described");
+        assert_eq!(age.count, 2);
+        assert_eq!(age.value_types.len(), 2);
+        assert_eq!(person.indices.len(), 1);
+
+        let knows = &description.relationship_types[0];
+        assert_eq!((knows.name.as_str(), knows.count), ("KNOWS", 2));
+        assert_eq!(knows.endpoints.len(), 2);
+        assert_eq!(knows.properties[0].name, "since");
+
+        let sampled = graph
+            .describe()
+            .with_sample_size(1)
+            .execute()
+            .expect("schema is sampled");
+        assert_eq!(sampled.labels.len(), 2);
+        assert_eq!(sampled.relationship_types[0].count, 2);
+        assert_eq!(sampled.relationship_types[0].endpoints.len(), 1);
+        let _ = graph.delete();
+    }
+}
+
+#[cfg(feature = "codegen")]
+mod codegen {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::codegen::{CodeGenerator, HEADER};
+    use falkordb::FalkorClientBuilder;
+
+    #[test]
+    fn test_generate_types_for_a_live_graph() {
+        if skip_if_no_server() {
+            return;
+        }
+        let Ok(conn_info) = get_test_connection_info() else {
+            return;
+        };
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .build()
+            .expect("client builds");
+        let mut graph = client.select_graph("test_codegen");
+        let _ = graph.delete();
+        graph
+            .query(
+                "CREATE (:Person {firstName: 'A', age: 30})-[:KNOWS {since: 2020}]->(:Person {firstName: 'B'})",
+            )
+            .execute()
+            .expect("graph is created");
+
+        let source = CodeGenerator::new()
+            .with_sample_size(100)
+            .generate(&mut graph)
+            .expect("types are generated");
+        assert!(source.starts_with(HEADER));
+        assert!(source.contains("pub struct Person {"));
+        assert!(source.contains("    pub first_name: String,"));
+        assert!(source.contains("    pub age: Option<i64>,"));
+        assert!(source.contains("impl falkordb::FromFalkorValue for Knows {"));
+        assert!(source.contains("    pub since: i64,"));
+        let _ = graph.delete();
+    }
+}
+
+#[cfg(feature = "query-stats")]
+mod query_stats {
+    use super::{get_test_connection_info, skip_if_no_server};
+    use falkordb::{FalkorClientBuilder, QueryStatsConfig, QueryStatsOrder};
+    use std::time::Duration;
+
+    #[test]
+    fn test_query_stats_record_calls_and_slow_queries() {
+        if skip_if_no_server() {
+            return;
+        }
+        let Ok(conn_info) = get_test_connection_info() else {
+            return;
+        };
+        let client = FalkorClientBuilder::new()
+            .with_connection_info(conn_info)
+            .with_query_stats(QueryStatsConfig::new().with_slow_threshold(Duration::ZERO))
+            .build()
+            .expect("client builds");
+        let mut graph = client.select_graph("test_query_stats");
+        let _ = graph.delete();
+        graph
+            .query("UNWIND range(1, 3) AS i CREATE (:Item {id: i})")
+            .execute()
+            .expect("items are created");
+        let stats = client.query_stats().expect("query stats are enabled");
+        stats.reset();
+
+        for id in [1, 2] {
+            let query = format!("MATCH (n:Item) WHERE n.id >= {id} RETURN n");
+            graph.ro_query(&query).execute().expect("items are read");
+        }
+        assert!(graph.query("MATCH (n:Item RETURN n").execute().is_err());
+
+        let top = stats.top(1, QueryStatsOrder::Calls);
+        let entry = &top[0];
+        assert_eq!(entry.query, "MATCH (n:Item) WHERE n.id >= ? RETURN n");
+        assert_eq!((entry.calls, entry.errors, entry.rows_returned), (2, 0, 5));
+        assert_eq!(entry.cache_hits + entry.cache_misses, 2);
+        assert!(entry.plan.as_ref().is_some_and(|plan| !plan.is_empty()));
+        assert_eq!(stats.entries().len(), 2);
+
+        let slow = stats.slow_queries();
+        assert_eq!(slow.len(), 3);
+        assert!(slow[2].failed);
+        let _ = graph.delete();
+    }
 }